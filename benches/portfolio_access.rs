@@ -0,0 +1,226 @@
+//! Compares the throughput of Traders sharing a `MetaPortfolio` behind an `Arc<Mutex<_>>` against
+//! Traders sending requests to a single `PortfolioActor` via their own `PortfolioHandle`, and to
+//! one `PortfolioActor` shard per Market via a `ShardedPortfolioHandle`.
+//!
+//! Run with `cargo bench --bench portfolio_access`.
+
+use barter::{
+    execution::{Fees, FillEvent},
+    portfolio::{
+        actor::PortfolioActor,
+        allocator::DefaultAllocator,
+        portfolio::MetaPortfolio,
+        repository::{
+            in_memory::InMemoryRepository,
+            shared_balance::{BalanceLedger, SharedBalanceRepository},
+            BalanceHandler, PositionHandler, StatisticHandler, TransactionHandler,
+        },
+        risk::DefaultRisk,
+        FillUpdater, MarketUpdater,
+    },
+    statistic::summary::pnl::PnLReturnSummary,
+    strategy::Decision,
+};
+use barter_data::{model::MarketEvent, test_util::market_trade};
+use barter_integration::model::{Instrument, InstrumentKind, Market, Side};
+use chrono::Utc;
+use parking_lot::Mutex;
+use std::{
+    sync::{Arc, Barrier},
+    thread,
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+/// Number of `MarketEvent`s each Trader applies to the Portfolio.
+const EVENTS_PER_TRADER: usize = 50_000;
+
+/// Numbers of concurrent Traders (one per Market) to benchmark.
+const TRADERS: [usize; 4] = [1, 4, 16, 64];
+
+type BenchPortfolio<Repository = InMemoryRepository<PnLReturnSummary>> =
+    MetaPortfolio<Repository, DefaultAllocator, DefaultRisk, PnLReturnSummary>;
+
+fn markets(traders: usize) -> Vec<Market> {
+    (0..traders)
+        .map(|index| {
+            Market::new(
+                "binance",
+                Instrument::from((
+                    format!("coin{}", index),
+                    "usdt".to_owned(),
+                    InstrumentKind::Spot,
+                )),
+            )
+        })
+        .collect()
+}
+
+/// Build a `MetaPortfolio` with an open Position in every Market, so every `MarketEvent` updates
+/// a Position.
+fn portfolio<Repository>(
+    engine_id: Uuid,
+    markets: &[Market],
+    repository: Repository,
+) -> BenchPortfolio<Repository>
+where
+    Repository:
+        PositionHandler + BalanceHandler + StatisticHandler<PnLReturnSummary> + TransactionHandler,
+{
+    let mut portfolio = MetaPortfolio::builder()
+        .engine_id(engine_id)
+        .markets(markets.to_vec())
+        .starting_cash(1_000_000.0)
+        .repository(repository)
+        .allocation_manager(DefaultAllocator {
+            default_order_value: 100.0,
+        })
+        .risk_manager(DefaultRisk {})
+        .statistic_config(())
+        .build_and_init()
+        .expect("failed to build & initialise MetaPortfolio");
+
+    for market in markets {
+        portfolio
+            .update_from_fill(&FillEvent {
+                time: Utc::now(),
+                exchange: market.exchange.clone(),
+                instrument: market.instrument.clone(),
+                market_meta: Default::default(),
+                decision: Decision::Long,
+                quantity: 1.0,
+                fill_value_gross: 1000.0,
+                fees: Fees::default(),
+                tag: None,
+            })
+            .expect("failed to enter Position");
+    }
+
+    portfolio
+}
+
+fn market_event(market: &Market) -> MarketEvent {
+    let mut event = market_trade(Side::Buy);
+    event.exchange = market.exchange.clone();
+    event.instrument = market.instrument.clone();
+    event
+}
+
+/// Run one thread per Market, each applying `EVENTS_PER_TRADER` `MarketEvent`s to it's own
+/// Portfolio accessor. Returns the time taken for every thread to finish.
+fn run_traders<Accessor>(markets: &[Market], accessors: Vec<Accessor>) -> Duration
+where
+    Accessor: MarketUpdater + Send + 'static,
+{
+    let barrier = Arc::new(Barrier::new(markets.len() + 1));
+
+    let handles = markets
+        .iter()
+        .zip(accessors)
+        .map(|(market, mut accessor)| {
+            let event = market_event(market);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                for _ in 0..EVENTS_PER_TRADER {
+                    accessor
+                        .update_from_market(&event)
+                        .expect("failed to update Portfolio from market");
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    barrier.wait();
+    let start = Instant::now();
+    handles
+        .into_iter()
+        .for_each(|handle| handle.join().expect("Trader thread panicked"));
+    start.elapsed()
+}
+
+/// `MarketUpdater` that locks a `MetaPortfolio` shared by every Trader, as the `Engine` does.
+struct SharedMutex(Arc<Mutex<BenchPortfolio>>);
+
+impl MarketUpdater for SharedMutex {
+    fn update_from_market(
+        &mut self,
+        market: &MarketEvent,
+    ) -> Result<
+        Vec<barter::portfolio::position::PositionUpdate>,
+        barter::portfolio::error::PortfolioError,
+    > {
+        self.0.lock().update_from_market(market)
+    }
+}
+
+fn bench_mutex(markets: &[Market]) -> Duration {
+    let portfolio = Arc::new(Mutex::new(portfolio(
+        Uuid::new_v4(),
+        markets,
+        InMemoryRepository::new(),
+    )));
+    let accessors = markets
+        .iter()
+        .map(|_| SharedMutex(Arc::clone(&portfolio)))
+        .collect();
+
+    run_traders(markets, accessors)
+}
+
+fn bench_actor(markets: &[Market]) -> Duration {
+    let (handle, actor) = PortfolioActor::spawn(portfolio(
+        Uuid::new_v4(),
+        markets,
+        InMemoryRepository::new(),
+    ));
+    let accessors = markets.iter().map(|_| handle.clone()).collect();
+    drop(handle);
+
+    let elapsed = run_traders(markets, accessors);
+    actor.join().expect("PortfolioActor thread panicked");
+    elapsed
+}
+
+/// One `PortfolioActor` shard per Market, sharing the Balance via a `BalanceLedger`.
+fn bench_sharded(markets: &[Market]) -> Duration {
+    let engine_id = Uuid::new_v4();
+    let ledger = BalanceLedger::new();
+    let (handle, actors) = PortfolioActor::spawn_sharded(markets.iter().map(|market| {
+        let repository = SharedBalanceRepository::new(InMemoryRepository::new(), ledger.clone());
+        let shard = std::slice::from_ref(market);
+        (shard.to_vec(), portfolio(engine_id, shard, repository))
+    }));
+    let accessors = markets.iter().map(|_| handle.clone()).collect();
+    drop(handle);
+
+    let elapsed = run_traders(markets, accessors);
+    for actor in actors {
+        actor.join().expect("PortfolioActor thread panicked");
+    }
+    elapsed
+}
+
+fn main() {
+    println!(
+        "{:>8} {:>22} {:>22} {:>22}",
+        "traders", "mutex (events/s)", "actor (events/s)", "sharded (events/s)"
+    );
+
+    for traders in TRADERS {
+        let markets = markets(traders);
+        let events = (traders * EVENTS_PER_TRADER) as f64;
+
+        let mutex = bench_mutex(&markets);
+        let actor = bench_actor(&markets);
+        let sharded = bench_sharded(&markets);
+
+        println!(
+            "{:>8} {:>22.0} {:>22.0} {:>22.0}",
+            traders,
+            events / mutex.as_secs_f64(),
+            events / actor.as_secs_f64(),
+            events / sharded.as_secs_f64()
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use barter::{
+    clock::Clock,
     data::historical,
     engine::{trader::Trader, Engine},
     event::{Event, EventTx},
@@ -75,13 +76,17 @@ async fn main() {
             .data(historical::MarketFeed::new(
                 load_json_market_event_candles().into_iter(),
             ))
-            .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
+            .strategy(RSIStrategy::new(StrategyConfig {
+                rsi_period: 14,
+                clock: Clock::Live,
+            }))
             .execution(SimulatedExecution::new(ExecutionConfig {
                 simulated_fees_pct: Fees {
                     exchange: 0.1,
                     slippage: 0.05,
                     network: 0.0,
                 },
+                clock: Clock::Live,
             }))
             .build()
             .expect("failed to build trader"),
@@ -107,7 +112,8 @@ async fn main() {
 
     // Run Engine trading & listen to Events it produces
     tokio::spawn(listen_to_engine_events(event_rx));
-    engine.run().await;
+    let session_outcome = engine.run().await;
+    session_outcome.summary.table().printstd();
 }
 
 fn load_json_market_event_candles() -> Vec<MarketEvent> {
@@ -171,6 +177,10 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
                 // Balance update Event occurred in Engine
                 println!("{balance_update:?}");
             }
+            Event::Discrepancy(discrepancy) => {
+                // Reconciliation Discrepancy Event occurred in Engine
+                println!("{discrepancy:?}");
+            }
         }
     }
 }
@@ -0,0 +1,9 @@
+//! `barter` command line interface for running backtests & live trading sessions from a
+//! declarative engine configuration file. See `barter help` for usage.
+
+use barter::config::{cli, registry::Registry};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    cli::run(Registry::default())
+}
@@ -0,0 +1,236 @@
+//! Terminal dashboard for monitoring & controlling a running Barter Engine via it's embedded
+//! Server.
+//!
+//! Usage: `barter-dashboard [--url http://127.0.0.1:8080] [--token <token>] [--poll-secs 2]
+//! [--stale-secs 30]`. The API token can also be provided via the `BARTER_API_TOKEN`
+//! environment variable.
+
+use barter::dashboard::{
+    client::ApiClient,
+    error::DashboardError,
+    terminal::{parse_keys, Terminal},
+    Action, DashboardState, Key,
+};
+use barter::event::Event;
+use futures::{Stream, StreamExt};
+use std::{io::Read, pin::Pin, process, thread, time::Duration};
+use tokio::sync::mpsc;
+
+const USAGE: &str = "Usage: barter-dashboard [--url <url>] [--token <token>] [--poll-secs <secs>] \
+[--stale-secs <secs>]";
+
+/// Interval between redraws of the dashboard.
+const RENDER_INTERVAL: Duration = Duration::from_millis(250);
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<Event, DashboardError>> + Send>>;
+
+#[derive(Debug)]
+struct Args {
+    url: String,
+    token: String,
+    poll_interval: Duration,
+    stale_after: Duration,
+}
+
+impl Args {
+    fn parse() -> Result<Self, String> {
+        let mut args = Args {
+            url: "http://127.0.0.1:8080".to_owned(),
+            token: std::env::var("BARTER_API_TOKEN").unwrap_or_default(),
+            poll_interval: Duration::from_secs(2),
+            stale_after: Duration::from_secs(30),
+        };
+
+        let mut arguments = std::env::args().skip(1);
+        while let Some(argument) = arguments.next() {
+            let mut value = || {
+                arguments
+                    .next()
+                    .ok_or_else(|| format!("missing value for {}", argument))
+            };
+
+            match argument.as_str() {
+                "--url" => args.url = value()?,
+                "--token" => args.token = value()?,
+                "--poll-secs" => args.poll_interval = parse_secs(&value()?)?,
+                "--stale-secs" => args.stale_after = parse_secs(&value()?)?,
+                "--help" | "-h" => {
+                    println!("{}", USAGE);
+                    process::exit(0)
+                }
+                other => return Err(format!("unknown argument: {}\n{}", other, USAGE)),
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+fn parse_secs(value: &str) -> Result<Duration, String> {
+    value
+        .parse()
+        .map(Duration::from_secs)
+        .map_err(|_| format!("invalid number of seconds: {}", value))
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse().unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(2)
+    });
+
+    if let Err(error) = run(args).await {
+        eprintln!("barter-dashboard failed: {}", error);
+        process::exit(1);
+    }
+}
+
+async fn run(args: Args) -> Result<(), DashboardError> {
+    let client = ApiClient::new(args.url, args.token);
+    let mut state = DashboardState::new(args.stale_after);
+    let mut events: Option<EventStream> = None;
+
+    let terminal = Terminal::enter()?;
+    let mut key_rx = spawn_key_reader();
+
+    let mut poll = tokio::time::interval(args.poll_interval);
+    let mut render = tokio::time::interval(RENDER_INTERVAL);
+
+    loop {
+        tokio::select! {
+            _ = poll.tick() => {
+                if events.is_none() {
+                    events = connect_events(&client, &mut state).await;
+                }
+                refresh(&client, &mut state).await;
+            }
+
+            event = next_event(&mut events) => match event {
+                Some(Ok(event)) => state.apply_event(event),
+                Some(Err(error)) => {
+                    state.set_status(format!("Event stream error: {}", error));
+                }
+                None => {
+                    events = None;
+                    state.set_connected(false);
+                    state.set_status("Event stream disconnected, reconnecting");
+                }
+            },
+
+            key = key_rx.recv() => {
+                let action = match key {
+                    Some(key) => state.handle_key(key),
+                    None => Some(Action::Quit),
+                };
+
+                match action {
+                    Some(Action::Quit) => break,
+                    Some(action) => act(&client, &mut state, action).await,
+                    None => {}
+                }
+            }
+
+            _ = render.tick() => {
+                terminal.draw(&state.render())?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Read keys from stdin on a dedicated thread, since stdin reads block.
+fn spawn_key_reader() -> mpsc::UnboundedReceiver<Key> {
+    let (key_tx, key_rx) = mpsc::unbounded_channel();
+
+    thread::spawn(move || {
+        let mut stdin = std::io::stdin();
+        let mut buffer = [0u8; 16];
+
+        while let Ok(read) = stdin.read(&mut buffer) {
+            if read == 0 {
+                break;
+            }
+
+            for key in parse_keys(&buffer[..read]) {
+                if key_tx.send(key).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    key_rx
+}
+
+async fn connect_events(client: &ApiClient, state: &mut DashboardState) -> Option<EventStream> {
+    match client.events().await {
+        Ok(stream) => {
+            state.set_connected(true);
+            Some(Box::pin(stream))
+        }
+        Err(error) => {
+            state.set_connected(false);
+            state.set_status(format!("failed to connect to Event stream: {}", error));
+            None
+        }
+    }
+}
+
+async fn next_event(events: &mut Option<EventStream>) -> Option<Result<Event, DashboardError>> {
+    match events {
+        Some(stream) => stream.next().await,
+        None => futures::future::pending().await,
+    }
+}
+
+/// Refresh the snapshots that are not derived from the Event stream.
+async fn refresh(client: &ApiClient, state: &mut DashboardState) {
+    let (positions, balance, statistics) = futures::join!(
+        client.open_positions(),
+        client.balance(),
+        client.statistics()
+    );
+
+    let mut errors = Vec::new();
+    match positions {
+        Ok(positions) => state.set_open_positions(positions),
+        Err(error) => errors.push(format!("positions: {}", error)),
+    }
+    match balance {
+        Ok(balance) => state.set_balance(balance),
+        Err(error) => errors.push(format!("balance: {}", error)),
+    }
+    match statistics {
+        Ok(statistics) => state.set_statistics(statistics),
+        Err(error) => errors.push(format!("statistics: {}", error)),
+    }
+
+    if !errors.is_empty() {
+        state.set_status(format!("failed to refresh {}", errors.join(", ")));
+    }
+}
+
+async fn act(client: &ApiClient, state: &mut DashboardState, action: Action) {
+    let (description, result) = match &action {
+        Action::ExitPosition(market) => (
+            format!("exit Position in {:?}", market),
+            client.exit_position(market).await,
+        ),
+        Action::ExitAllPositions => (
+            "exit all Positions".to_owned(),
+            client.exit_all_positions().await,
+        ),
+        Action::Terminate => (
+            "terminate Engine".to_owned(),
+            client.terminate("terminated via barter-dashboard").await,
+        ),
+        Action::Quit => return,
+    };
+
+    match result {
+        Ok(()) => state.set_status(format!("sent {}", description)),
+        Err(error) => state.set_status(format!("failed to {}: {}", description, error)),
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Source of the time used to timestamp the [`Signal`](crate::strategy::Signal),
+/// [`OrderEvent`](crate::portfolio::OrderEvent) & [`FillEvent`](crate::execution::FillEvent)s
+/// generated by the Strategy, Portfolio & Execution components.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Clock {
+    /// Wall-clock time, for live & paper trading.
+    #[default]
+    Live,
+    /// Time of the market data being actioned, for deterministic backtests
+    /// (eg/ [`regression`](crate::regression) snapshots).
+    Historical,
+}
+
+impl Clock {
+    /// Determine the current time according to this [`Clock`], given the time of the market data
+    /// being actioned (eg/ `MarketEvent` exchange_time, `Signal` time).
+    pub fn time(&self, market_time: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Clock::Live => Utc::now(),
+            Clock::Historical => market_time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn historical_clock_uses_market_time_and_live_clock_uses_wall_clock() {
+        let market_time = Utc::now() - Duration::days(365);
+
+        assert_eq!(Clock::Historical.time(market_time), market_time);
+        assert!(Clock::Live.time(market_time) > market_time);
+    }
+}
@@ -0,0 +1,401 @@
+use crate::{
+    config::{error::ConfigError, registry::Registry, EngineConfig},
+    engine::{outcome::SessionOutcome, Command},
+    event::{sink::JsonLinesTx, EventTx, MessageTransmitter},
+    statistic::summary::trading::TradingSummary,
+};
+use std::{path::PathBuf, process::ExitCode};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+const USAGE: &str = "\
+Usage:
+    barter run <CONFIG> [--events <PATH>] [--json]
+        Run the Engine described by the CONFIG file (.json, .toml, .yaml) until it stops,
+        printing the trading session summary.
+        --events <PATH>  Append every Engine Event to the JSON lines file at PATH.
+        --json           Print the SessionOutcome as JSON rather than a summary table.
+    barter check <CONFIG>
+        Validate the CONFIG file & the components it references.
+    barter components
+        List the names of every registered component.";
+
+/// Capacity of the [`Command`](crate::engine::Command) channel of the [`Engine`](crate::engine::Engine).
+const COMMAND_CAPACITY: usize = 20;
+
+/// Capacity of the broadcast channel streaming [`Event`](crate::event::Event)s to the
+/// [`Server`](crate::server::Server) /events WebSocket clients.
+#[cfg(feature = "server")]
+const SERVER_EVENT_CAPACITY: usize = 1024;
+
+/// Command parsed from the `barter` command line arguments.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Cli {
+    Run {
+        config: PathBuf,
+        events: Option<PathBuf>,
+        json: bool,
+    },
+    Check {
+        config: PathBuf,
+    },
+    Components,
+    Help,
+}
+
+impl Cli {
+    /// Parse the [`Cli`] command from the provided arguments, excluding the binary name.
+    pub fn parse<Args>(args: Args) -> Result<Self, String>
+    where
+        Args: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter();
+
+        match args.next().as_deref() {
+            Some("run") => {
+                let mut config = None;
+                let mut events = None;
+                let mut json = false;
+
+                while let Some(arg) = args.next() {
+                    match arg.as_str() {
+                        "--events" => {
+                            events = Some(PathBuf::from(
+                                args.next().ok_or("missing value for --events")?,
+                            ))
+                        }
+                        "--json" => json = true,
+                        flag if flag.starts_with("--") => {
+                            return Err(format!("unknown argument: {}", flag))
+                        }
+                        _ if config.is_none() => config = Some(PathBuf::from(arg)),
+                        _ => return Err(format!("unexpected argument: {}", arg)),
+                    }
+                }
+
+                Ok(Self::Run {
+                    config: config.ok_or("missing CONFIG file")?,
+                    events,
+                    json,
+                })
+            }
+            Some("check") => match (args.next(), args.next()) {
+                (Some(config), None) => Ok(Self::Check {
+                    config: PathBuf::from(config),
+                }),
+                (None, _) => Err("missing CONFIG file".to_owned()),
+                (Some(_), Some(arg)) => Err(format!("unexpected argument: {}", arg)),
+            },
+            Some("components") => Ok(Self::Components),
+            Some("help" | "--help" | "-h") => Ok(Self::Help),
+            Some(command) => Err(format!("unknown command: {}", command)),
+            None => Err("missing command".to_owned()),
+        }
+    }
+}
+
+/// Run the `barter` command line interface using the process arguments, constructing components
+/// with the provided [`Registry`].
+///
+/// User-defined components are made available to the CLI by calling this from a binary with a
+/// [`Registry`] they are registered in.
+///
+/// eg/ `fn main() -> ExitCode { cli::run(Registry::default().register_strategy("my", My::new)) }`
+pub fn run(registry: Registry) -> ExitCode {
+    let cli = match Cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match execute(cli, &registry) {
+        Ok(exit_code) => exit_code,
+        Err(error) => {
+            eprintln!("barter: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Execute the [`Cli`] command.
+pub fn execute(cli: Cli, registry: &Registry) -> Result<ExitCode, ConfigError> {
+    match cli {
+        Cli::Run {
+            config,
+            events,
+            json,
+        } => {
+            let config = EngineConfig::from_path(config)?;
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .map_err(ConfigError::Runtime)?;
+
+            let outcome = runtime.block_on(run_engine(config, registry, events))?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&outcome)
+                        .expect("SessionOutcome is serialisable to JSON")
+                );
+            } else {
+                outcome.summary.table().printstd();
+                println!("Engine stopped: {:?}", outcome.termination);
+                for failure in &outcome.trader_failures {
+                    println!("Trader {:?} failed: {}", failure.market, failure.reason);
+                }
+            }
+
+            Ok(if outcome.trader_failures.is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+
+        Cli::Check { config } => {
+            EngineConfig::from_path(&config)?.validate(registry)?;
+            println!("{} is valid", config.display());
+            Ok(ExitCode::SUCCESS)
+        }
+
+        Cli::Components => {
+            for (kind, names) in registry.names() {
+                println!("{}: {}", kind, names.join(", "));
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+
+        Cli::Help => {
+            println!("{}", USAGE);
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+/// Initialise & run the [`Engine`](crate::engine::Engine) described by the [`EngineConfig`],
+/// optionally appending every [`Event`](crate::event::Event) to a JSON lines file.
+///
+/// The [`Engine`](crate::engine::Engine) is sent a [`Command::Terminate`] on Ctrl-C, and is
+/// supervised via a [`Server`](crate::server::Server) if the [`EngineConfig`] configures one.
+async fn run_engine(
+    config: EngineConfig,
+    registry: &Registry,
+    events: Option<PathBuf>,
+) -> Result<SessionOutcome<TradingSummary>, ConfigError> {
+    let mut events = events
+        .map(|path| {
+            JsonLinesTx::open(&path).map_err(|error| ConfigError::Io {
+                path: path.display().to_string(),
+                error,
+            })
+        })
+        .transpose()?;
+
+    // Command transmitter is held until the Engine stops so it is not terminated prematurely
+    let (command_tx, command_rx) = mpsc::channel(COMMAND_CAPACITY);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+
+    #[cfg(feature = "server")]
+    let server = config.server.clone();
+
+    let engine = config
+        .init(registry, command_rx, EventTx::new(event_tx))
+        .await?;
+
+    #[cfg(feature = "server")]
+    let (mut server_events, server) = match server {
+        Some(server) => {
+            let (server_events, server) = server::spawn(server, command_tx.clone())?;
+            (Some(server_events), Some(server))
+        }
+        None => (None, None),
+    };
+
+    let event_writer = tokio::spawn(async move {
+        while let Some(event) = event_rx.recv().await {
+            #[cfg(feature = "server")]
+            if let Some(server_events) = server_events.as_mut() {
+                server_events.send(event.clone());
+            }
+
+            if let Some(events) = events.as_mut() {
+                events.send(event);
+            }
+        }
+    });
+
+    let terminate_on_ctrl_c = tokio::spawn(terminate_on_ctrl_c(command_tx.clone()));
+
+    let outcome = engine.run().await;
+    terminate_on_ctrl_c.abort();
+    let _ = event_writer.await;
+
+    #[cfg(feature = "server")]
+    if let Some(server) = server {
+        server.stop().await;
+    }
+    drop(command_tx);
+
+    Ok(outcome)
+}
+
+/// Send the [`Engine`](crate::engine::Engine) a [`Command::Terminate`] once Ctrl-C is received.
+async fn terminate_on_ctrl_c(command_tx: mpsc::Sender<Command>) {
+    if let Err(error) = tokio::signal::ctrl_c().await {
+        warn!(%error, "failed to listen for Ctrl-C, Engine can only be terminated remotely");
+        return;
+    }
+
+    info!("received Ctrl-C, terminating Engine");
+    if command_tx
+        .send(Command::Terminate("received Ctrl-C".to_owned()))
+        .await
+        .is_err()
+    {
+        warn!("failed to send Command::Terminate to Engine command_rx since it has stopped");
+    }
+}
+
+/// [`Server`](crate::server::Server) supervising the [`Engine`](crate::engine::Engine) run by the
+/// `barter` command line interface.
+#[cfg(feature = "server")]
+mod server {
+    use super::SERVER_EVENT_CAPACITY;
+    use crate::{
+        config::error::ConfigError,
+        engine::Command,
+        event::sink::BroadcastEventTx,
+        server::{Server, ServerConfig},
+    };
+    use tokio::{
+        sync::{broadcast, mpsc, oneshot},
+        task::JoinHandle,
+    };
+    use tracing::{info, warn};
+
+    /// Running [`Server`] that stops serving requests once the Engine has stopped.
+    pub(super) struct RunningServer {
+        shutdown_tx: oneshot::Sender<()>,
+        server: JoinHandle<()>,
+    }
+
+    impl RunningServer {
+        /// Stop the [`Server`], waiting for in-flight requests to complete.
+        pub(super) async fn stop(self) {
+            let _ = self.shutdown_tx.send(());
+            let _ = self.server.await;
+        }
+    }
+
+    /// Bind & spawn a [`Server`] sending [`Command`]s to the Engine. Returns the
+    /// [`BroadcastEventTx`] the Engine's Events must be forwarded to for the /events WebSocket.
+    pub(super) fn spawn(
+        config: ServerConfig,
+        command_tx: mpsc::Sender<Command>,
+    ) -> Result<(BroadcastEventTx, RunningServer), ConfigError> {
+        let (event_tx, _) = broadcast::channel(SERVER_EVENT_CAPACITY);
+        let events = BroadcastEventTx::new(event_tx);
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+
+        let (address, server) = Server::builder()
+            .config(config)
+            .command_tx(command_tx)
+            .events(events.clone())
+            .build()?
+            .bind(async {
+                let _ = shutdown_rx.await;
+            })?;
+        info!(%address, "supervising Engine via Server");
+
+        let server = tokio::spawn(async move {
+            if let Err(error) = server.await {
+                warn!(%error, "Server stopped serving requests");
+            }
+        });
+
+        Ok((
+            events,
+            RunningServer {
+                shutdown_tx,
+                server,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn cli_parses_commands() {
+        assert_eq!(
+            Cli::parse(args(&[
+                "run",
+                "engine.json",
+                "--json",
+                "--events",
+                "events.jsonl"
+            ])),
+            Ok(Cli::Run {
+                config: PathBuf::from("engine.json"),
+                events: Some(PathBuf::from("events.jsonl")),
+                json: true,
+            })
+        );
+        assert_eq!(
+            Cli::parse(args(&["check", "engine.toml"])),
+            Ok(Cli::Check {
+                config: PathBuf::from("engine.toml")
+            })
+        );
+        assert_eq!(Cli::parse(args(&["components"])), Ok(Cli::Components));
+        assert_eq!(Cli::parse(args(&["--help"])), Ok(Cli::Help));
+
+        assert!(Cli::parse(args(&[])).is_err());
+        assert!(Cli::parse(args(&["run"])).is_err());
+        assert!(Cli::parse(args(&["run", "engine.json", "--verbose"])).is_err());
+        assert!(Cli::parse(args(&["check", "a.json", "b.json"])).is_err());
+        assert!(Cli::parse(args(&["backtest", "engine.json"])).is_err());
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn run_engine_stops_supervising_server_once_engine_stops() {
+        let mut config = serde_json::json!({
+            "markets": [{
+                "market": { "exchange": "binance", "base": "btc", "quote": "usdt", "instrument_type": "spot" },
+                "data": { "source": "candles", "path": "examples/data/candles_1h.json" }
+            }],
+            "strategy": { "name": "rsi", "params": { "rsi_period": 14 } },
+            "allocator": { "name": "default", "params": { "default_order_value": 100.0 } },
+            "risk": { "name": "default" },
+            "execution": {
+                "name": "simulated",
+                "params": { "simulated_fees_pct": { "exchange": 0.1, "slippage": 0.05, "network": 0.0 } }
+            },
+            "starting_cash": 10000.0,
+            "statistics": { "starting_equity": 10000.0, "trading_days_per_year": 365, "risk_free_return": 0.0 }
+        });
+        config["server"] = serde_json::json!({ "address": "127.0.0.1:0", "api_token": "secret" });
+        let config = serde_json::from_value::<EngineConfig>(config).unwrap();
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let outcome = runtime
+            .block_on(run_engine(config, &Registry::default(), None))
+            .unwrap();
+
+        assert!(outcome.trader_failures.is_empty());
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    data::error::DataError,
+    engine::error::EngineError,
+    portfolio::{error::PortfolioError, repository::error::RepositoryError},
+};
+use thiserror::Error;
+
+/// All errors generated in the barter::config module.
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to access file {path}: {error}")]
+    Io { path: String, error: std::io::Error },
+
+    #[error("Failed to start the tokio runtime: {0}")]
+    Runtime(std::io::Error),
+
+    #[error("Unsupported configuration format: {0}")]
+    UnsupportedFormat(String),
+
+    #[error("Failed to parse configuration: {0}")]
+    Parse(String),
+
+    #[error("Configuration must contain at least one market")]
+    NoMarkets,
+
+    #[error("Market {0} is configured more than once")]
+    DuplicateMarket(String),
+
+    #[error("No {kind} component registered with the name: {name}")]
+    UnknownComponent { kind: &'static str, name: String },
+
+    #[error("Invalid params for {kind} component {name}: {error}")]
+    InvalidParams {
+        kind: &'static str,
+        name: String,
+        error: serde_json::Error,
+    },
+
+    #[error("Failed to load historical data from {path}: {error}")]
+    HistoricalData {
+        path: String,
+        error: serde_json::Error,
+    },
+
+    #[error("Failed to initialise live data feed: {0}")]
+    Data(Box<DataError>),
+
+    #[error("Failed to initialise repository: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("Failed to build Portfolio: {0}")]
+    Portfolio(#[from] PortfolioError),
+
+    #[error("Failed to build Engine: {0}")]
+    Engine(#[from] EngineError),
+
+    #[cfg(feature = "server")]
+    #[error("Failed to start Server: {0}")]
+    Server(#[from] crate::server::error::ServerError),
+}
+
+impl From<DataError> for ConfigError {
+    fn from(error: DataError) -> Self {
+        Self::Data(Box::new(error))
+    }
+}
@@ -0,0 +1,541 @@
+use crate::{
+    config::{
+        error::ConfigError,
+        registry::{BoxedAllocator, BoxedExecution, BoxedRisk, BoxedStrategy, Registry},
+        repository::{ConfiguredRepository, RepositoryConfig},
+    },
+    data::{historical, live, MarketGenerator},
+    engine::{trader::Trader, Command, Engine, TRADER_COMMAND_CAPACITY},
+    event::{Event, MessageTransmitter},
+    portfolio::{portfolio::MetaPortfolio, PositionMode},
+    statistic::summary::{
+        trading::{Config as StatisticConfig, TradingSummary},
+        Initialiser,
+    },
+};
+use barter_data::model::{subscription::Subscription, Candle, DataKind, MarketEvent};
+use barter_integration::model::{Market, MarketId};
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Barter config module specific errors.
+pub mod error;
+
+/// Registry of named strategy, allocator, risk & execution component constructors.
+pub mod registry;
+
+/// Runtime selected repository used to persist the Portfolio state.
+pub mod repository;
+
+/// `barter` command line interface for running an [`Engine`] from an [`EngineConfig`] file.
+/// Requires the `cli` feature.
+#[cfg(feature = "cli")]
+pub mod cli;
+
+/// Data component constructed from a [`DataConfig`].
+pub type BoxedData = Box<dyn MarketGenerator + Send>;
+
+/// Portfolio constructed from an [`EngineConfig`].
+pub type ConfiguredPortfolio =
+    MetaPortfolio<ConfiguredRepository<TradingSummary>, BoxedAllocator, BoxedRisk, TradingSummary>;
+
+/// [`Engine`] constructed from an [`EngineConfig`].
+pub type ConfiguredEngine<EventTx> =
+    Engine<EventTx, TradingSummary, ConfiguredPortfolio, BoxedData, BoxedStrategy, BoxedExecution>;
+
+/// Declarative description of an [`Engine`], deserialised from a JSON, TOML or YAML file.
+///
+/// Components are referenced by name & constructed from their params using a [`Registry`], so
+/// user-defined strategies, allocators, risk managers & executions can be plugged in. Every
+/// [`Market`] is traded by it's own [`Trader`], with it's own data feed, strategy & execution.
+///
+/// eg/ JSON
+/// ```json
+/// {
+///   "markets": [{
+///     "market": { "exchange": "binance", "base": "btc", "quote": "usdt", "instrument_type": "spot" },
+///     "data": { "source": "candles", "path": "examples/data/candles_1h.json" }
+///   }],
+///   "strategy": { "name": "rsi", "params": { "rsi_period": 14 } },
+///   "allocator": { "name": "default", "params": { "default_order_value": 100.0 } },
+///   "risk": { "name": "default" },
+///   "execution": { "name": "simulated", "params": { "simulated_fees_pct": { "exchange": 0.1, "slippage": 0.05, "network": 0.0 } } },
+///   "repository": { "kind": "in_memory" },
+///   "starting_cash": 10000.0,
+///   "statistics": { "starting_equity": 10000.0, "trading_days_per_year": 365, "risk_free_return": 0.0 },
+///   "server": { "address": "127.0.0.1:8080", "api_token": "secret" }
+/// }
+/// ```
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct EngineConfig {
+    /// Unique identifier of the [`Engine`]. A new one is generated if not provided.
+    #[serde(default)]
+    pub engine_id: Option<Uuid>,
+    /// [`Market`]s to trade, each with it's own data feed.
+    pub markets: Vec<MarketConfig>,
+    /// Strategy constructed for every [`Market`].
+    pub strategy: ComponentConfig,
+    /// Allocator used by the Portfolio.
+    pub allocator: ComponentConfig,
+    /// Risk manager used by the Portfolio.
+    pub risk: ComponentConfig,
+    /// Execution constructed for every [`Market`].
+    pub execution: ComponentConfig,
+    /// Repository the Portfolio state is persisted in. Defaults to in-memory.
+    #[serde(default)]
+    pub repository: RepositoryConfig,
+    /// Cash the Portfolio starts trading with.
+    pub starting_cash: f64,
+    /// [`PositionMode`] of the Portfolio (`one_way` or `hedge`). Defaults to one-way.
+    #[serde(default)]
+    pub position_mode: PositionMode,
+    /// Configuration of the per-market & Engine [`TradingSummary`] statistics.
+    pub statistics: StatisticConfig,
+    /// Optional [`Server`](crate::server::Server) the `barter` command line interface serves
+    /// whilst the [`Engine`] runs, so a (eg/ live) session can be supervised & controlled
+    /// remotely. Requires the `server` feature.
+    #[cfg(feature = "server")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<crate::server::ServerConfig>,
+}
+
+/// [`Market`] to trade & the source of it's [`MarketEvent`]s.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct MarketConfig {
+    pub market: Market,
+    pub data: DataConfig,
+}
+
+/// Source of the [`MarketEvent`]s for a [`Market`], selected by the `source` field. Relative
+/// paths are resolved against the working directory.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum DataConfig {
+    /// Backtest using a JSON file containing an array of [`Candle`]s.
+    Candles { path: PathBuf },
+    /// Backtest using a JSON file containing an array of [`MarketEvent`]s.
+    MarketEvents { path: PathBuf },
+    /// Trade live using the [`MarketEvent`]s of the provided [`Subscription`]s.
+    Live { subscriptions: Vec<Subscription> },
+}
+
+/// Component referenced by the name it is registered with in a [`Registry`], and the params it
+/// is constructed from.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct ComponentConfig {
+    pub name: String,
+    /// Omitted when serialised if null, since TOML cannot represent null values.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub params: serde_json::Value,
+}
+
+/// Format of an [`EngineConfig`] file.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ConfigFormat {
+    Json,
+    /// Requires the `toml` feature.
+    Toml,
+    /// Requires the `yaml` feature.
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Determine the [`ConfigFormat`] from the file extension of the provided path.
+    pub fn from_path(path: &Path) -> Result<Self, ConfigError> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Ok(Self::Json),
+            Some("toml") => Ok(Self::Toml),
+            Some("yaml" | "yml") => Ok(Self::Yaml),
+            _ => Err(ConfigError::UnsupportedFormat(format!(
+                "cannot determine format of {} - expected a .json, .toml, .yaml or .yml extension",
+                path.display()
+            ))),
+        }
+    }
+}
+
+impl EngineConfig {
+    /// Read & parse the [`EngineConfig`] file at the provided path, determining the
+    /// [`ConfigFormat`] from the file extension.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path)?;
+        let contents = read_file(path)?;
+        Self::parse(&contents, format)
+    }
+
+    /// Parse an [`EngineConfig`] in the provided [`ConfigFormat`].
+    pub fn parse(contents: &str, format: ConfigFormat) -> Result<Self, ConfigError> {
+        match format {
+            ConfigFormat::Json => serde_json::from_str(contents)
+                .map_err(|error| ConfigError::Parse(error.to_string())),
+
+            #[cfg(feature = "toml")]
+            ConfigFormat::Toml => {
+                toml::from_str(contents).map_err(|error| ConfigError::Parse(error.to_string()))
+            }
+
+            #[cfg(feature = "yaml")]
+            ConfigFormat::Yaml => serde_yaml::from_str(contents)
+                .map_err(|error| ConfigError::Parse(error.to_string())),
+
+            #[allow(unreachable_patterns)]
+            unsupported => Err(ConfigError::UnsupportedFormat(format!(
+                "{:?} requires the `{}` feature",
+                unsupported,
+                format!("{:?}", unsupported).to_lowercase()
+            ))),
+        }
+    }
+
+    /// Validate the [`EngineConfig`] without initialising any data feeds or repositories. Checks
+    /// every [`Market`] is unique, and that every component is registered & constructable from
+    /// it's params.
+    pub fn validate(&self, registry: &Registry) -> Result<(), ConfigError> {
+        self.markets()?;
+        registry.strategy(&self.strategy)?;
+        registry.allocator(&self.allocator)?;
+        registry.risk(&self.risk)?;
+        registry.execution(&self.execution)?;
+        Ok(())
+    }
+
+    /// Initialise the [`Engine`] described by this [`EngineConfig`], constructing the referenced
+    /// components using the provided [`Registry`]. Live data feeds connect to their exchanges
+    /// before this returns.
+    pub async fn init<EventTx>(
+        self,
+        registry: &Registry,
+        command_rx: mpsc::Receiver<Command>,
+        event_tx: EventTx,
+    ) -> Result<ConfiguredEngine<EventTx>, ConfigError>
+    where
+        EventTx: MessageTransmitter<Event> + Clone + Send + 'static,
+    {
+        self.validate(registry)?;
+        let engine_id = self.engine_id.unwrap_or_else(Uuid::new_v4);
+
+        // Build global shared-state MetaPortfolio
+        let portfolio = Arc::new(Mutex::new(
+            MetaPortfolio::builder()
+                .engine_id(engine_id)
+                .markets(self.markets()?)
+                .starting_cash(self.starting_cash)
+                .repository(self.repository.init()?)
+                .allocation_manager(registry.allocator(&self.allocator)?)
+                .risk_manager(registry.risk(&self.risk)?)
+                .statistic_config(self.statistics)
+                .position_mode(self.position_mode)
+                .build_and_init()?,
+        ));
+
+        // Build a Trader for every Market
+        let mut traders = Vec::with_capacity(self.markets.len());
+        let mut trader_command_txs = HashMap::with_capacity(self.markets.len());
+
+        for MarketConfig { market, data } in self.markets {
+            let (trader_command_tx, trader_command_rx) = mpsc::channel(TRADER_COMMAND_CAPACITY);
+            trader_command_txs.insert(market.clone(), trader_command_tx);
+
+            traders.push(
+                Trader::builder()
+                    .engine_id(engine_id)
+                    .market(market.clone())
+                    .command_rx(trader_command_rx)
+                    .event_tx(event_tx.clone())
+                    .portfolio(Arc::clone(&portfolio))
+                    .data(data.init(&market).await?)
+                    .strategy(registry.strategy(&self.strategy)?)
+                    .execution(registry.execution(&self.execution)?)
+                    .build()?,
+            );
+        }
+
+        Ok(Engine::builder()
+            .engine_id(engine_id)
+            .command_rx(command_rx)
+            .portfolio(portfolio)
+            .traders(traders)
+            .trader_command_txs(trader_command_txs)
+            .statistics_summary(TradingSummary::init(self.statistics))
+            .build()?)
+    }
+
+    /// Every configured [`Market`], validating there is at least one & that none are duplicated.
+    fn markets(&self) -> Result<Vec<Market>, ConfigError> {
+        if self.markets.is_empty() {
+            return Err(ConfigError::NoMarkets);
+        }
+
+        let mut unique = HashSet::with_capacity(self.markets.len());
+        self.markets
+            .iter()
+            .map(|config| {
+                if unique.insert(&config.market) {
+                    Ok(config.market.clone())
+                } else {
+                    Err(ConfigError::DuplicateMarket(
+                        MarketId::from(&config.market).0,
+                    ))
+                }
+            })
+            .collect()
+    }
+}
+
+impl DataConfig {
+    /// Initialise the data feed of the provided [`Market`] described by this [`DataConfig`].
+    pub async fn init(self, market: &Market) -> Result<BoxedData, ConfigError> {
+        match self {
+            Self::Candles { path } => {
+                let market_events = read_json::<Vec<Candle>>(&path)?
+                    .into_iter()
+                    .map(|candle| MarketEvent {
+                        exchange_time: candle.end_time,
+                        received_time: candle.end_time,
+                        exchange: market.exchange.clone(),
+                        instrument: market.instrument.clone(),
+                        kind: DataKind::Candle(candle),
+                    })
+                    .collect::<Vec<_>>();
+
+                Ok(Box::new(historical::MarketFeed::new(
+                    market_events.into_iter(),
+                )))
+            }
+
+            Self::MarketEvents { path } => Ok(Box::new(historical::MarketFeed::new(
+                read_json::<Vec<MarketEvent>>(&path)?.into_iter(),
+            ))),
+
+            Self::Live { subscriptions } => {
+                Ok(Box::new(live::MarketFeed::init(subscriptions).await?))
+            }
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, ConfigError> {
+    fs::read_to_string(path).map_err(|error| ConfigError::Io {
+        path: path.display().to_string(),
+        error,
+    })
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, ConfigError> {
+    serde_json::from_str(&read_file(path)?).map_err(|error| ConfigError::HistoricalData {
+        path: path.display().to_string(),
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::InstrumentKind;
+
+    fn config_json() -> &'static str {
+        r#"{
+            "markets": [{
+                "market": { "exchange": "binance", "base": "btc", "quote": "usdt", "instrument_type": "spot" },
+                "data": { "source": "candles", "path": "examples/data/candles_1h.json" }
+            }],
+            "strategy": { "name": "rsi", "params": { "rsi_period": 14 } },
+            "allocator": { "name": "default", "params": { "default_order_value": 100.0 } },
+            "risk": { "name": "default" },
+            "execution": {
+                "name": "simulated",
+                "params": { "simulated_fees_pct": { "exchange": 0.1, "slippage": 0.05, "network": 0.0 } }
+            },
+            "starting_cash": 10000.0,
+            "statistics": { "starting_equity": 10000.0, "trading_days_per_year": 365, "risk_free_return": 0.0 }
+        }"#
+    }
+
+    #[test]
+    fn engine_config_parses_json() {
+        let config = EngineConfig::parse(config_json(), ConfigFormat::Json).unwrap();
+
+        assert_eq!(config.engine_id, None);
+        assert_eq!(
+            config.markets,
+            vec![MarketConfig {
+                market: Market::new("binance", ("btc", "usdt", InstrumentKind::Spot)),
+                data: DataConfig::Candles {
+                    path: PathBuf::from("examples/data/candles_1h.json")
+                },
+            }]
+        );
+        assert_eq!(config.risk.params, serde_json::Value::Null);
+        assert_eq!(config.repository, RepositoryConfig::InMemory);
+        assert_eq!(config.position_mode, PositionMode::OneWay);
+        assert!(config.validate(&Registry::default()).is_ok());
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn engine_config_parses_optional_server() {
+        assert_eq!(
+            EngineConfig::parse(config_json(), ConfigFormat::Json)
+                .unwrap()
+                .server,
+            None
+        );
+
+        let mut config = serde_json::from_str::<serde_json::Value>(config_json()).unwrap();
+        config["server"] =
+            serde_json::json!({ "address": "127.0.0.1:8080", "api_token": "secret" });
+        let config = EngineConfig::parse(&config.to_string(), ConfigFormat::Json).unwrap();
+
+        assert_eq!(
+            config.server,
+            Some(crate::server::ServerConfig {
+                address: "127.0.0.1:8080".parse().unwrap(),
+                api_token: "secret".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn engine_config_validation_rejects_invalid_markets_and_components() {
+        let registry = Registry::default();
+        let valid = EngineConfig::parse(config_json(), ConfigFormat::Json).unwrap();
+
+        let mut no_markets = valid.clone();
+        no_markets.markets.clear();
+        assert!(matches!(
+            no_markets.validate(&registry),
+            Err(ConfigError::NoMarkets)
+        ));
+
+        let mut duplicate_markets = valid.clone();
+        duplicate_markets
+            .markets
+            .push(duplicate_markets.markets[0].clone());
+        assert!(matches!(
+            duplicate_markets.validate(&registry),
+            Err(ConfigError::DuplicateMarket(market)) if market == "binance_btc_usdt_spot"
+        ));
+
+        let mut unknown_strategy = valid;
+        unknown_strategy.strategy.name = "ichimoku".to_owned();
+        assert!(matches!(
+            unknown_strategy.validate(&registry),
+            Err(ConfigError::UnknownComponent {
+                kind: "strategy",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn config_format_is_determined_from_extension() {
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("engine.json")).unwrap(),
+            ConfigFormat::Json
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("engine.toml")).unwrap(),
+            ConfigFormat::Toml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("engine.yml")).unwrap(),
+            ConfigFormat::Yaml
+        );
+        assert!(ConfigFormat::from_path(Path::new("engine.ini")).is_err());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn engine_config_round_trips_toml() {
+        let expected = EngineConfig::parse(config_json(), ConfigFormat::Json).unwrap();
+
+        let config = EngineConfig::parse(
+            r#"
+            starting_cash = 10000.0
+
+            [[markets]]
+            market = { exchange = "binance", base = "btc", quote = "usdt", instrument_type = "spot" }
+            data = { source = "candles", path = "examples/data/candles_1h.json" }
+
+            [strategy]
+            name = "rsi"
+            params = { rsi_period = 14 }
+
+            [allocator]
+            name = "default"
+            params = { default_order_value = 100.0 }
+
+            [risk]
+            name = "default"
+
+            [execution]
+            name = "simulated"
+            params = { simulated_fees_pct = { exchange = 0.1, slippage = 0.05, network = 0.0 } }
+
+            [statistics]
+            starting_equity = 10000.0
+            trading_days_per_year = 365
+            risk_free_return = 0.0
+            "#,
+            ConfigFormat::Toml,
+        )
+        .unwrap();
+        assert_eq!(config, expected);
+
+        // Serialise via a toml::Value, which orders tables after plain values as TOML requires
+        let serialised = toml::to_string(&toml::Value::try_from(&config).unwrap()).unwrap();
+        assert_eq!(
+            EngineConfig::parse(&serialised, ConfigFormat::Toml).unwrap(),
+            expected
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn engine_config_round_trips_yaml() {
+        let expected = EngineConfig::parse(config_json(), ConfigFormat::Json).unwrap();
+
+        let config = EngineConfig::parse(
+            r#"
+            markets:
+              - market: { exchange: binance, base: btc, quote: usdt, instrument_type: spot }
+                data: { source: candles, path: examples/data/candles_1h.json }
+            strategy:
+              name: rsi
+              params: { rsi_period: 14 }
+            allocator:
+              name: default
+              params: { default_order_value: 100.0 }
+            risk:
+              name: default
+            execution:
+              name: simulated
+              params:
+                simulated_fees_pct: { exchange: 0.1, slippage: 0.05, network: 0.0 }
+            starting_cash: 10000.0
+            statistics:
+              starting_equity: 10000.0
+              trading_days_per_year: 365
+              risk_free_return: 0.0
+            "#,
+            ConfigFormat::Yaml,
+        )
+        .unwrap();
+        assert_eq!(config, expected);
+
+        let serialised = serde_yaml::to_string(&config).unwrap();
+        assert_eq!(
+            EngineConfig::parse(&serialised, ConfigFormat::Yaml).unwrap(),
+            expected
+        );
+    }
+}
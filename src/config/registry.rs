@@ -0,0 +1,412 @@
+use crate::{
+    config::{error::ConfigError, ComponentConfig},
+    execution::{
+        simulated::{Config as SimulatedConfig, SimulatedExecution},
+        ExecutionClient,
+    },
+    portfolio::{
+        allocator::{DefaultAllocator, OrderAllocator},
+        risk::{DefaultRisk, DynOrderEvaluator, OrderEvaluator},
+    },
+    strategy::{
+        example::{Config as RSIConfig, RSIStrategy},
+        technical::{
+            bollinger::{BollingerStrategy, Config as BollingerConfig},
+            donchian::{Config as DonchianConfig, DonchianStrategy},
+            ma_crossover::{Config as MACrossoverConfig, MACrossoverStrategy},
+            macd::{Config as MACDConfig, MACDStrategy},
+            rsi::{Config as RSIThresholdConfig, RSIThresholdStrategy},
+        },
+        SignalGenerator,
+    },
+};
+use serde::de::{DeserializeOwned, Error as _, IgnoredAny};
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    fmt::{Debug, Display, Formatter},
+};
+
+/// Strategy component constructed from a [`Registry`].
+pub type BoxedStrategy = Box<dyn SignalGenerator + Send>;
+
+/// Execution component constructed from a [`Registry`].
+pub type BoxedExecution = Box<dyn ExecutionClient + Send>;
+
+/// Allocator component constructed from a [`Registry`].
+pub type BoxedAllocator = Box<dyn OrderAllocator + Send>;
+
+/// Risk manager component constructed from a [`Registry`].
+pub type BoxedRisk = Box<dyn DynOrderEvaluator + Send>;
+
+/// Constructs a component from it's JSON params.
+type Factory<Component> =
+    Box<dyn Fn(serde_json::Value) -> Result<Component, serde_json::Error> + Send + Sync>;
+
+/// Registry of named component constructors, used to build the components referenced by name in
+/// an [`EngineConfig`](super::EngineConfig).
+///
+/// The [`Default`] registry contains the built-in components:
+/// - strategy: "rsi" ([`RSIStrategy`] with [`RSIConfig`] params)
+/// - strategy: "rsi_threshold" ([`RSIThresholdStrategy`] with [`RSIThresholdConfig`] params)
+/// - strategy: "ma_crossover" ([`MACrossoverStrategy`] with [`MACrossoverConfig`] params)
+/// - strategy: "bollinger" ([`BollingerStrategy`] with [`BollingerConfig`] params)
+/// - strategy: "macd" ([`MACDStrategy`] with [`MACDConfig`] params)
+/// - strategy: "donchian" ([`DonchianStrategy`] with [`DonchianConfig`] params)
+/// - allocator: "default" ([`DefaultAllocator`] params)
+/// - risk: "default" ([`DefaultRisk`], no params)
+/// - execution: "simulated" ([`SimulatedExecution`] with [`SimulatedConfig`] params)
+///
+/// User-defined components are plugged in by registering a constructor under a new name. The
+/// constructor params are deserialised from the component's `params` in the configuration. Strategy
+/// constructors are fallible, so params that deserialise but are invalid (eg/ a zero period) are
+/// rejected with a [`ConfigError::InvalidParams`].
+///
+/// eg/ `Registry::default().register_strategy("my_strategy", MyStrategy::new)`
+pub struct Registry {
+    strategies: BTreeMap<String, Factory<BoxedStrategy>>,
+    allocators: BTreeMap<String, Factory<BoxedAllocator>>,
+    risk_managers: BTreeMap<String, Factory<BoxedRisk>>,
+    executions: BTreeMap<String, Factory<BoxedExecution>>,
+}
+
+impl Debug for Registry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Registry")
+            .field("strategies", &self.strategies.keys())
+            .field("allocators", &self.allocators.keys())
+            .field("risk_managers", &self.risk_managers.keys())
+            .field("executions", &self.executions.keys())
+            .finish()
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::empty()
+            .register_strategy("rsi", |config: RSIConfig| {
+                Ok::<_, Infallible>(RSIStrategy::new(config))
+            })
+            .register_strategy("rsi_threshold", |config: RSIThresholdConfig| {
+                RSIThresholdStrategy::new(config)
+            })
+            .register_strategy("ma_crossover", |config: MACrossoverConfig| {
+                MACrossoverStrategy::new(config)
+            })
+            .register_strategy("bollinger", |config: BollingerConfig| {
+                BollingerStrategy::new(config)
+            })
+            .register_strategy("macd", |config: MACDConfig| MACDStrategy::new(config))
+            .register_strategy("donchian", |config: DonchianConfig| {
+                DonchianStrategy::new(config)
+            })
+            .register_allocator("default", |allocator: DefaultAllocator| allocator)
+            .register_risk("default", |_: IgnoredAny| DefaultRisk {})
+            .register_execution("simulated", |config: SimulatedConfig| {
+                SimulatedExecution::new(config)
+            })
+    }
+}
+
+impl Registry {
+    /// Constructs a new [`Registry`] without any registered components.
+    pub fn empty() -> Self {
+        Self {
+            strategies: BTreeMap::new(),
+            allocators: BTreeMap::new(),
+            risk_managers: BTreeMap::new(),
+            executions: BTreeMap::new(),
+        }
+    }
+
+    /// Register a fallible [`SignalGenerator`] constructor with the provided name, replacing any
+    /// existing strategy with the same name. A new strategy is constructed for every market, and
+    /// any constructor error is surfaced as a [`ConfigError::InvalidParams`].
+    pub fn register_strategy<Params, Strategy, Error, Constructor>(
+        mut self,
+        name: &str,
+        constructor: Constructor,
+    ) -> Self
+    where
+        Params: DeserializeOwned,
+        Strategy: SignalGenerator + Send + 'static,
+        Error: Display,
+        Constructor: Fn(Params) -> Result<Strategy, Error> + Send + Sync + 'static,
+    {
+        self.strategies.insert(
+            name.to_owned(),
+            Box::new(move |params| {
+                let strategy = constructor(serde_json::from_value(params)?)
+                    .map_err(serde_json::Error::custom)?;
+                Ok(Box::new(strategy) as BoxedStrategy)
+            }),
+        );
+        self
+    }
+
+    /// Register an [`OrderAllocator`] constructor with the provided name, replacing any existing
+    /// allocator with the same name.
+    pub fn register_allocator<Params, Allocator, Constructor>(
+        mut self,
+        name: &str,
+        constructor: Constructor,
+    ) -> Self
+    where
+        Params: DeserializeOwned,
+        Allocator: OrderAllocator + Send + 'static,
+        Constructor: Fn(Params) -> Allocator + Send + Sync + 'static,
+    {
+        self.allocators.insert(
+            name.to_owned(),
+            Box::new(move |params| {
+                serde_json::from_value(params)
+                    .map(|params| Box::new(constructor(params)) as BoxedAllocator)
+            }),
+        );
+        self
+    }
+
+    /// Register an [`OrderEvaluator`] constructor with the provided name, replacing any existing
+    /// risk manager with the same name.
+    pub fn register_risk<Params, RiskManager, Constructor>(
+        mut self,
+        name: &str,
+        constructor: Constructor,
+    ) -> Self
+    where
+        Params: DeserializeOwned,
+        RiskManager: OrderEvaluator + Send + 'static,
+        Constructor: Fn(Params) -> RiskManager + Send + Sync + 'static,
+    {
+        self.risk_managers.insert(
+            name.to_owned(),
+            Box::new(move |params| {
+                serde_json::from_value(params)
+                    .map(|params| Box::new(constructor(params)) as BoxedRisk)
+            }),
+        );
+        self
+    }
+
+    /// Register an [`ExecutionClient`] constructor with the provided name, replacing any existing
+    /// execution with the same name. A new execution is constructed for every market.
+    pub fn register_execution<Params, Execution, Constructor>(
+        mut self,
+        name: &str,
+        constructor: Constructor,
+    ) -> Self
+    where
+        Params: DeserializeOwned,
+        Execution: ExecutionClient + Send + 'static,
+        Constructor: Fn(Params) -> Execution + Send + Sync + 'static,
+    {
+        self.executions.insert(
+            name.to_owned(),
+            Box::new(move |params| {
+                serde_json::from_value(params)
+                    .map(|params| Box::new(constructor(params)) as BoxedExecution)
+            }),
+        );
+        self
+    }
+
+    /// Construct the strategy described by the [`ComponentConfig`].
+    pub fn strategy(&self, config: &ComponentConfig) -> Result<BoxedStrategy, ConfigError> {
+        construct(&self.strategies, "strategy", config)
+    }
+
+    /// Construct the allocator described by the [`ComponentConfig`].
+    pub fn allocator(&self, config: &ComponentConfig) -> Result<BoxedAllocator, ConfigError> {
+        construct(&self.allocators, "allocator", config)
+    }
+
+    /// Construct the risk manager described by the [`ComponentConfig`].
+    pub fn risk(&self, config: &ComponentConfig) -> Result<BoxedRisk, ConfigError> {
+        construct(&self.risk_managers, "risk", config)
+    }
+
+    /// Construct the execution described by the [`ComponentConfig`].
+    pub fn execution(&self, config: &ComponentConfig) -> Result<BoxedExecution, ConfigError> {
+        construct(&self.executions, "execution", config)
+    }
+
+    /// Names of every registered component, keyed by component kind.
+    pub fn names(&self) -> BTreeMap<&'static str, Vec<&str>> {
+        BTreeMap::from([
+            (
+                "strategy",
+                self.strategies.keys().map(String::as_str).collect(),
+            ),
+            (
+                "allocator",
+                self.allocators.keys().map(String::as_str).collect(),
+            ),
+            (
+                "risk",
+                self.risk_managers.keys().map(String::as_str).collect(),
+            ),
+            (
+                "execution",
+                self.executions.keys().map(String::as_str).collect(),
+            ),
+        ])
+    }
+}
+
+/// Construct a component using the [`Factory`] registered with the [`ComponentConfig`] name.
+fn construct<Component>(
+    factories: &BTreeMap<String, Factory<Component>>,
+    kind: &'static str,
+    config: &ComponentConfig,
+) -> Result<Component, ConfigError> {
+    let factory = factories
+        .get(&config.name)
+        .ok_or_else(|| ConfigError::UnknownComponent {
+            kind,
+            name: config.name.clone(),
+        })?;
+
+    factory(config.params.clone()).map_err(|error| ConfigError::InvalidParams {
+        kind,
+        name: config.name.clone(),
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::Signal;
+    use barter_data::model::MarketEvent;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Deserialize)]
+    struct NeverConfig {
+        enabled: bool,
+    }
+
+    struct NeverStrategy;
+
+    impl SignalGenerator for NeverStrategy {
+        fn generate_signal(&mut self, _: &MarketEvent) -> Option<Signal> {
+            None
+        }
+    }
+
+    fn component(name: &str, params: serde_json::Value) -> ComponentConfig {
+        ComponentConfig {
+            name: name.to_owned(),
+            params,
+        }
+    }
+
+    #[test]
+    fn registry_constructs_registered_components_by_name() {
+        let registry = Registry::default().register_strategy("never", |config: NeverConfig| {
+            assert!(config.enabled);
+            Ok::<_, Infallible>(NeverStrategy)
+        });
+
+        assert!(registry
+            .strategy(&component("never", json!({ "enabled": true })))
+            .is_ok());
+        assert!(registry
+            .strategy(&component("rsi", json!({ "rsi_period": 14 })))
+            .is_ok());
+        assert!(registry.risk(&component("default", json!(null))).is_ok());
+        assert!(registry
+            .allocator(&component(
+                "default",
+                json!({ "default_order_value": 100.0 })
+            ))
+            .is_ok());
+
+        assert_eq!(
+            registry.names()["strategy"],
+            vec![
+                "bollinger",
+                "donchian",
+                "ma_crossover",
+                "macd",
+                "never",
+                "rsi",
+                "rsi_threshold"
+            ],
+        );
+    }
+
+    #[test]
+    fn registry_constructs_technical_strategies_from_params() {
+        let registry = Registry::default();
+
+        for (name, params) in [
+            (
+                "rsi_threshold",
+                json!({ "rsi_period": 14, "oversold": 30.0, "overbought": 70.0, "scaling": { "kind": "linear" } }),
+            ),
+            (
+                "ma_crossover",
+                json!({ "fast_period": 10, "slow_period": 30, "moving_average": "exponential" }),
+            ),
+            ("bollinger", json!({ "period": 20, "multiplier": 2.0 })),
+            (
+                "macd",
+                json!({ "fast_period": 12, "slow_period": 26, "signal_period": 9 }),
+            ),
+            ("donchian", json!({ "period": 20 })),
+        ] {
+            assert!(
+                registry.strategy(&component(name, params)).is_ok(),
+                "failed to construct {} strategy",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn registry_rejects_unknown_components_and_invalid_params() {
+        let registry = Registry::default();
+
+        assert!(matches!(
+            registry.execution(&component("binance", json!(null))),
+            Err(ConfigError::UnknownComponent { kind: "execution", name }) if name == "binance"
+        ));
+
+        assert!(matches!(
+            registry.strategy(&component("rsi", json!({ "rsi_period": "fourteen" }))),
+            Err(ConfigError::InvalidParams { kind: "strategy", name, .. }) if name == "rsi"
+        ));
+    }
+
+    #[test]
+    fn registry_rejects_strategy_params_the_constructor_rejects() {
+        let registry = Registry::default();
+
+        for (name, params) in [
+            ("donchian", json!({ "period": 0 })),
+            ("bollinger", json!({ "period": 0, "multiplier": 2.0 })),
+            (
+                "ma_crossover",
+                json!({ "fast_period": 30, "slow_period": 10 }),
+            ),
+            (
+                "macd",
+                json!({ "fast_period": 26, "slow_period": 26, "signal_period": 9 }),
+            ),
+            (
+                "rsi_threshold",
+                json!({ "rsi_period": 14, "oversold": 70.0, "overbought": 30.0 }),
+            ),
+        ] {
+            assert!(
+                matches!(
+                    registry.strategy(&component(name, params)),
+                    Err(ConfigError::InvalidParams { kind: "strategy", name: actual, .. }) if actual == name
+                ),
+                "{} strategy accepted invalid params",
+                name
+            );
+        }
+    }
+}
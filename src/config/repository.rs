@@ -0,0 +1,190 @@
+use crate::{
+    portfolio::{
+        position::{Position, PositionId},
+        repository::{
+            error::RepositoryError,
+            file::{Config as FileConfig, FileRepository},
+            in_memory::InMemoryRepository,
+            redis::{
+                pool::{Config as PoolConfig, RedisPool},
+                Config as RedisConfig, RedisRepository,
+            },
+            BalanceHandler, PositionHandler, StatisticHandler, TransactionHandler,
+        },
+        Balance,
+    },
+    statistic::summary::PositionSummariser,
+};
+use barter_integration::model::{Market, MarketId};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt::Debug;
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use crate::portfolio::repository::sqlite::{Config as SqliteConfig, SqliteRepository};
+
+/// Repository used to persist the Portfolio state, selected by the `kind` field.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RepositoryConfig {
+    /// [`InMemoryRepository`] - no fault tolerant guarantees.
+    #[default]
+    InMemory,
+    /// [`RedisRepository`] connected to the Redis server at the configured uri.
+    Redis(RedisConfig),
+    /// [`FileRepository`] stored in the configured directory.
+    File(FileConfig),
+    /// [`SqliteRepository`] stored in the configured database file. Requires the `sqlite`
+    /// feature.
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteConfig),
+}
+
+impl RepositoryConfig {
+    /// Construct the [`ConfiguredRepository`] described by this [`RepositoryConfig`].
+    pub fn init<Statistic>(self) -> Result<ConfiguredRepository<Statistic>, RepositoryError>
+    where
+        Statistic: PositionSummariser + Serialize + DeserializeOwned,
+    {
+        Ok(match self {
+            Self::InMemory => ConfiguredRepository::InMemory(InMemoryRepository::new()),
+            Self::Redis(cfg) => ConfiguredRepository::Redis(RedisRepository::with_pool(
+                RedisPool::new(&cfg.uri, PoolConfig::default())
+                    .map_err(|error| RepositoryError::ConnectionError(error.to_string()))?,
+            )),
+            Self::File(cfg) => ConfiguredRepository::File(FileRepository::open(cfg)?),
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(cfg) => ConfiguredRepository::Sqlite(SqliteRepository::open(cfg)?),
+        })
+    }
+}
+
+/// Repository selected at runtime via a [`RepositoryConfig`]. Implements [`PositionHandler`],
+/// [`BalanceHandler`], [`StatisticHandler`] & [`TransactionHandler`] by delegating to the
+/// selected repository.
+pub enum ConfiguredRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    InMemory(InMemoryRepository<Statistic>),
+    Redis(RedisRepository<Statistic>),
+    File(FileRepository<Statistic>),
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteRepository<Statistic>),
+}
+
+/// Delegate a method call to the repository selected by a [`ConfiguredRepository`].
+macro_rules! delegate {
+    ($self:ident, $repository:ident => $call:expr) => {
+        match $self {
+            Self::InMemory($repository) => $call,
+            Self::Redis($repository) => $call,
+            Self::File($repository) => $call,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite($repository) => $call,
+        }
+    };
+}
+
+impl<Statistic> Debug for ConfiguredRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        delegate!(self, repository => repository.fmt(f))
+    }
+}
+
+impl<Statistic> PositionHandler for ConfiguredRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_open_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        delegate!(self, repository => repository.set_open_position(engine_id, position))
+    }
+
+    fn get_open_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        delegate!(self, repository => repository.get_open_position(position_id))
+    }
+
+    fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+        &mut self,
+        engine_id: Uuid,
+        markets: Markets,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        delegate!(self, repository => repository.get_open_positions(engine_id, markets))
+    }
+
+    fn remove_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        delegate!(self, repository => repository.remove_position(position_id))
+    }
+
+    fn set_exited_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        delegate!(self, repository => repository.set_exited_position(engine_id, position))
+    }
+
+    fn get_exited_positions(&mut self, engine_id: Uuid) -> Result<Vec<Position>, RepositoryError> {
+        delegate!(self, repository => repository.get_exited_positions(engine_id))
+    }
+}
+
+impl<Statistic> BalanceHandler for ConfiguredRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
+        delegate!(self, repository => repository.set_balance(engine_id, balance))
+    }
+
+    fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+        delegate!(self, repository => repository.get_balance(engine_id))
+    }
+}
+
+impl<Statistic> StatisticHandler<Statistic> for ConfiguredRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_statistics(
+        &mut self,
+        market_id: MarketId,
+        statistic: Statistic,
+    ) -> Result<(), RepositoryError> {
+        delegate!(self, repository => repository.set_statistics(market_id, statistic))
+    }
+
+    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+        delegate!(self, repository => repository.get_statistics(market_id))
+    }
+}
+
+impl<Statistic> TransactionHandler for ConfiguredRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn begin_transaction(&mut self) -> Result<(), RepositoryError> {
+        delegate!(self, repository => repository.begin_transaction())
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), RepositoryError> {
+        delegate!(self, repository => repository.commit_transaction())
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), RepositoryError> {
+        delegate!(self, repository => repository.rollback_transaction())
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    dashboard::error::DashboardError,
+    event::Event,
+    portfolio::{position::Position, Balance},
+    statistic::summary::trading::TradingSummary,
+};
+use barter_integration::model::Market;
+use futures::{Stream, StreamExt};
+use hyper::{
+    client::HttpConnector,
+    header::{HeaderValue, AUTHORIZATION},
+    Body, Method, Request,
+};
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message};
+
+/// Client for the HTTP & WebSocket API served by the [`Server`](crate::server::Server) of an
+/// [`Engine`](crate::engine::Engine).
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    /// Base URL of the [`Server`](crate::server::Server) (eg/ http://127.0.0.1:8080).
+    base_url: String,
+    /// Value of the `Authorization` header sent with every request.
+    authorization: String,
+    http: hyper::Client<HttpConnector>,
+}
+
+impl ApiClient {
+    /// Constructs a new [`ApiClient`] for the [`Server`](crate::server::Server) at the provided
+    /// base URL, authenticating with the provided API token.
+    pub fn new<Url, Token>(base_url: Url, api_token: Token) -> Self
+    where
+        Url: Into<String>,
+        Token: AsRef<str>,
+    {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            authorization: format!("Bearer {}", api_token.as_ref()),
+            http: hyper::Client::new(),
+        }
+    }
+
+    /// Fetch the open [`Position`]s of the [`Engine`](crate::engine::Engine).
+    pub async fn open_positions(&self) -> Result<Vec<Position>, DashboardError> {
+        self.get("/positions").await
+    }
+
+    /// Fetch the current [`Balance`] of the [`Engine`](crate::engine::Engine).
+    pub async fn balance(&self) -> Result<Balance, DashboardError> {
+        self.get("/balance").await
+    }
+
+    /// Fetch the [`TradingSummary`] of every [`Market`], keyed by
+    /// [`MarketId`](barter_integration::model::MarketId). Requires the
+    /// [`Engine`](crate::engine::Engine) statistics to be [`TradingSummary`]s.
+    pub async fn statistics(&self) -> Result<BTreeMap<String, TradingSummary>, DashboardError> {
+        self.get("/statistics").await
+    }
+
+    /// Exit the open [`Position`] in the provided [`Market`].
+    pub async fn exit_position(&self, market: &Market) -> Result<(), DashboardError> {
+        self.post("/positions/exit", serde_json::to_vec(market)?)
+            .await
+    }
+
+    /// Exit every open [`Position`].
+    pub async fn exit_all_positions(&self) -> Result<(), DashboardError> {
+        self.post("/positions/exit", Vec::new()).await
+    }
+
+    /// Terminate the [`Engine`](crate::engine::Engine) with the provided message.
+    pub async fn terminate(&self, message: &str) -> Result<(), DashboardError> {
+        self.post("/terminate", message.as_bytes().to_vec()).await
+    }
+
+    /// Connect to the [`Event`] stream WebSocket, returning a [`Stream`] of every [`Event`] sent
+    /// by the [`Engine`](crate::engine::Engine) from now on.
+    pub async fn events(
+        &self,
+    ) -> Result<impl Stream<Item = Result<Event, DashboardError>>, DashboardError> {
+        let url = format!(
+            "{}/events",
+            self.base_url
+                .replacen("https://", "wss://", 1)
+                .replacen("http://", "ws://", 1)
+        );
+
+        let mut request = url.into_client_request()?;
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, self.authorization_header()?);
+
+        let (websocket, _) = tokio_tungstenite::connect_async(request).await?;
+
+        Ok(websocket.filter_map(|message| async move {
+            match message {
+                Ok(Message::Text(text)) => {
+                    Some(serde_json::from_str(&text).map_err(DashboardError::from))
+                }
+                Ok(_) => None,
+                Err(error) => Some(Err(DashboardError::from(error))),
+            }
+        }))
+    }
+
+    fn authorization_header(&self) -> Result<HeaderValue, DashboardError> {
+        HeaderValue::from_str(&self.authorization)
+            .map_err(|error| DashboardError::Request(error.into()))
+    }
+
+    async fn get<Response: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Response, DashboardError> {
+        let body = self.send(Method::GET, path, Vec::new()).await?;
+        serde_json::from_slice(&body).map_err(DashboardError::from)
+    }
+
+    async fn post(&self, path: &str, body: Vec<u8>) -> Result<(), DashboardError> {
+        self.send(Method::POST, path, body).await.map(|_| ())
+    }
+
+    /// Send a request, returning the response body if the [`Server`](crate::server::Server)
+    /// responded with a success status.
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Vec<u8>,
+    ) -> Result<hyper::body::Bytes, DashboardError> {
+        let request = Request::builder()
+            .method(method)
+            .uri(format!("{}{}", self.base_url, path))
+            .header(AUTHORIZATION, self.authorization_header()?)
+            .body(Body::from(body))?;
+
+        let response = self.http.request(request).await?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+
+        if status.is_success() {
+            Ok(body)
+        } else {
+            Err(DashboardError::Api {
+                status: status.as_u16(),
+                message: String::from_utf8_lossy(&body).into_owned(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::Command,
+        event::{sink::BroadcastEventTx, MessageTransmitter},
+        server::{Server, ServerConfig},
+    };
+    use barter_integration::model::InstrumentKind;
+    use chrono::{DateTime, Utc};
+    use tokio::sync::{broadcast, mpsc};
+
+    fn balance() -> Balance {
+        Balance {
+            time: DateTime::<Utc>::MIN_UTC,
+            total: 1000.0,
+            available: 1000.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn api_client_interacts_with_server() {
+        let (command_tx, mut command_rx) = mpsc::channel(10);
+        let (event_tx, _) = broadcast::channel(10);
+        let mut events = BroadcastEventTx::new(event_tx);
+
+        let (address, server) = Server::builder()
+            .config(ServerConfig {
+                address: "127.0.0.1:0".parse().unwrap(),
+                api_token: "secret".to_owned(),
+            })
+            .command_tx(command_tx)
+            .events(events.clone())
+            .build()
+            .unwrap()
+            .bind(futures::future::pending())
+            .unwrap();
+        tokio::spawn(server);
+
+        let client = ApiClient::new(format!("http://{}/", address), "secret");
+        let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+        // Mock Engine answering the fetch Commands & forwarding the others
+        let (actioned_tx, mut actioned_rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(command) = command_rx.recv().await {
+                match command {
+                    Command::FetchBalance(balance_tx) => {
+                        let _ = balance_tx.send(Ok(balance()));
+                    }
+                    command => {
+                        let _ = actioned_tx.send(command);
+                    }
+                }
+            }
+        });
+
+        assert_eq!(client.balance().await.unwrap(), balance());
+
+        client.exit_position(&market).await.unwrap();
+        assert!(matches!(
+            actioned_rx.recv().await,
+            Some(Command::ExitPosition(exited)) if exited == market
+        ));
+
+        client.terminate("done").await.unwrap();
+        assert!(matches!(
+            actioned_rx.recv().await,
+            Some(Command::Terminate(message)) if message == "done"
+        ));
+
+        let mut stream = Box::pin(client.events().await.unwrap());
+        events.send(Event::Balance(balance()));
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            Event::Balance(balance())
+        );
+
+        // Invalid API token is rejected
+        let unauthorised = ApiClient::new(format!("http://{}", address), "wrong");
+        assert!(matches!(
+            unauthorised.balance().await,
+            Err(DashboardError::Api { status: 401, .. })
+        ));
+    }
+}
@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+/// All errors generated in the barter::dashboard module.
+#[derive(Error, Debug)]
+pub enum DashboardError {
+    #[error("Failed to send HTTP request: {0}")]
+    Http(#[from] hyper::Error),
+
+    #[error("Failed to build HTTP request: {0}")]
+    Request(#[from] hyper::http::Error),
+
+    #[error("Server responded with {status}: {message}")]
+    Api { status: u16, message: String },
+
+    #[error("Failed to deserialise response: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Event stream WebSocket error: {0}")]
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+
+    #[error("Failed to access the terminal: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for DashboardError {
+    fn from(error: tokio_tungstenite::tungstenite::Error) -> Self {
+        Self::WebSocket(Box::new(error))
+    }
+}
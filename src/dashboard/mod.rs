@@ -0,0 +1,526 @@
+use crate::{
+    event::Event,
+    execution::FillEvent,
+    portfolio::{
+        position::{Position, PositionId},
+        Balance,
+    },
+    statistic::summary::{trading::TradingSummary, TableBuilder},
+};
+use barter_integration::model::{Market, MarketId};
+use prettytable::{Cell, Row};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::{self, Display, Formatter, Write},
+    time::{Duration, Instant},
+};
+
+/// Barter dashboard module specific errors.
+pub mod error;
+
+/// Client for the HTTP & WebSocket API served by the [`Server`](crate::server::Server).
+pub mod client;
+
+/// Raw terminal mode & keyboard input parsing.
+pub mod terminal;
+
+/// Number of the most recent [`FillEvent`]s displayed.
+const RECENT_FILLS: usize = 10;
+
+/// Key pressed by the dashboard user.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Key {
+    Up,
+    Down,
+    Char(char),
+}
+
+/// Action requested by the dashboard user that must be sent to the
+/// [`Engine`](crate::engine::Engine).
+#[derive(Clone, PartialEq, Debug)]
+pub enum Action {
+    /// Exit the [`Position`] in the [`Market`] (key: x).
+    ExitPosition(Market),
+    /// Exit every open [`Position`] (key: X).
+    ExitAllPositions,
+    /// Terminate the [`Engine`](crate::engine::Engine) (key: T).
+    Terminate,
+    /// Quit the dashboard, leaving the [`Engine`](crate::engine::Engine) running (key: q).
+    Quit,
+}
+
+/// Live view of an [`Engine`](crate::engine::Engine), maintained from it's [`Event`] stream &
+/// periodic snapshots fetched from the [`Server`](crate::server::Server).
+#[derive(Debug)]
+pub struct DashboardState {
+    /// Open [`Position`]s keyed by [`PositionId`].
+    open_positions: BTreeMap<PositionId, Position>,
+    /// Index of the selected open [`Position`].
+    selected: usize,
+    /// Most recent [`FillEvent`]s, newest first.
+    recent_fills: VecDeque<FillEvent>,
+    balance: Option<Balance>,
+    /// [`TradingSummary`] of each [`Market`], keyed by [`MarketId`].
+    statistics: BTreeMap<String, TradingSummary>,
+    /// Time the last MarketEvent was received for each [`Market`], keyed by [`MarketId`].
+    feeds: BTreeMap<String, Instant>,
+    /// Duration without a MarketEvent after which a [`Market`] feed is displayed as stale.
+    stale_after: Duration,
+    /// Whether the [`Event`] stream is currently connected.
+    connected: bool,
+    /// Outcome of the last [`Action`] or connection error, displayed to the user.
+    status: String,
+}
+
+impl DashboardState {
+    /// Constructs a new empty [`DashboardState`], displaying a [`Market`] feed as stale if no
+    /// MarketEvent is received for the provided duration.
+    pub fn new(stale_after: Duration) -> Self {
+        Self {
+            open_positions: BTreeMap::new(),
+            selected: 0,
+            recent_fills: VecDeque::with_capacity(RECENT_FILLS),
+            balance: None,
+            statistics: BTreeMap::new(),
+            feeds: BTreeMap::new(),
+            stale_after,
+            connected: false,
+            status: String::new(),
+        }
+    }
+
+    /// Update the [`DashboardState`] with an [`Event`] received from the [`Event`] stream.
+    pub fn apply_event(&mut self, event: Event) {
+        match event {
+            Event::Market(market) => {
+                let market_id = MarketId::new(&market.exchange, &market.instrument);
+                self.feeds.insert(market_id.0, Instant::now());
+            }
+            Event::Fill(fill) => {
+                self.recent_fills.push_front(fill);
+                self.recent_fills.truncate(RECENT_FILLS);
+            }
+            Event::PositionNew(position) => {
+                self.open_positions
+                    .insert(position.position_id.clone(), position);
+            }
+            Event::PositionUpdate(update) => {
+                if let Some(position) = self.open_positions.get_mut(&update.position_id) {
+                    position.current_symbol_price = update.current_symbol_price;
+                    position.current_value_gross = update.current_value_gross;
+                    position.unrealised_profit_loss = update.unrealised_profit_loss;
+                }
+            }
+            Event::PositionExit(exit) => {
+                self.open_positions.remove(&exit.position_id);
+                self.balance = Some(exit.exit_balance);
+                self.clamp_selection();
+            }
+            Event::Balance(balance) => self.balance = Some(balance),
+            _ => {}
+        }
+    }
+
+    /// Replace the open [`Position`]s with a snapshot fetched from the
+    /// [`Engine`](crate::engine::Engine).
+    pub fn set_open_positions(&mut self, positions: Vec<Position>) {
+        self.open_positions = positions
+            .into_iter()
+            .map(|position| (position.position_id.clone(), position))
+            .collect();
+        self.clamp_selection();
+    }
+
+    pub fn set_balance(&mut self, balance: Balance) {
+        self.balance = Some(balance);
+    }
+
+    pub fn set_statistics(&mut self, statistics: BTreeMap<String, TradingSummary>) {
+        self.statistics = statistics;
+    }
+
+    pub fn set_connected(&mut self, connected: bool) {
+        self.connected = connected;
+    }
+
+    /// Set the status message displayed to the user.
+    pub fn set_status<S: Into<String>>(&mut self, status: S) {
+        self.status = status.into();
+    }
+
+    /// [`Market`] of the selected open [`Position`], if any.
+    pub fn selected_market(&self) -> Option<Market> {
+        self.open_positions
+            .values()
+            .nth(self.selected)
+            .map(|position| Market::new(position.exchange.clone(), position.instrument.clone()))
+    }
+
+    /// Handle a [`Key`] pressed by the user, returning the [`Action`] it requests, if any.
+    pub fn handle_key(&mut self, key: Key) -> Option<Action> {
+        match key {
+            Key::Up | Key::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                None
+            }
+            Key::Down | Key::Char('j') => {
+                self.selected += 1;
+                self.clamp_selection();
+                None
+            }
+            Key::Char('x') => {
+                let market = self.selected_market();
+                if market.is_none() {
+                    self.set_status("no open Position selected");
+                }
+                market.map(Action::ExitPosition)
+            }
+            Key::Char('X') => Some(Action::ExitAllPositions),
+            Key::Char('T') => Some(Action::Terminate),
+            Key::Char('q') => Some(Action::Quit),
+            _ => None,
+        }
+    }
+
+    fn clamp_selection(&mut self) {
+        self.selected = self
+            .selected
+            .min(self.open_positions.len().saturating_sub(1));
+    }
+
+    /// Render the [`DashboardState`] as text for the terminal.
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+
+        let _ = writeln!(
+            output,
+            "Barter Dashboard  [{}]",
+            if self.connected {
+                "connected"
+            } else {
+                "disconnected"
+            }
+        );
+        match &self.balance {
+            Some(balance) => {
+                let _ = writeln!(
+                    output,
+                    "Balance: total {:.2} | available {:.2}",
+                    balance.total, balance.available
+                );
+            }
+            None => output.push_str("Balance: -\n"),
+        }
+
+        output.push_str("\nOpen Positions\n");
+        output.push_str(&self.open_positions_table().to_string());
+
+        output.push_str("\nRecent Fills\n");
+        output.push_str(&self.recent_fills_table().to_string());
+
+        output.push_str("\nStatistics\n");
+        output.push_str(&self.statistics_table().to_string());
+
+        output.push_str("\nFeed Health\n");
+        output.push_str(&self.feed_health_table(Instant::now()).to_string());
+
+        output.push_str(
+            "\n[up/k] [down/j] select | [x] exit selected | [X] exit all | [T] terminate | [q] quit\n",
+        );
+        if !self.status.is_empty() {
+            let _ = writeln!(output, "{}", self.status);
+        }
+
+        output
+    }
+
+    fn open_positions_table(&self) -> TextTable {
+        let mut table = TextTable::new(row![
+            "",
+            "Market",
+            "Side",
+            "Quantity",
+            "Entry Price",
+            "Current Price",
+            "Unrealised PnL",
+        ]);
+
+        for (index, position) in self.open_positions.values().enumerate() {
+            table.add_row(row![
+                if index == self.selected { ">" } else { "" },
+                MarketId::new(&position.exchange, &position.instrument).0,
+                format!("{:?}", position.side),
+                format!("{:.4}", position.quantity),
+                format!("{:.4}", position.enter_avg_price_gross),
+                format!("{:.4}", position.current_symbol_price),
+                format!("{:.2}", position.unrealised_profit_loss),
+            ]);
+        }
+
+        table
+    }
+
+    fn recent_fills_table(&self) -> TextTable {
+        let mut table = TextTable::new(row![
+            "Time", "Market", "Decision", "Quantity", "Value", "Fees"
+        ]);
+
+        for fill in &self.recent_fills {
+            table.add_row(row![
+                fill.time.format("%H:%M:%S"),
+                MarketId::new(&fill.exchange, &fill.instrument).0,
+                format!("{:?}", fill.decision),
+                format!("{:.4}", fill.quantity),
+                format!("{:.2}", fill.fill_value_gross),
+                format!("{:.4}", fill.fees.calculate_total_fees()),
+            ]);
+        }
+
+        table
+    }
+
+    fn statistics_table(&self) -> TextTable {
+        let mut titles = self
+            .statistics
+            .values()
+            .next()
+            .map(TableBuilder::titles)
+            .unwrap_or_else(Row::empty);
+        titles.insert_cell(0, Cell::new("Market"));
+        let mut table = TextTable::new(titles);
+
+        for (market, summary) in &self.statistics {
+            let mut row = summary.row();
+            row.insert_cell(0, Cell::new(market));
+            table.add_row(row);
+        }
+
+        table
+    }
+
+    fn feed_health_table(&self, now: Instant) -> TextTable {
+        let mut table = TextTable::new(row!["Market", "Last MarketEvent", "Status"]);
+
+        for (market, last_event) in &self.feeds {
+            let elapsed = now.saturating_duration_since(*last_event);
+            table.add_row(row![
+                market,
+                format!("{:.1}s ago", elapsed.as_secs_f64()),
+                if elapsed > self.stale_after {
+                    "stale"
+                } else {
+                    "healthy"
+                },
+            ]);
+        }
+
+        table
+    }
+}
+
+/// Plain text table of prettytable [`Row`]s.
+///
+/// Rendering a prettytable [`Table`](prettytable::Table) transmutes it into a `TableSlice`, which
+/// is unsound (RUSTSEC-2022-0074) & crashes on recent compilers, so the dashboard formats the
+/// [`Row`] cell contents itself.
+#[derive(Debug)]
+struct TextTable {
+    titles: Row,
+    rows: Vec<Row>,
+}
+
+impl TextTable {
+    fn new(titles: Row) -> Self {
+        Self {
+            titles,
+            rows: Vec::new(),
+        }
+    }
+
+    fn add_row(&mut self, row: Row) {
+        self.rows.push(row);
+    }
+
+    fn cells(row: &Row) -> Vec<String> {
+        row.iter().map(Cell::get_content).collect()
+    }
+}
+
+impl Display for TextTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let lines = std::iter::once(&self.titles)
+            .chain(&self.rows)
+            .map(Self::cells)
+            .collect::<Vec<_>>();
+
+        let mut widths = Vec::<usize>::new();
+        for line in &lines {
+            for (column, cell) in line.iter().enumerate() {
+                let width = cell.chars().count();
+                match widths.get_mut(column) {
+                    Some(max) => *max = (*max).max(width),
+                    None => widths.push(width),
+                }
+            }
+        }
+
+        for (index, line) in lines.iter().enumerate() {
+            let line = line
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            writeln!(f, "{}", line.trim_end())?;
+
+            if index == 0 {
+                let separator = widths
+                    .iter()
+                    .map(|width| "-".repeat(*width))
+                    .collect::<Vec<_>>()
+                    .join("-+-");
+                writeln!(f, "{}", separator)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::position::{PositionExit, PositionUpdate},
+        test_util::{fill_event, position},
+    };
+    use barter_data::test_util::market_trade;
+    use barter_integration::model::{InstrumentKind, Side};
+    use chrono::{DateTime, Utc};
+
+    fn balance(total: f64) -> Balance {
+        Balance {
+            time: DateTime::<Utc>::MIN_UTC,
+            total,
+            available: total,
+        }
+    }
+
+    fn position_with_id(position_id: &str) -> Position {
+        Position {
+            position_id: position_id.to_owned(),
+            ..position()
+        }
+    }
+
+    #[test]
+    fn dashboard_state_applies_events() {
+        let mut state = DashboardState::new(Duration::from_secs(30));
+
+        state.apply_event(Event::Market(market_trade(Side::Buy)));
+        state.apply_event(Event::PositionNew(position_with_id("a")));
+        state.apply_event(Event::PositionUpdate(PositionUpdate {
+            position_id: "a".to_owned(),
+            update_time: DateTime::<Utc>::MIN_UTC,
+            current_symbol_price: 110.0,
+            current_value_gross: 110.0,
+            unrealised_profit_loss: 10.0,
+            max_adverse_excursion: 0.0,
+            max_favourable_excursion: 0.0,
+        }));
+        for _ in 0..RECENT_FILLS + 2 {
+            state.apply_event(Event::Fill(fill_event()));
+        }
+        state.apply_event(Event::Balance(balance(1000.0)));
+
+        assert_eq!(state.open_positions["a"].unrealised_profit_loss, 10.0);
+        assert_eq!(state.recent_fills.len(), RECENT_FILLS);
+        assert_eq!(state.balance, Some(balance(1000.0)));
+        assert_eq!(state.feeds.len(), 1);
+
+        let rendered = state.render();
+        assert!(rendered.contains("Balance: total 1000.00 | available 1000.00"));
+        assert!(rendered.contains("binance_eth_usdt_spot"));
+        assert!(rendered.contains("10.00"));
+        assert!(rendered.contains("healthy"));
+
+        state.apply_event(Event::PositionExit(PositionExit {
+            position_id: "a".to_owned(),
+            exit_time: DateTime::<Utc>::MIN_UTC,
+            exit_balance: balance(1010.0),
+            exit_fees: Default::default(),
+            exit_fees_total: 0.0,
+            exit_avg_price_gross: 110.0,
+            exit_value_gross: 110.0,
+            realised_profit_loss: 10.0,
+            max_adverse_excursion: 0.0,
+            max_favourable_excursion: 0.0,
+        }));
+
+        assert!(state.open_positions.is_empty());
+        assert_eq!(state.balance, Some(balance(1010.0)));
+    }
+
+    #[test]
+    fn dashboard_state_handles_keys() {
+        let mut state = DashboardState::new(Duration::from_secs(30));
+
+        // No open Position to exit
+        assert_eq!(state.handle_key(Key::Char('x')), None);
+
+        let mut btc = position_with_id("b");
+        btc.instrument = ("btc", "usdt", InstrumentKind::Spot).into();
+        state.set_open_positions(vec![position_with_id("a"), btc.clone()]);
+
+        let eth_market = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+        let btc_market = Market::new(btc.exchange.clone(), btc.instrument.clone());
+
+        assert_eq!(
+            state.handle_key(Key::Char('x')),
+            Some(Action::ExitPosition(eth_market.clone()))
+        );
+
+        // Selection is clamped to the open Positions
+        assert_eq!(state.handle_key(Key::Down), None);
+        assert_eq!(state.handle_key(Key::Char('j')), None);
+        assert_eq!(
+            state.handle_key(Key::Char('x')),
+            Some(Action::ExitPosition(btc_market))
+        );
+        assert_eq!(state.handle_key(Key::Up), None);
+        assert_eq!(state.selected_market(), Some(eth_market));
+
+        assert_eq!(
+            state.handle_key(Key::Char('X')),
+            Some(Action::ExitAllPositions)
+        );
+        assert_eq!(state.handle_key(Key::Char('T')), Some(Action::Terminate));
+        assert_eq!(state.handle_key(Key::Char('t')), None);
+        assert_eq!(state.handle_key(Key::Char('q')), Some(Action::Quit));
+    }
+
+    #[test]
+    fn text_table_aligns_columns() {
+        let mut table = TextTable::new(row!["Market", "Status"]);
+        table.add_row(row!["binance_btc_usdt_spot", "stale"]);
+
+        assert_eq!(
+            table.to_string(),
+            "Market                | Status\n\
+             ----------------------+-------\n\
+             binance_btc_usdt_spot | stale\n"
+        );
+    }
+
+    #[test]
+    fn dashboard_state_marks_stale_feeds() {
+        let mut state = DashboardState::new(Duration::from_secs(30));
+        state
+            .feeds
+            .insert("binance_btc_usdt_spot".to_owned(), Instant::now());
+
+        let later = Instant::now() + Duration::from_secs(60);
+        let table = state.feed_health_table(later).to_string();
+        assert!(table.contains("stale"));
+    }
+}
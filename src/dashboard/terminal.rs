@@ -0,0 +1,118 @@
+use crate::dashboard::Key;
+use std::io::{self, Write};
+
+/// Enter the alternate screen & hide the cursor.
+const ENTER_SCREEN: &str = "\x1b[?1049h\x1b[?25l";
+
+/// Show the cursor & leave the alternate screen.
+const LEAVE_SCREEN: &str = "\x1b[?25h\x1b[?1049l";
+
+/// Move the cursor to the top left & clear the screen.
+const CLEAR_SCREEN: &str = "\x1b[H\x1b[J";
+
+/// Guard that switches the terminal to an alternate screen with unbuffered, unechoed keyboard
+/// input, restoring the original terminal state when dropped.
+///
+/// On non-unix platforms input remains line buffered, so keys must be followed by enter.
+#[derive(Debug)]
+pub struct Terminal {
+    #[cfg(unix)]
+    original: libc::termios,
+}
+
+impl Terminal {
+    /// Switch the terminal to the dashboard mode.
+    pub fn enter() -> io::Result<Self> {
+        #[cfg(unix)]
+        let original = {
+            // SAFETY: termios is a plain C struct, fully initialised by tcgetattr before use
+            let mut original = unsafe { std::mem::zeroed::<libc::termios>() };
+            if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut original) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mut raw = original;
+            raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            original
+        };
+
+        let mut stdout = io::stdout();
+        stdout.write_all(ENTER_SCREEN.as_bytes())?;
+        stdout.flush()?;
+
+        Ok(Self {
+            #[cfg(unix)]
+            original,
+        })
+    }
+
+    /// Replace the screen contents with the provided frame.
+    pub fn draw(&self, frame: &str) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(CLEAR_SCREEN.as_bytes())?;
+        stdout.write_all(frame.as_bytes())?;
+        stdout.flush()
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(LEAVE_SCREEN.as_bytes());
+        let _ = stdout.flush();
+
+        // SAFETY: restores the termios fetched by tcgetattr in Terminal::enter
+        #[cfg(unix)]
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// Parse the [`Key`]s contained in bytes read from the terminal. Arrow keys are sent as ANSI
+/// escape sequences (eg/ ESC [ A), and unrecognised bytes are ignored.
+pub fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match &bytes[index..] {
+            [0x1b, b'[', b'A', ..] => {
+                keys.push(Key::Up);
+                index += 3;
+            }
+            [0x1b, b'[', b'B', ..] => {
+                keys.push(Key::Down);
+                index += 3;
+            }
+            [byte, ..] => {
+                if byte.is_ascii_graphic() {
+                    keys.push(Key::Char(*byte as char));
+                }
+                index += 1;
+            }
+            [] => break,
+        }
+    }
+
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_keys_handles_characters_and_arrows() {
+        assert_eq!(
+            parse_keys(b"x\x1b[A\x1b[Bq\n\x1b"),
+            vec![Key::Char('x'), Key::Up, Key::Down, Key::Char('q')]
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use crate::data::{Feed, MarketGenerator};
+use crate::data::{AsyncMarketGenerator, Feed, MarketGenerator};
+use async_trait::async_trait;
 use barter_data::model::MarketEvent;
 
 /// Historical [`Feed`] of [`MarketEvent`]s.
@@ -21,6 +22,16 @@ where
     }
 }
 
+#[async_trait]
+impl<I> AsyncMarketGenerator for MarketFeed<I>
+where
+    I: Iterator<Item = MarketEvent> + Send,
+{
+    async fn next(&mut self) -> Feed<MarketEvent> {
+        self.generate()
+    }
+}
+
 impl<I> MarketFeed<I>
 where
     I: Iterator<Item = MarketEvent>,
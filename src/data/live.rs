@@ -1,4 +1,5 @@
-use super::{error::DataError, Feed, MarketGenerator};
+use super::{error::DataError, AsyncMarketGenerator, Feed, MarketGenerator};
+use async_trait::async_trait;
 use barter_data::{
     builder::Streams,
     model::{subscription::Subscription, MarketEvent},
@@ -23,6 +24,16 @@ impl MarketGenerator for MarketFeed {
     }
 }
 
+#[async_trait]
+impl AsyncMarketGenerator for MarketFeed {
+    async fn next(&mut self) -> Feed<MarketEvent> {
+        self.market_rx
+            .recv()
+            .await
+            .map_or(Feed::Finished, Feed::Next)
+    }
+}
+
 impl MarketFeed {
     /// Initialises a live [`MarketFeed`] that yields [`MarketEvent`]s for each [`Subscription`]
     /// provided.
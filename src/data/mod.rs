@@ -1,3 +1,4 @@
+use async_trait::async_trait;
 use barter_data::model::MarketEvent;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,24 @@ pub trait MarketGenerator {
     fn generate(&mut self) -> Feed<MarketEvent>;
 }
 
+impl<Data> MarketGenerator for Box<Data>
+where
+    Data: MarketGenerator + ?Sized,
+{
+    fn generate(&mut self) -> Feed<MarketEvent> {
+        (**self).generate()
+    }
+}
+
+/// Asynchronously generates the latest [`MarketEvent`], awaiting it rather than blocking the
+/// thread. Acts as the system heartbeat for a [`Trader`](crate::engine::trader::Trader) running
+/// on the tokio runtime.
+#[async_trait]
+pub trait AsyncMarketGenerator {
+    /// Await & return the latest [`MarketEvent`].
+    async fn next(&mut self) -> Feed<MarketEvent>;
+}
+
 /// Communicates the state of the [`Feed`] as well as the next event.
 #[derive(Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub enum Feed<Event> {
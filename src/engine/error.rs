@@ -1,4 +1,8 @@
-use crate::portfolio::repository::error::RepositoryError;
+use crate::{
+    execution::error::ExecutionError,
+    portfolio::{error::PortfolioError, repository::error::RepositoryError},
+};
+use barter_integration::model::Market;
 use thiserror::Error;
 
 /// All errors generated in barter-engine.
@@ -9,4 +13,28 @@ pub enum EngineError {
 
     #[error("Failed to interact with repository")]
     RepositoryInteractionError(#[from] RepositoryError),
+
+    #[error("Failed to serialise statistics: {0}")]
+    StatisticSerialisationError(#[from] serde_json::Error),
+
+    #[error("Engine has no TraderFactory to construct a Trader for an added Market")]
+    TraderFactoryMissing,
+
+    #[error("Failed to construct Trader: {0}")]
+    TraderConstruction(String),
+
+    #[error("Market is already being traded by the Engine: {0:?}")]
+    MarketAlreadyTraded(Market),
+
+    #[error("Market is not being traded by the Engine: {0:?}")]
+    MarketNotTraded(Market),
+
+    #[error("Failed to remove Market from the Engine {0:?}: {1}")]
+    MarketRemoval(Market, String),
+
+    #[error("Trader failed to interact with the Portfolio: {0}")]
+    Portfolio(#[from] PortfolioError),
+
+    #[error("Trader failed to execute an OrderEvent: {0}")]
+    Execution(#[from] ExecutionError),
 }
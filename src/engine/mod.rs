@@ -1,21 +1,31 @@
 use crate::{
-    data::MarketGenerator,
-    engine::{error::EngineError, trader::Trader},
+    data::{AsyncMarketGenerator, MarketGenerator},
+    engine::{
+        error::EngineError,
+        outcome::{SessionOutcome, Termination, TraderFailure},
+        trader::{Trader, TraderFactory},
+    },
     event::{Event, MessageTransmitter},
-    execution::ExecutionClient,
+    execution::{AccountReporter, AsyncExecutionClient, ExecutionClient},
     portfolio::{
-        position::Position,
-        repository::{PositionHandler, StatisticHandler},
-        FillUpdater, MarketUpdater, OrderGenerator,
+        error::PortfolioError,
+        position::{Position, PositionLeg},
+        repository::{BalanceHandler, PositionHandler, StatisticHandler},
+        Balance, FillUpdater, MarketInitialiser, MarketUpdater, OrderGenerator, Reconciler,
     },
-    statistic::summary::{PositionSummariser, TableBuilder},
-    strategy::SignalGenerator,
+    statistic::summary::{session::SessionSummary, PositionSummariser, TableBuilder},
+    strategy::{Signal, SignalGenerator},
 };
 use barter_integration::model::{Market, MarketId};
 use parking_lot::Mutex;
-use prettytable::Table;
 use serde::Serialize;
-use std::{collections::HashMap, fmt::Debug, sync::Arc, thread};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Debug, Formatter},
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+    thread,
+};
 use tokio::sync::{mpsc, oneshot};
 use tracing::{error, info, warn};
 use uuid::Uuid;
@@ -23,11 +33,82 @@ use uuid::Uuid;
 /// Barter Engine module specific errors.
 pub mod error;
 
+/// Outcome of a trading session, including the [`SessionSummary`], final
+/// [`Balance`](crate::portfolio::Balance), open [`Position`]s, [`Trader`] failures & the reason
+/// the [`Engine`] stopped.
+pub mod outcome;
+
 /// Contains the trading event loop for a Trader capable of trading a single market pair. A Trader
 /// has it's own Data handler, Strategy & Execution handler, as well as shared access to a global
 /// Portfolio instance.
 pub mod trader;
 
+/// Capacity of the [`Command`] channel of every [`Trader`] added to a running [`Engine`] via
+/// [`Command::AddMarket`].
+pub const TRADER_COMMAND_CAPACITY: usize = 10;
+
+/// Notification sent when a [`Trader`] stops, containing it's [`Market`], or the
+/// [`TraderFailure`] if it panicked or returned an error.
+type TraderStopped = Result<Market, TraderFailure>;
+
+/// Runs a [`Trader`] (eg/ on it's own thread), sending a [`TraderStopped`] notification when it
+/// stops.
+type TraderSpawner<EventTx, Statistic, Portfolio, Data, Strategy, Execution> = fn(
+    Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>,
+    mpsc::UnboundedSender<TraderStopped>,
+);
+
+/// Reconciles the persisted Portfolio state of every [`Market`] traded against the account state
+/// reported by the execution venue when the [`Engine`] starts running (eg/ after resuming from a
+/// restart). An [`Event::Discrepancy`] is sent on the provided event transmitter for every
+/// difference found, leaving the persisted state untouched.
+pub struct Reconciliation<Portfolio> {
+    reconcile: Box<dyn FnOnce(&mut Portfolio, &[Market]) + Send + Sync>,
+}
+
+impl<Portfolio> Debug for Reconciliation<Portfolio> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reconciliation").finish()
+    }
+}
+
+impl<Portfolio> Reconciliation<Portfolio> {
+    /// Reconcile the persisted state of the provided [`Market`]s.
+    fn reconcile(self, portfolio: &mut Portfolio, markets: &[Market]) {
+        (self.reconcile)(portfolio, markets)
+    }
+}
+
+impl<Portfolio> Reconciliation<Portfolio>
+where
+    Portfolio: Reconciler,
+{
+    /// Constructs a new [`Reconciliation`] using the provided [`AccountReporter`]. Values within
+    /// the absolute tolerance are considered equal.
+    pub fn new<Reporter, EventTx>(reporter: Reporter, tolerance: f64, mut event_tx: EventTx) -> Self
+    where
+        Reporter: AccountReporter + Send + Sync + 'static,
+        EventTx: MessageTransmitter<Event> + Send + Sync + 'static,
+    {
+        Self {
+            reconcile: Box::new(move |portfolio, markets| {
+                let discrepancies = reporter
+                    .fetch_account()
+                    .map_err(PortfolioError::from)
+                    .and_then(|account| portfolio.reconcile(markets, &account, tolerance));
+
+                match discrepancies {
+                    Ok(discrepancies) => event_tx.send_many(discrepancies),
+                    Err(error) => error!(
+                        %error,
+                        "failed to reconcile persisted Portfolio state against execution venue"
+                    ),
+                }
+            }),
+        }
+    }
+}
+
 /// Commands that can be actioned by an [`Engine`] and it's associated [`Trader`]s.
 #[derive(Debug)]
 pub enum Command {
@@ -35,6 +116,25 @@ pub enum Command {
     /// `oneshot::Sender`. Involves the [`Engine`] only.
     FetchOpenPositions(oneshot::Sender<Result<Vec<Position>, EngineError>>),
 
+    /// Fetches the [`Engine`]'s current [`Balance`] and sends it on the provided
+    /// `oneshot::Sender`. Involves the [`Engine`] only.
+    FetchBalance(oneshot::Sender<Result<Balance, EngineError>>),
+
+    /// Fetches a [`PositionLeg`] detailing the margin & PnL of every open (tagged or untagged)
+    /// [`Position`] held in the [`Market`] (eg/ it's [`PositionMode::Hedge`] legs), and sends
+    /// them on the provided `oneshot::Sender`. Involves the [`Engine`] only.
+    ///
+    /// [`PositionMode::Hedge`]: crate::portfolio::PositionMode::Hedge
+    FetchLegs(
+        Market,
+        oneshot::Sender<Result<Vec<PositionLeg>, EngineError>>,
+    ),
+
+    /// Fetches the statistics of every [`Market`] traded by the [`Engine`], serialised as JSON &
+    /// keyed by [`MarketId`], and sends them on the provided `oneshot::Sender`. Involves the
+    /// [`Engine`] only.
+    FetchStatistics(oneshot::Sender<Result<BTreeMap<String, serde_json::Value>, EngineError>>),
+
     /// Terminate every running [`Trader`] associated with this [`Engine`]. Involves all [`Trader`]s.
     Terminate(String),
 
@@ -44,6 +144,24 @@ pub enum Command {
     /// Exit a [`Position`]. Uses the [`Market`] provided to route this [`Command`] to the relevant
     /// [`Trader`] instance. Involves one [`Trader`].
     ExitPosition(Market),
+
+    /// Action an advisory [`Signal`] (eg/ generated remotely for a [`Market`]). Uses the
+    /// [`Signal`]'s [`Market`] to route this [`Command`] to the relevant [`Trader`] instance.
+    /// Involves one [`Trader`].
+    Signal(Signal),
+
+    /// Add a [`Market`] to the running [`Engine`]. Initialises the [`Market`] statistics in the
+    /// Portfolio (see [`MarketInitialiser`]) & runs a new [`Trader`] constructed by the
+    /// [`Engine`]'s [`TraderFactory`], sending the outcome on the provided `oneshot::Sender`.
+    /// Involves the new [`Trader`].
+    AddMarket(Market, oneshot::Sender<Result<(), EngineError>>),
+
+    /// Retire a [`Market`] from the running [`Engine`]. The [`Market`]'s [`Trader`] exits it's open
+    /// [`Position`] before terminating. Once the [`Trader`] has stopped, the outcome is sent on the
+    /// provided `oneshot::Sender`, failing if the [`Trader`] failed or a [`Position`] is still
+    /// open in the [`Market`]. The [`Market`] statistics are still included in the
+    /// [`SessionSummary`]. Involves one [`Trader`].
+    RemoveMarket(Market, oneshot::Sender<Result<(), EngineError>>),
 }
 
 /// Lego components for constructing an [`Engine`] via the new() constructor method.
@@ -53,9 +171,9 @@ where
     EventTx: MessageTransmitter<Event> + Send,
     Statistic: Serialize + Send,
     Portfolio: MarketUpdater + OrderGenerator + FillUpdater + Send,
-    Data: MarketGenerator + Send,
+    Data: Send,
     Strategy: SignalGenerator + Send,
-    Execution: ExecutionClient + Send,
+    Execution: Send,
 {
     /// Unique identifier for an [`Engine`] in Uuid v4 format. Used as a unique identifier seed for
     /// the Portfolio, Trader & Positions associated with this [`Engine`].
@@ -72,6 +190,17 @@ where
     /// Uses trading session's exited [`Position`]s to calculate an average statistical summary
     /// across all [`Market`]s traded.
     pub statistics_summary: Statistic,
+    /// Optional [`TraderFactory`] used to construct the [`Trader`] for every [`Market`] added via
+    /// [`Command::AddMarket`].
+    pub trader_factory:
+        Option<TraderFactory<EventTx, Statistic, Portfolio, Data, Strategy, Execution>>,
+    /// Optional [`Reconciliation`] of the persisted Portfolio state against the execution venue,
+    /// performed when the [`Engine`] starts running.
+    pub reconciliation: Option<Reconciliation<Portfolio>>,
+    /// Optional mpsc::UnboundedReceiver for receiving the cross-market [`Signal`]s generated by
+    /// [`CrossMarketStrategy`](crate::strategy::cross_market::CrossMarketStrategy) handles, which
+    /// are routed to the relevant [`Trader`].
+    pub signal_rx: Option<mpsc::UnboundedReceiver<Signal>>,
 }
 
 /// Multi-threaded Trading Engine capable of trading with an arbitrary number of [`Trader`]s, one
@@ -87,15 +216,16 @@ where
     EventTx: MessageTransmitter<Event>,
     Statistic: PositionSummariser + Serialize + Send,
     Portfolio: PositionHandler
+        + BalanceHandler
         + StatisticHandler<Statistic>
         + MarketUpdater
         + OrderGenerator
         + FillUpdater
         + Send
         + 'static,
-    Data: MarketGenerator + Send + 'static,
+    Data: Send + 'static,
     Strategy: SignalGenerator + Send,
-    Execution: ExecutionClient + Send,
+    Execution: Send,
 {
     /// Unique identifier for an [`Engine`] in Uuid v4 format. Used as a unique identifier seed for
     /// the Portfolio, Trader & Positions associated with this [`Engine`].
@@ -113,6 +243,19 @@ where
     /// Uses trading session's exited [`Position`]s to calculate an average statistical summary
     /// across all [`Market`]s traded.
     statistics_summary: Statistic,
+    /// Optional [`TraderFactory`] used to construct the [`Trader`] for every [`Market`] added via
+    /// [`Command::AddMarket`].
+    trader_factory: Option<TraderFactory<EventTx, Statistic, Portfolio, Data, Strategy, Execution>>,
+    /// Optional [`Reconciliation`] of the persisted Portfolio state against the execution venue,
+    /// performed when the [`Engine`] starts running.
+    reconciliation: Option<Reconciliation<Portfolio>>,
+    /// Optional mpsc::UnboundedReceiver for receiving the cross-market [`Signal`]s generated by
+    /// [`CrossMarketStrategy`](crate::strategy::cross_market::CrossMarketStrategy) handles, which
+    /// are routed to the relevant [`Trader`].
+    signal_rx: Option<mpsc::UnboundedReceiver<Signal>>,
+    /// [`Market`]s retired via [`Command::RemoveMarket`], whose statistics are still included in
+    /// the [`SessionSummary`].
+    retired_markets: Vec<Market>,
 }
 
 impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -121,15 +264,17 @@ where
     EventTx: MessageTransmitter<Event> + Send + 'static,
     Statistic: PositionSummariser + TableBuilder + Serialize + Send + 'static,
     Portfolio: PositionHandler
+        + BalanceHandler
         + StatisticHandler<Statistic>
         + MarketUpdater
+        + MarketInitialiser
         + OrderGenerator
         + FillUpdater
         + Send
         + 'static,
-    Data: MarketGenerator + Send,
+    Data: Send,
     Strategy: SignalGenerator + Send + 'static,
-    Execution: ExecutionClient + Send + 'static,
+    Execution: Send + 'static,
 {
     /// Constructs a new trading [`Engine`] instance using the provided [`EngineLego`].
     pub fn new(lego: EngineLego<EventTx, Statistic, Portfolio, Data, Strategy, Execution>) -> Self {
@@ -144,6 +289,10 @@ where
             traders: lego.traders,
             trader_command_txs: lego.trader_command_txs,
             statistics_summary: lego.statistics_summary,
+            trader_factory: lego.trader_factory,
+            reconciliation: lego.reconciliation,
+            signal_rx: lego.signal_rx,
+            retired_markets: Vec::new(),
         }
     }
 
@@ -152,20 +301,74 @@ where
         EngineBuilder::new()
     }
 
-    /// Run the trading [`Engine`]. Spawns a thread for each [`Trader`] to run on. Asynchronously
-    /// receives [`Command`]s via the `command_rx` and actions them
-    /// (eg/ terminate_traders, fetch_open_positions). If all of the [`Trader`]s stop organically
-    /// (eg/ due to a finished [`MarketGenerator`]), the [`Engine`] terminates & prints a summary
-    /// for the trading session.
-    pub async fn run(mut self) {
-        // Run Traders on threads & send notification when they have stopped organically
-        let mut notify_traders_stopped = self.run_traders().await;
+    /// Runs every [`Trader`] using the provided [`TraderSpawner`], then asynchronously receives
+    /// [`Command`]s via the `command_rx` and actions them (eg/ terminate_traders,
+    /// fetch_open_positions, add_market) until every running [`Trader`] stops organically
+    /// (eg/ due to a finished [`MarketGenerator`]) or the [`Engine`] is commanded to terminate.
+    /// Returns the [`SessionOutcome`] of the trading session.
+    async fn run_until_terminated(
+        mut self,
+        spawn_trader: TraderSpawner<EventTx, Statistic, Portfolio, Data, Strategy, Execution>,
+    ) -> SessionOutcome<Statistic> {
+        // Create channel to notify the Engine when each Trader stops, including any failure
+        let (stopped_tx, mut stopped_rx) = mpsc::unbounded_channel();
+
+        // Reconcile the persisted Portfolio state against the execution venue before trading
+        if let Some(reconciliation) = self.reconciliation.take() {
+            let markets = self
+                .trader_command_txs
+                .keys()
+                .cloned()
+                .collect::<Vec<Market>>();
+            reconciliation.reconcile(&mut self.portfolio.lock(), &markets);
+        }
+
+        // Starting Balance of the trading session, used to start the SessionSummary equity curve
+        let starting_balance = self
+            .portfolio
+            .lock()
+            .get_balance(self.engine_id)
+            .unwrap_or_else(|error| {
+                warn!(
+                    ?error,
+                    "failed to get starting Balance, trading session equity curve starts at zero"
+                );
+                Balance::default()
+            });
+
+        // Run the initial Traders
+        let traders = std::mem::take(&mut self.traders);
+        let mut running_traders = traders.len();
+        for trader in traders {
+            spawn_trader(trader, stopped_tx.clone());
+        }
+
+        // Pending Command::RemoveMarket outcomes, sent once the retired Market's Trader stops
+        let mut removals = HashMap::new();
+
+        // Cross-market Signals are received on their own channel, if any
+        let mut signal_rx = self.signal_rx.take();
+
+        let mut trader_failures = Vec::new();
+        let termination = loop {
+            if running_traders == 0 {
+                break Termination::Organic;
+            }
 
-        loop {
             // Action received commands from remote, or wait for all Traders to stop organically
             tokio::select! {
-                _ = notify_traders_stopped.recv() => {
-                    break;
+                stopped = stopped_rx.recv() => {
+                    running_traders -= 1;
+                    if let Some(stopped) = stopped {
+                        self.complete_market_removal(&mut removals, &stopped);
+                        if let Err(failure) = stopped {
+                            trader_failures.push(failure);
+                        }
+                    }
+                },
+
+                Some(signal) = recv_signal(&mut signal_rx) => {
+                    self.route_signal(signal).await;
                 },
 
                 command = self.command_rx.recv() => {
@@ -174,9 +377,17 @@ where
                             Command::FetchOpenPositions(positions_tx) => {
                                 self.fetch_open_positions(positions_tx).await;
                             },
+                            Command::FetchBalance(balance_tx) => {
+                                self.fetch_balance(balance_tx).await;
+                            },
+                            Command::FetchLegs(market, legs_tx) => {
+                                self.fetch_legs(market, legs_tx).await;
+                            },
+                            Command::FetchStatistics(statistics_tx) => {
+                                self.fetch_statistics(statistics_tx).await;
+                            },
                             Command::Terminate(message) => {
-                                self.terminate_traders(message).await;
-                                break;
+                                break Termination::Commanded(message);
                             },
                             Command::ExitPosition(market) => {
                                 self.exit_position(market).await;
@@ -184,50 +395,201 @@ where
                             Command::ExitAllPositions => {
                                 self.exit_all_positions().await;
                             },
+                            Command::Signal(signal) => {
+                                self.route_signal(signal).await;
+                            },
+                            Command::AddMarket(market, added_tx) => {
+                                let added = self.add_market(market, spawn_trader, &stopped_tx).await;
+                                if added.is_ok() {
+                                    running_traders += 1;
+                                }
+                                if added_tx.send(added).is_err() {
+                                    warn!(
+                                        why = "oneshot receiver dropped",
+                                        "cannot send Command::AddMarket outcome"
+                                    );
+                                }
+                            },
+                            Command::RemoveMarket(market, removed_tx) => {
+                                match self.remove_market(market).await {
+                                    // Outcome is sent once the retired Market's Trader stops
+                                    Ok(Some(market)) => {
+                                        removals.insert(market, removed_tx);
+                                    }
+                                    Ok(None) => send_removal_outcome(removed_tx, Ok(())),
+                                    Err(error) => send_removal_outcome(removed_tx, Err(error)),
+                                }
+                            },
                         }
                     } else {
                         // Terminate traders due to dropped receiver
-                        break;
+                        break Termination::CommandChannelDropped;
+                    }
+                }
+            }
+        };
+
+        // Terminate any Traders still running, regardless of why the Engine is stopping
+        match &termination {
+            Termination::Organic => {}
+            Termination::Commanded(message) => self.terminate_traders(message.clone()).await,
+            Termination::CommandChannelDropped => {
+                self.terminate_traders("remote command transmitter dropped".to_owned())
+                    .await
+            }
+        }
+
+        // Await every remaining Trader stopping, collecting any failures that occur meanwhile
+        drop(stopped_tx);
+        while running_traders > 0 {
+            match stopped_rx.recv().await {
+                Some(stopped) => {
+                    running_traders -= 1;
+                    self.complete_market_removal(&mut removals, &stopped);
+                    if let Err(failure) = stopped {
+                        trader_failures.push(failure);
                     }
                 }
+                None => break,
             }
         }
 
-        // Print Trading Session Summary
-        self.generate_session_summary().printstd();
+        self.generate_session_outcome(starting_balance, termination, trader_failures)
     }
 
-    /// Runs each [`Trader`] it's own thread. Sends a message on the returned `mpsc::Receiver<bool>`
-    /// if all the [`Trader`]s have stopped organically (eg/ due to a finished [`MarketEvent`] feed).
-    async fn run_traders(&mut self) -> mpsc::Receiver<bool> {
-        // Extract Traders out of the Engine so we can move them into threads
-        let traders = std::mem::take(&mut self.traders);
+    /// Add a [`Market`] to the running [`Engine`]. Constructs it's [`Trader`] using the
+    /// [`TraderFactory`], initialises the [`Market`] statistics if none are persisted (eg/ from
+    /// before it was retired), and runs the [`Trader`] using the provided [`TraderSpawner`].
+    async fn add_market(
+        &mut self,
+        market: Market,
+        spawn_trader: TraderSpawner<EventTx, Statistic, Portfolio, Data, Strategy, Execution>,
+        stopped_tx: &mpsc::UnboundedSender<TraderStopped>,
+    ) -> Result<(), EngineError> {
+        if self.trader_command_txs.contains_key(&market) {
+            return Err(EngineError::MarketAlreadyTraded(market));
+        }
 
-        // Run each Trader instance on it's own thread
-        let mut thread_handles = Vec::with_capacity(traders.len());
-        for trader in traders.into_iter() {
-            let handle = thread::spawn(move || trader.run());
-            thread_handles.push(handle);
+        let trader_factory = self
+            .trader_factory
+            .as_mut()
+            .ok_or(EngineError::TraderFactoryMissing)?;
+
+        // Construct the Trader with the receiver of the Market's Command channel
+        let (command_tx, command_rx) = mpsc::channel(TRADER_COMMAND_CAPACITY);
+        let trader = trader_factory.construct(market.clone(), command_rx).await?;
+        if trader.market() != &market {
+            return Err(EngineError::TraderConstruction(format!(
+                "TraderFactory constructed a Trader for {:?} rather than {:?}",
+                trader.market(),
+                market
+            )));
         }
 
-        // Create channel to notify the Engine when the Traders have stopped organically
-        let (notify_tx, notify_rx) = mpsc::channel(1);
+        self.portfolio.lock().init_market(&market)?;
 
-        // Create Task that notifies Engine when the Traders have stopped organically
-        tokio::spawn(async move {
-            for handle in thread_handles {
-                if let Err(err) = handle.join() {
-                    error!(
-                        error = &*format!("{:?}", err),
-                        "Trader thread has panicked during execution",
-                    )
-                }
+        spawn_trader(trader, stopped_tx.clone());
+        self.retired_markets.retain(|retired| retired != &market);
+        self.trader_command_txs.insert(market.clone(), command_tx);
+
+        info!(
+            engine_id = %self.engine_id,
+            ?market,
+            "added Market to running Engine"
+        );
+
+        Ok(())
+    }
+
+    /// Retire a [`Market`] from the running [`Engine`]. Sends a [`Command::ExitPosition`]
+    /// followed by a [`Command::Terminate`] to it's [`Trader`], which exits the open
+    /// [`Position`] before stopping. Returns the [`Market`] if the removal completes once it's
+    /// [`Trader`] stops, or None if the [`Trader`] had already stopped & the [`Market`] is flat.
+    async fn remove_market(&mut self, market: Market) -> Result<Option<Market>, EngineError> {
+        let command_tx = self
+            .trader_command_txs
+            .remove(&market)
+            .ok_or_else(|| EngineError::MarketNotTraded(market.clone()))?;
+
+        self.retired_markets.push(market.clone());
+
+        for command in [
+            Command::ExitPosition(market.clone()),
+            Command::Terminate("Market removed from Engine".to_owned()),
+        ] {
+            if command_tx.send(command).await.is_err() {
+                warn!(
+                    market = ?market,
+                    why = "Trader has already stopped",
+                    "failed to send Command to retired Market's Trader"
+                );
+                return self.verify_market_removed(&market).map(|_| None);
             }
+        }
 
-            let _ = notify_tx.send(true).await;
-        });
+        Ok(Some(market))
+    }
 
-        notify_rx
+    /// Send the outcome of the pending [`Command::RemoveMarket`] (if any) for the stopped
+    /// [`Trader`]'s [`Market`]. Fails if the [`Trader`] failed, or did not exit the open
+    /// [`Position`] before stopping.
+    fn complete_market_removal(
+        &mut self,
+        removals: &mut HashMap<Market, oneshot::Sender<Result<(), EngineError>>>,
+        stopped: &TraderStopped,
+    ) {
+        let market = match stopped {
+            Ok(market) => market,
+            Err(failure) => &failure.market,
+        };
+
+        let removed_tx = match removals.remove(market) {
+            Some(removed_tx) => removed_tx,
+            None => return,
+        };
+
+        let removed = match stopped {
+            Ok(market) => self.verify_market_removed(market),
+            Err(failure) => Err(EngineError::MarketRemoval(
+                failure.market.clone(),
+                format!("Trader failed before stopping: {}", failure.reason),
+            )),
+        };
+
+        send_removal_outcome(removed_tx, removed);
+    }
+
+    /// Verify the retired [`Market`] has no open [`Position`] now it's [`Trader`] has stopped.
+    fn verify_market_removed(&mut self, market: &Market) -> Result<(), EngineError> {
+        let open_positions = self
+            .portfolio
+            .lock()
+            .get_open_positions(self.engine_id, std::iter::once(market))?;
+
+        if !open_positions.is_empty() {
+            return Err(EngineError::MarketRemoval(
+                market.clone(),
+                format!(
+                    "{} Position(s) still open after it's Trader stopped",
+                    open_positions.len()
+                ),
+            ));
+        }
+
+        info!(
+            engine_id = %self.engine_id,
+            ?market,
+            "removed Market from running Engine"
+        );
+
+        Ok(())
+    }
+
+    /// Every [`Market`] traded during the trading session, including retired [`Market`]s.
+    fn traded_markets(&self) -> impl Iterator<Item = &Market> {
+        self.trader_command_txs
+            .keys()
+            .chain(self.retired_markets.iter())
     }
 
     /// Fetches all the [`Engine`]'s open [`Position`]s and sends them on the provided
@@ -239,7 +601,7 @@ where
         let open_positions = self
             .portfolio
             .lock()
-            .get_open_positions(self.engine_id, self.trader_command_txs.keys())
+            .get_open_positions(self.engine_id, self.traded_markets())
             .map_err(EngineError::RepositoryInteractionError);
 
         if positions_tx.send(open_positions).is_err() {
@@ -250,6 +612,68 @@ where
         }
     }
 
+    /// Fetches the [`Engine`]'s current [`Balance`] and sends it on the provided
+    /// `oneshot::Sender`.
+    async fn fetch_balance(&self, balance_tx: oneshot::Sender<Result<Balance, EngineError>>) {
+        let balance = self
+            .portfolio
+            .lock()
+            .get_balance(self.engine_id)
+            .map_err(EngineError::RepositoryInteractionError);
+
+        if balance_tx.send(balance).is_err() {
+            warn!(
+                why = "oneshot receiver dropped",
+                "cannot action Command::FetchBalance"
+            );
+        }
+    }
+
+    /// Fetches a [`PositionLeg`] for every open (tagged or untagged) [`Position`] held in the
+    /// [`Market`] and sends them on the provided `oneshot::Sender`.
+    async fn fetch_legs(
+        &self,
+        market: Market,
+        legs_tx: oneshot::Sender<Result<Vec<PositionLeg>, EngineError>>,
+    ) {
+        let legs = self
+            .portfolio
+            .lock()
+            .get_open_positions(self.engine_id, std::iter::once(&market))
+            .map(|positions| positions.iter().map(PositionLeg::from).collect())
+            .map_err(EngineError::RepositoryInteractionError);
+
+        if legs_tx.send(legs).is_err() {
+            warn!(
+                why = "oneshot receiver dropped",
+                "cannot action Command::FetchLegs"
+            );
+        }
+    }
+
+    /// Fetches the statistics of every [`Market`] traded by the [`Engine`], serialised as JSON,
+    /// and sends them on the provided `oneshot::Sender`.
+    async fn fetch_statistics(
+        &self,
+        statistics_tx: oneshot::Sender<Result<BTreeMap<String, serde_json::Value>, EngineError>>,
+    ) {
+        let statistics = self
+            .traded_markets()
+            .map(|market| {
+                let market_id = MarketId::from(market);
+                let statistics = self.portfolio.lock().get_statistics(&market_id)?;
+                Ok((market_id.0, serde_json::to_value(statistics)?))
+            })
+            .collect();
+
+        if statistics_tx.send(statistics).is_err() {
+            warn!(
+                why = "oneshot receiver dropped",
+                "cannot action Command::FetchStatistics"
+            );
+        }
+    }
+
     /// Terminate every running [`Trader`] associated with this [`Engine`].
     async fn terminate_traders(&self, message: String) {
         // Firstly, exit all Positions
@@ -283,7 +707,7 @@ where
                 error!(
                     market = &*format!("{:?}", market),
                     why = "dropped receiver",
-                    "failed to send Command::Terminate to Trader command_rx"
+                    "failed to send Command::ExitPosition to Trader command_rx"
                 );
             }
         }
@@ -301,7 +725,7 @@ where
                 error!(
                     market = &*format!("{:?}", market_ref),
                     why = "dropped receiver",
-                    "failed to send Command::Terminate to Trader command_rx"
+                    "failed to send Command::ExitPosition to Trader command_rx"
                 );
             }
         } else {
@@ -313,45 +737,275 @@ where
         }
     }
 
+    /// Action a [`Signal`]. Uses the [`Signal`]'s [`Market`] to route this [`Command`] to the
+    /// relevant [`Trader`] instance.
+    ///
+    /// A [`Signal`] for a [`Market`] who's [`Trader`] has already stopped (eg/ it's backtest
+    /// market data ended) cannot be actioned, so it's discarded with a warning.
+    async fn route_signal(&self, signal: Signal) {
+        let market = Market::new(signal.exchange.clone(), signal.instrument.clone());
+
+        if let Some(command_tx) = self.trader_command_txs.get(&market) {
+            if command_tx.send(Command::Signal(signal)).await.is_err() {
+                warn!(
+                    market = &*format!("{:?}", market),
+                    why = "Trader has already stopped",
+                    "discarded Signal routed to stopped Trader"
+                );
+            }
+        } else {
+            warn!(
+                market = &*format!("{:?}", market),
+                why = "Engine has no trader_command_tx associated with Signal Market",
+                "failed to route Signal"
+            );
+        }
+    }
+
+    /// Generate the [`SessionOutcome`] of the trading session. Fetches the final [`Balance`] &
+    /// open [`Position`]s from the Portfolio, and generates the [`SessionSummary`] from the
+    /// starting [`Balance`].
+    fn generate_session_outcome(
+        self,
+        starting_balance: Balance,
+        termination: Termination,
+        trader_failures: Vec<TraderFailure>,
+    ) -> SessionOutcome<Statistic> {
+        let balance = self
+            .portfolio
+            .lock()
+            .get_balance(self.engine_id)
+            .map_err(|error| {
+                warn!(
+                    ?error,
+                    "failed to get Balance when generating trading session outcome"
+                );
+            })
+            .ok();
+
+        let open_positions = self
+            .portfolio
+            .lock()
+            .get_open_positions(self.engine_id, self.traded_markets())
+            .unwrap_or_else(|error| {
+                warn!(
+                    ?error,
+                    "failed to get open Positions when generating trading session outcome"
+                );
+                Vec::new()
+            });
+
+        SessionOutcome {
+            summary: self.generate_session_summary(starting_balance),
+            balance,
+            open_positions,
+            trader_failures,
+            termination,
+        }
+    }
+
     /// Generate a trading session summary. Uses the Portfolio's statistics per [`Market`] in
-    /// combination with the average statistics across all [`Market`]s traded.
-    fn generate_session_summary(mut self) -> Table {
+    /// combination with the average statistics across all [`Market`]s traded, and the trading
+    /// session's starting [`Balance`] & exited [`Position`]s.
+    fn generate_session_summary(mut self, starting_balance: Balance) -> SessionSummary<Statistic> {
         // Fetch statistics for each Market
-        let stats_per_market = self.trader_command_txs.into_keys().filter_map(|market| {
-            let market_id = MarketId::from(&market);
+        let stats_per_market = self
+            .traded_markets()
+            .filter_map(|market| {
+                let market_id = MarketId::from(market);
 
-            match self.portfolio.lock().get_statistics(&market_id) {
-                Ok(statistics) => Some((market_id.0, statistics)),
-                Err(error) => {
-                    error!(
-                        ?error,
-                        ?market,
-                        "failed to get Market statistics when generating trading session summary"
-                    );
-                    None
+                match self.portfolio.lock().get_statistics(&market_id) {
+                    Ok(statistics) => Some((market_id.0, statistics)),
+                    Err(error) => {
+                        error!(
+                            ?error,
+                            ?market,
+                            "failed to get Market statistics when generating trading session summary"
+                        );
+                        None
+                    }
                 }
-            }
-        });
+            })
+            .collect::<BTreeMap<String, Statistic>>();
 
         // Generate average statistics across all markets using session's exited Positions
-        self.portfolio
+        let exited_positions = self
+            .portfolio
             .lock()
             .get_exited_positions(self.engine_id)
-            .map(|exited_positions| {
-                self.statistics_summary.generate_summary(&exited_positions);
-            })
             .unwrap_or_else(|error| {
                 warn!(
                     ?error,
                     why = "failed to get exited Positions from Portfolio's repository",
                     "failed to generate Statistics summary for trading session"
                 );
+                Vec::new()
             });
+        let initial_statistics = self.statistics_summary;
+        self.statistics_summary.generate_summary(&exited_positions);
 
-        // Combine Total & Per-Market Statistics Into Table
-        crate::statistic::summary::combine(
-            stats_per_market.chain([("Total".to_owned(), self.statistics_summary)]),
+        // Attribute the performance of tagged Positions (eg/ per sub-strategy) to each PositionTag
+        SessionSummary::new(
+            self.engine_id,
+            starting_balance,
+            stats_per_market,
+            self.statistics_summary,
+            exited_positions,
         )
+        .with_tags(initial_statistics)
+    }
+}
+
+impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+    Engine<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event> + Send + 'static,
+    Statistic: PositionSummariser + TableBuilder + Serialize + Send + 'static,
+    Portfolio: PositionHandler
+        + BalanceHandler
+        + StatisticHandler<Statistic>
+        + MarketUpdater
+        + MarketInitialiser
+        + OrderGenerator
+        + FillUpdater
+        + Send
+        + 'static,
+    Data: MarketGenerator + Send + 'static,
+    Strategy: SignalGenerator + Send + 'static,
+    Execution: ExecutionClient + Send + 'static,
+{
+    /// Run the trading [`Engine`]. Spawns a thread for each [`Trader`] to run on, including the
+    /// [`Trader`]s of [`Market`]s added via [`Command::AddMarket`]. Asynchronously receives
+    /// [`Command`]s via the `command_rx` and actions them
+    /// (eg/ terminate_traders, fetch_open_positions). If all of the [`Trader`]s stop organically
+    /// (eg/ due to a finished [`MarketGenerator`]), the [`Engine`] terminates & returns the
+    /// [`SessionOutcome`] of the trading session.
+    pub async fn run(self) -> SessionOutcome<Statistic> {
+        self.run_until_terminated(Self::spawn_trader_thread).await
+    }
+
+    /// Runs the [`Trader`] on it's own thread. Sends a [`TraderStopped`] notification when the
+    /// [`Trader`] stops, reporting a [`TraderFailure`] if it panicked or returned an error.
+    fn spawn_trader_thread(
+        trader: Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>,
+        stopped_tx: mpsc::UnboundedSender<TraderStopped>,
+    ) {
+        thread::spawn(move || {
+            let market = trader.market().clone();
+
+            let stopped = match panic::catch_unwind(AssertUnwindSafe(|| trader.run())) {
+                Ok(Ok(())) => Ok(market),
+                Ok(Err(error)) => {
+                    let failure = TraderFailure::from_error(market, &error);
+                    error!(
+                        market = &*format!("{:?}", failure.market),
+                        reason = &*failure.reason,
+                        "Trader thread has failed during execution",
+                    );
+                    Err(failure)
+                }
+                Err(payload) => {
+                    let failure = TraderFailure::from_panic(market, payload);
+                    error!(
+                        market = &*format!("{:?}", failure.market),
+                        reason = &*failure.reason,
+                        "Trader thread has panicked during execution",
+                    );
+                    Err(failure)
+                }
+            };
+
+            let _ = stopped_tx.send(stopped);
+        });
+    }
+}
+
+impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+    Engine<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event> + Send + 'static,
+    Statistic: PositionSummariser + TableBuilder + Serialize + Send + 'static,
+    Portfolio: PositionHandler
+        + BalanceHandler
+        + StatisticHandler<Statistic>
+        + MarketUpdater
+        + MarketInitialiser
+        + OrderGenerator
+        + FillUpdater
+        + Send
+        + 'static,
+    Data: AsyncMarketGenerator + Send + 'static,
+    Strategy: SignalGenerator + Send + 'static,
+    Execution: AsyncExecutionClient + Send + 'static,
+{
+    /// Run the trading [`Engine`], with every [`Trader`] running as a task on the tokio runtime
+    /// rather than on it's own thread. Enables trading thousands of [`Market`]s without
+    /// thousands of threads. Otherwise behaves the same as [`Engine::run`].
+    pub async fn run_async(self) -> SessionOutcome<Statistic> {
+        self.run_until_terminated(Self::spawn_trader_task).await
+    }
+
+    /// Runs the [`Trader`] as a task on the tokio runtime. Sends a [`TraderStopped`] notification
+    /// when the [`Trader`] stops, reporting a [`TraderFailure`] if it panicked or returned an
+    /// error.
+    fn spawn_trader_task(
+        trader: Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>,
+        stopped_tx: mpsc::UnboundedSender<TraderStopped>,
+    ) {
+        let market = trader.market().clone();
+        let trader_task = tokio::spawn(trader.run_async());
+
+        tokio::spawn(async move {
+            let stopped = match trader_task.await {
+                Ok(Ok(())) => Ok(market),
+                Ok(Err(error)) => {
+                    let failure = TraderFailure::from_error(market, &error);
+                    error!(
+                        market = &*format!("{:?}", failure.market),
+                        reason = &*failure.reason,
+                        "Trader task has failed during execution",
+                    );
+                    Err(failure)
+                }
+                Err(error) if error.is_panic() => {
+                    let failure = TraderFailure::from_panic(market, error.into_panic());
+                    error!(
+                        market = &*format!("{:?}", failure.market),
+                        reason = &*failure.reason,
+                        "Trader task has panicked during execution",
+                    );
+                    Err(failure)
+                }
+                Err(_) => Err(TraderFailure {
+                    market,
+                    reason: "Trader task was cancelled".to_owned(),
+                }),
+            };
+
+            let _ = stopped_tx.send(stopped);
+        });
+    }
+}
+
+/// Receive the next cross-market [`Signal`], waiting forever if the [`Engine`] has no
+/// signal_rx.
+async fn recv_signal(signal_rx: &mut Option<mpsc::UnboundedReceiver<Signal>>) -> Option<Signal> {
+    match signal_rx {
+        Some(signal_rx) => signal_rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Send the outcome of a [`Command::RemoveMarket`] on the provided `oneshot::Sender`.
+fn send_removal_outcome(
+    removed_tx: oneshot::Sender<Result<(), EngineError>>,
+    removed: Result<(), EngineError>,
+) {
+    if removed_tx.send(removed).is_err() {
+        warn!(
+            why = "oneshot receiver dropped",
+            "cannot send Command::RemoveMarket outcome"
+        );
     }
 }
 
@@ -362,9 +1016,9 @@ where
     EventTx: MessageTransmitter<Event>,
     Statistic: Serialize + Send,
     Portfolio: MarketUpdater + OrderGenerator + FillUpdater + Send,
-    Data: MarketGenerator + Send,
+    Data: Send,
     Strategy: SignalGenerator + Send,
-    Execution: ExecutionClient + Send,
+    Execution: Send,
 {
     engine_id: Option<Uuid>,
     command_rx: Option<mpsc::Receiver<Command>>,
@@ -372,6 +1026,9 @@ where
     traders: Option<Vec<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>>>,
     trader_command_txs: Option<HashMap<Market, mpsc::Sender<Command>>>,
     statistics_summary: Option<Statistic>,
+    trader_factory: Option<TraderFactory<EventTx, Statistic, Portfolio, Data, Strategy, Execution>>,
+    reconciliation: Option<Reconciliation<Portfolio>>,
+    signal_rx: Option<mpsc::UnboundedReceiver<Signal>>,
 }
 
 impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -380,14 +1037,15 @@ where
     EventTx: MessageTransmitter<Event>,
     Statistic: PositionSummariser + Serialize + Send,
     Portfolio: PositionHandler
+        + BalanceHandler
         + StatisticHandler<Statistic>
         + MarketUpdater
         + OrderGenerator
         + FillUpdater
         + Send,
-    Data: MarketGenerator + Send,
+    Data: Send,
     Strategy: SignalGenerator + Send,
-    Execution: ExecutionClient + Send,
+    Execution: Send,
 {
     fn new() -> Self {
         Self {
@@ -397,6 +1055,9 @@ where
             traders: None,
             trader_command_txs: None,
             statistics_summary: None,
+            trader_factory: None,
+            reconciliation: None,
+            signal_rx: None,
         }
     }
 
@@ -445,6 +1106,32 @@ where
         }
     }
 
+    /// Optional [`TraderFactory`] enabling [`Market`]s to be added to the running [`Engine`] via
+    /// [`Command::AddMarket`].
+    pub fn trader_factory(
+        self,
+        value: TraderFactory<EventTx, Statistic, Portfolio, Data, Strategy, Execution>,
+    ) -> Self {
+        Self {
+            trader_factory: Some(value),
+            ..self
+        }
+    }
+
+    pub fn reconciliation(self, value: Reconciliation<Portfolio>) -> Self {
+        Self {
+            reconciliation: Some(value),
+            ..self
+        }
+    }
+
+    pub fn signal_rx(self, value: mpsc::UnboundedReceiver<Signal>) -> Self {
+        Self {
+            signal_rx: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(
         self,
     ) -> Result<Engine<EventTx, Statistic, Portfolio, Data, Strategy, Execution>, EngineError> {
@@ -467,6 +1154,10 @@ where
             statistics_summary: self
                 .statistics_summary
                 .ok_or(EngineError::BuilderIncomplete("statistics_summary"))?,
+            trader_factory: self.trader_factory,
+            reconciliation: self.reconciliation,
+            signal_rx: self.signal_rx,
+            retired_markets: Vec::new(),
         })
     }
 }
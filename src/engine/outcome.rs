@@ -0,0 +1,119 @@
+use crate::{
+    engine::error::EngineError,
+    portfolio::{position::Position, Balance},
+    statistic::summary::session::SessionSummary,
+};
+use barter_integration::model::Market;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
+/// Outcome of a trading session, returned by [`Engine::run`](super::Engine::run) once the
+/// [`Engine`](super::Engine) stops.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct SessionOutcome<Statistic> {
+    /// Per-market & total statistics, exited [`Position`]s & equity curve of the trading session.
+    pub summary: SessionSummary<Statistic>,
+    /// Final [`Balance`] of the trading session. `None` if it could not be fetched from the
+    /// Portfolio's repository.
+    pub balance: Option<Balance>,
+    /// [`Position`]s that were still open when the [`Engine`](super::Engine) stopped.
+    pub open_positions: Vec<Position>,
+    /// Every [`Trader`](super::trader::Trader) that failed during the trading session.
+    pub trader_failures: Vec<TraderFailure>,
+    /// Why the [`Engine`](super::Engine) stopped.
+    pub termination: Termination,
+}
+
+impl<Statistic> SessionOutcome<Statistic> {
+    /// Determines if the trading session stopped organically with no
+    /// [`Trader`](super::trader::Trader) failures.
+    pub fn is_clean(&self) -> bool {
+        self.termination == Termination::Organic && self.trader_failures.is_empty()
+    }
+}
+
+/// Reason an [`Engine`](super::Engine) stopped running.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub enum Termination {
+    /// Every [`Trader`](super::trader::Trader) stopped organically (eg/ due to a finished
+    /// [`MarketGenerator`](crate::data::MarketGenerator)).
+    Organic,
+    /// A [`Command::Terminate`](super::Command::Terminate) was received, containing the provided
+    /// message.
+    Commanded(String),
+    /// The remote [`Command`](super::Command) transmitter was dropped.
+    CommandChannelDropped,
+}
+
+/// A [`Trader`](super::trader::Trader) that panicked or returned an error during the trading
+/// session.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct TraderFailure {
+    /// [`Market`] the failed [`Trader`](super::trader::Trader) was trading.
+    pub market: Market,
+    /// Panic or error message of the failed [`Trader`](super::trader::Trader).
+    pub reason: String,
+}
+
+impl TraderFailure {
+    /// Constructs a new [`TraderFailure`] from the payload of a
+    /// [`Trader`](super::trader::Trader) panic.
+    pub fn from_panic(market: Market, payload: Box<dyn Any + Send>) -> Self {
+        let reason = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(message) => (*message).to_owned(),
+                Err(_) => "unknown panic payload".to_owned(),
+            },
+        };
+
+        Self { market, reason }
+    }
+
+    /// Constructs a new [`TraderFailure`] from the [`EngineError`] a
+    /// [`Trader`](super::trader::Trader) returned.
+    pub fn from_error(market: Market, error: &EngineError) -> Self {
+        Self {
+            market,
+            reason: error.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::InstrumentKind;
+
+    #[test]
+    fn trader_failure_from_panic_payload() {
+        let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+        let actual = std::panic::catch_unwind(|| panic!("failed to generate Fill: {}", 1))
+            .map_err(|payload| TraderFailure::from_panic(market.clone(), payload))
+            .unwrap_err();
+        assert_eq!(actual.reason, "failed to generate Fill: 1");
+
+        let actual = std::panic::catch_unwind(|| panic!("failed to generate order"))
+            .map_err(|payload| TraderFailure::from_panic(market.clone(), payload))
+            .unwrap_err();
+        assert_eq!(actual.reason, "failed to generate order");
+        assert_eq!(actual.market, market);
+    }
+
+    #[test]
+    fn trader_failure_from_error() {
+        let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+        let actual = TraderFailure::from_error(
+            market.clone(),
+            &EngineError::MarketNotTraded(market.clone()),
+        );
+
+        assert_eq!(actual.market, market);
+        assert_eq!(
+            actual.reason,
+            EngineError::MarketNotTraded(market).to_string()
+        );
+    }
+}
@@ -1,15 +1,20 @@
 use super::{error::EngineError, Command};
 use crate::{
-    data::{Feed, MarketGenerator},
-    event::{Event, MessageTransmitter},
-    execution::ExecutionClient,
-    portfolio::{FillUpdater, MarketUpdater, OrderGenerator},
+    data::{AsyncMarketGenerator, Feed, MarketGenerator},
+    event::{sink::MetricsTx, Event, MessageTransmitter},
+    execution::{netting::NettedOrders, AsyncExecutionClient, ExecutionClient, FillEvent},
+    portfolio::{FillUpdater, MarketUpdater, OrderEvent, OrderGenerator},
     strategy::{SignalForceExit, SignalGenerator},
 };
+use barter_data::model::MarketEvent;
 use barter_integration::model::Market;
+use futures::future::BoxFuture;
 use parking_lot::Mutex;
 use serde::Serialize;
-use std::{collections::VecDeque, fmt::Debug, marker::PhantomData, sync::Arc};
+use std::{
+    collections::VecDeque, fmt::Debug, future::Future, marker::PhantomData, sync::Arc,
+    time::Instant,
+};
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
@@ -21,9 +26,7 @@ where
     EventTx: MessageTransmitter<Event>,
     Statistic: Serialize + Send,
     Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
-    Data: MarketGenerator,
     Strategy: SignalGenerator,
-    Execution: ExecutionClient,
 {
     /// Identifier for the [`Engine`](super::Engine) this [`Trader`] is associated with
     /// (1-to-many relationship).
@@ -35,7 +38,9 @@ where
     /// [`Event`] transmitter for sending every [`Event`] the [`Trader`] encounters to an external sink.
     pub event_tx: EventTx,
     /// Shared-access to a global Portfolio instance that implements [`MarketUpdater`],
-    /// [`OrderGenerator`] & [`FillUpdater`].
+    /// [`OrderGenerator`] & [`FillUpdater`]. To avoid every [`Trader`] contending on the same
+    /// lock, give each [`Trader`] it's own
+    /// [`PortfolioHandle`](crate::portfolio::actor::PortfolioHandle).
     pub portfolio: Arc<Mutex<Portfolio>>,
     /// Data handler that implements [`MarketGenerator`].
     pub data: Data,
@@ -43,6 +48,8 @@ where
     pub strategy: Strategy,
     /// Execution handler that implements [`ExecutionClient`].
     pub execution: Execution,
+    /// Optional [`MetricsTx`] recording unhealthy [`Feed`]s & trading loop latency.
+    pub metrics: Option<MetricsTx>,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -57,9 +64,9 @@ where
     EventTx: MessageTransmitter<Event>,
     Statistic: Serialize + Send,
     Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
-    Data: MarketGenerator + Send,
+    Data: Send,
     Strategy: SignalGenerator + Send,
-    Execution: ExecutionClient + Send,
+    Execution: Send,
 {
     /// Identifier for the [`Engine`](super::Engine) this [`Trader`] is associated with
     /// (1-to-many relationship).
@@ -82,6 +89,8 @@ where
     strategy: Strategy,
     /// Execution handler that implements [`ExecutionClient`].
     execution: Execution,
+    /// Optional [`MetricsTx`] recording unhealthy [`Feed`]s & trading loop latency.
+    metrics: Option<MetricsTx>,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -91,9 +100,9 @@ where
     EventTx: MessageTransmitter<Event>,
     Statistic: Serialize + Send,
     Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
-    Data: MarketGenerator + Send,
+    Data: Send,
     Strategy: SignalGenerator + Send,
-    Execution: ExecutionClient + Send,
+    Execution: Send,
 {
     /// Constructs a new [`Trader`] instance using the provided [`TraderLego`].
     pub fn new(lego: TraderLego<EventTx, Statistic, Portfolio, Data, Strategy, Execution>) -> Self {
@@ -113,6 +122,7 @@ where
             data: lego.data,
             strategy: lego.strategy,
             execution: lego.execution,
+            metrics: lego.metrics,
             _statistic_marker: PhantomData::default(),
         }
     }
@@ -122,114 +132,133 @@ where
         TraderBuilder::new()
     }
 
-    /// Run the trading event-loop for this [`Trader`] instance. Loop will run until [`Trader`]
-    /// receives a [`Command::Terminate`] via the mpsc::Receiver command_rx, or the
-    /// [`MarketGenerator`] yields [`Feed::Finished`].
-    pub fn run(mut self) {
-        // Run trading loop for this Trader instance
-        'trading: loop {
-            // Check for new remote Commands before continuing to generate another MarketEvent
-            while let Some(command) = self.receive_remote_command() {
-                match command {
-                    Command::Terminate(_) => break 'trading,
-                    Command::ExitPosition(market) => {
-                        self.event_q
-                            .push_back(Event::SignalForceExit(SignalForceExit::from(market)));
-                    }
-                    _ => continue,
+    /// Returns the [`Market`] this [`Trader`] is trading.
+    pub fn market(&self) -> &Market {
+        &self.market
+    }
+
+    /// Handle an [`Event`] from the event_q, sending every generated [`Event`] to the event_tx
+    /// & pushing those requiring further handling onto the event_q. Returns the [`OrderEvent`]
+    /// if the [`Event`] is an [`Event::OrderNew`] that requires execution.
+    fn handle_event(&mut self, event: Event) -> Result<Option<OrderEvent>, EngineError> {
+        match event {
+            Event::Market(market) => {
+                for signal in self.strategy.generate_signals(&market) {
+                    self.event_tx.send(Event::Signal(signal.clone()));
+                    self.event_q.push_back(Event::Signal(signal));
+                }
+
+                let position_updates = self.portfolio.lock().update_from_market(&market)?;
+
+                for position_update in position_updates {
+                    self.event_tx.send(Event::PositionUpdate(position_update));
                 }
             }
 
-            // If the Feed<MarketEvent> yields, populate event_q with the next MarketEvent
-            match self.data.generate() {
-                Feed::Next(market) => {
-                    self.event_tx.send(Event::Market(market.clone()));
-                    self.event_q.push_back(Event::Market(market));
+            Event::Signal(signal) => {
+                if let Some(order) = self.portfolio.lock().generate_order(&signal)? {
+                    self.event_tx.send(Event::OrderNew(order.clone()));
+                    self.event_q.push_back(Event::OrderNew(order));
                 }
-                Feed::Unhealthy => {
-                    warn!(
-                        engine_id = %self.engine_id,
-                        market = ?self.market,
-                        action = "continuing while waiting for healthy Feed",
-                        "MarketFeed unhealthy"
-                    );
-                    continue 'trading;
+            }
+
+            Event::SignalForceExit(signal_force_exit) => {
+                let orders = self
+                    .portfolio
+                    .lock()
+                    .generate_exit_orders(signal_force_exit)?;
+
+                for order in orders {
+                    self.event_tx.send(Event::OrderNew(order.clone()));
+                    self.event_q.push_back(Event::OrderNew(order));
                 }
-                Feed::Finished => break 'trading,
             }
 
-            // Handle Events in the event_q
-            // '--> While loop will break when event_q is empty and requires another MarketEvent
-            while let Some(event) = self.event_q.pop_back() {
-                match event {
-                    Event::Market(market) => {
-                        if let Some(signal) = self.strategy.generate_signal(&market) {
-                            self.event_tx.send(Event::Signal(signal.clone()));
-                            self.event_q.push_back(Event::Signal(signal));
-                        }
-
-                        if let Some(position_update) = self
-                            .portfolio
-                            .lock()
-                            .update_from_market(&market)
-                            .expect("failed to update Portfolio from market")
-                        {
-                            self.event_tx.send(Event::PositionUpdate(position_update));
-                        }
-                    }
+            Event::OrderNew(order) => return Ok(Some(order)),
 
-                    Event::Signal(signal) => {
-                        if let Some(order) = self
-                            .portfolio
-                            .lock()
-                            .generate_order(&signal)
-                            .expect("failed to generate order")
-                        {
-                            self.event_tx.send(Event::OrderNew(order.clone()));
-                            self.event_q.push_back(Event::OrderNew(order));
-                        }
-                    }
+            Event::Fill(fill) => {
+                let fill_side_effect_events = self.portfolio.lock().update_from_fill(&fill)?;
 
-                    Event::SignalForceExit(signal_force_exit) => {
-                        if let Some(order) = self
-                            .portfolio
-                            .lock()
-                            .generate_exit_order(signal_force_exit)
-                            .expect("failed to generate forced exit order")
-                        {
-                            self.event_tx.send(Event::OrderNew(order.clone()));
-                            self.event_q.push_back(Event::OrderNew(order));
-                        }
-                    }
+                self.event_tx.send_many(fill_side_effect_events);
+            }
+            _ => {}
+        }
 
-                    Event::OrderNew(order) => {
-                        let fill = self
-                            .execution
-                            .generate_fill(&order)
-                            .expect("failed to generate Fill");
+        Ok(None)
+    }
 
-                        self.event_tx.send(Event::Fill(fill.clone()));
-                        self.event_q.push_back(Event::Fill(fill));
-                    }
+    /// Handle every [`Event`] in the event_q, returning the [`OrderEvent`]s requiring execution.
+    /// [`OrderEvent`]s are executed once the event_q is drained, so the [`OrderEvent`]s of
+    /// independent (eg/ tagged) sub-strategies can be netted before reaching the venue.
+    fn drain_event_q(&mut self) -> Result<Vec<OrderEvent>, EngineError> {
+        let mut orders = Vec::new();
+        while let Some(event) = self.event_q.pop_back() {
+            orders.extend(self.handle_event(event)?);
+        }
+        Ok(orders)
+    }
 
-                    Event::Fill(fill) => {
-                        let fill_side_effect_events = self
-                            .portfolio
-                            .lock()
-                            .update_from_fill(&fill)
-                            .expect("failed to update Portfolio from fill");
+    /// Send the [`FillEvent`] generated by executing an [`OrderEvent`], and push it onto the
+    /// event_q.
+    fn handle_fill(&mut self, fill: FillEvent) {
+        self.event_tx.send(Event::Fill(fill.clone()));
+        self.event_q.push_back(Event::Fill(fill));
+    }
 
-                        self.event_tx.send_many(fill_side_effect_events);
-                    }
-                    _ => {}
+    /// Action a remote [`Command`]. Returns true if the [`Trader`] should stop trading.
+    fn action_command(&mut self, command: Command) -> bool {
+        match command {
+            Command::Terminate(_) => return true,
+            Command::ExitPosition(market) => {
+                self.event_q
+                    .push_back(Event::SignalForceExit(SignalForceExit::from(market)));
+            }
+            Command::Signal(signal) => {
+                self.event_tx.send(Event::Signal(signal.clone()));
+                self.event_q.push_back(Event::Signal(signal));
+            }
+            _ => {}
+        }
+
+        false
+    }
+
+    /// Send the [`MarketEvent`] yielded by the [`Feed`] & push it onto the event_q. Returns true
+    /// if the [`Feed`] is finished & the [`Trader`] should stop trading.
+    fn action_feed(&mut self, feed: Feed<MarketEvent>) -> bool {
+        match feed {
+            Feed::Next(market) => {
+                self.event_tx.send(Event::Market(market.clone()));
+                self.event_q.push_back(Event::Market(market));
+            }
+            Feed::Unhealthy => {
+                warn!(
+                    engine_id = %self.engine_id,
+                    market = ?self.market,
+                    action = "continuing while waiting for healthy Feed",
+                    "MarketFeed unhealthy"
+                );
+
+                if let Some(metrics) = &self.metrics {
+                    metrics.record_feed_unhealthy(&self.market);
                 }
             }
+            Feed::Finished => return true,
+        }
 
-            debug!(
-                engine_id = &*self.engine_id.to_string(),
-                market = &*format!("{:?}", self.market),
-                "Trader trading loop stopped"
-            );
+        false
+    }
+
+    /// Returns the time the event_q processing started if there are [`Event`]s to process &
+    /// metrics are being recorded in a [`MetricsTx`].
+    fn start_loop_timer(&self) -> Option<Instant> {
+        (self.metrics.is_some() && !self.event_q.is_empty()).then(Instant::now)
+    }
+
+    /// Record the trading loop latency if the event_q processing was timed.
+    fn record_loop_latency(&self, started: Option<Instant>) {
+        if let (Some(metrics), Some(started)) = (&self.metrics, started) {
+            metrics.record_trader_loop(&self.market, started.elapsed());
         }
     }
 
@@ -261,6 +290,163 @@ where
     }
 }
 
+impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+    Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event>,
+    Statistic: Serialize + Send,
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
+    Data: MarketGenerator + Send,
+    Strategy: SignalGenerator + Send,
+    Execution: ExecutionClient + Send,
+{
+    /// Run the trading event-loop for this [`Trader`] instance. Loop will run until [`Trader`]
+    /// receives a [`Command::Terminate`] via the mpsc::Receiver command_rx, or the
+    /// [`MarketGenerator`] yields [`Feed::Finished`].
+    ///
+    /// Returns an [`EngineError`] if the Portfolio or Execution handler fails, stopping the
+    /// [`Trader`].
+    pub fn run(mut self) -> Result<(), EngineError> {
+        // Run trading loop for this Trader instance
+        'trading: loop {
+            // Check for new remote Commands before continuing to generate another MarketEvent
+            while let Some(command) = self.receive_remote_command() {
+                if self.action_command(command) {
+                    // Action Events already queued (eg/ SignalForceExit) before stopping
+                    self.process_event_q()?;
+                    break 'trading;
+                }
+            }
+
+            // If the Feed<MarketEvent> yields, populate event_q with the next MarketEvent
+            let feed = self.data.generate();
+            if self.action_feed(feed) {
+                break 'trading;
+            }
+
+            self.process_event_q()?;
+        }
+
+        debug!(
+            engine_id = &*self.engine_id.to_string(),
+            market = &*format!("{:?}", self.market),
+            "Trader trading loop stopped"
+        );
+
+        Ok(())
+    }
+
+    /// Handle the [`Event`]s in the event_q, netting the [`OrderEvent`]s generated along the way
+    /// & generating a [`FillEvent`] for every one of them from the net [`OrderEvent`]'s fill.
+    /// Returns once the event_q is empty and requires another [`MarketEvent`].
+    fn process_event_q(&mut self) -> Result<(), EngineError> {
+        let started = self.start_loop_timer();
+        loop {
+            let orders = self.drain_event_q()?;
+            if orders.is_empty() {
+                break;
+            }
+            let orders = NettedOrders::new(orders);
+            let net_fill = orders
+                .net_order()
+                .map(|order| self.execution.generate_fill(order))
+                .transpose()?;
+            orders
+                .split_fill(net_fill)
+                .into_iter()
+                .for_each(|fill| self.handle_fill(fill));
+        }
+        self.record_loop_latency(started);
+        Ok(())
+    }
+}
+
+impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+    Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event>,
+    Statistic: Serialize + Send,
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
+    Data: AsyncMarketGenerator + Send,
+    Strategy: SignalGenerator + Send,
+    Execution: AsyncExecutionClient + Send,
+{
+    /// Run the trading event-loop for this [`Trader`] instance as a task on the tokio runtime,
+    /// awaiting remote [`Command`]s, [`MarketEvent`]s & [`FillEvent`]s rather than blocking a
+    /// thread. Loop will run until [`Trader`] receives a [`Command::Terminate`] via the
+    /// mpsc::Receiver command_rx, or the [`AsyncMarketGenerator`] yields [`Feed::Finished`].
+    ///
+    /// The Portfolio is still accessed synchronously, so it should not block for long
+    /// (eg/ on a remote repository) or it will stall the runtime worker thread.
+    ///
+    /// Returns an [`EngineError`] if the Portfolio or Execution handler fails, stopping the
+    /// [`Trader`].
+    pub async fn run_async(mut self) -> Result<(), EngineError> {
+        'trading: loop {
+            // Await the next remote Command or MarketEvent, prioritising Commands
+            tokio::select! {
+                biased;
+
+                command = self.command_rx.recv() => {
+                    let command = command.unwrap_or_else(|| {
+                        warn!(
+                            action = "synthesising a Command::Terminate",
+                            "remote Command transmitter has been dropped"
+                        );
+                        Command::Terminate("remote command transmitter dropped".to_owned())
+                    });
+
+                    if self.action_command(command) {
+                        // Action Events already queued (eg/ SignalForceExit) before stopping
+                        self.process_event_q_async().await?;
+                        break 'trading;
+                    }
+                }
+
+                feed = self.data.next() => {
+                    if self.action_feed(feed) {
+                        break 'trading;
+                    }
+                }
+            }
+
+            self.process_event_q_async().await?;
+        }
+
+        debug!(
+            engine_id = %self.engine_id,
+            market = ?self.market,
+            "Trader trading loop stopped"
+        );
+
+        Ok(())
+    }
+
+    /// Handle the [`Event`]s in the event_q, netting the [`OrderEvent`]s generated along the way
+    /// & awaiting the net [`OrderEvent`]'s fill to generate a [`FillEvent`] for every one of them.
+    /// Returns once the event_q is empty and requires another [`MarketEvent`].
+    async fn process_event_q_async(&mut self) -> Result<(), EngineError> {
+        let started = self.start_loop_timer();
+        loop {
+            let orders = self.drain_event_q()?;
+            if orders.is_empty() {
+                break;
+            }
+            let orders = NettedOrders::new(orders);
+            let net_fill = match orders.net_order() {
+                Some(order) => Some(self.execution.execute(order).await?),
+                None => None,
+            };
+            orders
+                .split_fill(net_fill)
+                .into_iter()
+                .for_each(|fill| self.handle_fill(fill));
+        }
+        self.record_loop_latency(started);
+        Ok(())
+    }
+}
+
 /// Builder to construct [`Trader`] instances.
 #[derive(Debug, Default)]
 pub struct TraderBuilder<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -268,9 +454,7 @@ where
     EventTx: MessageTransmitter<Event>,
     Statistic: Serialize + Send,
     Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
-    Data: MarketGenerator,
     Strategy: SignalGenerator,
-    Execution: ExecutionClient,
 {
     engine_id: Option<Uuid>,
     market: Option<Market>,
@@ -280,6 +464,7 @@ where
     data: Option<Data>,
     strategy: Option<Strategy>,
     execution: Option<Execution>,
+    metrics: Option<MetricsTx>,
     _statistic_marker: Option<PhantomData<Statistic>>,
 }
 
@@ -289,9 +474,9 @@ where
     EventTx: MessageTransmitter<Event>,
     Statistic: Serialize + Send,
     Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
-    Data: MarketGenerator + Send,
+    Data: Send,
     Strategy: SignalGenerator + Send,
-    Execution: ExecutionClient + Send,
+    Execution: Send,
 {
     fn new() -> Self {
         Self {
@@ -303,6 +488,7 @@ where
             data: None,
             strategy: None,
             execution: None,
+            metrics: None,
             _statistic_marker: None,
         }
     }
@@ -363,6 +549,14 @@ where
         }
     }
 
+    /// Optional [`MetricsTx`] to record unhealthy [`Feed`]s & trading loop latency in.
+    pub fn metrics(self, value: MetricsTx) -> Self {
+        Self {
+            metrics: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(
         self,
     ) -> Result<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>, EngineError> {
@@ -390,7 +584,94 @@ where
             execution: self
                 .execution
                 .ok_or(EngineError::BuilderIncomplete("execution"))?,
+            metrics: self.metrics,
             _statistic_marker: PhantomData::default(),
         })
     }
 }
+
+/// Asynchronously constructs a [`Trader`] for the provided [`Market`] & `mpsc::Receiver<Command>`.
+type TraderConstructor<EventTx, Statistic, Portfolio, Data, Strategy, Execution> = Box<
+    dyn FnMut(
+            Market,
+            mpsc::Receiver<Command>,
+        ) -> BoxFuture<
+            'static,
+            Result<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>, EngineError>,
+        > + Send
+        + Sync,
+>;
+
+/// Constructs the [`Trader`] for a [`Market`] added to a running [`Engine`](super::Engine) via
+/// [`Command::AddMarket`]. The [`Trader`] must be constructed with the provided [`Market`] &
+/// `mpsc::Receiver<Command>`, since the [`Engine`](super::Engine) routes it's [`Command`]s using
+/// the associated transmitter.
+///
+/// eg/ `TraderFactory::new(move |market, command_rx| async move { Trader::builder()...build() })`
+pub struct TraderFactory<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event>,
+    Statistic: Serialize + Send,
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
+    Data: Send,
+    Strategy: SignalGenerator + Send,
+    Execution: Send,
+{
+    construct: TraderConstructor<EventTx, Statistic, Portfolio, Data, Strategy, Execution>,
+}
+
+impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution> Debug
+    for TraderFactory<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event>,
+    Statistic: Serialize + Send,
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
+    Data: Send,
+    Strategy: SignalGenerator + Send,
+    Execution: Send,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TraderFactory").finish()
+    }
+}
+
+impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+    TraderFactory<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event>,
+    Statistic: Serialize + Send,
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
+    Data: Send,
+    Strategy: SignalGenerator + Send,
+    Execution: Send,
+{
+    /// Constructs a new [`TraderFactory`] from the provided asynchronous constructor. The
+    /// constructor may await (eg/ to initialise a live [`MarketEvent`] feed).
+    pub fn new<Constructor, Construction>(mut constructor: Constructor) -> Self
+    where
+        Constructor: FnMut(Market, mpsc::Receiver<Command>) -> Construction + Send + Sync + 'static,
+        Construction: Future<
+                Output = Result<
+                    Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>,
+                    EngineError,
+                >,
+            > + Send
+            + 'static,
+    {
+        Self {
+            construct: Box::new(move |market, command_rx| {
+                Box::pin(constructor(market, command_rx))
+            }),
+        }
+    }
+
+    /// Construct the [`Trader`] for the provided [`Market`], using the `mpsc::Receiver<Command>`
+    /// associated with the [`Engine`](super::Engine)'s [`Command`] transmitter for the [`Market`].
+    pub async fn construct(
+        &mut self,
+        market: Market,
+        command_rx: mpsc::Receiver<Command>,
+    ) -> Result<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>, EngineError> {
+        (self.construct)(market, command_rx).await
+    }
+}
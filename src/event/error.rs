@@ -0,0 +1,24 @@
+use crate::portfolio::repository::error::RepositoryError;
+use thiserror::Error;
+
+/// All errors generated in the barter::event module.
+#[derive(Error, Debug)]
+pub enum JournalError {
+    #[error("Failed to read or write journal: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to serialise or deserialise journal data: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Corrupt journal entry at line {line}: {error}")]
+    CorruptEntry {
+        line: usize,
+        error: serde_json::Error,
+    },
+
+    #[error("Journal entry sequence {actual} does not follow previous sequence {previous}")]
+    OutOfSequence { previous: u64, actual: u64 },
+
+    #[error("Failed to interact with repository: {0}")]
+    Repository(#[from] RepositoryError),
+}
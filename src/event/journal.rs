@@ -0,0 +1,469 @@
+use crate::{
+    event::{error::JournalError, Event, MessageTransmitter},
+    portfolio::repository::file::SyncPolicy,
+};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{debug, error, warn};
+
+/// Entry in the [`Journal`], containing an [`Event`] & it's position in the [`Journal`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct JournalEntry {
+    /// Monotonically increasing sequence number, starting at 1.
+    pub sequence: u64,
+    pub event: Event,
+}
+
+/// Borrowed [`JournalEntry`] used to serialise an [`Event`] without cloning it.
+#[derive(Serialize)]
+struct JournalEntryRef<'a> {
+    sequence: u64,
+    event: &'a Event,
+}
+
+/// Sequence number of a [`JournalEntry`], deserialised without the [`Event`] so entries that are
+/// not required can be skipped cheaply.
+#[derive(Deserialize)]
+struct JournalEntryHeader {
+    sequence: u64,
+}
+
+/// Append-only journal of [`Event`]s, persisted as newline delimited JSON [`JournalEntry`]s.
+///
+/// Every appended [`JournalEntry`] is flushed to the OS before returning, and synchronised with
+/// the storage device according to the [`SyncPolicy`]. A torn final entry (eg/ from a crash
+/// mid-write) is discarded when the [`Journal`] is opened.
+///
+/// Once a [`PortfolioSnapshot`](super::recovery::PortfolioSnapshot) is written, the
+/// [`JournalEntry`]s it reflects are removed via truncate(), bounding the size of the [`Journal`]
+/// & the cost of opening & recovering from it.
+#[derive(Debug)]
+pub struct Journal {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    sequence: u64,
+    sync_policy: SyncPolicy,
+    entries_since_sync: usize,
+}
+
+impl Journal {
+    /// Opens the [`Journal`] at the provided path, creating it if it does not exist. Appended
+    /// [`JournalEntry`]s continue the sequence of the existing [`JournalEntry`]s, and are
+    /// synchronised with the storage device according to the provided [`SyncPolicy`].
+    pub fn open<P: Into<PathBuf>>(path: P, sync_policy: SyncPolicy) -> Result<Self, JournalError> {
+        let path = path.into();
+
+        // Only the sequence of the existing JournalEntries is required, so skip every Event
+        let (_, sequence, valid_len) = read_entries(&path, u64::MAX)?;
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        // Discard any torn final entry so new entries start on a fresh line
+        file.set_len(valid_len)?;
+
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+            sequence,
+            sync_policy,
+            entries_since_sync: 0,
+        })
+    }
+
+    /// Sequence number of the last [`JournalEntry`] appended to the [`Journal`].
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Path of the [`Journal`] file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an [`Event`] to the [`Journal`], returning the sequence number of the new
+    /// [`JournalEntry`].
+    pub fn append(&mut self, event: &Event) -> Result<u64, JournalError> {
+        let sequence = self.sequence + 1;
+
+        serde_json::to_writer(&mut self.writer, &JournalEntryRef { sequence, event })?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+        self.sequence = sequence;
+
+        self.entries_since_sync += 1;
+        let sync = match self.sync_policy {
+            SyncPolicy::Always => true,
+            SyncPolicy::EveryRecords(entries) => self.entries_since_sync >= entries,
+            SyncPolicy::Never => false,
+        };
+        if sync {
+            self.sync()?;
+        }
+
+        Ok(sequence)
+    }
+
+    /// Synchronise the [`Journal`] file with the underlying storage device.
+    pub fn sync(&mut self) -> Result<(), JournalError> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()?;
+        self.entries_since_sync = 0;
+        Ok(())
+    }
+
+    /// Remove every [`JournalEntry`] before the provided sequence number (eg/ those reflected in
+    /// a [`PortfolioSnapshot`](super::recovery::PortfolioSnapshot) that has been written). The
+    /// [`JournalEntry`] at the provided sequence is retained, so the sequence continues when the
+    /// [`Journal`] is reopened.
+    ///
+    /// The retained [`JournalEntry`]s are written to a temporary file that atomically replaces
+    /// the [`Journal`] file, so a crash never loses a [`JournalEntry`] after the sequence.
+    pub fn truncate(&mut self, sequence: u64) -> Result<(), JournalError> {
+        self.writer.flush()?;
+
+        let temporary = self.path.with_extension("truncate");
+        let mut retained = BufWriter::new(File::create(&temporary)?);
+        let mut removed = 0;
+        scan_entries(&self.path, |entry_sequence, line| {
+            match entry_sequence >= sequence {
+                true => retained.write_all(line)?,
+                false => removed += 1,
+            }
+            Ok(())
+        })?;
+        retained.flush()?;
+        retained.get_ref().sync_all()?;
+        drop(retained);
+
+        fs::rename(&temporary, &self.path)?;
+        sync_directory(&self.path)?;
+
+        self.writer = BufWriter::new(OpenOptions::new().append(true).open(&self.path)?);
+        self.entries_since_sync = 0;
+
+        debug!(
+            path = %self.path.display(),
+            sequence,
+            removed,
+            "truncated journal"
+        );
+
+        Ok(())
+    }
+
+    /// Read every [`JournalEntry`] in the [`Journal`] at the provided path. Returns an empty
+    /// `Vec` if the [`Journal`] does not exist.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<JournalEntry>, JournalError> {
+        Self::read_after(path, 0)
+    }
+
+    /// Read every [`JournalEntry`] in the [`Journal`] at the provided path with a sequence number
+    /// greater than the one provided. The [`Event`]s of the preceding [`JournalEntry`]s are
+    /// skipped without being deserialised.
+    pub fn read_after<P: AsRef<Path>>(
+        path: P,
+        sequence: u64,
+    ) -> Result<Vec<JournalEntry>, JournalError> {
+        read_entries(path.as_ref(), sequence).map(|(entries, _, _)| entries)
+    }
+}
+
+/// Synchronise the directory containing the provided path with the underlying storage device, so
+/// a rename into it survives power loss.
+pub(crate) fn sync_directory(path: &Path) -> Result<(), JournalError> {
+    #[cfg(unix)]
+    if let Some(directory) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        File::open(directory)?.sync_all()?;
+    }
+
+    #[cfg(not(unix))]
+    let _ = path;
+
+    Ok(())
+}
+
+/// Read every complete [`JournalEntry`] in the file with a sequence number greater than the one
+/// provided, along with the sequence number of the last [`JournalEntry`] & the length in bytes of
+/// the file containing every complete [`JournalEntry`].
+fn read_entries(path: &Path, after: u64) -> Result<(Vec<JournalEntry>, u64, u64), JournalError> {
+    let mut entries = Vec::new();
+    let mut line_number = 0;
+
+    let (sequence, valid_len) = scan_entries(path, |sequence, line| {
+        line_number += 1;
+        if sequence > after {
+            let entry = serde_json::from_slice::<JournalEntry>(line).map_err(|error| {
+                JournalError::CorruptEntry {
+                    line: line_number,
+                    error,
+                }
+            })?;
+            entries.push(entry);
+        }
+        Ok(())
+    })?;
+
+    Ok((entries, sequence, valid_len))
+}
+
+/// Scan every complete line of the file, passing each [`JournalEntry`] sequence number & raw line
+/// (including it's terminating newline) to the provided closure. Returns the sequence number of
+/// the last [`JournalEntry`] & the length in bytes of the file containing every complete line.
+///
+/// A torn final entry is discarded, whereas a corrupt entry elsewhere is an error.
+fn scan_entries<F>(path: &Path, mut f: F) -> Result<(u64, u64), JournalError>
+where
+    F: FnMut(u64, &[u8]) -> Result<(), JournalError>,
+{
+    if !path.exists() {
+        return Ok((0, 0));
+    }
+
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut previous: Option<u64> = None;
+    let mut valid_len = 0;
+    let mut line = Vec::new();
+
+    for line_number in 1.. {
+        line.clear();
+        let bytes = reader.read_until(b'\n', &mut line)?;
+        if bytes == 0 {
+            break;
+        }
+
+        // Entries are only durable once their terminating newline has been written
+        if !line.ends_with(b"\n") {
+            warn!(
+                path = %path.display(),
+                line = line_number,
+                "discarding torn final journal entry"
+            );
+            break;
+        }
+
+        let sequence = serde_json::from_slice::<JournalEntryHeader>(&line)
+            .map_err(|error| JournalError::CorruptEntry {
+                line: line_number,
+                error,
+            })?
+            .sequence;
+
+        if let Some(previous) = previous {
+            if sequence != previous + 1 {
+                return Err(JournalError::OutOfSequence {
+                    previous,
+                    actual: sequence,
+                });
+            }
+        }
+
+        f(sequence, &line)?;
+        valid_len += bytes as u64;
+        previous = Some(sequence);
+    }
+
+    Ok((previous.unwrap_or_default(), valid_len))
+}
+
+/// [`MessageTransmitter`] that appends every [`Event`] to a shared [`Journal`] before forwarding
+/// it to the inner [`MessageTransmitter`] (eg/ an [`EventTx`](super::EventTx)).
+#[derive(Debug, Clone)]
+pub struct JournalTx<Tx> {
+    journal: Arc<Mutex<Journal>>,
+    inner: Tx,
+}
+
+impl<Tx> JournalTx<Tx> {
+    /// Constructs a new [`JournalTx`] using the provided shared [`Journal`] & inner
+    /// [`MessageTransmitter`].
+    pub fn new(journal: Arc<Mutex<Journal>>, inner: Tx) -> Self {
+        Self { journal, inner }
+    }
+
+    fn append(journal: &mut Journal, event: &Event) {
+        if let Err(error) = journal.append(event) {
+            error!(
+                ?error,
+                path = %journal.path().display(),
+                "failed to append Event to journal"
+            );
+        }
+    }
+}
+
+impl<Tx> MessageTransmitter<Event> for JournalTx<Tx>
+where
+    Tx: MessageTransmitter<Event>,
+{
+    fn send(&mut self, message: Event) {
+        Self::append(&mut self.journal.lock(), &message);
+        self.inner.send(message);
+    }
+
+    fn send_many(&mut self, messages: Vec<Event>) {
+        // Hold the Journal lock so the Events are journaled contiguously
+        {
+            let mut journal = self.journal.lock();
+            messages
+                .iter()
+                .for_each(|message| Self::append(&mut journal, message));
+        }
+
+        self.inner.send_many(messages);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event::EventTx, portfolio::Balance};
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+
+    fn journal_path() -> PathBuf {
+        std::env::temp_dir().join(format!("barter_journal_{}.jsonl", Uuid::new_v4()))
+    }
+
+    fn balance_event(total: f64) -> Event {
+        Event::Balance(Balance {
+            time: DateTime::<Utc>::MIN_UTC,
+            total,
+            available: total,
+        })
+    }
+
+    #[test]
+    fn journal_append_and_read() {
+        let path = journal_path();
+
+        let mut journal = Journal::open(&path, SyncPolicy::Always).unwrap();
+        assert_eq!(journal.append(&balance_event(1.0)).unwrap(), 1);
+        assert_eq!(journal.append(&balance_event(2.0)).unwrap(), 2);
+        drop(journal);
+
+        // Reopened Journal continues the sequence
+        let mut journal = Journal::open(&path, SyncPolicy::Always).unwrap();
+        assert_eq!(journal.sequence(), 2);
+        assert_eq!(journal.append(&balance_event(3.0)).unwrap(), 3);
+
+        let entries = Journal::read(&path).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[2].event, balance_event(3.0));
+
+        let entries = Journal::read_after(&path, 2).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].sequence, 3);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn journal_open_discards_torn_final_entry() {
+        let path = journal_path();
+
+        let mut journal = Journal::open(&path, SyncPolicy::Always).unwrap();
+        journal.append(&balance_event(1.0)).unwrap();
+        drop(journal);
+
+        // Simulate a crash mid-write
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"sequence\":2,\"ev").unwrap();
+        drop(file);
+
+        assert_eq!(Journal::read(&path).unwrap().len(), 1);
+
+        let mut journal = Journal::open(&path, SyncPolicy::Always).unwrap();
+        assert_eq!(journal.append(&balance_event(2.0)).unwrap(), 2);
+
+        let entries = Journal::read(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].event, balance_event(2.0));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn journal_truncate_removes_entries_before_sequence() {
+        let path = journal_path();
+
+        let mut journal = Journal::open(&path, SyncPolicy::EveryRecords(2)).unwrap();
+        for total in 1..=4 {
+            journal.append(&balance_event(total as f64)).unwrap();
+        }
+
+        journal.truncate(3).unwrap();
+        assert_eq!(journal.append(&balance_event(5.0)).unwrap(), 5);
+        drop(journal);
+
+        let sequences = Journal::read(&path)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.sequence)
+            .collect::<Vec<u64>>();
+        assert_eq!(sequences, vec![3, 4, 5]);
+
+        // Truncating every entry retains the last, so a reopened Journal continues the sequence
+        let mut journal = Journal::open(&path, SyncPolicy::Never).unwrap();
+        journal.truncate(5).unwrap();
+        drop(journal);
+
+        let mut journal = Journal::open(&path, SyncPolicy::Never).unwrap();
+        assert_eq!(journal.sequence(), 5);
+        assert_eq!(journal.append(&balance_event(6.0)).unwrap(), 6);
+        assert_eq!(Journal::read_after(&path, 5).unwrap().len(), 1);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn journal_read_returns_error_for_corrupt_entry() {
+        let path = journal_path();
+        std::fs::write(&path, "not json\n{}\n").unwrap();
+
+        assert!(matches!(
+            Journal::read(&path),
+            Err(JournalError::CorruptEntry { line: 1, .. })
+        ));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn journal_tx_journals_and_forwards_events() {
+        let path = journal_path();
+        let journal = Arc::new(Mutex::new(
+            Journal::open(&path, SyncPolicy::Always).unwrap(),
+        ));
+
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut journal_tx = JournalTx::new(journal, EventTx::new(event_tx));
+
+        journal_tx.send(balance_event(1.0));
+        journal_tx.send_many(vec![balance_event(2.0), balance_event(3.0)]);
+
+        let journaled = Journal::read(&path)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.event)
+            .collect::<Vec<Event>>();
+
+        let mut forwarded = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            forwarded.push(event);
+        }
+
+        assert_eq!(journaled.len(), 3);
+        assert_eq!(journaled, forwarded);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
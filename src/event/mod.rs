@@ -0,0 +1,155 @@
+use crate::execution::FillEvent;
+use crate::portfolio::position::{Position, PositionExit, PositionUpdate};
+use crate::portfolio::reconcile::Discrepancy;
+use crate::portfolio::{Balance, OrderEvent};
+use crate::strategy::{Signal, SignalForceExit};
+use barter_data::model::MarketEvent;
+use barter_integration::model::Market;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Barter event module specific errors.
+pub mod error;
+
+/// Append-only journal of every [`Event`], used as a durable log for event-sourcing.
+pub mod journal;
+
+/// Rebuilds Portfolio state after a crash by replaying the [`Event`] journal on top of the most
+/// recent snapshot.
+pub mod recovery;
+
+/// Composable [`MessageTransmitter`]s for fanning out, filtering, buffering, persisting &
+/// aggregating the [`Event`] stream.
+pub mod sink;
+
+/// Events that occur when bartering. [`MarketEvent`], [`Signal`], [`OrderEvent`], and
+/// [`FillEvent`] are vital to the [`Trader`](crate::engine::trader::Trader) event loop, dictating
+/// the trading sequence. The [`PositionExit`] Event is a representation of work done by the
+/// system, and is useful for analysing performance & reconciliations.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum Event {
+    Market(MarketEvent),
+    Signal(Signal),
+    SignalForceExit(SignalForceExit),
+    OrderNew(OrderEvent),
+    OrderUpdate,
+    Fill(FillEvent),
+    PositionNew(Position),
+    PositionUpdate(PositionUpdate),
+    PositionExit(PositionExit),
+    Balance(Balance),
+    Discrepancy(Discrepancy),
+}
+
+/// Data-less discriminant of an [`Event`], used to select & aggregate [`Event`]s by type.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum EventKind {
+    Market,
+    Signal,
+    SignalForceExit,
+    OrderNew,
+    OrderUpdate,
+    Fill,
+    PositionNew,
+    PositionUpdate,
+    PositionExit,
+    Balance,
+    Discrepancy,
+}
+
+impl Event {
+    /// Determine the [`EventKind`] of this [`Event`].
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::Market(_) => EventKind::Market,
+            Event::Signal(_) => EventKind::Signal,
+            Event::SignalForceExit(_) => EventKind::SignalForceExit,
+            Event::OrderNew(_) => EventKind::OrderNew,
+            Event::OrderUpdate => EventKind::OrderUpdate,
+            Event::Fill(_) => EventKind::Fill,
+            Event::PositionNew(_) => EventKind::PositionNew,
+            Event::PositionUpdate(_) => EventKind::PositionUpdate,
+            Event::PositionExit(_) => EventKind::PositionExit,
+            Event::Balance(_) => EventKind::Balance,
+            Event::Discrepancy(_) => EventKind::Discrepancy,
+        }
+    }
+
+    /// [`Market`] this [`Event`] was generated for, if it contains one. [`PositionUpdate`] &
+    /// [`PositionExit`] only identify their [`Market`] via their position_id, so return `None`.
+    pub fn market(&self) -> Option<Market> {
+        let (exchange, instrument) = match self {
+            Event::Market(market) => (&market.exchange, &market.instrument),
+            Event::Signal(signal) => (&signal.exchange, &signal.instrument),
+            Event::SignalForceExit(signal) => (&signal.exchange, &signal.instrument),
+            Event::OrderNew(order) => (&order.exchange, &order.instrument),
+            Event::Fill(fill) => (&fill.exchange, &fill.instrument),
+            Event::PositionNew(position) => (&position.exchange, &position.instrument),
+            _ => return None,
+        };
+
+        Some(Market::new(exchange.clone(), instrument.clone()))
+    }
+}
+
+/// Message transmitter for sending Barter messages to downstream consumers.
+pub trait MessageTransmitter<Message> {
+    /// Attempts to send a message to an external message subscriber.
+    fn send(&mut self, message: Message);
+
+    /// Attempts to send many messages to an external message subscriber.
+    fn send_many(&mut self, messages: Vec<Message>);
+}
+
+/// Transmitter for sending Barter [`Event`]s to an external sink. Useful for event-sourcing,
+/// real-time dashboards & general monitoring.
+#[derive(Debug, Clone)]
+pub struct EventTx {
+    /// Flag to communicate if the external [`Event`] receiver has been dropped.
+    receiver_dropped: bool,
+    /// [`Event`] channel transmitter to send [`Event`]s to an external sink.
+    event_tx: mpsc::UnboundedSender<Event>,
+}
+
+impl MessageTransmitter<Event> for EventTx {
+    fn send(&mut self, message: Event) {
+        if self.receiver_dropped {
+            return;
+        }
+
+        if self.event_tx.send(message).is_err() {
+            warn!(
+                action = "setting receiver_dropped = true",
+                why = "event receiver dropped",
+                "cannot send Events"
+            );
+            self.receiver_dropped = true;
+        }
+    }
+
+    fn send_many(&mut self, messages: Vec<Event>) {
+        if self.receiver_dropped {
+            return;
+        }
+
+        for message in messages {
+            self.send(message);
+
+            if self.receiver_dropped {
+                return;
+            }
+        }
+    }
+}
+
+impl EventTx {
+    /// Constructs a new [`EventTx`] instance using the provided channel transmitter.
+    pub fn new(event_tx: mpsc::UnboundedSender<Event>) -> Self {
+        Self {
+            receiver_dropped: false,
+            event_tx,
+        }
+    }
+}
@@ -0,0 +1,550 @@
+use crate::{
+    event::{
+        error::JournalError,
+        journal::{sync_directory, Journal, JournalEntry},
+        Event,
+    },
+    portfolio::{
+        position::{Position, PositionId},
+        repository::{BalanceHandler, PositionHandler, StatisticHandler},
+        Balance,
+    },
+    statistic::summary::{Initialiser, PositionSummariser},
+};
+use barter_integration::model::{Market, MarketId};
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::Write,
+    path::Path,
+};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Snapshot of the Portfolio state at a point in the [`Journal`]. Recovery only replays the
+/// [`JournalEntry`]s after the snapshot, and a checkpoint() truncates the [`JournalEntry`]s the
+/// snapshot reflects from the [`Journal`], bounding it's size & the replay time.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct PortfolioSnapshot<Statistic> {
+    /// Identifier of the Engine the Portfolio state belongs to. A restarted Engine must use the
+    /// same engine_id so the recovered [`Position`]s can be found.
+    pub engine_id: Uuid,
+    /// Sequence number of the last [`JournalEntry`] reflected in this snapshot.
+    pub sequence: u64,
+    pub balance: Balance,
+    pub open_positions: Vec<Position>,
+    pub exited_positions: Vec<Position>,
+    /// Statistics for each market, keyed by [`MarketId`].
+    pub statistics: BTreeMap<String, Statistic>,
+}
+
+impl<Statistic> PortfolioSnapshot<Statistic> {
+    /// Capture a [`PortfolioSnapshot`] of the provided Portfolio's state for every [`Market`].
+    ///
+    /// The Portfolio is locked before the [`Journal`], matching the lock order of a
+    /// [`Trader`](crate::engine::trader::Trader) sending [`Event`]s whilst updating the Portfolio.
+    pub fn capture<Portfolio>(
+        engine_id: Uuid,
+        markets: &[Market],
+        portfolio: &Mutex<Portfolio>,
+        journal: &Mutex<Journal>,
+    ) -> Result<Self, JournalError>
+    where
+        Portfolio: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    {
+        let mut portfolio = portfolio.lock();
+        let journal = journal.lock();
+
+        let statistics = markets
+            .iter()
+            .map(|market| {
+                let market_id = MarketId::from(market);
+                portfolio
+                    .get_statistics(&market_id)
+                    .map(|statistic| (market_id.0, statistic))
+            })
+            .collect::<Result<BTreeMap<String, Statistic>, _>>()?;
+
+        Ok(Self {
+            engine_id,
+            sequence: journal.sequence(),
+            balance: portfolio.get_balance(engine_id)?,
+            open_positions: portfolio.get_open_positions(engine_id, markets.iter())?,
+            exited_positions: portfolio.get_exited_positions(engine_id)?,
+            statistics,
+        })
+    }
+
+    /// Capture a [`PortfolioSnapshot`] of the provided Portfolio's state, durably write it to the
+    /// provided path & truncate the [`JournalEntry`]s it reflects from the [`Journal`].
+    ///
+    /// [`JournalEntry`]s appended whilst the snapshot is written are after the snapshot sequence,
+    /// so they are retained.
+    pub fn checkpoint<Portfolio, P>(
+        engine_id: Uuid,
+        markets: &[Market],
+        portfolio: &Mutex<Portfolio>,
+        journal: &Mutex<Journal>,
+        path: P,
+    ) -> Result<Self, JournalError>
+    where
+        Portfolio: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+        Statistic: Serialize,
+        P: AsRef<Path>,
+    {
+        let snapshot = Self::capture(engine_id, markets, portfolio, journal)?;
+        snapshot.write(path)?;
+        journal.lock().truncate(snapshot.sequence)?;
+        Ok(snapshot)
+    }
+
+    /// Atomically & durably write the [`PortfolioSnapshot`] to the provided path as JSON.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), JournalError>
+    where
+        Statistic: Serialize,
+    {
+        // Write to a synchronised temporary file & rename so a crash never leaves a partial
+        // snapshot, and the snapshot survives power loss before the Journal is truncated
+        let path = path.as_ref();
+        let temporary = path.with_extension("tmp");
+        let mut file = File::create(&temporary)?;
+        file.write_all(&serde_json::to_vec(self)?)?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(temporary, path)?;
+        sync_directory(path)
+    }
+
+    /// Read the [`PortfolioSnapshot`] at the provided path. Returns `None` if it does not exist.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Option<Self>, JournalError>
+    where
+        Statistic: DeserializeOwned,
+    {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+}
+
+/// Portfolio state rebuilt from a [`PortfolioSnapshot`] & the [`JournalEntry`]s after it.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct RecoveredState<Statistic> {
+    /// Sequence number of the last [`JournalEntry`] reflected in the state.
+    pub sequence: u64,
+    /// Latest [`Balance`]. `None` if no [`Balance`] was snapshot or journaled.
+    pub balance: Option<Balance>,
+    pub open_positions: BTreeMap<PositionId, Position>,
+    pub exited_positions: Vec<Position>,
+    /// Statistics for each market, keyed by [`MarketId`].
+    pub statistics: BTreeMap<String, Statistic>,
+}
+
+impl<Statistic> RecoveredState<Statistic>
+where
+    Statistic: Initialiser + PositionSummariser,
+{
+    /// Constructs a new [`RecoveredState`] from the optional [`PortfolioSnapshot`].
+    pub fn new(snapshot: Option<PortfolioSnapshot<Statistic>>) -> Self {
+        match snapshot {
+            None => Self {
+                sequence: 0,
+                balance: None,
+                open_positions: BTreeMap::new(),
+                exited_positions: Vec::new(),
+                statistics: BTreeMap::new(),
+            },
+            Some(snapshot) => Self {
+                sequence: snapshot.sequence,
+                balance: Some(snapshot.balance),
+                open_positions: snapshot
+                    .open_positions
+                    .into_iter()
+                    .map(|position| (position.position_id.clone(), position))
+                    .collect(),
+                exited_positions: snapshot.exited_positions,
+                statistics: snapshot.statistics,
+            },
+        }
+    }
+
+    /// Replay the [`JournalEntry`]s that occurred after the current sequence. Statistics for
+    /// markets with no existing statistics are initialised using the provided config.
+    pub fn replay<Entries>(&mut self, entries: Entries, statistic_config: Statistic::Config)
+    where
+        Entries: IntoIterator<Item = JournalEntry>,
+    {
+        for entry in entries {
+            if entry.sequence <= self.sequence {
+                continue;
+            }
+
+            self.apply(entry.event, statistic_config);
+            self.sequence = entry.sequence;
+        }
+    }
+
+    /// Apply an [`Event`] to the state. [`Event`]s that do not change Portfolio state are ignored.
+    fn apply(&mut self, event: Event, statistic_config: Statistic::Config) {
+        match event {
+            Event::PositionNew(position) => {
+                self.open_positions
+                    .insert(position.position_id.clone(), position);
+            }
+
+            Event::PositionUpdate(update) => match self.open_positions.get_mut(&update.position_id)
+            {
+                Some(position) => position.apply_update(&update),
+                None => warn!(
+                    position_id = &*update.position_id,
+                    "ignoring journaled PositionUpdate for a Position that isn't open"
+                ),
+            },
+
+            Event::PositionExit(exit) => match self.open_positions.remove(&exit.position_id) {
+                Some(mut position) => {
+                    position.apply_exit(&exit);
+
+                    self.statistics
+                        .entry(MarketId::new(&position.exchange, &position.instrument).0)
+                        .or_insert_with(|| Statistic::init(statistic_config))
+                        .update(&position);
+
+                    self.exited_positions.push(position);
+                }
+                // Occurs if a snapshot already reflects the exit before it was journaled
+                None => warn!(
+                    position_id = &*exit.position_id,
+                    "ignoring journaled PositionExit for a Position that isn't open"
+                ),
+            },
+
+            Event::Balance(balance) => {
+                self.balance = Some(balance);
+            }
+
+            _ => {}
+        }
+    }
+
+    /// Restore the [`RecoveredState`] into the provided Portfolio repository (eg/ a freshly
+    /// initialised [`MetaPortfolio`](crate::portfolio::portfolio::MetaPortfolio)).
+    pub fn restore<Repository>(
+        self,
+        engine_id: Uuid,
+        repository: &mut Repository,
+    ) -> Result<(), JournalError>
+    where
+        Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    {
+        if let Some(balance) = self.balance {
+            repository.set_balance(engine_id, balance)?;
+        }
+
+        for (market_id, statistic) in self.statistics {
+            repository.set_statistics(MarketId(market_id), statistic)?;
+        }
+
+        for position in self.open_positions.into_values() {
+            repository.set_open_position(engine_id, position)?;
+        }
+
+        for position in self.exited_positions {
+            repository.set_exited_position(engine_id, position)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Rebuild the Portfolio state by replaying the [`Journal`] on top of the most recent
+/// [`PortfolioSnapshot`] (if one exists).
+pub fn recover<Statistic, JournalPath, SnapshotPath>(
+    journal_path: JournalPath,
+    snapshot_path: SnapshotPath,
+    statistic_config: Statistic::Config,
+) -> Result<RecoveredState<Statistic>, JournalError>
+where
+    Statistic: Initialiser + PositionSummariser + DeserializeOwned,
+    JournalPath: AsRef<Path>,
+    SnapshotPath: AsRef<Path>,
+{
+    let mut state = RecoveredState::new(PortfolioSnapshot::read(snapshot_path)?);
+    let snapshot_sequence = state.sequence;
+
+    let entries = Journal::read_after(journal_path, snapshot_sequence)?;
+    let replayed = entries.len();
+    state.replay(entries, statistic_config);
+
+    info!(
+        snapshot_sequence,
+        replayed,
+        sequence = state.sequence,
+        "recovered Portfolio state from journal"
+    );
+
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::{
+            position::{determine_position_id, PositionExit, PositionUpdate},
+            repository::{file::SyncPolicy, in_memory::InMemoryRepository},
+        },
+        statistic::summary::trade::TradeSummary,
+        test_util::position,
+    };
+    use chrono::{DateTime, Utc};
+
+    fn entries() -> Vec<JournalEntry> {
+        let time = DateTime::<Utc>::MIN_UTC;
+        let mut position = position();
+        position.meta.enter_time = time;
+        position.meta.update_time = time;
+        let position_id = position.position_id.clone();
+
+        let events = vec![
+            Event::PositionNew(position),
+            Event::Balance(Balance {
+                time,
+                total: 1000.0,
+                available: 900.0,
+            }),
+            Event::PositionUpdate(PositionUpdate {
+                position_id: position_id.clone(),
+                update_time: time,
+                current_symbol_price: 110.0,
+                current_value_gross: 110.0,
+                unrealised_profit_loss: 10.0,
+                max_adverse_excursion: -5.0,
+                max_favourable_excursion: 10.0,
+            }),
+            Event::PositionExit(PositionExit {
+                position_id,
+                exit_time: time,
+                exit_balance: Balance {
+                    time,
+                    total: 1020.0,
+                    available: 900.0,
+                },
+                exit_fees: Default::default(),
+                exit_fees_total: 0.0,
+                exit_avg_price_gross: 120.0,
+                exit_value_gross: 120.0,
+                realised_profit_loss: 20.0,
+                max_adverse_excursion: -5.0,
+                max_favourable_excursion: 20.0,
+            }),
+            Event::Balance(Balance {
+                time,
+                total: 1020.0,
+                available: 1020.0,
+            }),
+        ];
+
+        events
+            .into_iter()
+            .enumerate()
+            .map(|(index, event)| JournalEntry {
+                sequence: index as u64 + 1,
+                event,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn replay_journal_entries_rebuilds_state() {
+        let mut state = RecoveredState::<TradeSummary>::new(None);
+
+        // Replay up to & including the PositionUpdate
+        state.replay(entries().into_iter().take(3), ());
+        assert_eq!(state.sequence, 3);
+        assert_eq!(state.balance.unwrap().available, 900.0);
+        let open_position = state.open_positions.values().next().unwrap();
+        assert_eq!(open_position.current_symbol_price, 110.0);
+        assert_eq!(open_position.max_favourable_excursion, 10.0);
+
+        // Replaying every entry skips those already applied
+        state.replay(entries(), ());
+        assert_eq!(state.sequence, 5);
+        assert_eq!(state.balance.unwrap().available, 1020.0);
+        assert!(state.open_positions.is_empty());
+        assert_eq!(state.exited_positions.len(), 1);
+        assert_eq!(state.exited_positions[0].realised_profit_loss, 20.0);
+        assert_eq!(state.exited_positions[0].max_favourable_excursion, 20.0);
+
+        let statistic = state.statistics.values().next().unwrap();
+        assert_eq!(statistic.trades(), 1);
+    }
+
+    #[test]
+    fn replay_ignores_exit_already_reflected_in_snapshot() {
+        let mut state = RecoveredState::<TradeSummary>::new(None);
+        state.replay(entries(), ());
+
+        // Journal entry for an exit the state already reflects
+        let mut duplicate_exit = entries().remove(3);
+        duplicate_exit.sequence = 6;
+        state.replay([duplicate_exit], ());
+
+        assert_eq!(state.sequence, 6);
+        assert_eq!(state.exited_positions.len(), 1);
+        assert_eq!(state.statistics.values().next().unwrap().trades(), 1);
+    }
+
+    #[test]
+    fn restore_recovered_state_into_repository() {
+        let engine_id = Uuid::new_v4();
+
+        let mut state = RecoveredState::<TradeSummary>::new(None);
+        state.replay(entries().into_iter().take(3), ());
+
+        let mut repository = InMemoryRepository::<TradeSummary>::new();
+        state.clone().restore(engine_id, &mut repository).unwrap();
+
+        assert_eq!(
+            repository.get_balance(engine_id).unwrap(),
+            state.balance.unwrap()
+        );
+        let open_position = state.open_positions.into_values().next().unwrap();
+        assert_eq!(
+            repository
+                .get_open_position(&open_position.position_id)
+                .unwrap(),
+            Some(open_position)
+        );
+    }
+
+    #[test]
+    fn recover_from_snapshot_and_journal() {
+        let directory = std::env::temp_dir();
+        let journal_path = directory.join(format!("barter_recover_{}.jsonl", Uuid::new_v4()));
+        let snapshot_path = directory.join(format!("barter_recover_{}.json", Uuid::new_v4()));
+
+        let mut journal = Journal::open(&journal_path, SyncPolicy::Always).unwrap();
+        for entry in entries() {
+            journal.append(&entry.event).unwrap();
+        }
+
+        // Snapshot reflecting the first 3 JournalEntries
+        let mut snapshot_state = RecoveredState::<TradeSummary>::new(None);
+        snapshot_state.replay(entries().into_iter().take(3), ());
+        PortfolioSnapshot {
+            engine_id: Uuid::new_v4(),
+            sequence: snapshot_state.sequence,
+            balance: snapshot_state.balance.unwrap(),
+            open_positions: snapshot_state.open_positions.into_values().collect(),
+            exited_positions: snapshot_state.exited_positions,
+            statistics: snapshot_state.statistics,
+        }
+        .write(&snapshot_path)
+        .unwrap();
+
+        let actual = recover::<TradeSummary, _, _>(&journal_path, &snapshot_path, ()).unwrap();
+
+        let mut expected = RecoveredState::<TradeSummary>::new(None);
+        expected.replay(entries(), ());
+        assert_eq!(actual, expected);
+
+        fs::remove_file(journal_path).unwrap();
+        fs::remove_file(snapshot_path).unwrap();
+    }
+
+    #[test]
+    fn checkpoint_truncates_journal_entries_reflected_in_snapshot() {
+        let directory = std::env::temp_dir();
+        let journal_path = directory.join(format!("barter_checkpoint_{}.jsonl", Uuid::new_v4()));
+        let snapshot_path = directory.join(format!("barter_checkpoint_{}.json", Uuid::new_v4()));
+        let engine_id = Uuid::new_v4();
+
+        // JournalEntries of a Position with an id the repository derives from the engine_id
+        let entries = entries()
+            .into_iter()
+            .map(|mut entry| {
+                match &mut entry.event {
+                    Event::PositionNew(position) => {
+                        position.position_id = determine_position_id(
+                            engine_id,
+                            &position.exchange,
+                            &position.instrument,
+                        )
+                    }
+                    Event::PositionUpdate(update) => {
+                        update.position_id = determine_position_id(
+                            engine_id,
+                            &position().exchange,
+                            &position().instrument,
+                        )
+                    }
+                    Event::PositionExit(exit) => {
+                        exit.position_id = determine_position_id(
+                            engine_id,
+                            &position().exchange,
+                            &position().instrument,
+                        )
+                    }
+                    _ => {}
+                }
+                entry
+            })
+            .collect::<Vec<JournalEntry>>();
+
+        let journal = Mutex::new(Journal::open(&journal_path, SyncPolicy::Always).unwrap());
+        for entry in entries.iter().take(3) {
+            journal.lock().append(&entry.event).unwrap();
+        }
+
+        // Portfolio state reflecting the first 3 JournalEntries
+        let mut journaled = RecoveredState::<TradeSummary>::new(None);
+        journaled.replay(entries.iter().take(3).cloned(), ());
+        let markets = journaled
+            .open_positions
+            .values()
+            .map(|position| Market::new(position.exchange.clone(), position.instrument.clone()))
+            .collect::<Vec<Market>>();
+        let mut repository = InMemoryRepository::<TradeSummary>::new();
+        journaled.restore(engine_id, &mut repository).unwrap();
+        repository
+            .set_statistics(MarketId::from(&markets[0]), TradeSummary::init(()))
+            .unwrap();
+        let portfolio = Mutex::new(repository);
+
+        let snapshot = PortfolioSnapshot::checkpoint(
+            engine_id,
+            &markets,
+            &portfolio,
+            &journal,
+            &snapshot_path,
+        )
+        .unwrap();
+        assert_eq!(snapshot.sequence, 3);
+
+        // Only the JournalEntry at the snapshot sequence is retained
+        let sequences = Journal::read(&journal_path)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.sequence)
+            .collect::<Vec<u64>>();
+        assert_eq!(sequences, vec![3]);
+
+        for entry in entries.iter().skip(3) {
+            journal.lock().append(&entry.event).unwrap();
+        }
+
+        let actual = recover::<TradeSummary, _, _>(&journal_path, &snapshot_path, ()).unwrap();
+        assert_eq!(actual.sequence, 5);
+        assert!(actual.open_positions.is_empty());
+        assert_eq!(actual.exited_positions.len(), 1);
+
+        fs::remove_file(journal_path).unwrap();
+        fs::remove_file(snapshot_path).unwrap();
+    }
+}
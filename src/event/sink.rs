@@ -0,0 +1,760 @@
+use crate::{
+    event::{Event, EventKind, MessageTransmitter},
+    portfolio::{
+        position::{determine_market_position_id, PositionId},
+        Balance,
+    },
+};
+use barter_integration::model::{Market, MarketId};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{error, warn};
+
+/// [`MessageTransmitter`] that sends every [`Event`] to both of it's inner
+/// [`MessageTransmitter`]s. Nest [`FanOutTx`]s to send to more than two sinks.
+///
+/// eg/ `FanOutTx::new(event_tx, FanOutTx::new(json_lines_tx, metrics_tx))`
+#[derive(Debug, Clone)]
+pub struct FanOutTx<First, Second> {
+    first: First,
+    second: Second,
+}
+
+impl<First, Second> FanOutTx<First, Second> {
+    /// Constructs a new [`FanOutTx`] sending to both of the provided [`MessageTransmitter`]s.
+    pub fn new(first: First, second: Second) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<First, Second> MessageTransmitter<Event> for FanOutTx<First, Second>
+where
+    First: MessageTransmitter<Event>,
+    Second: MessageTransmitter<Event>,
+{
+    fn send(&mut self, message: Event) {
+        self.first.send(message.clone());
+        self.second.send(message);
+    }
+
+    fn send_many(&mut self, messages: Vec<Event>) {
+        self.first.send_many(messages.clone());
+        self.second.send_many(messages);
+    }
+}
+
+/// [`MessageTransmitter`] that only forwards the [`Event`]s accepted by it's predicate to the
+/// inner [`MessageTransmitter`].
+///
+/// See [`kinds`] & [`markets`] for common predicates.
+#[derive(Debug, Clone)]
+pub struct FilterTx<Tx, Predicate> {
+    inner: Tx,
+    predicate: Predicate,
+}
+
+impl<Tx, Predicate> FilterTx<Tx, Predicate>
+where
+    Predicate: Fn(&Event) -> bool,
+{
+    /// Constructs a new [`FilterTx`] forwarding the [`Event`]s accepted by the provided predicate
+    /// to the inner [`MessageTransmitter`].
+    pub fn new(inner: Tx, predicate: Predicate) -> Self {
+        Self { inner, predicate }
+    }
+}
+
+impl<Tx, Predicate> MessageTransmitter<Event> for FilterTx<Tx, Predicate>
+where
+    Tx: MessageTransmitter<Event>,
+    Predicate: Fn(&Event) -> bool,
+{
+    fn send(&mut self, message: Event) {
+        if (self.predicate)(&message) {
+            self.inner.send(message);
+        }
+    }
+
+    fn send_many(&mut self, messages: Vec<Event>) {
+        let messages = messages
+            .into_iter()
+            .filter(|message| (self.predicate)(message))
+            .collect::<Vec<_>>();
+
+        if !messages.is_empty() {
+            self.inner.send_many(messages);
+        }
+    }
+}
+
+/// [`FilterTx`] predicate accepting [`Event`]s of the provided [`EventKind`]s.
+pub fn kinds(kinds: Vec<EventKind>) -> impl Fn(&Event) -> bool + Clone + Send {
+    move |event| kinds.contains(&event.kind())
+}
+
+/// [`FilterTx`] predicate accepting [`Event`]s generated for the provided [`Market`]s.
+///
+/// [`PositionUpdate`](crate::portfolio::position::PositionUpdate) &
+/// [`PositionExit`](crate::portfolio::position::PositionExit) [`Event`]s are matched using the
+/// market of their (tagged or untagged) position_id. [`Event`]s without an associated [`Market`]
+/// (eg/ [`Balance`]) are rejected.
+pub fn markets(markets: Vec<Market>) -> impl Fn(&Event) -> bool + Clone + Send {
+    let position_id_suffixes = markets
+        .iter()
+        .map(|market| format!("_{}_{}_position", market.exchange, market.instrument))
+        .collect::<Vec<_>>();
+
+    move |event| {
+        let position_id = match event {
+            Event::PositionUpdate(update) => &update.position_id,
+            Event::PositionExit(exit) => &exit.position_id,
+            _ => return matches!(event.market(), Some(market) if markets.contains(&market)),
+        };
+
+        let market_position_id = determine_market_position_id(position_id);
+        position_id_suffixes
+            .iter()
+            .any(|suffix| market_position_id.ends_with(suffix))
+    }
+}
+
+/// Action taken by a [`BoundedEventTx`] when it's channel is full.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub enum OverflowPolicy {
+    /// Drop the [`Event`] that does not fit in the channel & count it as dropped.
+    Drop,
+    /// Block the sending thread until the channel has capacity.
+    ///
+    /// Do not use with a consumer running on the same single threaded runtime as the sender,
+    /// since the consumer can never be scheduled to free capacity.
+    Block,
+}
+
+/// Transmitter for sending Barter [`Event`]s into a bounded channel, applying the
+/// [`OverflowPolicy`] when the channel is full. Protects the
+/// [`Trader`](crate::engine::trader::Trader) from unbounded memory growth behind a slow consumer.
+#[derive(Debug, Clone)]
+pub struct BoundedEventTx {
+    /// Flag to communicate if the external [`Event`] receiver has been dropped.
+    receiver_dropped: bool,
+    /// Bounded [`Event`] channel transmitter to send [`Event`]s to an external sink.
+    event_tx: mpsc::Sender<Event>,
+    /// Action taken when the [`Event`] channel is full.
+    policy: OverflowPolicy,
+    /// Number of [`Event`]s dropped due to a full channel, shared by every clone.
+    dropped: Arc<AtomicU64>,
+}
+
+impl MessageTransmitter<Event> for BoundedEventTx {
+    fn send(&mut self, message: Event) {
+        if self.receiver_dropped {
+            return;
+        }
+
+        let result = match self.policy {
+            OverflowPolicy::Drop => match self.event_tx.try_send(message) {
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    Ok(())
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => Err(()),
+                Ok(()) => Ok(()),
+            },
+            // futures block_on is used since tokio's blocking_send panics inside a runtime
+            OverflowPolicy::Block => {
+                futures::executor::block_on(self.event_tx.send(message)).map_err(|_| ())
+            }
+        };
+
+        if result.is_err() {
+            warn!(
+                action = "setting receiver_dropped = true",
+                why = "event receiver dropped",
+                "cannot send Events"
+            );
+            self.receiver_dropped = true;
+        }
+    }
+
+    fn send_many(&mut self, messages: Vec<Event>) {
+        for message in messages {
+            self.send(message);
+
+            if self.receiver_dropped {
+                return;
+            }
+        }
+    }
+}
+
+impl BoundedEventTx {
+    /// Constructs a new [`BoundedEventTx`] instance using the provided bounded channel
+    /// transmitter & [`OverflowPolicy`].
+    pub fn new(event_tx: mpsc::Sender<Event>, policy: OverflowPolicy) -> Self {
+        Self {
+            receiver_dropped: false,
+            event_tx,
+            policy,
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Number of [`Event`]s dropped by this [`BoundedEventTx`] & it's clones due to a full
+    /// channel.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Transmitter for broadcasting Barter [`Event`]s to every currently subscribed receiver
+/// (eg/ WebSocket clients). [`Event`]s sent while there are no subscribers are discarded, and
+/// subscribers that fall behind by more than the channel capacity miss the oldest [`Event`]s.
+#[derive(Debug, Clone)]
+pub struct BroadcastEventTx {
+    event_tx: broadcast::Sender<Event>,
+}
+
+impl MessageTransmitter<Event> for BroadcastEventTx {
+    fn send(&mut self, message: Event) {
+        // Only errors if there are no subscribers, which is not a failure for a broadcast
+        let _ = self.event_tx.send(message);
+    }
+
+    fn send_many(&mut self, messages: Vec<Event>) {
+        messages.into_iter().for_each(|message| self.send(message))
+    }
+}
+
+impl BroadcastEventTx {
+    /// Constructs a new [`BroadcastEventTx`] instance using the provided broadcast transmitter.
+    pub fn new(event_tx: broadcast::Sender<Event>) -> Self {
+        Self { event_tx }
+    }
+
+    /// Subscribe a new receiver to the [`Event`]s sent after this call.
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.event_tx.subscribe()
+    }
+}
+
+/// [`MessageTransmitter`] that appends every [`Event`] to a file as newline delimited JSON. Every
+/// clone writes to the same file.
+///
+/// Unlike the [`Journal`](super::journal::Journal), [`Event`]s are not sequenced, making this
+/// suitable for audit trails & external tooling rather than recovery.
+#[derive(Debug, Clone)]
+pub struct JsonLinesTx {
+    path: PathBuf,
+    writer: Arc<Mutex<BufWriter<File>>>,
+}
+
+impl JsonLinesTx {
+    /// Opens the JSON lines file at the provided path for appending, creating it if it does not
+    /// exist.
+    pub fn open<P: Into<PathBuf>>(path: P) -> Result<Self, std::io::Error> {
+        let path = path.into();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        Ok(Self {
+            path,
+            writer: Arc::new(Mutex::new(BufWriter::new(file))),
+        })
+    }
+
+    /// Path of the JSON lines file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn write(&self, messages: &[Event]) {
+        let mut writer = self.writer.lock();
+
+        let result = messages
+            .iter()
+            .try_for_each(|message| {
+                serde_json::to_writer(&mut *writer, message)?;
+                writer.write_all(b"\n").map_err(serde_json::Error::io)
+            })
+            .and_then(|_| writer.flush().map_err(serde_json::Error::io));
+
+        if let Err(error) = result {
+            error!(
+                ?error,
+                path = %self.path.display(),
+                "failed to write Events to JSON lines file"
+            );
+        }
+    }
+}
+
+impl MessageTransmitter<Event> for JsonLinesTx {
+    fn send(&mut self, message: Event) {
+        self.write(&[message]);
+    }
+
+    fn send_many(&mut self, messages: Vec<Event>) {
+        self.write(&messages);
+    }
+}
+
+/// Upper bounds (in seconds) of the [`LatencyHistogram`] buckets.
+pub const LATENCY_BUCKETS: [f64; 12] = [
+    0.000_01, 0.000_05, 0.000_1, 0.000_5, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0,
+];
+
+/// In-process aggregate of the [`Event`] stream & Engine health, maintained by a [`MetricsTx`].
+/// Rendered in the Prometheus text exposition format by [`metrics::render`](crate::metrics::render).
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct EventMetrics {
+    /// Number of [`Event`]s received for each [`EventKind`].
+    pub events: BTreeMap<EventKind, u64>,
+    /// Number of [`Event`]s received for each market (keyed by [`MarketId`]) & [`EventKind`].
+    pub market_events: BTreeMap<String, BTreeMap<EventKind, u64>>,
+    /// Number of Positions entered but not yet exited.
+    pub open_positions: u64,
+    /// Unrealised profit & loss of every open Position, keyed by [`PositionId`].
+    pub open_position_profit_loss: BTreeMap<PositionId, OpenPositionProfitLoss>,
+    /// Sum of the fees of every [`FillEvent`](crate::execution::FillEvent).
+    pub fees_total: f64,
+    /// Sum of the realised profit & loss of every exited Position.
+    pub realised_profit_loss: f64,
+    /// Most recent Portfolio [`Balance`].
+    pub balance: Option<Balance>,
+    /// Number of [`Feed::Unhealthy`](crate::data::Feed::Unhealthy) occurrences for each market
+    /// (keyed by [`MarketId`]).
+    pub feed_unhealthy: BTreeMap<String, u64>,
+    /// Latency of each repository operation.
+    pub repository_latency: BTreeMap<String, LatencyHistogram>,
+    /// Latency of processing a [`Trader`](crate::engine::trader::Trader) trading loop event_q for
+    /// each market (keyed by [`MarketId`]).
+    pub trader_loop_latency: BTreeMap<String, LatencyHistogram>,
+}
+
+/// Unrealised profit & loss of an open Position in [`EventMetrics`].
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct OpenPositionProfitLoss {
+    /// [`MarketId`] of the Position's market.
+    pub market: String,
+    pub unrealised_profit_loss: f64,
+}
+
+/// Cumulative histogram of observed durations, bucketed by [`LATENCY_BUCKETS`].
+#[derive(Copy, Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct LatencyHistogram {
+    /// Number of observations less than or equal to each of the [`LATENCY_BUCKETS`].
+    pub buckets: [u64; LATENCY_BUCKETS.len()],
+    /// Sum of every observation, in seconds.
+    pub sum: f64,
+    /// Number of observations.
+    pub count: u64,
+}
+
+impl LatencyHistogram {
+    /// Record an observed duration.
+    pub fn observe(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+
+        self.buckets
+            .iter_mut()
+            .zip(LATENCY_BUCKETS)
+            .filter(|(_, upper_bound)| seconds <= *upper_bound)
+            .for_each(|(bucket, _)| *bucket += 1);
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+impl EventMetrics {
+    /// Number of [`Event`]s received of the provided [`EventKind`].
+    pub fn count(&self, kind: EventKind) -> u64 {
+        self.events.get(&kind).copied().unwrap_or_default()
+    }
+
+    /// Number of [`Event`]s received of the provided [`EventKind`] for the provided [`Market`].
+    pub fn market_count(&self, market: &Market, kind: EventKind) -> u64 {
+        self.market_events
+            .get(&MarketId::from(market).0)
+            .and_then(|events| events.get(&kind))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Update the [`EventMetrics`] with the provided [`Event`].
+    pub fn update(&mut self, event: &Event) {
+        *self.events.entry(event.kind()).or_default() += 1;
+
+        if let Some(market) = event.market() {
+            *self
+                .market_events
+                .entry(MarketId::from(&market).0)
+                .or_default()
+                .entry(event.kind())
+                .or_default() += 1;
+        }
+
+        match event {
+            Event::Fill(fill) => self.fees_total += fill.fees.calculate_total_fees(),
+            Event::PositionNew(position) => {
+                self.open_positions += 1;
+                self.open_position_profit_loss.insert(
+                    position.position_id.clone(),
+                    OpenPositionProfitLoss {
+                        market: MarketId::new(&position.exchange, &position.instrument).0,
+                        unrealised_profit_loss: position.unrealised_profit_loss,
+                    },
+                );
+            }
+            Event::PositionUpdate(update) => {
+                if let Some(position) = self.open_position_profit_loss.get_mut(&update.position_id)
+                {
+                    position.unrealised_profit_loss = update.unrealised_profit_loss;
+                }
+            }
+            Event::PositionExit(exit) => {
+                self.open_positions = self.open_positions.saturating_sub(1);
+                self.open_position_profit_loss.remove(&exit.position_id);
+                self.realised_profit_loss += exit.realised_profit_loss;
+                self.balance = Some(exit.exit_balance);
+            }
+            Event::Balance(balance) => self.balance = Some(*balance),
+            _ => {}
+        }
+    }
+}
+
+/// [`MessageTransmitter`] that aggregates every [`Event`] into [`EventMetrics`] shared by every
+/// clone, so many [`Trader`](crate::engine::trader::Trader)s contribute to one aggregate.
+///
+/// Besides the [`Event`] stream (eg/ combined with an [`EventTx`](crate::event::EventTx) using a
+/// [`FanOutTx`]), it records:
+/// - [`Feed::Unhealthy`](crate::data::Feed::Unhealthy) occurrences & trading loop latency via the
+///   [`TraderBuilder::metrics`](crate::engine::trader::TraderBuilder::metrics).
+/// - Repository latency via the
+///   [`InstrumentedRepository`](crate::portfolio::repository::instrumented::InstrumentedRepository).
+#[derive(Debug, Clone, Default)]
+pub struct MetricsTx {
+    metrics: Arc<Mutex<EventMetrics>>,
+}
+
+impl MetricsTx {
+    /// Constructs a new [`MetricsTx`] with empty [`EventMetrics`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of the current [`EventMetrics`].
+    pub fn metrics(&self) -> EventMetrics {
+        self.metrics.lock().clone()
+    }
+
+    /// Record a [`Feed::Unhealthy`](crate::data::Feed::Unhealthy) yielded for the provided
+    /// [`Market`].
+    pub fn record_feed_unhealthy(&self, market: &Market) {
+        *self
+            .metrics
+            .lock()
+            .feed_unhealthy
+            .entry(MarketId::from(market).0)
+            .or_default() += 1;
+    }
+
+    /// Record the time a [`Trader`](crate::engine::trader::Trader) trading the provided
+    /// [`Market`] took to process the event_q of one trading loop iteration.
+    pub fn record_trader_loop(&self, market: &Market, duration: Duration) {
+        self.metrics
+            .lock()
+            .trader_loop_latency
+            .entry(MarketId::from(market).0)
+            .or_default()
+            .observe(duration);
+    }
+
+    /// Record the time a repository operation took.
+    pub fn record_repository(&self, operation: &str, duration: Duration) {
+        let mut metrics = self.metrics.lock();
+        match metrics.repository_latency.get_mut(operation) {
+            Some(histogram) => histogram.observe(duration),
+            None => {
+                let mut histogram = LatencyHistogram::default();
+                histogram.observe(duration);
+                metrics
+                    .repository_latency
+                    .insert(operation.to_owned(), histogram);
+            }
+        }
+    }
+}
+
+impl MessageTransmitter<Event> for MetricsTx {
+    fn send(&mut self, message: Event) {
+        self.metrics.lock().update(&message);
+    }
+
+    fn send_many(&mut self, messages: Vec<Event>) {
+        let mut metrics = self.metrics.lock();
+        messages.iter().for_each(|message| metrics.update(message));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::MarketMeta,
+        execution::{Fees, FillEvent},
+        portfolio::position::PositionExit,
+        strategy::Decision,
+    };
+    use barter_data::test_util::market_trade;
+    use barter_integration::model::{Exchange, Instrument, InstrumentKind, Side};
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+
+    fn balance_event(total: f64) -> Event {
+        Event::Balance(Balance {
+            time: DateTime::<Utc>::MIN_UTC,
+            total,
+            available: total,
+        })
+    }
+
+    fn market_event(exchange: &'static str, base: &'static str) -> Event {
+        let mut market = market_trade(Side::Buy);
+        market.exchange = Exchange::from(exchange);
+        market.instrument = Instrument::from((base, "usdt", InstrumentKind::Spot));
+        Event::Market(market)
+    }
+
+    fn fill_event(exchange_fee: f64) -> Event {
+        Event::Fill(FillEvent {
+            time: DateTime::<Utc>::MIN_UTC,
+            exchange: Exchange::from("binance"),
+            instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+            market_meta: MarketMeta {
+                close: 100.0,
+                time: DateTime::<Utc>::MIN_UTC,
+            },
+            decision: Decision::Long,
+            quantity: 1.0,
+            fill_value_gross: 100.0,
+            fees: Fees {
+                exchange: exchange_fee,
+                slippage: 0.0,
+                network: 0.0,
+            },
+            tag: None,
+        })
+    }
+
+    fn position_exit_event(position_id: String, realised_profit_loss: f64) -> Event {
+        Event::PositionExit(PositionExit {
+            position_id,
+            exit_time: DateTime::<Utc>::MIN_UTC,
+            exit_balance: Balance {
+                time: DateTime::<Utc>::MIN_UTC,
+                total: 1000.0 + realised_profit_loss,
+                available: 1000.0 + realised_profit_loss,
+            },
+            exit_fees: Fees::default(),
+            exit_fees_total: 0.0,
+            exit_avg_price_gross: 0.0,
+            exit_value_gross: 0.0,
+            realised_profit_loss,
+            max_adverse_excursion: 0.0,
+            max_favourable_excursion: 0.0,
+        })
+    }
+
+    #[test]
+    fn fan_out_tx_sends_to_every_sink() {
+        let first = MetricsTx::new();
+        let second = MetricsTx::new();
+        let mut fan_out = FanOutTx::new(first.clone(), second.clone());
+
+        fan_out.send(balance_event(1.0));
+        fan_out.send_many(vec![balance_event(2.0), fill_event(1.0)]);
+
+        for sink in [first, second] {
+            let metrics = sink.metrics();
+            assert_eq!(metrics.count(EventKind::Balance), 2);
+            assert_eq!(metrics.count(EventKind::Fill), 1);
+        }
+    }
+
+    #[test]
+    fn filter_tx_by_kinds() {
+        let metrics = MetricsTx::new();
+        let mut filter = FilterTx::new(metrics.clone(), kinds(vec![EventKind::Fill]));
+
+        filter.send(balance_event(1.0));
+        filter.send_many(vec![fill_event(1.0), market_event("binance", "eth")]);
+
+        let metrics = metrics.metrics();
+        assert_eq!(metrics.count(EventKind::Fill), 1);
+        assert_eq!(metrics.events.len(), 1);
+    }
+
+    #[test]
+    fn filter_tx_by_markets() {
+        let market = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+        let position_id = crate::portfolio::position::determine_position_id(
+            Uuid::new_v4(),
+            &market.exchange,
+            &market.instrument,
+        );
+
+        let tagged_position_id = format!("{}_tag_momentum", position_id);
+
+        let metrics = MetricsTx::new();
+        let mut filter = FilterTx::new(metrics.clone(), markets(vec![market]));
+
+        filter.send_many(vec![
+            market_event("binance", "eth"),
+            market_event("binance", "btc"),
+            market_event("ftx", "eth"),
+            fill_event(1.0),
+            position_exit_event(position_id, 10.0),
+            position_exit_event(tagged_position_id, 10.0),
+            position_exit_event("other_binance_btc_usdt_position".to_owned(), 10.0),
+            position_exit_event(
+                "other_binance_btc_usdt_position_tag_momentum".to_owned(),
+                10.0,
+            ),
+            balance_event(1.0),
+        ]);
+
+        let metrics = metrics.metrics();
+        assert_eq!(metrics.count(EventKind::Market), 1);
+        assert_eq!(metrics.count(EventKind::Fill), 1);
+        assert_eq!(metrics.count(EventKind::PositionExit), 2);
+        assert_eq!(metrics.count(EventKind::Balance), 0);
+    }
+
+    #[test]
+    fn bounded_event_tx_drops_events_when_full() {
+        let (event_tx, mut event_rx) = mpsc::channel(2);
+        let mut bounded = BoundedEventTx::new(event_tx, OverflowPolicy::Drop);
+
+        bounded.send_many(vec![
+            balance_event(1.0),
+            balance_event(2.0),
+            balance_event(3.0),
+        ]);
+
+        assert_eq!(bounded.dropped(), 1);
+        assert_eq!(event_rx.try_recv().unwrap(), balance_event(1.0));
+        assert_eq!(event_rx.try_recv().unwrap(), balance_event(2.0));
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn bounded_event_tx_blocks_until_capacity() {
+        let (event_tx, mut event_rx) = mpsc::channel(1);
+        let mut bounded = BoundedEventTx::new(event_tx, OverflowPolicy::Block);
+
+        let sender = std::thread::spawn(move || {
+            bounded.send_many(vec![
+                balance_event(1.0),
+                balance_event(2.0),
+                balance_event(3.0),
+            ]);
+            bounded.dropped()
+        });
+
+        let received = (0..3)
+            .map(|_| event_rx.blocking_recv().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(sender.join().unwrap(), 0);
+        assert_eq!(
+            received,
+            vec![balance_event(1.0), balance_event(2.0), balance_event(3.0)]
+        );
+    }
+
+    #[test]
+    fn bounded_event_tx_stops_sending_when_receiver_dropped() {
+        let (event_tx, event_rx) = mpsc::channel(1);
+        let mut bounded = BoundedEventTx::new(event_tx, OverflowPolicy::Block);
+        drop(event_rx);
+
+        bounded.send_many(vec![balance_event(1.0), balance_event(2.0)]);
+
+        assert!(bounded.receiver_dropped);
+        assert_eq!(bounded.dropped(), 0);
+    }
+
+    #[test]
+    fn broadcast_event_tx_sends_to_every_subscriber() {
+        let (event_tx, _) = broadcast::channel(4);
+        let mut broadcast = BroadcastEventTx::new(event_tx);
+
+        // Events sent without subscribers are discarded
+        broadcast.send(balance_event(1.0));
+
+        let mut first = broadcast.subscribe();
+        let mut second = broadcast.subscribe();
+        broadcast.send_many(vec![balance_event(2.0), balance_event(3.0)]);
+
+        for receiver in [&mut first, &mut second] {
+            assert_eq!(receiver.try_recv().unwrap(), balance_event(2.0));
+            assert_eq!(receiver.try_recv().unwrap(), balance_event(3.0));
+            assert!(receiver.try_recv().is_err());
+        }
+    }
+
+    #[test]
+    fn json_lines_tx_appends_events() {
+        let path = std::env::temp_dir().join(format!("barter_json_lines_{}.jsonl", Uuid::new_v4()));
+
+        let mut json_lines = JsonLinesTx::open(&path).unwrap();
+        json_lines.send(balance_event(1.0));
+        json_lines
+            .clone()
+            .send_many(vec![fill_event(1.0), balance_event(2.0)]);
+        drop(json_lines);
+
+        let events = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<Event>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            vec![balance_event(1.0), fill_event(1.0), balance_event(2.0)]
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn metrics_tx_aggregates_events() {
+        let mut metrics_tx = MetricsTx::new();
+        let mut clone = metrics_tx.clone();
+
+        metrics_tx.send_many(vec![fill_event(1.5), fill_event(0.5)]);
+        clone.send(position_exit_event("position".to_owned(), -20.0));
+        clone.send(position_exit_event("position".to_owned(), 50.0));
+
+        let metrics = metrics_tx.metrics();
+        assert_eq!(metrics.count(EventKind::Fill), 2);
+        assert_eq!(metrics.count(EventKind::PositionExit), 2);
+        assert_eq!(metrics.open_positions, 0);
+        assert_eq!(metrics.fees_total, 2.0);
+        assert_eq!(metrics.realised_profit_loss, 30.0);
+        assert_eq!(metrics.balance.unwrap().total, 1050.0);
+    }
+}
@@ -1,8 +1,11 @@
 use thiserror::Error;
 
 /// All errors generated in the barter::execution module.
-#[derive(Error, Copy, Clone, Debug)]
+#[derive(Error, Clone, Debug)]
 pub enum ExecutionError {
     #[error("Failed to build struct due to missing attributes: {0}")]
     BuilderIncomplete(&'static str),
+
+    #[error("Failed to fetch account state from the execution venue: {0}")]
+    AccountUnavailable(String),
 }
@@ -1,4 +1,9 @@
-use crate::{data::MarketMeta, portfolio::OrderEvent, strategy::Decision};
+use crate::{
+    data::MarketMeta,
+    portfolio::{position::PositionTag, Balance, OrderEvent},
+    strategy::Decision,
+};
+use async_trait::async_trait;
 use barter_integration::model::{Exchange, Instrument};
 use chrono::{DateTime, Utc};
 use error::ExecutionError;
@@ -10,12 +15,56 @@ pub mod error;
 /// Handlers for simulated and live [`OrderEvent`] execution.
 pub mod simulated;
 
+/// Netting of the per-tag [`OrderEvent`]s of a market into the single [`OrderEvent`] executed at
+/// the venue.
+pub mod netting;
+
 /// Generates a result [`FillEvent`] by executing an [`OrderEvent`].
 pub trait ExecutionClient {
     /// Return a [`FillEvent`] from executing the input [`OrderEvent`].
     fn generate_fill(&self, order: &OrderEvent) -> Result<FillEvent, ExecutionError>;
 }
 
+impl<Execution> ExecutionClient for Box<Execution>
+where
+    Execution: ExecutionClient + ?Sized,
+{
+    fn generate_fill(&self, order: &OrderEvent) -> Result<FillEvent, ExecutionError> {
+        (**self).generate_fill(order)
+    }
+}
+
+/// Asynchronously generates a result [`FillEvent`] by executing an [`OrderEvent`], awaiting the
+/// execution venue's response rather than blocking the thread.
+#[async_trait]
+pub trait AsyncExecutionClient {
+    /// Await & return a [`FillEvent`] from executing the input [`OrderEvent`].
+    async fn execute(&self, order: &OrderEvent) -> Result<FillEvent, ExecutionError>;
+}
+
+/// Reports the account state held at the execution venue. Used to reconcile the persisted
+/// Portfolio state when resuming an [`Engine`](crate::engine::Engine) after a restart.
+pub trait AccountReporter {
+    /// Return an [`AccountSnapshot`] of the account state currently held at the execution venue.
+    fn fetch_account(&self) -> Result<AccountSnapshot, ExecutionError>;
+}
+
+/// Account state held at the execution venue at a point in time.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct AccountSnapshot {
+    pub balance: Balance,
+    pub positions: Vec<VenuePosition>,
+}
+
+/// Open position held at the execution venue.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct VenuePosition {
+    pub exchange: Exchange,
+    pub instrument: Instrument,
+    /// +ve or -ve quantity of symbol contracts held, depending on the position Side.
+    pub quantity: f64,
+}
+
 /// Fills are journals of work done by an Execution handler. These are sent back to the portfolio
 /// so it can apply updates.
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
@@ -33,6 +82,9 @@ pub struct FillEvent {
     pub fill_value_gross: f64,
     /// All fee types incurred when executing an [`OrderEvent`], and their associated [`FeeAmount`].
     pub fees: Fees,
+    /// Optional [`PositionTag`] propagated from the [`OrderEvent`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<PositionTag>,
 }
 
 impl FillEvent {
@@ -76,6 +128,7 @@ pub struct FillEventBuilder {
     pub quantity: Option<f64>,
     pub fill_value_gross: Option<f64>,
     pub fees: Option<Fees>,
+    pub tag: Option<PositionTag>,
 }
 
 impl FillEventBuilder {
@@ -139,6 +192,13 @@ impl FillEventBuilder {
         }
     }
 
+    pub fn tag(self, value: PositionTag) -> Self {
+        Self {
+            tag: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<FillEvent, ExecutionError> {
         Ok(FillEvent {
             time: self.time.ok_or(ExecutionError::BuilderIncomplete("time"))?,
@@ -161,6 +221,7 @@ impl FillEventBuilder {
                 .fill_value_gross
                 .ok_or(ExecutionError::BuilderIncomplete("fill_value_gross"))?,
             fees: self.fees.ok_or(ExecutionError::BuilderIncomplete("fees"))?,
+            tag: self.tag,
         })
     }
 }
@@ -0,0 +1,192 @@
+use crate::{
+    execution::{Fees, FillEvent},
+    portfolio::OrderEvent,
+    strategy::Decision,
+};
+
+/// [`OrderEvent`]s generated for one market in the same trading loop iteration (eg/ by
+/// independent sub-strategies holding separately tagged
+/// [`Position`](crate::portfolio::position::Position)s), netted into the single [`OrderEvent`]
+/// executed at the venue.
+///
+/// The [`FillEvent`] of the net [`OrderEvent`] is split back into a [`FillEvent`] per tagged
+/// [`OrderEvent`], so each tag's Position is entered or exited with it's own quantity. Opposing
+/// quantities that cancel out are crossed internally at the net fill price (or the
+/// [`OrderEvent`]'s close price if nothing is executed at the venue).
+#[derive(Clone, PartialEq, Debug)]
+pub struct NettedOrders {
+    orders: Vec<OrderEvent>,
+    net: Option<OrderEvent>,
+}
+
+impl NettedOrders {
+    /// Net the provided [`OrderEvent`]s, which must all be for the same market. A single
+    /// [`OrderEvent`] is executed unchanged.
+    pub fn new(mut orders: Vec<OrderEvent>) -> Self {
+        let net = match orders.as_slice() {
+            [] => None,
+            [order] => Some(order.clone()),
+            [first, .., last] => {
+                let quantity = orders.iter().map(|order| order.quantity).sum::<f64>();
+                (quantity.abs() > f64::EPSILON).then(|| OrderEvent {
+                    time: last.time,
+                    exchange: first.exchange.clone(),
+                    instrument: first.instrument.clone(),
+                    market_meta: last.market_meta,
+                    decision: if quantity > 0.0 {
+                        Decision::Long
+                    } else {
+                        Decision::Short
+                    },
+                    quantity,
+                    order_type: first.order_type,
+                    tag: None,
+                })
+            }
+        };
+
+        if orders.len() == 1 {
+            orders.clear();
+        }
+
+        Self { orders, net }
+    }
+
+    /// Net [`OrderEvent`] to execute at the venue. `None` if the [`OrderEvent`]s cancel out.
+    pub fn net_order(&self) -> Option<&OrderEvent> {
+        self.net.as_ref()
+    }
+
+    /// Split the [`FillEvent`] of the net [`OrderEvent`] back into a [`FillEvent`] for every
+    /// netted [`OrderEvent`]. Fees are attributed in proportion to each [`OrderEvent`]'s absolute
+    /// quantity.
+    pub fn split_fill(self, net_fill: Option<FillEvent>) -> Vec<FillEvent> {
+        // Single OrderEvent was executed unchanged
+        if self.orders.is_empty() {
+            return net_fill.into_iter().collect();
+        }
+
+        let price = net_fill
+            .as_ref()
+            .filter(|fill| fill.quantity.abs() > f64::EPSILON)
+            .map(|fill| fill.fill_value_gross / fill.quantity.abs());
+        let fees = net_fill.as_ref().map(|fill| fill.fees).unwrap_or_default();
+        let total_quantity = self
+            .orders
+            .iter()
+            .map(|order| order.quantity.abs())
+            .sum::<f64>();
+
+        self.orders
+            .into_iter()
+            .map(|order| {
+                let share = order.quantity.abs() / total_quantity;
+                FillEvent {
+                    time: net_fill.as_ref().map_or(order.time, |fill| fill.time),
+                    fill_value_gross: order.quantity.abs()
+                        * price.unwrap_or(order.market_meta.close),
+                    fees: Fees {
+                        exchange: fees.exchange * share,
+                        slippage: fees.slippage * share,
+                        network: fees.network * share,
+                    },
+                    exchange: order.exchange,
+                    instrument: order.instrument,
+                    market_meta: order.market_meta,
+                    decision: order.decision,
+                    quantity: order.quantity,
+                    tag: order.tag,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::MarketMeta, portfolio::OrderType, test_util::order_event};
+
+    fn order(decision: Decision, quantity: f64, tag: &str) -> OrderEvent {
+        OrderEvent {
+            market_meta: MarketMeta {
+                close: 100.0,
+                ..Default::default()
+            },
+            decision,
+            quantity,
+            order_type: OrderType::Market,
+            tag: Some(tag.to_owned()),
+            ..order_event()
+        }
+    }
+
+    fn fill(order: &OrderEvent, price: f64, fees: f64) -> FillEvent {
+        FillEvent {
+            time: order.time,
+            exchange: order.exchange.clone(),
+            instrument: order.instrument.clone(),
+            market_meta: order.market_meta,
+            decision: order.decision,
+            quantity: order.quantity,
+            fill_value_gross: order.quantity.abs() * price,
+            fees: Fees {
+                exchange: fees,
+                ..Default::default()
+            },
+            tag: order.tag.clone(),
+        }
+    }
+
+    #[test]
+    fn single_order_is_executed_unchanged() {
+        let order = order(Decision::Long, 2.0, "a");
+        let netted = NettedOrders::new(vec![order.clone()]);
+        assert_eq!(netted.net_order(), Some(&order));
+
+        let net_fill = fill(&order, 101.0, 1.0);
+        assert_eq!(netted.split_fill(Some(net_fill.clone())), vec![net_fill]);
+    }
+
+    #[test]
+    fn opposing_orders_are_netted_and_the_fill_split_per_tag() {
+        let long = order(Decision::Long, 3.0, "a");
+        let short = order(Decision::Short, -1.0, "b");
+        let netted = NettedOrders::new(vec![long, short]);
+
+        let net_order = netted.net_order().unwrap().clone();
+        assert_eq!(net_order.quantity, 2.0);
+        assert_eq!(net_order.decision, Decision::Long);
+        assert_eq!(net_order.tag, None);
+
+        let fills = netted.split_fill(Some(fill(&net_order, 110.0, 4.0)));
+        assert_eq!(fills.len(), 2);
+
+        assert_eq!(fills[0].tag.as_deref(), Some("a"));
+        assert_eq!(fills[0].decision, Decision::Long);
+        assert_eq!(fills[0].quantity, 3.0);
+        assert_eq!(fills[0].fill_value_gross, 330.0);
+        assert_eq!(fills[0].fees.exchange, 3.0);
+
+        assert_eq!(fills[1].tag.as_deref(), Some("b"));
+        assert_eq!(fills[1].decision, Decision::Short);
+        assert_eq!(fills[1].quantity, -1.0);
+        assert_eq!(fills[1].fill_value_gross, 110.0);
+        assert_eq!(fills[1].fees.exchange, 1.0);
+    }
+
+    #[test]
+    fn orders_that_cancel_out_are_crossed_without_venue_execution() {
+        let long = order(Decision::Long, 1.0, "a");
+        let close_long = order(Decision::CloseLong, -1.0, "b");
+        let netted = NettedOrders::new(vec![long, close_long]);
+        assert_eq!(netted.net_order(), None);
+
+        let fills = netted.split_fill(None);
+        assert_eq!(fills.len(), 2);
+        assert!(fills
+            .iter()
+            .all(|fill| fill.fill_value_gross == 100.0 && fill.fees.calculate_total_fees() == 0.0));
+        assert_eq!(fills[1].decision, Decision::CloseLong);
+    }
+}
@@ -1,8 +1,9 @@
-use chrono::Utc;
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+use crate::clock::Clock;
 use crate::execution::error::ExecutionError;
-use crate::execution::{ExecutionClient, Fees, FillEvent};
+use crate::execution::{AsyncExecutionClient, ExecutionClient, Fees, FillEvent};
 use crate::portfolio::OrderEvent;
 
 /// Configuration for constructing a [`SimulatedExecution`] via the new() constructor method.
@@ -10,6 +11,9 @@ use crate::portfolio::OrderEvent;
 pub struct Config {
     /// Simulated fee percentage to be used for each [`Fees`] field in decimal form (eg/ 0.01 for 1%)
     pub simulated_fees_pct: Fees,
+    /// [`Clock`] used to timestamp generated [`FillEvent`]s.
+    #[serde(default)]
+    pub clock: Clock,
 }
 
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
@@ -17,6 +21,7 @@ pub struct Config {
 /// simulated broker interaction.
 pub struct SimulatedExecution {
     fees_pct: Fees,
+    clock: Clock,
 }
 
 impl ExecutionClient for SimulatedExecution {
@@ -25,7 +30,7 @@ impl ExecutionClient for SimulatedExecution {
         let fill_value_gross = SimulatedExecution::calculate_fill_value_gross(order);
 
         Ok(FillEvent {
-            time: Utc::now(),
+            time: self.clock.time(order.time),
             exchange: order.exchange.clone(),
             instrument: order.instrument.clone(),
             market_meta: order.market_meta,
@@ -33,15 +38,24 @@ impl ExecutionClient for SimulatedExecution {
             quantity: order.quantity,
             fill_value_gross,
             fees: self.calculate_fees(&fill_value_gross),
+            tag: order.tag.clone(),
         })
     }
 }
 
+#[async_trait]
+impl AsyncExecutionClient for SimulatedExecution {
+    async fn execute(&self, order: &OrderEvent) -> Result<FillEvent, ExecutionError> {
+        self.generate_fill(order)
+    }
+}
+
 impl SimulatedExecution {
     /// Constructs a new [`SimulatedExecution`] component.
     pub fn new(cfg: Config) -> Self {
         Self {
             fees_pct: cfg.simulated_fees_pct,
+            clock: cfg.clock,
         }
     }
 
@@ -73,6 +87,7 @@ mod tests {
                 slippage: 0.05,
                 network: 0.0,
             },
+            clock: Clock::Live,
         });
 
         let mut input_order = order_event();
@@ -128,6 +143,7 @@ mod tests {
                 slippage: 0.1,
                 network: 0.001,
             },
+            clock: Clock::Live,
         });
 
         let input_fill_value_gross = 100.0;
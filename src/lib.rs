@@ -62,6 +62,7 @@
 //! ### Strategy
 //! ```
 //! use barter::{
+//!     clock::Clock,
 //!     strategy::{SignalGenerator, example::{Config as StrategyConfig, RSIStrategy}},
 //! };
 //! use barter_data::{
@@ -72,6 +73,7 @@
 //!
 //! let config = StrategyConfig {
 //!     rsi_period: 14,
+//!     clock: Clock::Live,
 //! };
 //!
 //! let mut strategy = RSIStrategy::new(config);
@@ -84,8 +86,9 @@
 //! ### Portfolio
 //! ```
 //! use barter::{
+//!     clock::Clock,
 //!     portfolio::{
-//!         MarketUpdater, OrderGenerator, FillUpdater,
+//!         MarketUpdater, OrderGenerator, FillUpdater, PositionMode,
 //!         portfolio::{PortfolioLego, MetaPortfolio},
 //!         repository::in_memory::InMemoryRepository,
 //!         allocator::DefaultAllocator,
@@ -109,11 +112,13 @@
 //!     allocator: DefaultAllocator{ default_order_value: 100.0 },
 //!     risk: DefaultRisk{},
 //!     starting_cash: 10000.0,
+//!     position_mode: PositionMode::OneWay,
 //!     statistic_config: StatisticConfig {
 //!         starting_equity: 10000.0 ,
 //!         trading_days_per_year: 365,
 //!         risk_free_return: 0.0
 //!     },
+//!     clock: Clock::Live,
 //!     _statistic_marker: PhantomData::<TradingSummary>::default()
 //! };
 //!
@@ -141,6 +146,7 @@
 //! ### Execution
 //! ```
 //! use barter::{
+//!     clock::Clock,
 //!     test_util,
 //!     portfolio::OrderEvent,
 //!     execution::{
@@ -154,7 +160,8 @@
 //!         exchange: 0.1,
 //!         slippage: 0.05, // Simulated slippage modelled as a Fee
 //!         network: 0.0,
-//!     }
+//!     },
+//!     clock: Clock::Live,
 //! };
 //!
 //! let mut execution = SimulatedExecution::new(config);
@@ -211,6 +218,11 @@
 /// system heartbeat.
 pub mod data;
 
+/// Defines the Clock used to timestamp the Events generated by the Strategy, Portfolio &
+/// Execution components. Backtests use the time of the market data being actioned, rather than
+/// the wall-clock time, so they are deterministic.
+pub mod clock;
+
 /// Defines a SignalEvent and SignalForceExit, as well as the SignalGenerator trait for handling the
 /// generation of them. Contains an example RSIStrategy implementation that analyses a MarketEvent
 /// and may generate a new advisory SignalEvent to be analysed by the Portfolio OrderGenerator.
@@ -232,7 +244,8 @@ pub mod execution;
 
 /// Defines an Event enum that contains variants that are vital to the trading event loop
 /// (eg/ MarketEvent). Other variants communicate work done by the system (eg/ FillEvent), as well
-/// as changes in system state (eg/ PositionUpdate).
+/// as changes in system state (eg/ PositionUpdate). Events can be journaled & replayed to recover
+/// the Portfolio state after a crash.
 pub mod event;
 
 /// Defines various iterative statistical methods that can be used to calculate trading performance
@@ -245,6 +258,31 @@ pub mod statistic;
 /// Execution components, as well as shared access to a global Portfolio.
 pub mod engine;
 
+/// Backtest regression harness. Records the full Event stream & final statistics of a
+/// deterministic backtest Engine run as a Snapshot, and compares it against a stored golden file
+/// with floating point tolerance & readable diffs.
+pub mod regression;
+
+/// Renders the Engine health & trading KPI [`EventMetrics`](event::sink::EventMetrics) (eg/
+/// MarketEvent throughput, open Positions, repository & trading loop latency) in the Prometheus
+/// text exposition format.
+pub mod metrics;
+
+/// Declarative Engine configuration (JSON, TOML or YAML) describing the markets, data sources &
+/// components of an Engine, with a Registry for plugging in user-defined components by name.
+/// Provides the `barter` command line interface with the `cli` feature.
+pub mod config;
+
+/// Optional embedded HTTP & WebSocket server for remotely controlling an Engine via it's Commands,
+/// fetching balances & per-market statistics, and streaming the Event feed as JSON.
+#[cfg(feature = "server")]
+pub mod server;
+
+/// Optional terminal dashboard state, API client & terminal handling used by the
+/// `barter-dashboard` binary to monitor & control a running Engine via it's [`server`].
+#[cfg(feature = "dashboard")]
+pub mod dashboard;
+
 #[macro_use]
 extern crate prettytable;
 
@@ -255,9 +293,23 @@ pub mod test_util {
         portfolio::{position::Position, OrderEvent, OrderType},
         strategy::{Decision, Signal},
     };
+    use barter_data::model::{DataKind, MarketEvent};
     pub use barter_data::test_util::{market_candle, market_trade};
     use barter_integration::model::{Exchange, Instrument, InstrumentKind, Side};
-    use chrono::Utc;
+    use chrono::{Duration, Utc};
+
+    /// Build a [`MarketEvent`] containing a one minute [`Candle`](barter_data::model::Candle)
+    /// with the provided high, low & close.
+    pub fn market_candle_hlc(high: f64, low: f64, close: f64) -> MarketEvent {
+        let mut market = market_candle(Duration::minutes(1));
+        if let DataKind::Candle(candle) = &mut market.kind {
+            candle.open = close;
+            candle.high = high;
+            candle.low = low;
+            candle.close = close;
+        }
+        market
+    }
 
     /// Build a [`Signal`].
     pub fn signal() -> Signal {
@@ -267,6 +319,8 @@ pub mod test_util {
             instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
             signals: Default::default(),
             market_meta: Default::default(),
+            tag: None,
+            contributions: Vec::new(),
         }
     }
 
@@ -280,6 +334,7 @@ pub mod test_util {
             decision: Decision::default(),
             quantity: 1.0,
             order_type: OrderType::default(),
+            tag: None,
         }
     }
 
@@ -294,6 +349,7 @@ pub mod test_util {
             quantity: 1.0,
             fill_value_gross: 100.0,
             fees: Fees::default(),
+            tag: None,
         }
     }
 
@@ -318,6 +374,9 @@ pub mod test_util {
             current_value_gross: 100.0,
             unrealised_profit_loss: 0.0,
             realised_profit_loss: 0.0,
+            max_adverse_excursion: 0.0,
+            max_favourable_excursion: 0.0,
+            tag: None,
         }
     }
 }
@@ -0,0 +1,364 @@
+use crate::event::{
+    sink::{EventMetrics, LatencyHistogram, LATENCY_BUCKETS},
+    EventKind,
+};
+use std::{collections::BTreeMap, fmt::Write};
+
+/// Render the [`EventMetrics`] (eg/ a snapshot from a [`MetricsTx`](crate::event::sink::MetricsTx))
+/// in the Prometheus text exposition format.
+pub fn render(metrics: &EventMetrics) -> String {
+    let mut output = String::new();
+
+    for (kind, name, help) in [
+        (
+            EventKind::Market,
+            "barter_market_events_total",
+            "Number of MarketEvents received per market.",
+        ),
+        (
+            EventKind::Signal,
+            "barter_signals_total",
+            "Number of Signals generated per market.",
+        ),
+        (
+            EventKind::OrderNew,
+            "barter_orders_total",
+            "Number of OrderEvents generated per market.",
+        ),
+        (
+            EventKind::Fill,
+            "barter_fills_total",
+            "Number of FillEvents received per market.",
+        ),
+    ] {
+        let counters = metrics
+            .market_events
+            .iter()
+            .filter_map(|(market, events)| Some((market.clone(), *events.get(&kind)?)))
+            .collect();
+        render_counters(&mut output, name, help, &counters);
+    }
+    render_counters(
+        &mut output,
+        "barter_feed_unhealthy_total",
+        "Number of unhealthy market Feed occurrences per market.",
+        &metrics.feed_unhealthy,
+    );
+
+    render_header(
+        &mut output,
+        "barter_open_positions",
+        "Number of open Positions.",
+        "gauge",
+    );
+    let _ = writeln!(output, "barter_open_positions {}", metrics.open_positions);
+
+    let mut unrealised_profit_loss = BTreeMap::<&str, f64>::new();
+    for position in metrics.open_position_profit_loss.values() {
+        *unrealised_profit_loss.entry(&position.market).or_default() +=
+            position.unrealised_profit_loss;
+    }
+    render_header(
+        &mut output,
+        "barter_unrealised_profit_loss",
+        "Unrealised profit & loss of the open Positions per market.",
+        "gauge",
+    );
+    for (market, value) in unrealised_profit_loss {
+        let _ = writeln!(
+            output,
+            "barter_unrealised_profit_loss{{market=\"{}\"}} {}",
+            escape_label(market),
+            value
+        );
+    }
+
+    for (name, help, value) in [
+        (
+            "barter_balance_total",
+            "Total Portfolio Balance.",
+            metrics.balance.map(|balance| balance.total),
+        ),
+        (
+            "barter_balance_available",
+            "Available Portfolio Balance.",
+            metrics.balance.map(|balance| balance.available),
+        ),
+    ] {
+        render_header(&mut output, name, help, "gauge");
+        if let Some(value) = value {
+            let _ = writeln!(output, "{} {}", name, value);
+        }
+    }
+
+    render_histograms(
+        &mut output,
+        "barter_repository_latency_seconds",
+        "Latency of repository operations.",
+        "operation",
+        &metrics.repository_latency,
+    );
+    render_histograms(
+        &mut output,
+        "barter_trader_loop_latency_seconds",
+        "Latency of processing a Trader trading loop event queue per market.",
+        "market",
+        &metrics.trader_loop_latency,
+    );
+
+    output
+}
+
+/// Escape a label value as required by the Prometheus text exposition format.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn render_header(output: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(output, "# HELP {} {}", name, help);
+    let _ = writeln!(output, "# TYPE {} {}", name, kind);
+}
+
+fn render_counters(output: &mut String, name: &str, help: &str, counters: &BTreeMap<String, u64>) {
+    render_header(output, name, help, "counter");
+    for (market, count) in counters {
+        let _ = writeln!(
+            output,
+            "{}{{market=\"{}\"}} {}",
+            name,
+            escape_label(market),
+            count
+        );
+    }
+}
+
+fn render_histograms(
+    output: &mut String,
+    name: &str,
+    help: &str,
+    label: &str,
+    histograms: &BTreeMap<String, LatencyHistogram>,
+) {
+    render_header(output, name, help, "histogram");
+    for (value, histogram) in histograms {
+        let value = escape_label(value);
+
+        for (count, upper_bound) in histogram.buckets.iter().zip(LATENCY_BUCKETS) {
+            let _ = writeln!(
+                output,
+                "{}_bucket{{{}=\"{}\",le=\"{}\"}} {}",
+                name, label, value, upper_bound, count
+            );
+        }
+        let _ = writeln!(
+            output,
+            "{}_bucket{{{}=\"{}\",le=\"+Inf\"}} {}",
+            name, label, value, histogram.count
+        );
+        let _ = writeln!(
+            output,
+            "{}_sum{{{}=\"{}\"}} {}",
+            name, label, value, histogram.sum
+        );
+        let _ = writeln!(
+            output,
+            "{}_count{{{}=\"{}\"}} {}",
+            name, label, value, histogram.count
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::{sink::MetricsTx, Event, MessageTransmitter},
+        portfolio::{
+            position::{PositionExit, PositionUpdate},
+            Balance,
+        },
+        strategy::Signal,
+        test_util::{fill_event, position},
+    };
+    use barter_data::test_util::market_trade;
+    use barter_integration::model::{InstrumentKind, Market, MarketId, Side};
+    use chrono::{DateTime, Utc};
+    use std::time::Duration;
+
+    fn balance(total: f64, available: f64) -> Balance {
+        Balance {
+            time: DateTime::<Utc>::MIN_UTC,
+            total,
+            available,
+        }
+    }
+
+    fn line<'a>(output: &'a str, prefix: &str) -> Option<&'a str> {
+        output.lines().find(|line| line.starts_with(prefix))
+    }
+
+    #[test]
+    fn render_event_counters_and_gauges() {
+        let mut metrics = MetricsTx::new();
+
+        let market = market_trade(Side::Buy);
+        let label = MarketId::new(&market.exchange, &market.instrument).0;
+
+        let mut position = position();
+        position.exchange = market.exchange.clone();
+        position.instrument = market.instrument.clone();
+        position.position_id = "position".to_owned();
+        position.unrealised_profit_loss = 5.0;
+
+        metrics.send_many(vec![
+            Event::Market(market.clone()),
+            Event::Market(market.clone()),
+            Event::Signal(Signal {
+                time: DateTime::<Utc>::MIN_UTC,
+                exchange: market.exchange.clone(),
+                instrument: market.instrument.clone(),
+                signals: Default::default(),
+                market_meta: Default::default(),
+                tag: None,
+                contributions: Vec::new(),
+            }),
+            Event::Fill(fill_event()),
+            Event::PositionNew(position),
+            Event::PositionUpdate(PositionUpdate {
+                position_id: "position".to_owned(),
+                update_time: DateTime::<Utc>::MIN_UTC,
+                current_symbol_price: 0.0,
+                current_value_gross: 0.0,
+                unrealised_profit_loss: 12.5,
+                max_adverse_excursion: 0.0,
+                max_favourable_excursion: 0.0,
+            }),
+            Event::Balance(balance(1000.0, 900.0)),
+        ]);
+        metrics.record_feed_unhealthy(&Market::new(
+            market.exchange.clone(),
+            market.instrument.clone(),
+        ));
+
+        let output = render(&metrics.metrics());
+        assert_eq!(
+            line(&output, "barter_market_events_total{"),
+            Some(format!("barter_market_events_total{{market=\"{}\"}} 2", label).as_str())
+        );
+        assert_eq!(
+            line(&output, "barter_signals_total{"),
+            Some(format!("barter_signals_total{{market=\"{}\"}} 1", label).as_str())
+        );
+        assert_eq!(
+            line(&output, "barter_fills_total{"),
+            Some("barter_fills_total{market=\"binance_eth_usdt_spot\"} 1")
+        );
+        assert_eq!(line(&output, "barter_orders_total{"), None);
+        assert_eq!(
+            line(&output, "barter_feed_unhealthy_total{"),
+            Some(format!("barter_feed_unhealthy_total{{market=\"{}\"}} 1", label).as_str())
+        );
+        assert_eq!(
+            line(&output, "barter_open_positions "),
+            Some("barter_open_positions 1")
+        );
+        assert_eq!(
+            line(&output, "barter_unrealised_profit_loss{"),
+            Some(format!("barter_unrealised_profit_loss{{market=\"{}\"}} 12.5", label).as_str())
+        );
+        assert_eq!(
+            line(&output, "barter_balance_total "),
+            Some("barter_balance_total 1000")
+        );
+        assert_eq!(
+            line(&output, "barter_balance_available "),
+            Some("barter_balance_available 900")
+        );
+
+        // Exiting the Position removes it from the open Position gauges
+        metrics.send(Event::PositionExit(PositionExit {
+            position_id: "position".to_owned(),
+            exit_time: DateTime::<Utc>::MIN_UTC,
+            exit_balance: balance(1010.0, 1010.0),
+            exit_fees: Default::default(),
+            exit_fees_total: 0.0,
+            exit_avg_price_gross: 0.0,
+            exit_value_gross: 0.0,
+            realised_profit_loss: 10.0,
+            max_adverse_excursion: 0.0,
+            max_favourable_excursion: 0.0,
+        }));
+
+        let output = render(&metrics.metrics());
+        assert_eq!(
+            line(&output, "barter_open_positions "),
+            Some("barter_open_positions 0")
+        );
+        assert_eq!(line(&output, "barter_unrealised_profit_loss{"), None);
+        assert_eq!(
+            line(&output, "barter_balance_total "),
+            Some("barter_balance_total 1010")
+        );
+    }
+
+    #[test]
+    fn render_latency_histograms() {
+        let metrics = MetricsTx::new();
+        let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+        metrics.record_trader_loop(&market, Duration::from_micros(20));
+        metrics.record_trader_loop(&market, Duration::from_millis(2));
+        metrics.record_repository("get_balance", Duration::from_secs(10));
+
+        let output = render(&metrics.metrics());
+        let loop_bucket = |le: &str| {
+            line(
+                &output,
+                &format!(
+                    "barter_trader_loop_latency_seconds_bucket{{market=\"binance_btc_usdt_spot\",le=\"{}\"}}",
+                    le
+                ),
+            )
+            .and_then(|line| line.rsplit(' ').next())
+            .map(str::to_owned)
+        };
+
+        assert_eq!(loop_bucket("0.00001").as_deref(), Some("0"));
+        assert_eq!(loop_bucket("0.00005").as_deref(), Some("1"));
+        assert_eq!(loop_bucket("0.005").as_deref(), Some("2"));
+        assert_eq!(loop_bucket("+Inf").as_deref(), Some("2"));
+        assert_eq!(
+            line(
+                &output,
+                "barter_trader_loop_latency_seconds_count{market=\"binance_btc_usdt_spot\"}"
+            ),
+            Some("barter_trader_loop_latency_seconds_count{market=\"binance_btc_usdt_spot\"} 2")
+        );
+
+        // Observations above the largest bucket are only counted in the +Inf bucket
+        assert_eq!(
+            line(
+                &output,
+                "barter_repository_latency_seconds_bucket{operation=\"get_balance\",le=\"5\"}"
+            ),
+            Some("barter_repository_latency_seconds_bucket{operation=\"get_balance\",le=\"5\"} 0")
+        );
+        assert_eq!(
+            line(
+                &output,
+                "barter_repository_latency_seconds_bucket{operation=\"get_balance\",le=\"+Inf\"}"
+            ),
+            Some(
+                "barter_repository_latency_seconds_bucket{operation=\"get_balance\",le=\"+Inf\"} 1"
+            )
+        );
+    }
+
+    #[test]
+    fn escape_label_escapes_special_characters() {
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
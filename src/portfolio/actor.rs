@@ -0,0 +1,845 @@
+use crate::{
+    event::Event,
+    execution::{AccountSnapshot, FillEvent},
+    portfolio::{
+        block_on,
+        error::PortfolioError,
+        position::{Position, PositionId, PositionUpdate},
+        reconcile::Discrepancy,
+        repository::{error::RepositoryError, BalanceHandler, PositionHandler, StatisticHandler},
+        Balance, FillUpdater, MarketInitialiser, MarketUpdater, OrderEvent, OrderGenerator,
+        Reconciler,
+    },
+    strategy::{Signal, SignalForceExit},
+};
+use barter_data::model::MarketEvent;
+use barter_integration::model::{Exchange, Instrument, Market, MarketId};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+    sync::Arc,
+    thread,
+};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Request actioned by a [`PortfolioActor`] against the Portfolio it owns. Sends the result of
+/// the operation back to the requesting [`PortfolioHandle`].
+type Request<Portfolio> = Box<dyn FnOnce(&mut Portfolio) + Send>;
+
+/// Owns a Portfolio & sequentially actions the requests sent by every associated
+/// [`PortfolioHandle`], removing the need to share the Portfolio behind a lock.
+///
+/// Since requests are actioned one at a time, every request (eg/ a risk check when generating an
+/// [`OrderEvent`]) observes a consistent Portfolio state that already includes the effects of
+/// every previously actioned request.
+///
+/// A single [`PortfolioActor`] serialises every request just like a shared lock, but pays for a
+/// channel round-trip per request. To action the requests of different markets in parallel, shard
+/// the markets across several [`PortfolioActor`]s via [`PortfolioActor::spawn_sharded`].
+pub struct PortfolioActor<Portfolio> {
+    portfolio: Portfolio,
+    request_rx: mpsc::UnboundedReceiver<Request<Portfolio>>,
+}
+
+impl<Portfolio> PortfolioActor<Portfolio>
+where
+    Portfolio: Send + 'static,
+{
+    /// Constructs a new [`PortfolioActor`] that owns the provided Portfolio, and the
+    /// [`PortfolioHandle`] used to send it requests. Clone the [`PortfolioHandle`] to give every
+    /// [`Trader`](crate::engine::trader::Trader) it's own.
+    pub fn new(portfolio: Portfolio) -> (Self, PortfolioHandle<Portfolio>) {
+        let (request_tx, request_rx) = mpsc::unbounded_channel();
+
+        (
+            Self {
+                portfolio,
+                request_rx,
+            },
+            PortfolioHandle { request_tx },
+        )
+    }
+
+    /// Spawn a [`PortfolioActor`] that owns the provided Portfolio on a new thread. Returns the
+    /// [`PortfolioHandle`] used to send it requests, and the thread's `JoinHandle` that yields
+    /// the Portfolio once every [`PortfolioHandle`] has been dropped.
+    pub fn spawn(
+        portfolio: Portfolio,
+    ) -> (PortfolioHandle<Portfolio>, thread::JoinHandle<Portfolio>) {
+        let (actor, handle) = Self::new(portfolio);
+        (handle, thread::spawn(move || actor.run()))
+    }
+
+    /// Spawn a [`PortfolioActor`] on a new thread for every shard, where each shard is a Portfolio
+    /// owning the state of the provided [`Market`]s. Returns the [`ShardedPortfolioHandle`] that
+    /// routes every request to the shard owning it's market, and the `JoinHandle` of every shard.
+    ///
+    /// Shards share nothing, so Portfolios that must share a [`Balance`] should use a
+    /// [`SharedBalanceRepository`](crate::portfolio::repository::shared_balance::SharedBalanceRepository).
+    pub fn spawn_sharded<Shards>(
+        shards: Shards,
+    ) -> (
+        ShardedPortfolioHandle<Portfolio>,
+        Vec<thread::JoinHandle<Portfolio>>,
+    )
+    where
+        Shards: IntoIterator<Item = (Vec<Market>, Portfolio)>,
+    {
+        let mut routes = HashMap::new();
+        let (handles, actors) = shards
+            .into_iter()
+            .enumerate()
+            .map(|(shard, (markets, portfolio))| {
+                routes.extend(markets.iter().map(|market| (MarketId::from(market), shard)));
+                Self::spawn(portfolio)
+            })
+            .unzip();
+
+        (
+            ShardedPortfolioHandle {
+                shards: handles,
+                routes: Arc::new(routes),
+            },
+            actors,
+        )
+    }
+
+    /// Run the [`PortfolioActor`] request loop. Loop will run until every [`PortfolioHandle`] has
+    /// been dropped, returning the Portfolio.
+    ///
+    /// Blocks the current thread between requests, so it must not be run on an async runtime
+    /// worker (see [`PortfolioActor::spawn`]).
+    pub fn run(mut self) -> Portfolio {
+        info!("PortfolioActor started actioning requests");
+
+        while let Some(request) = self.request_rx.blocking_recv() {
+            request(&mut self.portfolio);
+        }
+
+        debug!("PortfolioActor stopped since every PortfolioHandle has been dropped");
+        self.portfolio
+    }
+}
+
+impl<Portfolio> Debug for PortfolioActor<Portfolio> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PortfolioActor").finish()
+    }
+}
+
+/// Cheaply cloneable handle to a [`PortfolioActor`]. Implements the same Portfolio traits as the
+/// Portfolio the [`PortfolioActor`] owns by sending it a request & waiting for the result.
+///
+/// Async callers should use [`PortfolioHandle::request_async`]. The blocking Portfolio trait
+/// methods may be called from a multi-threaded Tokio runtime, in which case the worker thread is
+/// handed off whilst waiting (ie/ `block_in_place`) so it's other tasks are not stalled.
+pub struct PortfolioHandle<Portfolio> {
+    request_tx: mpsc::UnboundedSender<Request<Portfolio>>,
+}
+
+impl<Portfolio> PortfolioHandle<Portfolio>
+where
+    Portfolio: Send + 'static,
+{
+    /// Send a request to the [`PortfolioActor`] & block until the result is returned. Returns
+    /// `None` if the [`PortfolioActor`] has stopped.
+    pub fn request<Operation, Output>(&self, operation: Operation) -> Option<Output>
+    where
+        Operation: FnOnce(&mut Portfolio) -> Output + Send + 'static,
+        Output: Send + 'static,
+    {
+        block_on(self.request_async(operation))
+    }
+
+    /// Send a request to the [`PortfolioActor`] & asynchronously wait for the result. Returns
+    /// `None` if the [`PortfolioActor`] has stopped.
+    pub async fn request_async<Operation, Output>(&self, operation: Operation) -> Option<Output>
+    where
+        Operation: FnOnce(&mut Portfolio) -> Output + Send + 'static,
+        Output: Send + 'static,
+    {
+        let (output_tx, output_rx) = oneshot::channel();
+
+        self.request_tx
+            .send(Box::new(move |portfolio| {
+                let _ = output_tx.send(operation(portfolio));
+            }))
+            .ok()?;
+
+        output_rx.await.ok()
+    }
+
+    /// Send a request to the [`PortfolioActor`] that returns a Portfolio [`Result`].
+    fn request_portfolio<Operation, Output>(
+        &self,
+        operation: Operation,
+    ) -> Result<Output, PortfolioError>
+    where
+        Operation: FnOnce(&mut Portfolio) -> Result<Output, PortfolioError> + Send + 'static,
+        Output: Send + 'static,
+    {
+        self.request(operation)
+            .unwrap_or(Err(PortfolioError::ActorTerminated))
+    }
+
+    /// Send a request to the [`PortfolioActor`] that returns a repository [`Result`].
+    fn request_repository<Operation, Output>(
+        &self,
+        operation: Operation,
+    ) -> Result<Output, RepositoryError>
+    where
+        Operation: FnOnce(&mut Portfolio) -> Result<Output, RepositoryError> + Send + 'static,
+        Output: Send + 'static,
+    {
+        self.request(operation).unwrap_or_else(|| {
+            Err(RepositoryError::ConnectionError(
+                "PortfolioActor has stopped".to_owned(),
+            ))
+        })
+    }
+}
+
+impl<Portfolio> Clone for PortfolioHandle<Portfolio> {
+    fn clone(&self) -> Self {
+        Self {
+            request_tx: self.request_tx.clone(),
+        }
+    }
+}
+
+impl<Portfolio> Debug for PortfolioHandle<Portfolio> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PortfolioHandle").finish()
+    }
+}
+
+impl<Portfolio> MarketUpdater for PortfolioHandle<Portfolio>
+where
+    Portfolio: MarketUpdater + Send + 'static,
+{
+    fn update_from_market(
+        &mut self,
+        market: &MarketEvent,
+    ) -> Result<Vec<PositionUpdate>, PortfolioError> {
+        let market = market.clone();
+        self.request_portfolio(move |portfolio| portfolio.update_from_market(&market))
+    }
+}
+
+impl<Portfolio> OrderGenerator for PortfolioHandle<Portfolio>
+where
+    Portfolio: OrderGenerator + Send + 'static,
+{
+    fn generate_order(&mut self, signal: &Signal) -> Result<Option<OrderEvent>, PortfolioError> {
+        let signal = signal.clone();
+        self.request_portfolio(move |portfolio| portfolio.generate_order(&signal))
+    }
+
+    fn generate_exit_order(
+        &mut self,
+        signal: SignalForceExit,
+    ) -> Result<Option<OrderEvent>, PortfolioError> {
+        self.request_portfolio(move |portfolio| portfolio.generate_exit_order(signal))
+    }
+
+    fn generate_exit_orders(
+        &mut self,
+        signal: SignalForceExit,
+    ) -> Result<Vec<OrderEvent>, PortfolioError> {
+        self.request_portfolio(move |portfolio| portfolio.generate_exit_orders(signal))
+    }
+}
+
+impl<Portfolio> FillUpdater for PortfolioHandle<Portfolio>
+where
+    Portfolio: FillUpdater + Send + 'static,
+{
+    fn update_from_fill(&mut self, fill: &FillEvent) -> Result<Vec<Event>, PortfolioError> {
+        let fill = fill.clone();
+        self.request_portfolio(move |portfolio| portfolio.update_from_fill(&fill))
+    }
+}
+
+impl<Portfolio> MarketInitialiser for PortfolioHandle<Portfolio>
+where
+    Portfolio: MarketInitialiser + Send + 'static,
+{
+    fn init_market(&mut self, market: &Market) -> Result<(), PortfolioError> {
+        let market = market.clone();
+        self.request_portfolio(move |portfolio| portfolio.init_market(&market))
+    }
+}
+
+impl<Portfolio> Reconciler for PortfolioHandle<Portfolio>
+where
+    Portfolio: Reconciler + Send + 'static,
+{
+    fn reconcile(
+        &mut self,
+        markets: &[Market],
+        account: &AccountSnapshot,
+        tolerance: f64,
+    ) -> Result<Vec<Event>, PortfolioError> {
+        let (markets, account) = (markets.to_vec(), account.clone());
+        self.request_portfolio(move |portfolio| portfolio.reconcile(&markets, &account, tolerance))
+    }
+}
+
+impl<Portfolio> PositionHandler for PortfolioHandle<Portfolio>
+where
+    Portfolio: PositionHandler + Send + 'static,
+{
+    fn set_open_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.request_repository(move |portfolio| portfolio.set_open_position(engine_id, position))
+    }
+
+    fn get_open_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        let position_id = position_id.clone();
+        self.request_repository(move |portfolio| portfolio.get_open_position(&position_id))
+    }
+
+    fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+        &mut self,
+        engine_id: Uuid,
+        markets: Markets,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        let markets = markets.cloned().collect::<Vec<Market>>();
+        self.request_repository(move |portfolio| {
+            portfolio.get_open_positions(engine_id, markets.iter())
+        })
+    }
+
+    fn remove_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        let position_id = position_id.clone();
+        self.request_repository(move |portfolio| portfolio.remove_position(&position_id))
+    }
+
+    fn set_exited_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.request_repository(move |portfolio| portfolio.set_exited_position(engine_id, position))
+    }
+
+    fn get_exited_positions(&mut self, engine_id: Uuid) -> Result<Vec<Position>, RepositoryError> {
+        self.request_repository(move |portfolio| portfolio.get_exited_positions(engine_id))
+    }
+}
+
+impl<Portfolio> BalanceHandler for PortfolioHandle<Portfolio>
+where
+    Portfolio: BalanceHandler + Send + 'static,
+{
+    fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
+        self.request_repository(move |portfolio| portfolio.set_balance(engine_id, balance))
+    }
+
+    fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+        self.request_repository(move |portfolio| portfolio.get_balance(engine_id))
+    }
+}
+
+impl<Portfolio, Statistic> StatisticHandler<Statistic> for PortfolioHandle<Portfolio>
+where
+    Portfolio: StatisticHandler<Statistic> + Send + 'static,
+    Statistic: Send + 'static,
+{
+    fn set_statistics(
+        &mut self,
+        market_id: MarketId,
+        statistic: Statistic,
+    ) -> Result<(), RepositoryError> {
+        self.request_repository(move |portfolio| portfolio.set_statistics(market_id, statistic))
+    }
+
+    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+        let market_id = market_id.clone();
+        self.request_repository(move |portfolio| portfolio.get_statistics(&market_id))
+    }
+}
+
+/// Cheaply cloneable handle to the [`PortfolioActor`] shards spawned via
+/// [`PortfolioActor::spawn_sharded`]. Implements the same Portfolio traits as the shard
+/// Portfolios by routing every market specific request to the shard owning the market, so
+/// requests for markets owned by different shards are actioned in parallel.
+///
+/// Requests that are not market specific are fanned out to every shard (eg/ exited
+/// [`Position`]s are returned grouped by shard), except for the [`Balance`] which is requested
+/// from the first shard, since the shards are expected to share it (see
+/// [`SharedBalanceRepository`](crate::portfolio::repository::shared_balance::SharedBalanceRepository)).
+pub struct ShardedPortfolioHandle<Portfolio> {
+    shards: Vec<PortfolioHandle<Portfolio>>,
+    routes: Arc<HashMap<MarketId, usize>>,
+}
+
+impl<Portfolio> ShardedPortfolioHandle<Portfolio> {
+    /// Returns the [`PortfolioHandle`] of the shard owning the market, if any.
+    fn shard(
+        &mut self,
+        exchange: &Exchange,
+        instrument: &Instrument,
+    ) -> Option<&mut PortfolioHandle<Portfolio>> {
+        let shard = *self.routes.get(&MarketId::new(exchange, instrument))?;
+        self.shards.get_mut(shard)
+    }
+
+    /// Returns the [`PortfolioHandle`] of the shard owning the market, or a
+    /// [`PortfolioError::MarketNotSharded`] if no shard owns it.
+    fn shard_or_err(
+        &mut self,
+        exchange: &Exchange,
+        instrument: &Instrument,
+    ) -> Result<&mut PortfolioHandle<Portfolio>, PortfolioError> {
+        let market_id = MarketId::new(exchange, instrument);
+        match self.routes.get(&market_id) {
+            Some(shard) => Ok(&mut self.shards[*shard]),
+            None => Err(PortfolioError::MarketNotSharded(market_id)),
+        }
+    }
+}
+
+impl<Portfolio> Clone for ShardedPortfolioHandle<Portfolio> {
+    fn clone(&self) -> Self {
+        Self {
+            shards: self.shards.clone(),
+            routes: Arc::clone(&self.routes),
+        }
+    }
+}
+
+impl<Portfolio> Debug for ShardedPortfolioHandle<Portfolio> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShardedPortfolioHandle")
+            .field("shards", &self.shards.len())
+            .field("markets", &self.routes.len())
+            .finish()
+    }
+}
+
+impl<Portfolio> MarketUpdater for ShardedPortfolioHandle<Portfolio>
+where
+    Portfolio: MarketUpdater + Send + 'static,
+{
+    fn update_from_market(
+        &mut self,
+        market: &MarketEvent,
+    ) -> Result<Vec<PositionUpdate>, PortfolioError> {
+        // No shard owns the market, so there are no open Positions to update
+        match self.shard(&market.exchange, &market.instrument) {
+            Some(shard) => shard.update_from_market(market),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+impl<Portfolio> OrderGenerator for ShardedPortfolioHandle<Portfolio>
+where
+    Portfolio: OrderGenerator + Send + 'static,
+{
+    fn generate_order(&mut self, signal: &Signal) -> Result<Option<OrderEvent>, PortfolioError> {
+        self.shard_or_err(&signal.exchange, &signal.instrument)?
+            .generate_order(signal)
+    }
+
+    fn generate_exit_order(
+        &mut self,
+        signal: SignalForceExit,
+    ) -> Result<Option<OrderEvent>, PortfolioError> {
+        match self.shard(&signal.exchange, &signal.instrument) {
+            Some(shard) => shard.generate_exit_order(signal),
+            None => Ok(None),
+        }
+    }
+
+    fn generate_exit_orders(
+        &mut self,
+        signal: SignalForceExit,
+    ) -> Result<Vec<OrderEvent>, PortfolioError> {
+        match self.shard(&signal.exchange, &signal.instrument) {
+            Some(shard) => shard.generate_exit_orders(signal),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+impl<Portfolio> FillUpdater for ShardedPortfolioHandle<Portfolio>
+where
+    Portfolio: FillUpdater + Send + 'static,
+{
+    fn update_from_fill(&mut self, fill: &FillEvent) -> Result<Vec<Event>, PortfolioError> {
+        self.shard_or_err(&fill.exchange, &fill.instrument)?
+            .update_from_fill(fill)
+    }
+}
+
+impl<Portfolio> MarketInitialiser for ShardedPortfolioHandle<Portfolio>
+where
+    Portfolio: MarketInitialiser + Send + 'static,
+{
+    fn init_market(&mut self, market: &Market) -> Result<(), PortfolioError> {
+        self.shard_or_err(&market.exchange, &market.instrument)?
+            .init_market(market)
+    }
+}
+
+impl<Portfolio> Reconciler for ShardedPortfolioHandle<Portfolio>
+where
+    Portfolio: Reconciler + Send + 'static,
+{
+    fn reconcile(
+        &mut self,
+        markets: &[Market],
+        account: &AccountSnapshot,
+        tolerance: f64,
+    ) -> Result<Vec<Event>, PortfolioError> {
+        let mut shard_markets = vec![Vec::new(); self.shards.len()];
+        for market in markets {
+            let shard = self
+                .routes
+                .get(&MarketId::from(market))
+                .ok_or_else(|| PortfolioError::MarketNotSharded(MarketId::from(market)))?;
+            shard_markets[*shard].push(market.clone());
+        }
+
+        let mut events = Vec::new();
+        for (shard, markets) in self.shards.iter_mut().zip(shard_markets) {
+            if !markets.is_empty() {
+                events.extend(shard.reconcile(&markets, account, tolerance)?);
+            }
+        }
+
+        // Every shard reconciles the shared Balance, so only report the first Balance discrepancy
+        let mut balance_reported = false;
+        events.retain(|event| match event {
+            Event::Discrepancy(Discrepancy::Balance { .. }) => {
+                !std::mem::replace(&mut balance_reported, true)
+            }
+            _ => true,
+        });
+
+        Ok(events)
+    }
+}
+
+impl<Portfolio> PositionHandler for ShardedPortfolioHandle<Portfolio>
+where
+    Portfolio: PositionHandler + Send + 'static,
+{
+    fn set_open_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.shard(&position.exchange, &position.instrument)
+            .ok_or(RepositoryError::WriteError)?
+            .set_open_position(engine_id, position)
+    }
+
+    fn get_open_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        for shard in &mut self.shards {
+            if let Some(position) = shard.get_open_position(position_id)? {
+                return Ok(Some(position));
+            }
+        }
+        Ok(None)
+    }
+
+    fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+        &mut self,
+        engine_id: Uuid,
+        markets: Markets,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        let mut shard_markets = vec![Vec::new(); self.shards.len()];
+        for market in markets {
+            if let Some(shard) = self.routes.get(&MarketId::from(market)) {
+                shard_markets[*shard].push(market);
+            }
+        }
+
+        let mut positions = Vec::new();
+        for (shard, markets) in self.shards.iter_mut().zip(shard_markets) {
+            if !markets.is_empty() {
+                positions.extend(shard.get_open_positions(engine_id, markets.into_iter())?);
+            }
+        }
+        Ok(positions)
+    }
+
+    fn remove_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        for shard in &mut self.shards {
+            if let Some(position) = shard.remove_position(position_id)? {
+                return Ok(Some(position));
+            }
+        }
+        Ok(None)
+    }
+
+    fn set_exited_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.shard(&position.exchange, &position.instrument)
+            .ok_or(RepositoryError::WriteError)?
+            .set_exited_position(engine_id, position)
+    }
+
+    fn get_exited_positions(&mut self, engine_id: Uuid) -> Result<Vec<Position>, RepositoryError> {
+        let mut positions = Vec::new();
+        for shard in &mut self.shards {
+            positions.extend(shard.get_exited_positions(engine_id)?);
+        }
+        Ok(positions)
+    }
+}
+
+impl<Portfolio> BalanceHandler for ShardedPortfolioHandle<Portfolio>
+where
+    Portfolio: BalanceHandler + Send + 'static,
+{
+    fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
+        self.shards
+            .first_mut()
+            .ok_or(RepositoryError::WriteError)?
+            .set_balance(engine_id, balance)
+    }
+
+    fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+        self.shards
+            .first_mut()
+            .ok_or(RepositoryError::ExpectedDataNotPresentError)?
+            .get_balance(engine_id)
+    }
+}
+
+impl<Portfolio, Statistic> StatisticHandler<Statistic> for ShardedPortfolioHandle<Portfolio>
+where
+    Portfolio: StatisticHandler<Statistic> + Send + 'static,
+    Statistic: Send + 'static,
+{
+    fn set_statistics(
+        &mut self,
+        market_id: MarketId,
+        statistic: Statistic,
+    ) -> Result<(), RepositoryError> {
+        let shard = *self
+            .routes
+            .get(&market_id)
+            .ok_or(RepositoryError::WriteError)?;
+        self.shards[shard].set_statistics(market_id, statistic)
+    }
+
+    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+        let shard = *self
+            .routes
+            .get(market_id)
+            .ok_or(RepositoryError::ExpectedDataNotPresentError)?;
+        self.shards[shard].get_statistics(market_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::{
+            allocator::DefaultAllocator,
+            portfolio::MetaPortfolio,
+            repository::{
+                in_memory::InMemoryRepository,
+                shared_balance::{BalanceLedger, SharedBalanceRepository},
+            },
+            risk::DefaultRisk,
+        },
+        statistic::summary::pnl::PnLReturnSummary,
+        strategy::{Decision, SignalStrength},
+        test_util::{fill_event, signal},
+    };
+    use barter_data::test_util::market_trade;
+    use barter_integration::model::{InstrumentKind, Side};
+
+    type TestPortfolio = MetaPortfolio<
+        InMemoryRepository<PnLReturnSummary>,
+        DefaultAllocator,
+        DefaultRisk,
+        PnLReturnSummary,
+    >;
+
+    fn portfolio(engine_id: Uuid) -> TestPortfolio {
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![Market::new(
+                "binance",
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap()
+    }
+
+    #[test]
+    fn handles_action_requests_against_the_actor_portfolio() {
+        let engine_id = Uuid::new_v4();
+        let (mut handle, actor) = PortfolioActor::spawn(portfolio(engine_id));
+        let mut other_handle = handle.clone();
+
+        let mut input_signal = signal();
+        input_signal
+            .signals
+            .insert(Decision::Long, SignalStrength(1.0));
+        assert!(handle.generate_order(&input_signal).unwrap().is_some());
+
+        let events = handle.update_from_fill(&fill_event()).unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [Event::PositionNew(_), Event::Balance(_)]
+        ));
+
+        // Every handle observes the state changes requested by the others
+        let balance = other_handle.get_balance(engine_id).unwrap();
+        assert!(balance.available < 1000.0);
+
+        // Actor stops & returns the Portfolio once every handle is dropped
+        drop(handle);
+        drop(other_handle);
+        let mut portfolio = actor.join().unwrap();
+        assert_eq!(portfolio.get_balance(engine_id).unwrap(), balance);
+    }
+
+    #[test]
+    fn handle_returns_error_once_actor_has_stopped() {
+        let (actor, mut handle) = PortfolioActor::new(portfolio(Uuid::new_v4()));
+        drop(actor);
+
+        assert!(matches!(
+            handle.generate_order(&signal()),
+            Err(PortfolioError::ActorTerminated)
+        ));
+        assert!(matches!(
+            handle.get_balance(Uuid::new_v4()),
+            Err(RepositoryError::ConnectionError(_))
+        ));
+    }
+
+    #[test]
+    fn request_async_actions_request_without_blocking_the_runtime() {
+        let engine_id = Uuid::new_v4();
+        let (handle, _actor) = PortfolioActor::spawn(portfolio(engine_id));
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let balance = runtime.block_on(async move {
+            handle
+                .request_async(move |portfolio| portfolio.get_balance(engine_id))
+                .await
+        });
+
+        assert_eq!(balance.unwrap().unwrap().available, 1000.0);
+    }
+
+    #[test]
+    fn sharded_handle_routes_requests_to_the_shard_owning_the_market() {
+        type ShardPortfolio = MetaPortfolio<
+            SharedBalanceRepository<InMemoryRepository<PnLReturnSummary>>,
+            DefaultAllocator,
+            DefaultRisk,
+            PnLReturnSummary,
+        >;
+
+        let engine_id = Uuid::new_v4();
+        let ledger = BalanceLedger::new();
+        let eth = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+        let btc = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+        let shard = |market: &Market| -> (Vec<Market>, ShardPortfolio) {
+            let portfolio = MetaPortfolio::builder()
+                .engine_id(engine_id)
+                .markets(vec![market.clone()])
+                .starting_cash(1000.0)
+                .repository(SharedBalanceRepository::new(
+                    InMemoryRepository::new(),
+                    Arc::clone(&ledger),
+                ))
+                .allocation_manager(DefaultAllocator {
+                    default_order_value: 100.0,
+                })
+                .risk_manager(DefaultRisk {})
+                .statistic_config(())
+                .build_and_init()
+                .unwrap();
+            (vec![market.clone()], portfolio)
+        };
+        let (mut handle, actors) = PortfolioActor::spawn_sharded([shard(&eth), shard(&btc)]);
+
+        // Enter a Position in each market, each updating the shared Balance
+        let mut btc_fill = fill_event();
+        btc_fill.instrument = btc.instrument.clone();
+        handle.update_from_fill(&fill_event()).unwrap();
+        handle.update_from_fill(&btc_fill).unwrap();
+
+        let positions = handle
+            .get_open_positions(engine_id, [eth.clone(), btc.clone()].iter())
+            .unwrap();
+        assert_eq!(positions.len(), 2);
+
+        let balance = handle.get_balance(engine_id).unwrap();
+        let entered = positions
+            .iter()
+            .map(|position| position.enter_value_gross + position.enter_fees_total)
+            .sum::<f64>();
+        assert_eq!(balance.available, 1000.0 - entered);
+
+        // Each shard only owns the state of it's own market
+        drop(handle);
+        let mut shards = actors
+            .into_iter()
+            .map(|actor| actor.join().unwrap())
+            .collect::<Vec<_>>();
+        let eth_positions = shards[0]
+            .get_open_positions(engine_id, [eth.clone(), btc.clone()].iter())
+            .unwrap();
+        assert_eq!(eth_positions.len(), 1);
+        assert_eq!(eth_positions[0].instrument, eth.instrument);
+    }
+
+    #[test]
+    fn sharded_handle_rejects_orders_for_markets_no_shard_owns() {
+        let (mut handle, _actors) =
+            PortfolioActor::<TestPortfolio>::spawn_sharded(std::iter::empty());
+
+        assert!(matches!(
+            handle.generate_order(&signal()),
+            Err(PortfolioError::MarketNotSharded(_))
+        ));
+        assert!(handle
+            .update_from_market(&market_trade(Side::Buy))
+            .unwrap()
+            .is_empty());
+    }
+}
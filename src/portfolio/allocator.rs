@@ -16,6 +16,20 @@ pub trait OrderAllocator {
     );
 }
 
+impl<Allocator> OrderAllocator for Box<Allocator>
+where
+    Allocator: OrderAllocator + ?Sized,
+{
+    fn allocate_order(
+        &self,
+        order: &mut OrderEvent,
+        position: Option<&Position>,
+        signal_strength: SignalStrength,
+    ) {
+        (**self).allocate_order(order, position, signal_strength)
+    }
+}
+
 /// Default allocation manager that implements [`OrderAllocator`]. Order size is calculated by
 /// using the default_order_value, symbol close value, and [`SignalStrength`].
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
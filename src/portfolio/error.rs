@@ -1,4 +1,5 @@
-use crate::portfolio::repository::error::RepositoryError;
+use crate::{execution::error::ExecutionError, portfolio::repository::error::RepositoryError};
+use barter_integration::model::MarketId;
 use thiserror::Error;
 
 /// All errors generated in the barter::portfolio module.
@@ -21,4 +22,13 @@ pub enum PortfolioError {
 
     #[error("Failed to interact with repository")]
     RepositoryInteraction(#[from] RepositoryError),
+
+    #[error("Failed to send request to the PortfolioActor since it has stopped")]
+    ActorTerminated,
+
+    #[error("No PortfolioActor shard owns the market: {0}")]
+    MarketNotSharded(MarketId),
+
+    #[error("Failed to interact with execution venue: {0}")]
+    VenueInteraction(#[from] ExecutionError),
 }
@@ -1,16 +1,26 @@
 use crate::{
     data::MarketMeta,
     event::Event,
-    execution::FillEvent,
-    portfolio::{error::PortfolioError, position::PositionUpdate},
+    execution::{AccountSnapshot, FillEvent},
+    portfolio::{
+        error::PortfolioError,
+        position::{PositionTag, PositionUpdate},
+    },
     strategy::{Decision, Signal, SignalForceExit},
 };
 use barter_data::model::MarketEvent;
-use barter_integration::model::{Exchange, Instrument};
+use barter_integration::model::{Exchange, Instrument, Market};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use tokio::runtime::{Handle, RuntimeFlavor};
 use uuid::Uuid;
 
+/// Portfolio actor that owns a Portfolio on it's own thread & actions the requests sent by
+/// [`Trader`](crate::engine::trader::Trader)s over channels, as an alternative to sharing the
+/// Portfolio behind a lock.
+pub mod actor;
+
 /// Logic for [`OrderEvent`] quantity allocation.
 pub mod allocator;
 
@@ -26,6 +36,10 @@ pub mod portfolio;
 /// well as the logic for entering, updating and exiting them.
 pub mod position;
 
+/// Reconciliation of persisted Portfolio state against the account state reported by the
+/// execution venue.
+pub mod reconcile;
+
 /// Repositories for persisting Portfolio state.
 pub mod repository;
 
@@ -34,13 +48,13 @@ pub mod risk;
 
 /// Updates the Portfolio from an input [`MarketEvent`].
 pub trait MarketUpdater {
-    /// Determines if the Portfolio has an open Position relating to the input [`MarketEvent`]. If
-    /// so it updates it using the market data, and returns a [`PositionUpdate`] detailing the
-    /// changes.
+    /// Determines if the Portfolio has any open (tagged or untagged) Positions relating to the
+    /// input [`MarketEvent`]. If so it updates them using the market data, and returns a
+    /// [`PositionUpdate`] detailing the changes for each.
     fn update_from_market(
         &mut self,
         market: &MarketEvent,
-    ) -> Result<Option<PositionUpdate>, PortfolioError>;
+    ) -> Result<Vec<PositionUpdate>, PortfolioError>;
 }
 
 /// May generate an [`OrderEvent`] from an input advisory [`Signal`].
@@ -54,6 +68,17 @@ pub trait OrderGenerator {
         &mut self,
         signal: SignalForceExit,
     ) -> Result<Option<OrderEvent>, PortfolioError>;
+
+    /// Generates the exit [`OrderEvent`]s required to action the input [`SignalForceExit`]. A
+    /// Portfolio holding several tagged [`Position`](position::Position)s per market should exit
+    /// them all if the [`SignalForceExit`] is untagged. Defaults to
+    /// [`OrderGenerator::generate_exit_order`].
+    fn generate_exit_orders(
+        &mut self,
+        signal: SignalForceExit,
+    ) -> Result<Vec<OrderEvent>, PortfolioError> {
+        Ok(self.generate_exit_order(signal)?.into_iter().collect())
+    }
 }
 
 /// Updates the Portfolio from an input [`FillEvent`].
@@ -64,6 +89,30 @@ pub trait FillUpdater {
     fn update_from_fill(&mut self, fill: &FillEvent) -> Result<Vec<Event>, PortfolioError>;
 }
 
+/// Initialises the Portfolio state of a [`Market`] added to a running
+/// [`Engine`](crate::engine::Engine).
+pub trait MarketInitialiser {
+    /// Initialise the statistics of the provided [`Market`] using the Portfolio's statistic
+    /// configuration, unless statistics are already persisted for it (eg/ from before it was
+    /// retired).
+    fn init_market(&mut self, market: &Market) -> Result<(), PortfolioError>;
+}
+
+/// Reconciles the persisted Portfolio state against the account state reported by the execution
+/// venue (eg/ when resuming an [`Engine`](crate::engine::Engine) after a restart).
+pub trait Reconciler {
+    /// Reconcile the persisted [`Balance`] & open [`Position`](position::Position)s of the
+    /// provided [`Market`]s against the input [`AccountSnapshot`]. Returns an
+    /// [`Event::Discrepancy`] for every difference found (values within the absolute tolerance
+    /// are considered equal), leaving the persisted state untouched.
+    fn reconcile(
+        &mut self,
+        markets: &[Market],
+        account: &AccountSnapshot,
+        tolerance: f64,
+    ) -> Result<Vec<Event>, PortfolioError>;
+}
+
 /// Orders are generated by the portfolio and details work to be done by an Execution handler to
 /// open a trade.
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
@@ -79,6 +128,10 @@ pub struct OrderEvent {
     pub quantity: f64,
     /// MARKET, LIMIT etc
     pub order_type: OrderType,
+    /// Optional [`PositionTag`] of the [`Position`](position::Position)
+    /// this [`OrderEvent`] enters or exits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<PositionTag>,
 }
 
 impl OrderEvent {
@@ -105,6 +158,22 @@ impl Default for OrderType {
     }
 }
 
+/// Determines how a Portfolio holds [`Position`](position::Position)s in a market, mirroring the
+/// position modes offered by derivative venues.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionMode {
+    /// One [`Position`](position::Position) per market (& [`PositionTag`]), either Long or Short.
+    #[default]
+    OneWay,
+    /// Independent Long & Short [`Position`](position::Position) legs per market (&
+    /// [`PositionTag`]). CloseLong & CloseShort [`Decision`]s exit the Long & Short leg
+    /// respectively. See [`determine_hedge_leg_tag`](position::determine_hedge_leg_tag).
+    Hedge,
+}
+
 /// Builder to construct OrderEvent instances.
 #[derive(Debug, Default)]
 pub struct OrderEventBuilder {
@@ -115,6 +184,7 @@ pub struct OrderEventBuilder {
     pub decision: Option<Decision>,
     pub quantity: Option<f64>,
     pub order_type: Option<OrderType>,
+    pub tag: Option<PositionTag>,
 }
 
 impl OrderEventBuilder {
@@ -171,6 +241,13 @@ impl OrderEventBuilder {
         }
     }
 
+    pub fn tag(self, value: PositionTag) -> Self {
+        Self {
+            tag: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<OrderEvent, PortfolioError> {
         Ok(OrderEvent {
             time: self.time.ok_or(PortfolioError::BuilderIncomplete("time"))?,
@@ -192,6 +269,7 @@ impl OrderEventBuilder {
             order_type: self
                 .order_type
                 .ok_or(PortfolioError::BuilderIncomplete("order_type"))?,
+            tag: self.tag,
         })
     }
 }
@@ -232,3 +310,14 @@ impl Balance {
         format!("{}_balance", engine_id)
     }
 }
+
+/// Drive the future to completion from synchronous code. Inside a multi-threaded Tokio runtime
+/// the worker thread is handed off first, so the runtime's other tasks are not stalled.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| futures::executor::block_on(future))
+        }
+        _ => futures::executor::block_on(future),
+    }
+}
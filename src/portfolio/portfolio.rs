@@ -2,26 +2,35 @@ use super::{
     allocator::OrderAllocator,
     error::PortfolioError,
     position::{
-        determine_position_id, Position, PositionEnterer, PositionExiter, PositionId,
-        PositionUpdate, PositionUpdater,
+        determine_hedge_leg_tag, determine_tagged_position_id, Position, PositionEnterer,
+        PositionExiter, PositionId, PositionLeg, PositionTag, PositionUpdate, PositionUpdater,
+    },
+    reconcile,
+    repository::{
+        error::RepositoryError, BalanceHandler, PositionHandler, StatisticHandler,
+        TransactionHandler,
     },
-    repository::{error::RepositoryError, BalanceHandler, PositionHandler, StatisticHandler},
     risk::OrderEvaluator,
-    Balance, FillUpdater, MarketUpdater, OrderEvent, OrderGenerator, OrderType,
+    Balance, FillUpdater, MarketInitialiser, MarketUpdater, OrderEvent, OrderGenerator, OrderType,
+    PositionMode, Reconciler,
 };
 use crate::{
+    clock::Clock,
     data::MarketMeta,
     event::Event,
-    execution::FillEvent,
+    execution::{AccountSnapshot, FillEvent},
     statistic::summary::{Initialiser, PositionSummariser},
     strategy::{Decision, Signal, SignalForceExit, SignalStrength},
 };
 use barter_data::model::MarketEvent;
 use barter_integration::model::{Market, MarketId, Side};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::Serialize;
-use std::{collections::HashMap, marker::PhantomData};
-use tracing::info;
+use std::{
+    collections::{BTreeSet, HashMap},
+    marker::PhantomData,
+};
+use tracing::{info, warn};
 use uuid::Uuid;
 
 /// Lego components for constructing & initialising a [`MetaPortfolio`] via the init() constructor
@@ -48,9 +57,14 @@ where
     pub risk: RiskManager,
     /// Cash balance a [`MetaPortfolio`] starts with.
     pub starting_cash: f64,
+    /// [`PositionMode`] determining if Long & Short [`Position`]s can be held simultaneously in
+    /// a market.
+    pub position_mode: PositionMode,
     /// Configuration used to initialise the Statistics for every Market's performance tracked by a
     /// [`MetaPortfolio`].
     pub statistic_config: Statistic::Config,
+    /// [`Clock`] used to timestamp the [`OrderEvent`]s generated by a [`MetaPortfolio`].
+    pub clock: Clock,
     pub _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -73,6 +87,17 @@ where
     allocation_manager: Allocator,
     /// Risk manager implements [`OrderEvaluator`].
     risk_manager: RiskManager,
+    /// [`PositionMode`] determining if Long & Short [`Position`]s can be held simultaneously in
+    /// a market.
+    position_mode: PositionMode,
+    /// Configuration used to initialise the Statistics of every Market tracked by the
+    /// [`MetaPortfolio`], including those added via [`MarketInitialiser::init_market`].
+    statistic_config: Statistic::Config,
+    /// [`PositionTag`]s of the open (tagged or untagged) [`Position`]s held in each [`Market`],
+    /// so a [`MarketEvent`] only reads the open [`Position`]s of it's own [`Market`].
+    open_tags: HashMap<Market, BTreeSet<Option<PositionTag>>>,
+    /// [`Clock`] used to timestamp generated [`OrderEvent`]s.
+    clock: Clock,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -86,24 +111,45 @@ where
 {
     fn update_from_market(
         &mut self,
-        market: &MarketEvent,
-    ) -> Result<Option<PositionUpdate>, PortfolioError> {
-        // Determine the position_id associated to the input MarketEvent
-        let position_id =
-            determine_position_id(self.engine_id, &market.exchange, &market.instrument);
-
-        // Update Position if Portfolio has an open Position for that Symbol-Exchange combination
-        if let Some(mut position) = self.repository.get_open_position(&position_id)? {
+        market_event: &MarketEvent,
+    ) -> Result<Vec<PositionUpdate>, PortfolioError> {
+        // Determine the tag of every open (tagged or untagged) Position for the Symbol-Exchange
+        let market = Market::new(
+            market_event.exchange.clone(),
+            market_event.instrument.clone(),
+        );
+        let tags = match self.open_tags.get(&market) {
+            Some(tags) => tags.clone(),
+            None => return Ok(Vec::new()),
+        };
+
+        let mut position_updates = Vec::with_capacity(tags.len());
+        for tag in tags {
+            let position_id = determine_tagged_position_id(
+                self.engine_id,
+                &market.exchange,
+                &market.instrument,
+                tag.as_ref(),
+            );
+
+            // Forget Positions no longer open in the repository (eg/ after a rolled back entry)
+            let mut position = match self.repository.get_open_position(&position_id)? {
+                Some(position) => position,
+                None => {
+                    self.unindex_open_position(&market, &tag);
+                    continue;
+                }
+            };
+
             // Derive PositionUpdate event that communicates the open Position's change in state
-            let position_update = position.update(market);
+            position_updates.push(position.update(market_event));
 
             // Save updated open Position in the repository
-            self.repository.set_open_position(position)?;
-
-            Ok(Some(position_update))
-        } else {
-            Ok(None)
+            self.repository
+                .set_open_position(self.engine_id, position)?;
         }
+
+        Ok(position_updates)
     }
 }
 
@@ -116,38 +162,70 @@ where
     Statistic: Initialiser + PositionSummariser,
 {
     fn generate_order(&mut self, signal: &Signal) -> Result<Option<OrderEvent>, PortfolioError> {
-        // Determine the position_id & associated Option<Position> related to input SignalEvent
-        let position_id =
-            determine_position_id(self.engine_id, &signal.exchange, &signal.instrument);
-        let position = self.repository.get_open_position(&position_id)?;
+        // Parse signals from Strategy to determine the open Position (if any) the net signal
+        // decision acts on, it's associated strength & the PositionTag of the OrderEvent
+        let (position, tag, signal_decision, signal_strength) = match self.position_mode {
+            PositionMode::OneWay => {
+                // Determine the position_id & associated Option<Position> related to input Signal
+                let position_id = determine_tagged_position_id(
+                    self.engine_id,
+                    &signal.exchange,
+                    &signal.instrument,
+                    signal.tag.as_ref(),
+                );
+                let position = self.repository.get_open_position(&position_id)?;
+
+                let net_signal = parse_signal_decisions(&position.as_ref(), &signal.signals)
+                    .map(|(decision, strength)| (*decision, *strength));
+
+                match net_signal {
+                    None => return Ok(None),
+                    Some((decision, strength)) => {
+                        (position, signal.tag.clone(), decision, strength)
+                    }
+                }
+            }
+            PositionMode::Hedge => {
+                // Retrieve the Long & Short legs held under the Signal's PositionTag
+                let (long_tag, long_leg) = self.open_hedge_leg(signal, Side::Buy)?;
+                let (short_tag, short_leg) = self.open_hedge_leg(signal, Side::Sell)?;
+
+                match parse_hedge_signal_decisions(
+                    long_leg.as_ref(),
+                    short_leg.as_ref(),
+                    &signal.signals,
+                ) {
+                    None => return Ok(None),
+                    Some((decision @ (Decision::Long | Decision::CloseLong), strength)) => {
+                        (long_leg, Some(long_tag), *decision, *strength)
+                    }
+                    Some((decision, strength)) => {
+                        (short_leg, Some(short_tag), *decision, *strength)
+                    }
+                }
+            }
+        };
 
         // If signal is advising to open a new Position rather than close one, check we have cash
         if position.is_none() && self.no_cash_to_enter_new_position()? {
             return Ok(None);
         }
 
-        // Parse signals from Strategy to determine net signal decision & associated strength
-        let position = position.as_ref();
-        let (signal_decision, signal_strength) =
-            match parse_signal_decisions(&position, &signal.signals) {
-                None => return Ok(None),
-                Some(net_signal) => net_signal,
-            };
-
         // Construct mutable OrderEvent that can be modified by Allocation & Risk management
         let mut order = OrderEvent {
-            time: Utc::now(),
+            time: self.clock.time(signal.time),
             exchange: signal.exchange.clone(),
             instrument: signal.instrument.clone(),
             market_meta: signal.market_meta,
-            decision: *signal_decision,
+            decision: signal_decision,
             quantity: 0.0,
             order_type: OrderType::default(),
+            tag,
         };
 
         // Manage OrderEvent size allocation
         self.allocation_manager
-            .allocate_order(&mut order, position, *signal_strength);
+            .allocate_order(&mut order, position.as_ref(), signal_strength);
 
         // Manage global risk when evaluating OrderEvent - keep the same, refine or cancel
         Ok(self.risk_manager.evaluate_order(order))
@@ -158,8 +236,12 @@ where
         signal: SignalForceExit,
     ) -> Result<Option<OrderEvent>, PortfolioError> {
         // Determine PositionId associated with the SignalForceExit
-        let position_id =
-            determine_position_id(self.engine_id, &signal.exchange, &signal.instrument);
+        let position_id = determine_tagged_position_id(
+            self.engine_id,
+            &signal.exchange,
+            &signal.instrument,
+            signal.tag.as_ref(),
+        );
 
         // Retrieve Option<Position> associated with the PositionId
         let position = match self.repository.get_open_position(&position_id)? {
@@ -174,30 +256,186 @@ where
             Some(position) => position,
         };
 
-        Ok(Some(OrderEvent {
-            time: Utc::now(),
-            exchange: signal.exchange,
-            instrument: signal.instrument,
-            market_meta: MarketMeta {
-                close: position.current_symbol_price,
-                time: position.meta.update_time,
-            },
-            decision: position.determine_exit_decision(),
-            quantity: 0.0 - position.quantity,
-            order_type: OrderType::Market,
-        }))
+        Ok(Some(exit_order(position, self.clock.time(signal.time))))
+    }
+
+    fn generate_exit_orders(
+        &mut self,
+        signal: SignalForceExit,
+    ) -> Result<Vec<OrderEvent>, PortfolioError> {
+        // Tagged SignalForceExit only exits the Position with the same PositionTag
+        if signal.tag.is_some() {
+            return Ok(self.generate_exit_order(signal)?.into_iter().collect());
+        }
+
+        // Untagged SignalForceExit exits every open Position in the market
+        let time = self.clock.time(signal.time);
+        let market = Market::new(signal.exchange, signal.instrument);
+        let positions = self
+            .repository
+            .get_open_positions(self.engine_id, std::iter::once(&market))?;
+
+        if positions.is_empty() {
+            info!(
+                market = ?market,
+                outcome = "no forced exit OrderEvents generated",
+                "cannot generate forced exit OrderEvents for a market with no open Positions"
+            );
+        }
+
+        Ok(positions
+            .into_iter()
+            .map(|position| exit_order(position, time))
+            .collect())
     }
 }
 
-impl<Repository, Allocator, RiskManager, Statistic> FillUpdater
+/// Construct a market [`OrderEvent`] generated at the provided time that exits the provided open
+/// [`Position`].
+fn exit_order(position: Position, time: DateTime<Utc>) -> OrderEvent {
+    OrderEvent {
+        time,
+        market_meta: MarketMeta {
+            close: position.current_symbol_price,
+            time: position.meta.update_time,
+        },
+        decision: position.determine_exit_decision(),
+        quantity: 0.0 - position.quantity,
+        order_type: OrderType::Market,
+        exchange: position.exchange,
+        instrument: position.instrument,
+        tag: position.tag,
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic> MarketInitialiser
     for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
 where
     Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
     Allocator: OrderAllocator,
     RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+{
+    fn init_market(&mut self, market: &Market) -> Result<(), PortfolioError> {
+        let market_id = MarketId::from(market);
+        match self.repository.get_statistics(&market_id) {
+            Ok(_) => {}
+            Err(RepositoryError::ExpectedDataNotPresentError) => self
+                .repository
+                .set_statistics(market_id, Statistic::init(self.statistic_config))?,
+            Err(error) => return Err(PortfolioError::RepositoryInteraction(error)),
+        }
+
+        self.index_open_positions(std::slice::from_ref(market))
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic> Reconciler
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+{
+    fn reconcile(
+        &mut self,
+        markets: &[Market],
+        account: &AccountSnapshot,
+        tolerance: f64,
+    ) -> Result<Vec<Event>, PortfolioError> {
+        let balance = self.repository.get_balance(self.engine_id)?;
+        let open_positions = self
+            .repository
+            .get_open_positions(self.engine_id, markets.iter())?;
+
+        // Only reconcile the venue positions of the provided Markets, since the persisted open
+        // Positions of any other market were not fetched
+        let account = AccountSnapshot {
+            balance: account.balance,
+            positions: account
+                .positions
+                .iter()
+                .filter(|venue_position| {
+                    markets.iter().any(|market| {
+                        market.exchange == venue_position.exchange
+                            && market.instrument == venue_position.instrument
+                    })
+                })
+                .cloned()
+                .collect(),
+        };
+
+        let discrepancies = reconcile::reconcile(&balance, &open_positions, &account, tolerance);
+
+        Ok(discrepancies
+            .into_iter()
+            .map(|discrepancy| {
+                warn!(
+                    engine_id = %self.engine_id,
+                    ?discrepancy,
+                    "persisted Portfolio state differs from execution venue"
+                );
+                Event::Discrepancy(discrepancy)
+            })
+            .collect())
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic> FillUpdater
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic> + TransactionHandler,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
     Statistic: Initialiser + PositionSummariser + Serialize,
 {
     fn update_from_fill(&mut self, fill: &FillEvent) -> Result<Vec<Event>, PortfolioError> {
+        // Persist every Repository write for the FillEvent atomically, so a failure midway never
+        // leaves the Portfolio state partially updated (eg/ Position removed, Balance not credited)
+        self.repository.begin_transaction()?;
+
+        let result = self.apply_fill(fill).and_then(|events| {
+            self.repository.commit_transaction()?;
+            Ok(events)
+        });
+
+        // Only index the entered or exited Position once the FillEvent update is committed
+        if let Ok(events) = &result {
+            match events.first() {
+                Some(Event::PositionNew(position)) => self.index_open_position(position),
+                _ => self.unindex_open_position(
+                    &Market::new(fill.exchange.clone(), fill.instrument.clone()),
+                    &fill.tag,
+                ),
+            }
+        }
+
+        if result.is_err() {
+            if let Err(error) = self.repository.rollback_transaction() {
+                warn!(
+                    engine_id = %self.engine_id,
+                    %error,
+                    "failed to rollback Repository transaction after failed FillEvent update"
+                );
+            }
+        }
+
+        result
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic>
+    MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser + Serialize,
+{
+    /// Apply the [`FillEvent`] to the Portfolio state in the Repository, returning the generated
+    /// [`Event`]s.
+    fn apply_fill(&mut self, fill: &FillEvent) -> Result<Vec<Event>, PortfolioError> {
         // Allocate Vector<Event> to contain any update_from_fill generated events
         let mut generated_events: Vec<Event> = Vec::with_capacity(2);
 
@@ -205,8 +443,13 @@ where
         let mut balance = self.repository.get_balance(self.engine_id)?;
         balance.time = fill.time;
 
-        // Determine the position_id that is related to the input FillEvent
-        let position_id = determine_position_id(self.engine_id, &fill.exchange, &fill.instrument);
+        // Determine the (optionally tagged) position_id that is related to the input FillEvent
+        let position_id = determine_tagged_position_id(
+            self.engine_id,
+            &fill.exchange,
+            &fill.instrument,
+            fill.tag.as_ref(),
+        );
 
         // Determine FillEvent context based on existence or absence of an open Position
         match self.repository.remove_position(&position_id)? {
@@ -245,7 +488,8 @@ where
                 balance.available += -position.enter_value_gross - position.enter_fees_total;
 
                 // Add to current Positions in Repository
-                self.repository.set_open_position(position)?;
+                self.repository
+                    .set_open_position(self.engine_id, position)?;
             }
         };
 
@@ -267,8 +511,13 @@ where
     RiskManager: OrderEvaluator,
     Statistic: Initialiser + PositionSummariser,
 {
-    fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
-        self.repository.set_open_position(position)
+    fn set_open_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.index_open_position(&position);
+        self.repository.set_open_position(engine_id, position)
     }
 
     fn get_open_position(
@@ -290,7 +539,12 @@ where
         &mut self,
         position_id: &PositionId,
     ) -> Result<Option<Position>, RepositoryError> {
-        self.repository.remove_position(position_id)
+        let position = self.repository.remove_position(position_id)?;
+        if let Some(position) = &position {
+            let market = Market::new(position.exchange.clone(), position.instrument.clone());
+            self.unindex_open_position(&market, &position.tag);
+        }
+        Ok(position)
     }
 
     fn set_exited_position(&mut self, _: Uuid, position: Position) -> Result<(), RepositoryError> {
@@ -303,6 +557,23 @@ where
     }
 }
 
+impl<Repository, Allocator, RiskManager, Statistic> BalanceHandler
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+{
+    fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
+        self.repository.set_balance(engine_id, balance)
+    }
+
+    fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+        self.repository.get_balance(engine_id)
+    }
+}
+
 impl<Repository, Allocator, RiskManager, Statistic> StatisticHandler<Statistic>
     for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
 where
@@ -343,15 +614,83 @@ where
             repository: lego.repository,
             allocation_manager: lego.allocator,
             risk_manager: lego.risk,
+            position_mode: lego.position_mode,
+            statistic_config: lego.statistic_config,
+            open_tags: HashMap::new(),
+            clock: lego.clock,
             _statistic_marker: PhantomData::default(),
         };
 
         // Persist initial state in the repository
         portfolio.bootstrap_repository(lego.starting_cash, &lego.markets, lego.statistic_config)?;
+        portfolio.index_open_positions(&lego.markets)?;
 
         Ok(portfolio)
     }
 
+    /// Constructs a new [`MetaPortfolio`] using the provided [`PortfolioLego`] components, resuming
+    /// from the state already persisted in the Repository for the engine_id (eg/ after a restart).
+    /// The persisted [`Balance`], open [`Position`]s & Statistics are never overwritten.
+    pub fn resume(
+        lego: PortfolioLego<Repository, Allocator, RiskManager, Statistic>,
+    ) -> Result<Self, PortfolioError> {
+        // Construct MetaPortfolio instance
+        let mut portfolio = Self {
+            engine_id: lego.engine_id,
+            repository: lego.repository,
+            allocation_manager: lego.allocator,
+            risk_manager: lego.risk,
+            position_mode: lego.position_mode,
+            statistic_config: lego.statistic_config,
+            open_tags: HashMap::new(),
+            clock: lego.clock,
+            _statistic_marker: PhantomData,
+        };
+
+        // Load existing state from the repository
+        portfolio.resume_repository(&lego.markets, lego.statistic_config)?;
+
+        Ok(portfolio)
+    }
+
+    /// Load the [`MetaPortfolio`] state persisted in the repository, failing if no [`Balance`] is
+    /// persisted for the engine_id. Statistics are only initialised for markets without persisted
+    /// Statistics (eg/ a market added since the last run).
+    pub fn resume_repository(
+        &mut self,
+        markets: &[Market],
+        statistic_config: Statistic::Config,
+    ) -> Result<(), PortfolioError> {
+        let balance = self.repository.get_balance(self.engine_id)?;
+        let open_positions = self
+            .repository
+            .get_open_positions(self.engine_id, markets.iter())?;
+        open_positions
+            .iter()
+            .for_each(|position| self.index_open_position(position));
+
+        // Initialise Statistics for every Market without persisted Statistics
+        for market in markets {
+            let market_id = MarketId::from(market);
+            match self.repository.get_statistics(&market_id) {
+                Ok(_) => {}
+                Err(RepositoryError::ExpectedDataNotPresentError) => self
+                    .repository
+                    .set_statistics(market_id, Statistic::init(statistic_config))?,
+                Err(error) => return Err(PortfolioError::RepositoryInteraction(error)),
+            }
+        }
+
+        info!(
+            engine_id = %self.engine_id,
+            ?balance,
+            open_positions = open_positions.len(),
+            "resumed MetaPortfolio from persisted state"
+        );
+
+        Ok(())
+    }
+
     /// Persist initial [`MetaPortfolio`] state in the repository. This includes initialised
     /// Statistics every market provided, as well as starting `AvailableCash` & `TotalEquity`.
     pub fn bootstrap_repository<Markets, Id>(
@@ -382,11 +721,71 @@ where
         })
     }
 
+    /// Index the open (tagged or untagged) [`Position`]s persisted in the repository for the
+    /// provided [`Market`]s.
+    fn index_open_positions(&mut self, markets: &[Market]) -> Result<(), PortfolioError> {
+        self.repository
+            .get_open_positions(self.engine_id, markets.iter())?
+            .iter()
+            .for_each(|position| self.index_open_position(position));
+        Ok(())
+    }
+
+    /// Index the [`PositionTag`] of an open [`Position`] under it's [`Market`].
+    fn index_open_position(&mut self, position: &Position) {
+        self.open_tags
+            .entry(Market::new(
+                position.exchange.clone(),
+                position.instrument.clone(),
+            ))
+            .or_default()
+            .insert(position.tag.clone());
+    }
+
+    /// Remove the [`PositionTag`] of a no longer open [`Position`] from the index.
+    fn unindex_open_position(&mut self, market: &Market, tag: &Option<PositionTag>) {
+        if let Some(tags) = self.open_tags.get_mut(market) {
+            tags.remove(tag);
+            if tags.is_empty() {
+                self.open_tags.remove(market);
+            }
+        }
+    }
+
     /// Returns a [`MetaPortfolioBuilder`] instance.
     pub fn builder() -> MetaPortfolioBuilder<Repository, Allocator, RiskManager, Statistic> {
         MetaPortfolioBuilder::new()
     }
 
+    /// Returns a [`PositionLeg`] detailing the margin & PnL of every open (tagged or untagged)
+    /// [`Position`] held in the provided [`Market`] (eg/ it's [`PositionMode::Hedge`] legs).
+    pub fn open_legs(&mut self, market: &Market) -> Result<Vec<PositionLeg>, PortfolioError> {
+        Ok(self
+            .repository
+            .get_open_positions(self.engine_id, std::iter::once(market))?
+            .iter()
+            .map(PositionLeg::from)
+            .collect())
+    }
+
+    /// Returns the [`PositionTag`] & the open [`Position`] (if any) of the Long (Buy) or Short
+    /// (Sell) [`PositionMode::Hedge`] leg the input [`Signal`] is for.
+    fn open_hedge_leg(
+        &mut self,
+        signal: &Signal,
+        side: Side,
+    ) -> Result<(PositionTag, Option<Position>), PortfolioError> {
+        let tag = determine_hedge_leg_tag(signal.tag.as_ref(), side);
+        let position_id = determine_tagged_position_id(
+            self.engine_id,
+            &signal.exchange,
+            &signal.instrument,
+            Some(&tag),
+        );
+
+        Ok((tag, self.repository.get_open_position(&position_id)?))
+    }
+
     /// Determines if the Portfolio has any cash to enter a new [`Position`].
     fn no_cash_to_enter_new_position(&mut self) -> Result<bool, PortfolioError> {
         self.repository
@@ -411,6 +810,8 @@ where
     allocation_manager: Option<Allocator>,
     risk_manager: Option<RiskManager>,
     statistic_config: Option<Statistic::Config>,
+    position_mode: Option<PositionMode>,
+    clock: Option<Clock>,
     _statistic_marker: Option<PhantomData<Statistic>>,
 }
 
@@ -431,6 +832,8 @@ where
             allocation_manager: None,
             risk_manager: None,
             statistic_config: None,
+            position_mode: None,
+            clock: None,
             _statistic_marker: None,
         }
     }
@@ -484,9 +887,30 @@ where
         }
     }
 
+    pub fn position_mode(self, value: PositionMode) -> Self {
+        Self {
+            position_mode: Some(value),
+            ..self
+        }
+    }
+
+    pub fn clock(self, value: Clock) -> Self {
+        Self {
+            clock: Some(value),
+            ..self
+        }
+    }
+
     pub fn build_and_init(
         self,
     ) -> Result<MetaPortfolio<Repository, Allocator, RiskManager, Statistic>, PortfolioError> {
+        let statistic_config = self
+            .statistic_config
+            .ok_or(PortfolioError::BuilderIncomplete("statistic_config"))?;
+        let markets = self
+            .markets
+            .ok_or(PortfolioError::BuilderIncomplete("markets"))?;
+
         // Construct Portfolio
         let mut portfolio = MetaPortfolio {
             engine_id: self
@@ -501,6 +925,10 @@ where
             risk_manager: self
                 .risk_manager
                 .ok_or(PortfolioError::BuilderIncomplete("risk_manager"))?,
+            position_mode: self.position_mode.unwrap_or_default(),
+            statistic_config,
+            open_tags: HashMap::new(),
+            clock: self.clock.unwrap_or_default(),
             _statistic_marker: PhantomData::default(),
         };
 
@@ -508,17 +936,86 @@ where
         portfolio.bootstrap_repository(
             self.starting_cash
                 .ok_or(PortfolioError::BuilderIncomplete("starting_cash"))?,
-            &self
-                .markets
-                .ok_or(PortfolioError::BuilderIncomplete("markets"))?,
-            self.statistic_config
-                .ok_or(PortfolioError::BuilderIncomplete("statistic_config"))?,
+            &markets,
+            statistic_config,
         )?;
+        portfolio.index_open_positions(&markets)?;
+
+        Ok(portfolio)
+    }
+
+    /// Build a [`MetaPortfolio`] that resumes from the state already persisted in the Repository,
+    /// rather than bootstrapping it with the starting_cash. See [`MetaPortfolio::resume`].
+    pub fn build_and_resume(
+        self,
+    ) -> Result<MetaPortfolio<Repository, Allocator, RiskManager, Statistic>, PortfolioError> {
+        let statistic_config = self
+            .statistic_config
+            .ok_or(PortfolioError::BuilderIncomplete("statistic_config"))?;
+        let markets = self
+            .markets
+            .ok_or(PortfolioError::BuilderIncomplete("markets"))?;
+
+        // Construct Portfolio
+        let mut portfolio = MetaPortfolio {
+            engine_id: self
+                .engine_id
+                .ok_or(PortfolioError::BuilderIncomplete("engine_id"))?,
+            repository: self
+                .repository
+                .ok_or(PortfolioError::BuilderIncomplete("repository"))?,
+            allocation_manager: self
+                .allocation_manager
+                .ok_or(PortfolioError::BuilderIncomplete("allocation_manager"))?,
+            risk_manager: self
+                .risk_manager
+                .ok_or(PortfolioError::BuilderIncomplete("risk_manager"))?,
+            position_mode: self.position_mode.unwrap_or_default(),
+            statistic_config,
+            open_tags: HashMap::new(),
+            clock: self.clock.unwrap_or_default(),
+            _statistic_marker: PhantomData,
+        };
+
+        // Load existing state from the Repository
+        portfolio.resume_repository(&markets, statistic_config)?;
 
         Ok(portfolio)
     }
 }
 
+/// Parses an incoming [`Signal`]'s signals map for a [`PositionMode::Hedge`] market holding the
+/// provided Long & Short legs. Determines what the net signal [`Decision`] will be, and it's
+/// associated [`SignalStrength`].
+///
+/// Exiting an open leg takes precedence over entering a new leg (CloseLong before CloseShort).
+/// Entry signals are only considered for legs that are not already open, and conflicting entry
+/// signals yield None.
+pub fn parse_hedge_signal_decisions<'a>(
+    long_leg: Option<&Position>,
+    short_leg: Option<&Position>,
+    signals: &'a HashMap<Decision, SignalStrength>,
+) -> Option<(&'a Decision, &'a SignalStrength)> {
+    // Determine the presence of signals actionable given the open legs
+    let signal_close_long = long_leg.and(signals.get_key_value(&Decision::CloseLong));
+    let signal_close_short = short_leg.and(signals.get_key_value(&Decision::CloseShort));
+    let signal_long = signals
+        .get_key_value(&Decision::Long)
+        .filter(|_| long_leg.is_none());
+    let signal_short = signals
+        .get_key_value(&Decision::Short)
+        .filter(|_| short_leg.is_none());
+
+    // Check for close signals before net open signals
+    signal_close_long
+        .or(signal_close_short)
+        .or(match (signal_long, signal_short) {
+            (Some(signal_long), None) => Some(signal_long),
+            (None, Some(signal_short)) => Some(signal_short),
+            _ => None,
+        })
+}
+
 /// Parses an incoming [`Signal`]'s signals map. Determines what the net signal [`Decision`]
 /// will be, and it's associated [`SignalStrength`].
 pub fn parse_signal_decisions<'a>(
@@ -552,10 +1049,12 @@ pub fn parse_signal_decisions<'a>(
 pub mod tests {
     use super::*;
 
-    use crate::execution::Fees;
+    use crate::execution::{Fees, VenuePosition};
     use crate::portfolio::allocator::DefaultAllocator;
-    use crate::portfolio::position::PositionBuilder;
+    use crate::portfolio::position::{determine_position_id, PositionBuilder};
+    use crate::portfolio::reconcile::Discrepancy;
     use crate::portfolio::repository::error::RepositoryError;
+    use crate::portfolio::repository::in_memory::InMemoryRepository;
     use crate::portfolio::risk::DefaultRisk;
     use crate::statistic::summary::pnl::PnLReturnSummary;
     use crate::strategy::SignalForceExit;
@@ -587,7 +1086,11 @@ pub mod tests {
     }
 
     impl<Statistic> PositionHandler for MockRepository<Statistic> {
-        fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
+        fn set_open_position(
+            &mut self,
+            _: Uuid,
+            position: Position,
+        ) -> Result<(), RepositoryError> {
             self.position = Some(
                 Position::builder()
                     .side(position.side.clone())
@@ -600,7 +1103,9 @@ pub mod tests {
                     .exit_value_gross(position.exit_value_gross)
                     .exit_avg_price_gross(position.exit_avg_price_gross)
                     .unrealised_profit_loss(position.unrealised_profit_loss)
-                    .realised_profit_loss(position.realised_profit_loss),
+                    .realised_profit_loss(position.realised_profit_loss)
+                    .max_adverse_excursion(position.max_adverse_excursion)
+                    .max_favourable_excursion(position.max_favourable_excursion),
             );
             self.set_open_position.unwrap()(position)
         }
@@ -672,12 +1177,27 @@ pub mod tests {
         }
     }
 
+    impl<Statistic> TransactionHandler for MockRepository<Statistic> {
+        fn begin_transaction(&mut self) -> Result<(), RepositoryError> {
+            Ok(())
+        }
+
+        fn commit_transaction(&mut self) -> Result<(), RepositoryError> {
+            Ok(())
+        }
+
+        fn rollback_transaction(&mut self) -> Result<(), RepositoryError> {
+            Ok(())
+        }
+    }
+
     fn new_mocked_portfolio<Repository, Statistic>(
         mock_repository: Repository,
     ) -> Result<MetaPortfolio<Repository, DefaultAllocator, DefaultRisk, Statistic>, PortfolioError>
     where
         Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
         Statistic: PositionSummariser + Initialiser,
+        Statistic::Config: Default,
     {
         let builder = MetaPortfolio::builder()
             .engine_id(Uuid::new_v4())
@@ -697,6 +1217,7 @@ pub mod tests {
     where
         Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
         Statistic: PositionSummariser + Initialiser,
+        Statistic::Config: Default,
     {
         Ok(MetaPortfolio {
             engine_id: builder
@@ -711,6 +1232,10 @@ pub mod tests {
             risk_manager: builder
                 .risk_manager
                 .ok_or(PortfolioError::BuilderIncomplete("risk_manager"))?,
+            position_mode: PositionMode::default(),
+            statistic_config: builder.statistic_config.unwrap_or_default(),
+            open_tags: HashMap::new(),
+            clock: Clock::default(),
             _statistic_marker: Default::default(),
         })
     }
@@ -720,26 +1245,29 @@ pub mod tests {
             time: Utc::now(),
             exchange: Exchange::from("binance"),
             instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+            tag: None,
         }
     }
 
     #[test]
     fn update_from_market_with_long_position_increasing_in_value() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
-            Ok(Some({
-                let mut input_position = position();
-                input_position.side = Side::Buy;
-                input_position.quantity = 1.0;
-                input_position.enter_fees_total = 3.0;
-                input_position.current_symbol_price = 100.0;
-                input_position.current_value_gross = 100.0;
-                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
-                input_position
-            }))
-        });
-        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| {
+                Ok(Some({
+                    let mut input_position = position();
+                    input_position.side = Side::Buy;
+                    input_position.quantity = 1.0;
+                    input_position.enter_fees_total = 3.0;
+                    input_position.current_symbol_price = 100.0;
+                    input_position.current_value_gross = 100.0;
+                    input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
+                    input_position
+                }))
+            }),
+            set_open_position: Some(|_| Ok(())),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input MarketEvent
@@ -752,10 +1280,19 @@ pub mod tests {
             DataKind::OrderBook(_) => todo!(),
         };
 
+        // Index the mocked open Position under the input MarketEvent's Market
+        portfolio.open_tags.insert(
+            Market::new(
+                input_market.exchange.clone(),
+                input_market.instrument.clone(),
+            ),
+            BTreeSet::from([None]),
+        );
+
         let result_pos_update = portfolio
             .update_from_market(&input_market)
             .unwrap()
-            .unwrap();
+            .remove(0);
         let updated_position = portfolio.repository.position.unwrap();
 
         assert_eq!(updated_position.current_symbol_price.unwrap(), 200.0);
@@ -775,20 +1312,22 @@ pub mod tests {
     #[test]
     fn update_from_market_with_long_position_decreasing_in_value() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
-            Ok(Some({
-                let mut input_position = position();
-                input_position.side = Side::Buy;
-                input_position.quantity = 1.0;
-                input_position.enter_fees_total = 3.0;
-                input_position.current_symbol_price = 100.0;
-                input_position.current_value_gross = 100.0;
-                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
-                input_position
-            }))
-        });
-        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| {
+                Ok(Some({
+                    let mut input_position = position();
+                    input_position.side = Side::Buy;
+                    input_position.quantity = 1.0;
+                    input_position.enter_fees_total = 3.0;
+                    input_position.current_symbol_price = 100.0;
+                    input_position.current_value_gross = 100.0;
+                    input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
+                    input_position
+                }))
+            }),
+            set_open_position: Some(|_| Ok(())),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input MarketEvent
@@ -800,10 +1339,19 @@ pub mod tests {
             DataKind::OrderBook(_) => todo!(),
         };
 
+        // Index the mocked open Position under the input MarketEvent's Market
+        portfolio.open_tags.insert(
+            Market::new(
+                input_market.exchange.clone(),
+                input_market.instrument.clone(),
+            ),
+            BTreeSet::from([None]),
+        );
+
         let result_pos_update = portfolio
             .update_from_market(&input_market)
             .unwrap()
-            .unwrap();
+            .remove(0);
         let updated_position = portfolio.repository.position.unwrap();
 
         assert_eq!(updated_position.current_symbol_price.unwrap(), 50.0);
@@ -819,20 +1367,22 @@ pub mod tests {
     #[test]
     fn update_from_market_with_short_position_increasing_in_value() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
-            Ok(Some({
-                let mut input_position = position();
-                input_position.side = Side::Sell;
-                input_position.quantity = -1.0;
-                input_position.enter_fees_total = 3.0;
-                input_position.current_symbol_price = 100.0;
-                input_position.current_value_gross = 100.0;
-                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
-                input_position
-            }))
-        });
-        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| {
+                Ok(Some({
+                    let mut input_position = position();
+                    input_position.side = Side::Sell;
+                    input_position.quantity = -1.0;
+                    input_position.enter_fees_total = 3.0;
+                    input_position.current_symbol_price = 100.0;
+                    input_position.current_value_gross = 100.0;
+                    input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
+                    input_position
+                }))
+            }),
+            set_open_position: Some(|_| Ok(())),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input MarketEvent
@@ -845,10 +1395,19 @@ pub mod tests {
             DataKind::OrderBook(_) => todo!(),
         };
 
+        // Index the mocked open Position under the input MarketEvent's Market
+        portfolio.open_tags.insert(
+            Market::new(
+                input_market.exchange.clone(),
+                input_market.instrument.clone(),
+            ),
+            BTreeSet::from([None]),
+        );
+
         let result_pos_update = portfolio
             .update_from_market(&input_market)
             .unwrap()
-            .unwrap();
+            .remove(0);
         let updated_position = portfolio.repository.position.unwrap();
 
         assert_eq!(updated_position.current_symbol_price.unwrap(), 50.0);
@@ -864,20 +1423,22 @@ pub mod tests {
     #[test]
     fn update_from_market_with_short_position_decreasing_in_value() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
-            Ok(Some({
-                let mut input_position = position();
-                input_position.side = Side::Sell;
-                input_position.quantity = -1.0;
-                input_position.enter_fees_total = 3.0;
-                input_position.current_symbol_price = 100.0;
-                input_position.current_value_gross = 100.0;
-                input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
-                input_position
-            }))
-        });
-        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| {
+                Ok(Some({
+                    let mut input_position = position();
+                    input_position.side = Side::Sell;
+                    input_position.quantity = -1.0;
+                    input_position.enter_fees_total = 3.0;
+                    input_position.current_symbol_price = 100.0;
+                    input_position.current_value_gross = 100.0;
+                    input_position.unrealised_profit_loss = -3.0; // -3.0 from entry fees
+                    input_position
+                }))
+            }),
+            set_open_position: Some(|_| Ok(())),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input MarketEvent
@@ -890,10 +1451,19 @@ pub mod tests {
             DataKind::OrderBook(_) => todo!(),
         };
 
+        // Index the mocked open Position under the input MarketEvent's Market
+        portfolio.open_tags.insert(
+            Market::new(
+                input_market.exchange.clone(),
+                input_market.instrument.clone(),
+            ),
+            BTreeSet::from([None]),
+        );
+
         let result_pos_update = portfolio
             .update_from_market(&input_market)
             .unwrap()
-            .unwrap();
+            .remove(0);
         let updated_position = portfolio.repository.position.unwrap();
 
         assert_eq!(updated_position.current_symbol_price.unwrap(), 200.0);
@@ -909,18 +1479,33 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn update_from_market_without_open_positions_does_not_read_the_repository() {
+        // MockRepository panics if any open Position is read
+        let mut portfolio =
+            new_mocked_portfolio(MockRepository::<PnLReturnSummary>::default()).unwrap();
+
+        let position_updates = portfolio
+            .update_from_market(&market_trade(Side::Buy))
+            .unwrap();
+
+        assert!(position_updates.is_empty());
+    }
+
     #[test]
     fn generate_no_order_with_no_position_and_no_cash() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| Ok(None));
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 100.0,
-                available: 0.0,
-            })
-        });
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 100.0,
+                    available: 0.0,
+                })
+            }),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input SignalEvent
@@ -934,15 +1519,17 @@ pub mod tests {
     #[test]
     fn generate_no_order_with_position_and_no_cash() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| Ok(Some(position())));
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 100.0,
-                available: 0.0,
-            })
-        });
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(Some(position()))),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 100.0,
+                    available: 0.0,
+                })
+            }),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input SignalEvent
@@ -956,15 +1543,17 @@ pub mod tests {
     #[test]
     fn generate_order_long_with_no_position_and_input_net_long_signal() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| Ok(None));
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 100.0,
-                available: 100.0,
-            })
-        });
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 100.0,
+                    available: 100.0,
+                })
+            }),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input SignalEvent
@@ -981,15 +1570,17 @@ pub mod tests {
     #[test]
     fn generate_order_short_with_no_position_and_input_net_short_signal() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| Ok(None));
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 100.0,
-                available: 100.0,
-            })
-        });
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 100.0,
+                    available: 100.0,
+                })
+            }),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input SignalEvent
@@ -1007,21 +1598,23 @@ pub mod tests {
     #[test]
     fn generate_order_close_long_with_long_position_and_input_net_close_long_signal() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
-            Ok(Some({
-                let mut position = position();
-                position.side = Side::Buy;
-                position
-            }))
-        });
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 100.0,
-                available: 100.0,
-            })
-        });
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| {
+                Ok(Some({
+                    let mut position = position();
+                    position.side = Side::Buy;
+                    position
+                }))
+            }),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 100.0,
+                    available: 100.0,
+                })
+            }),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input SignalEvent
@@ -1039,21 +1632,23 @@ pub mod tests {
     #[test]
     fn generate_order_close_short_with_short_position_and_input_net_close_short_signal() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
-            Ok(Some({
-                let mut position = position();
-                position.side = Side::Sell;
-                position
-            }))
-        });
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 100.0,
-                available: 100.0,
-            })
-        });
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| {
+                Ok(Some({
+                    let mut position = position();
+                    position.side = Side::Sell;
+                    position
+                }))
+            }),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 100.0,
+                    available: 100.0,
+                })
+            }),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input SignalEvent
@@ -1071,15 +1666,17 @@ pub mod tests {
     #[test]
     fn generate_exit_order_with_long_position_open() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
-            Ok(Some({
-                let mut position = position();
-                position.side = Side::Buy;
-                position.quantity = 100.0;
-                position
-            }))
-        });
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| {
+                Ok(Some({
+                    let mut position = position();
+                    position.side = Side::Buy;
+                    position.quantity = 100.0;
+                    position
+                }))
+            }),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input SignalEvent
@@ -1099,15 +1696,17 @@ pub mod tests {
     #[test]
     fn generate_exit_order_with_short_position_open() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
-            Ok(Some({
-                let mut position = position();
-                position.side = Side::Sell;
-                position.quantity = -100.0;
-                position
-            }))
-        });
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| {
+                Ok(Some({
+                    let mut position = position();
+                    position.side = Side::Sell;
+                    position.quantity = -100.0;
+                    position
+                }))
+            }),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input SignalEvent
@@ -1127,8 +1726,10 @@ pub mod tests {
     #[test]
     fn generate_no_exit_order_when_no_open_position_to_exit() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| Ok(None));
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            ..Default::default()
+        };
 
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
@@ -1142,17 +1743,19 @@ pub mod tests {
     #[test]
     fn update_from_fill_entering_long_position() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 200.0,
-                available: 200.0,
-            })
-        });
-        mock_repository.remove_position = Some(|_| Ok(None));
-        mock_repository.set_open_position = Some(|_| Ok(()));
-        mock_repository.set_balance = Some(|_, _| Ok(()));
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 200.0,
+                    available: 200.0,
+                })
+            }),
+            remove_position: Some(|_| Ok(None)),
+            set_open_position: Some(|_| Ok(())),
+            set_balance: Some(|_, _| Ok(())),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input FillEvent
@@ -1181,17 +1784,19 @@ pub mod tests {
     #[test]
     fn update_from_fill_entering_short_position() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 200.0,
-                available: 200.0,
-            })
-        });
-        mock_repository.remove_position = Some(|_| Ok(None));
-        mock_repository.set_open_position = Some(|_| Ok(()));
-        mock_repository.set_balance = Some(|_, _| Ok(()));
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 200.0,
+                    available: 200.0,
+                })
+            }),
+            remove_position: Some(|_| Ok(None)),
+            set_open_position: Some(|_| Ok(())),
+            set_balance: Some(|_, _| Ok(())),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input FillEvent
@@ -1220,30 +1825,32 @@ pub mod tests {
     #[test]
     fn update_from_fill_exiting_long_position_in_profit() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 200.0,
-                available: 97.0,
-            })
-        });
-        mock_repository.remove_position = Some(|_| {
-            Ok({
-                Some({
-                    let mut input_position = position();
-                    input_position.side = Side::Buy;
-                    input_position.quantity = 1.0;
-                    input_position.enter_fees_total = 3.0;
-                    input_position.enter_value_gross = 100.0;
-                    input_position
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 200.0,
+                    available: 97.0,
                 })
-            })
-        });
-        mock_repository.get_statistics = Some(|_| Ok(PnLReturnSummary::default()));
-        mock_repository.set_statistics = Some(|_, _| Ok(()));
-        mock_repository.set_exited_position = Some(|_, _| Ok(()));
-        mock_repository.set_balance = Some(|_, _| Ok(()));
+            }),
+            remove_position: Some(|_| {
+                Ok({
+                    Some({
+                        let mut input_position = position();
+                        input_position.side = Side::Buy;
+                        input_position.quantity = 1.0;
+                        input_position.enter_fees_total = 3.0;
+                        input_position.enter_value_gross = 100.0;
+                        input_position
+                    })
+                })
+            }),
+            get_statistics: Some(|_| Ok(PnLReturnSummary::default())),
+            set_statistics: Some(|_, _| Ok(())),
+            set_exited_position: Some(|_, _| Ok(())),
+            set_balance: Some(|_, _| Ok(())),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input FillEvent
@@ -1273,30 +1880,32 @@ pub mod tests {
     #[test]
     fn update_from_fill_exiting_long_position_in_loss() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 200.0,
-                available: 97.0,
-            })
-        });
-        mock_repository.remove_position = Some(|_| {
-            Ok({
-                Some({
-                    let mut input_position = position();
-                    input_position.side = Side::Buy;
-                    input_position.quantity = 1.0;
-                    input_position.enter_fees_total = 3.0;
-                    input_position.enter_value_gross = 100.0;
-                    input_position
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 200.0,
+                    available: 97.0,
                 })
-            })
-        });
-        mock_repository.get_statistics = Some(|_| Ok(PnLReturnSummary::default()));
-        mock_repository.set_statistics = Some(|_, _| Ok(()));
-        mock_repository.set_exited_position = Some(|_, _| Ok(()));
-        mock_repository.set_balance = Some(|_, _| Ok(()));
+            }),
+            remove_position: Some(|_| {
+                Ok({
+                    Some({
+                        let mut input_position = position();
+                        input_position.side = Side::Buy;
+                        input_position.quantity = 1.0;
+                        input_position.enter_fees_total = 3.0;
+                        input_position.enter_value_gross = 100.0;
+                        input_position
+                    })
+                })
+            }),
+            get_statistics: Some(|_| Ok(PnLReturnSummary::default())),
+            set_statistics: Some(|_, _| Ok(())),
+            set_exited_position: Some(|_, _| Ok(())),
+            set_balance: Some(|_, _| Ok(())),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input FillEvent
@@ -1326,30 +1935,32 @@ pub mod tests {
     #[test]
     fn update_from_fill_exiting_short_position_in_profit() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 200.0,
-                available: 97.0,
-            })
-        });
-        mock_repository.remove_position = Some(|_| {
-            Ok({
-                Some({
-                    let mut input_position = position();
-                    input_position.side = Side::Sell;
-                    input_position.quantity = -1.0;
-                    input_position.enter_fees_total = 3.0;
-                    input_position.enter_value_gross = 100.0;
-                    input_position
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 200.0,
+                    available: 97.0,
                 })
-            })
-        });
-        mock_repository.get_statistics = Some(|_| Ok(PnLReturnSummary::default()));
-        mock_repository.set_statistics = Some(|_, _| Ok(()));
-        mock_repository.set_exited_position = Some(|_, _| Ok(()));
-        mock_repository.set_balance = Some(|_, _| Ok(()));
+            }),
+            remove_position: Some(|_| {
+                Ok({
+                    Some({
+                        let mut input_position = position();
+                        input_position.side = Side::Sell;
+                        input_position.quantity = -1.0;
+                        input_position.enter_fees_total = 3.0;
+                        input_position.enter_value_gross = 100.0;
+                        input_position
+                    })
+                })
+            }),
+            get_statistics: Some(|_| Ok(PnLReturnSummary::default())),
+            set_statistics: Some(|_, _| Ok(())),
+            set_exited_position: Some(|_, _| Ok(())),
+            set_balance: Some(|_, _| Ok(())),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input FillEvent
@@ -1379,30 +1990,32 @@ pub mod tests {
     #[test]
     fn update_from_fill_exiting_short_position_in_loss() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 200.0,
-                available: 97.0,
-            })
-        });
-        mock_repository.remove_position = Some(|_| {
-            Ok({
-                Some({
-                    let mut input_position = position();
-                    input_position.side = Side::Sell;
-                    input_position.quantity = -1.0;
-                    input_position.enter_fees_total = 3.0;
-                    input_position.enter_value_gross = 100.0;
-                    input_position
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 200.0,
+                    available: 97.0,
                 })
-            })
-        });
-        mock_repository.get_statistics = Some(|_| Ok(PnLReturnSummary::default()));
-        mock_repository.set_statistics = Some(|_, _| Ok(()));
-        mock_repository.set_exited_position = Some(|_, _| Ok(()));
-        mock_repository.set_balance = Some(|_, _| Ok(()));
+            }),
+            remove_position: Some(|_| {
+                Ok({
+                    Some({
+                        let mut input_position = position();
+                        input_position.side = Side::Sell;
+                        input_position.quantity = -1.0;
+                        input_position.enter_fees_total = 3.0;
+                        input_position.enter_value_gross = 100.0;
+                        input_position
+                    })
+                })
+            }),
+            get_statistics: Some(|_| Ok(PnLReturnSummary::default())),
+            set_statistics: Some(|_, _| Ok(())),
+            set_exited_position: Some(|_, _| Ok(())),
+            set_balance: Some(|_, _| Ok(())),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input FillEvent
@@ -1429,6 +2042,70 @@ pub mod tests {
         assert_eq!(updated_value, 200.0 + (100.0 - 150.0 - 6.0));
     }
 
+    #[test]
+    fn update_from_fill_failing_midway_leaves_repository_unchanged() {
+        // Build Portfolio without any persisted Statistics, so exiting a Position fails midway
+        let mut portfolio =
+            new_mocked_portfolio(InMemoryRepository::<PnLReturnSummary>::new()).unwrap();
+        let engine_id = portfolio.engine_id;
+
+        let balance = Balance {
+            time: Utc::now(),
+            total: 200.0,
+            available: 97.0,
+        };
+        portfolio
+            .repository
+            .set_balance(engine_id, balance)
+            .unwrap();
+
+        let mut open_position = position();
+        open_position.position_id = determine_position_id(
+            engine_id,
+            &open_position.exchange,
+            &open_position.instrument,
+        );
+        open_position.side = Side::Buy;
+        open_position.quantity = 1.0;
+        portfolio
+            .repository
+            .set_open_position(engine_id, open_position.clone())
+            .unwrap();
+
+        // Input FillEvent
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::CloseLong;
+        input_fill.quantity = -1.0;
+
+        let result = portfolio.update_from_fill(&input_fill);
+
+        assert!(matches!(
+            result,
+            Err(PortfolioError::RepositoryInteraction(
+                RepositoryError::ExpectedDataNotPresentError
+            ))
+        ));
+        assert_eq!(
+            portfolio.repository.get_balance(engine_id).unwrap(),
+            balance
+        );
+        assert_eq!(
+            portfolio
+                .repository
+                .get_open_position(&open_position.position_id)
+                .unwrap(),
+            Some(open_position)
+        );
+        assert!(portfolio
+            .repository
+            .get_exited_positions(engine_id)
+            .unwrap()
+            .is_empty());
+
+        // Repository accepts a new transaction after the rollback
+        assert!(portfolio.repository.begin_transaction().is_ok());
+    }
+
     #[test]
     fn parse_signal_decisions_to_net_close_long() {
         // Some(Position)
@@ -1587,4 +2264,340 @@ pub mod tests {
 
         assert_eq!(actual, None);
     }
+
+    #[test]
+    fn parse_hedge_signal_decisions_to_enter_short_leg_with_long_leg_open() {
+        let mut long_leg = position();
+        long_leg.side = Side::Buy;
+
+        // Signals HashMap
+        let mut signals = HashMap::with_capacity(4);
+        signals.insert(Decision::Long, SignalStrength(1.0));
+        signals.insert(Decision::Short, SignalStrength(1.0));
+
+        let actual = parse_hedge_signal_decisions(Some(&long_leg), None, &signals);
+
+        assert_eq!(actual.unwrap().0, &Decision::Short);
+    }
+
+    #[test]
+    fn parse_hedge_signal_decisions_to_close_short_leg_before_entering() {
+        let mut short_leg = position();
+        short_leg.side = Side::Sell;
+
+        // Signals HashMap
+        let mut signals = HashMap::with_capacity(4);
+        signals.insert(Decision::Long, SignalStrength(1.0));
+        signals.insert(Decision::CloseLong, SignalStrength(1.0));
+        signals.insert(Decision::CloseShort, SignalStrength(1.0));
+
+        let actual = parse_hedge_signal_decisions(None, Some(&short_leg), &signals);
+
+        assert_eq!(actual.unwrap().0, &Decision::CloseShort);
+    }
+
+    #[test]
+    fn parse_hedge_signal_decisions_to_none_with_both_legs_open_and_entry_signals() {
+        let long_leg = position();
+        let mut short_leg = position();
+        short_leg.side = Side::Sell;
+
+        // Signals HashMap
+        let mut signals = HashMap::with_capacity(4);
+        signals.insert(Decision::Long, SignalStrength(1.0));
+        signals.insert(Decision::Short, SignalStrength(1.0));
+
+        let actual = parse_hedge_signal_decisions(Some(&long_leg), Some(&short_leg), &signals);
+
+        assert_eq!(actual, None);
+    }
+
+    fn resumable_portfolio_builder(
+        engine_id: Uuid,
+        repository: InMemoryRepository<PnLReturnSummary>,
+    ) -> MetaPortfolioBuilder<
+        InMemoryRepository<PnLReturnSummary>,
+        DefaultAllocator,
+        DefaultRisk,
+        PnLReturnSummary,
+    > {
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![Market::new(
+                "binance",
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(repository)
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+    }
+
+    #[test]
+    fn init_and_resume_use_the_lego_position_mode() {
+        let lego = || PortfolioLego {
+            engine_id: Uuid::new_v4(),
+            markets: vec![Market::new(
+                "binance",
+                ("eth", "usdt", InstrumentKind::Spot),
+            )],
+            repository: InMemoryRepository::<PnLReturnSummary>::new(),
+            allocator: DefaultAllocator {
+                default_order_value: 100.0,
+            },
+            risk: DefaultRisk {},
+            starting_cash: 1000.0,
+            position_mode: PositionMode::Hedge,
+            statistic_config: (),
+            clock: Clock::default(),
+            _statistic_marker: PhantomData,
+        };
+
+        let portfolio = MetaPortfolio::init(lego()).unwrap();
+        assert_eq!(portfolio.position_mode, PositionMode::Hedge);
+
+        // Resuming requires the Balance persisted by the initialised MetaPortfolio
+        let mut resumed_lego = lego();
+        resumed_lego.engine_id = portfolio.engine_id;
+        resumed_lego.repository = portfolio.repository;
+        let portfolio = MetaPortfolio::resume(resumed_lego).unwrap();
+        assert_eq!(portfolio.position_mode, PositionMode::Hedge);
+    }
+
+    #[test]
+    fn build_and_resume_keeps_persisted_state() {
+        let engine_id = Uuid::new_v4();
+        let market_id = MarketId::new(
+            &Exchange::from("binance"),
+            &Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+        );
+
+        // Persist state from a previous run
+        let mut repository = InMemoryRepository::<PnLReturnSummary>::new();
+        let persisted_balance = Balance {
+            time: Utc::now(),
+            total: 1500.0,
+            available: 1400.0,
+        };
+        repository
+            .set_balance(engine_id, persisted_balance)
+            .unwrap();
+        let mut persisted_statistic = PnLReturnSummary::init(());
+        persisted_statistic.update(&position());
+        repository
+            .set_statistics(market_id.clone(), persisted_statistic)
+            .unwrap();
+        let mut open_position = position();
+        open_position.position_id = determine_position_id(
+            engine_id,
+            &open_position.exchange,
+            &open_position.instrument,
+        );
+        repository
+            .set_open_position(engine_id, open_position.clone())
+            .unwrap();
+
+        let mut portfolio = resumable_portfolio_builder(engine_id, repository)
+            .build_and_resume()
+            .unwrap();
+
+        assert_eq!(portfolio.get_balance(engine_id).unwrap(), persisted_balance);
+        assert_eq!(
+            portfolio.get_statistics(&market_id).unwrap().total,
+            persisted_statistic.total
+        );
+        assert_eq!(
+            portfolio
+                .get_open_position(&open_position.position_id)
+                .unwrap(),
+            Some(open_position)
+        );
+    }
+
+    #[test]
+    fn build_and_resume_fails_without_persisted_balance() {
+        let result = resumable_portfolio_builder(Uuid::new_v4(), InMemoryRepository::new())
+            .build_and_resume();
+
+        assert!(matches!(
+            result,
+            Err(PortfolioError::RepositoryInteraction(
+                RepositoryError::ExpectedDataNotPresentError
+            ))
+        ));
+    }
+
+    #[test]
+    fn reconcile_returns_discrepancy_events_without_modifying_state() {
+        let engine_id = Uuid::new_v4();
+        let markets = vec![Market::new(
+            "binance",
+            ("eth", "usdt", InstrumentKind::Spot),
+        )];
+
+        let mut portfolio = resumable_portfolio_builder(engine_id, InMemoryRepository::new())
+            .build_and_init()
+            .unwrap();
+        let persisted_balance = portfolio.get_balance(engine_id).unwrap();
+
+        // Venue position in a market that isn't being reconciled is ignored
+        let account = AccountSnapshot {
+            balance: Balance {
+                time: Utc::now(),
+                total: 900.0,
+                available: 900.0,
+            },
+            positions: vec![
+                VenuePosition {
+                    exchange: Exchange::from("binance"),
+                    instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+                    quantity: 1.0,
+                },
+                VenuePosition {
+                    exchange: Exchange::from("binance"),
+                    instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+                    quantity: 2.0,
+                },
+            ],
+        };
+
+        let events = portfolio.reconcile(&markets, &account, 1e-6).unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events[0],
+            Event::Discrepancy(Discrepancy::Balance { .. })
+        ));
+        assert!(matches!(
+            &events[1],
+            Event::Discrepancy(Discrepancy::PositionNotPersisted(venue_position))
+                if venue_position.instrument == markets[0].instrument
+        ));
+        assert_eq!(portfolio.get_balance(engine_id).unwrap(), persisted_balance);
+    }
+
+    #[test]
+    fn tagged_positions_in_the_same_market_are_held_updated_and_exited_independently() {
+        let engine_id = Uuid::new_v4();
+        let market = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+        let mut portfolio = resumable_portfolio_builder(engine_id, InMemoryRepository::new())
+            .build_and_init()
+            .unwrap();
+
+        // Enter opposing tagged Positions in the same market
+        let mut long_fill = fill_event();
+        long_fill.tag = Some("trend".to_owned());
+        long_fill.decision = Decision::Long;
+        long_fill.quantity = 1.0;
+        let mut short_fill = fill_event();
+        short_fill.tag = Some("reversion".to_owned());
+        short_fill.decision = Decision::Short;
+        short_fill.quantity = -1.0;
+        portfolio.update_from_fill(&long_fill).unwrap();
+        portfolio.update_from_fill(&short_fill).unwrap();
+
+        let open_positions = portfolio
+            .get_open_positions(engine_id, std::iter::once(&market))
+            .unwrap();
+        assert_eq!(open_positions.len(), 2);
+
+        // Every tagged Position in the market is updated by a MarketEvent
+        let mut input_market = market_trade(Side::Buy);
+        input_market.instrument = market.instrument.clone();
+        assert_eq!(
+            portfolio.update_from_market(&input_market).unwrap().len(),
+            2
+        );
+
+        // Tagged SignalForceExit only exits the Position with the same PositionTag
+        let exit_orders = portfolio
+            .generate_exit_orders(SignalForceExit::tagged(
+                market.exchange.clone(),
+                market.instrument.clone(),
+                "trend".to_owned(),
+            ))
+            .unwrap();
+        assert_eq!(exit_orders.len(), 1);
+        assert_eq!(exit_orders[0].tag.as_deref(), Some("trend"));
+        assert_eq!(exit_orders[0].decision, Decision::CloseLong);
+
+        // Untagged SignalForceExit exits every Position in the market
+        let exit_orders = portfolio
+            .generate_exit_orders(SignalForceExit::from(market.clone()))
+            .unwrap();
+        assert_eq!(exit_orders.len(), 2);
+
+        // Exiting the "trend" Position leaves the "reversion" Position open
+        let mut exit_fill = long_fill.clone();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        let events = portfolio.update_from_fill(&exit_fill).unwrap();
+        assert!(matches!(events[0], Event::PositionExit(_)));
+
+        let open_positions = portfolio
+            .get_open_positions(engine_id, std::iter::once(&market))
+            .unwrap();
+        assert_eq!(open_positions.len(), 1);
+        assert_eq!(open_positions[0].tag.as_deref(), Some("reversion"));
+    }
+
+    #[test]
+    fn hedge_mode_holds_and_exits_long_and_short_legs_independently() {
+        let engine_id = Uuid::new_v4();
+        let market = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+        let mut portfolio = resumable_portfolio_builder(engine_id, InMemoryRepository::new())
+            .position_mode(PositionMode::Hedge)
+            .build_and_init()
+            .unwrap();
+
+        // Fill every generated OrderEvent at a price of 100.0
+        let fill_order = |portfolio: &mut MetaPortfolio<_, _, _, _>, order: OrderEvent| {
+            let mut fill = fill_event();
+            fill.decision = order.decision;
+            fill.quantity = order.quantity;
+            fill.fill_value_gross = order.quantity.abs() * 100.0;
+            fill.tag = order.tag;
+            portfolio.update_from_fill(&fill).unwrap()
+        };
+
+        let hedge_signal = |decision: Decision| {
+            let mut input_signal = signal();
+            input_signal.instrument = market.instrument.clone();
+            input_signal.market_meta.close = 100.0;
+            input_signal.signals = HashMap::from([(decision, SignalStrength(1.0))]);
+            input_signal
+        };
+
+        // Enter the Long leg, then the Short leg whilst the Long leg is still open
+        for decision in [Decision::Long, Decision::Short] {
+            let input_signal = hedge_signal(decision);
+            let order = portfolio.generate_order(&input_signal).unwrap().unwrap();
+            assert_eq!(order.decision, decision);
+            fill_order(&mut portfolio, order);
+        }
+
+        let legs = portfolio.open_legs(&market).unwrap();
+        assert_eq!(legs.len(), 2);
+        let long_leg = legs.iter().find(|leg| leg.side == Side::Buy).unwrap();
+        let short_leg = legs.iter().find(|leg| leg.side == Side::Sell).unwrap();
+        assert_eq!(long_leg.tag.as_deref(), Some("long"));
+        assert_eq!(short_leg.tag.as_deref(), Some("short"));
+        assert_eq!(long_leg.margin, 100.0);
+        assert_eq!(short_leg.margin, 100.0);
+
+        // CloseLong is routed to the Long leg, leaving the Short leg open
+        let input_signal = hedge_signal(Decision::CloseLong);
+        let order = portfolio.generate_order(&input_signal).unwrap().unwrap();
+        assert_eq!(order.decision, Decision::CloseLong);
+        assert_eq!(order.tag.as_deref(), Some("long"));
+        let events = fill_order(&mut portfolio, order);
+        assert!(matches!(events[0], Event::PositionExit(_)));
+
+        let legs = portfolio.open_legs(&market).unwrap();
+        assert_eq!(legs.len(), 1);
+        assert_eq!(legs[0].side, Side::Sell);
+    }
 }
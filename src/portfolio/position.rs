@@ -5,9 +5,9 @@ use crate::{
 };
 use barter_data::model::{DataKind, MarketEvent};
 use barter_integration::model::{Exchange, Instrument, Side};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::convert::TryFrom;
+use std::{collections::BTreeMap, convert::TryFrom};
 use uuid::Uuid;
 
 /// Enters a new [`Position`].
@@ -33,6 +33,10 @@ pub trait PositionExiter {
 /// Communicates a String represents a unique [`Position`] identifier.
 pub type PositionId = String;
 
+/// Communicates a String tag (eg/ strategy id or signal id) that distinguishes one of several
+/// [`Position`]s held in the same market.
+pub type PositionTag = String;
+
 /// Returns a unique identifier for a [`Position`] given an engine_id, [`Exchange`] & [`Instrument`].
 pub fn determine_position_id(
     engine_id: Uuid,
@@ -42,6 +46,47 @@ pub fn determine_position_id(
     format!("{}_{}_{}_position", engine_id, exchange, instrument)
 }
 
+/// Returns a unique identifier for a [`Position`] given an engine_id, [`Exchange`], [`Instrument`]
+/// & optional [`PositionTag`]. Untagged [`Position`]s use the [`determine_position_id`]
+/// identifier, so only one untagged [`Position`] can exist per market.
+pub fn determine_tagged_position_id(
+    engine_id: Uuid,
+    exchange: &Exchange,
+    instrument: &Instrument,
+    tag: Option<&PositionTag>,
+) -> PositionId {
+    let position_id = determine_position_id(engine_id, exchange, instrument);
+    match tag {
+        None => position_id,
+        Some(tag) => format!("{}_tag_{}", position_id, tag),
+    }
+}
+
+/// Returns the untagged [`determine_position_id`] identifier of the market the provided (tagged or
+/// untagged) [`PositionId`] belongs to. See [`Position::market_position_id`].
+pub fn determine_market_position_id(position_id: &str) -> &str {
+    match position_id.find("_position_tag_") {
+        Some(index) => &position_id[..index + "_position".len()],
+        None => position_id,
+    }
+}
+
+/// Returns the [`PositionTag`] of the Long (Buy) or Short (Sell) leg of a
+/// [`PositionMode::Hedge`](super::PositionMode::Hedge) [`Position`], given the optional
+/// [`PositionTag`] (eg/ strategy id) the leg is held under. Keying each leg by it's own
+/// [`PositionTag`] means the Long & Short legs are held, exited & attributed independently.
+pub fn determine_hedge_leg_tag(tag: Option<&PositionTag>, side: Side) -> PositionTag {
+    let leg = match side {
+        Side::Buy => "long",
+        Side::Sell => "short",
+    };
+
+    match tag {
+        None => leg.to_owned(),
+        Some(tag) => format!("{}_{}", tag, leg),
+    }
+}
+
 /// Data encapsulating the state of an ongoing or closed [`Position`].
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct Position {
@@ -57,6 +102,11 @@ pub struct Position {
     /// [`Instrument`] associated with this [`Position`].
     pub instrument: Instrument,
 
+    /// Optional [`PositionTag`] distinguishing this [`Position`] from other [`Position`]s in the
+    /// same market (eg/ held by an independent sub-strategy).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<PositionTag>,
+
     /// Buy or Sell.
     ///
     /// Notes:
@@ -102,6 +152,16 @@ pub struct Position {
 
     /// Realised P&L after the [`Position`] has closed.
     pub realised_profit_loss: f64,
+
+    /// Maximum Adverse Excursion (MAE). Largest gross loss (<= 0.0) the [`Position`] experienced
+    /// whilst open, excluding fees.
+    #[serde(default)]
+    pub max_adverse_excursion: f64,
+
+    /// Maximum Favourable Excursion (MFE). Largest gross profit (>= 0.0) the [`Position`]
+    /// experienced whilst open, excluding fees.
+    #[serde(default)]
+    pub max_favourable_excursion: f64,
 }
 
 impl PositionEnterer for Position {
@@ -123,9 +183,15 @@ impl PositionEnterer for Position {
         let unrealised_profit_loss = -enter_fees_total * 2.0;
 
         Ok(Position {
-            position_id: determine_position_id(engine_id, &fill.exchange, &fill.instrument),
+            position_id: determine_tagged_position_id(
+                engine_id,
+                &fill.exchange,
+                &fill.instrument,
+                fill.tag.as_ref(),
+            ),
             exchange: fill.exchange.clone(),
             instrument: fill.instrument.clone(),
+            tag: fill.tag.clone(),
             meta: metadata,
             side: Position::parse_entry_side(fill)?,
             quantity: fill.quantity,
@@ -141,21 +207,27 @@ impl PositionEnterer for Position {
             current_value_gross: fill.fill_value_gross,
             unrealised_profit_loss,
             realised_profit_loss: 0.0,
+            max_adverse_excursion: 0.0,
+            max_favourable_excursion: 0.0,
         })
     }
 }
 
 impl PositionUpdater for Position {
     fn update(&mut self, market: &MarketEvent) -> PositionUpdate {
-        // Determine close from MarketEvent
-        let close = match &market.kind {
-            DataKind::Trade(trade) => trade.price,
-            DataKind::Candle(candle) => candle.close,
+        // Determine close, as well as the low & high prices traded since the last update
+        let (close, low, high) = match &market.kind {
+            DataKind::Trade(trade) => (trade.price, trade.price, trade.price),
+            DataKind::Candle(candle) => (candle.close, candle.low, candle.high),
             DataKind::OrderBook(_) => todo!(),
         };
 
         self.meta.update_time = market.exchange_time;
 
+        // Maximum adverse & favourable excursions
+        self.update_excursions(low);
+        self.update_excursions(high);
+
         self.current_symbol_price = close;
 
         // Market value gross
@@ -187,6 +259,9 @@ impl PositionExiter for Position {
         self.exit_value_gross = fill.fill_value_gross;
         self.exit_avg_price_gross = Position::calculate_avg_price_gross(fill);
 
+        // Maximum adverse & favourable excursions, including the exit price
+        self.update_excursions(self.exit_avg_price_gross);
+
         // Result profit & loss
         self.realised_profit_loss = self.calculate_realised_profit_loss();
         self.unrealised_profit_loss = self.realised_profit_loss;
@@ -206,6 +281,19 @@ impl Position {
         PositionBuilder::new()
     }
 
+    /// Returns the identifier of the untagged [`Position`] in the same market, which is shared by
+    /// every tagged [`Position`] in the market. See [`determine_tagged_position_id`].
+    pub fn market_position_id(&self) -> PositionId {
+        self.tag
+            .as_ref()
+            .and_then(|tag| {
+                self.position_id
+                    .strip_suffix(&format!("_tag_{}", tag))
+                    .map(str::to_owned)
+            })
+            .unwrap_or_else(|| self.position_id.clone())
+    }
+
     /// Calculates the [`Position::enter_avg_price_gross`] or [`Position::exit_avg_price_gross`] of
     /// a [`FillEvent`].
     pub fn calculate_avg_price_gross(fill: &FillEvent) -> f64 {
@@ -252,6 +340,65 @@ impl Position {
         }
     }
 
+    /// Calculate the gross profit or loss (excluding fees) of the [`Position`] at the provided
+    /// symbol price.
+    pub fn calculate_gross_profit_loss(&self, symbol_price: f64) -> f64 {
+        let value_gross = symbol_price * self.quantity.abs();
+
+        match self.side {
+            Side::Buy => value_gross - self.enter_value_gross,
+            Side::Sell => self.enter_value_gross - value_gross,
+        }
+    }
+
+    /// Updates the [`Position::max_adverse_excursion`] & [`Position::max_favourable_excursion`]
+    /// using the gross profit or loss at the provided symbol price.
+    pub fn update_excursions(&mut self, symbol_price: f64) {
+        let excursion = self.calculate_gross_profit_loss(symbol_price);
+        self.max_adverse_excursion = self.max_adverse_excursion.min(excursion);
+        self.max_favourable_excursion = self.max_favourable_excursion.max(excursion);
+    }
+
+    /// Applies a [`PositionUpdate`] to this open [`Position`]. Used to rebuild [`Position`] state
+    /// when replaying an [`Event`](crate::event::Event) journal.
+    pub fn apply_update(&mut self, update: &PositionUpdate) {
+        self.meta.update_time = update.update_time;
+        self.current_symbol_price = update.current_symbol_price;
+        self.current_value_gross = update.current_value_gross;
+        self.unrealised_profit_loss = update.unrealised_profit_loss;
+        self.max_adverse_excursion = update.max_adverse_excursion;
+        self.max_favourable_excursion = update.max_favourable_excursion;
+    }
+
+    /// Applies a [`PositionExit`] to this open [`Position`]. Used to rebuild [`Position`] state
+    /// when replaying an [`Event`](crate::event::Event) journal.
+    pub fn apply_exit(&mut self, exit: &PositionExit) {
+        self.meta.update_time = exit.exit_time;
+        self.meta.exit_balance = Some(exit.exit_balance);
+        self.exit_fees = exit.exit_fees;
+        self.exit_fees_total = exit.exit_fees_total;
+        self.exit_avg_price_gross = exit.exit_avg_price_gross;
+        self.exit_value_gross = exit.exit_value_gross;
+        self.realised_profit_loss = exit.realised_profit_loss;
+        self.unrealised_profit_loss = exit.realised_profit_loss;
+        self.max_adverse_excursion = exit.max_adverse_excursion;
+        self.max_favourable_excursion = exit.max_favourable_excursion;
+    }
+
+    /// Calculate the duration the [`Position`] was held for. Uses the last update time if the
+    /// [`Position`] is still open.
+    pub fn calculate_holding_duration(&self) -> Duration {
+        match self.meta.exit_balance {
+            None => self
+                .meta
+                .update_time
+                .signed_duration_since(self.meta.enter_time),
+            Some(exit_balance) => exit_balance
+                .time
+                .signed_duration_since(self.meta.enter_time),
+        }
+    }
+
     /// Calculate the PnL return of a closed [`Position`] - assumed [`Position::realised_profit_loss`] is
     /// appropriately calculated.
     pub fn calculate_profit_loss_return(&self) -> f64 {
@@ -265,6 +412,7 @@ pub struct PositionBuilder {
     pub position_id: Option<PositionId>,
     pub exchange: Option<Exchange>,
     pub instrument: Option<Instrument>,
+    pub tag: Option<PositionTag>,
     pub meta: Option<PositionMeta>,
     pub side: Option<Side>,
     pub quantity: Option<f64>,
@@ -280,6 +428,8 @@ pub struct PositionBuilder {
     pub current_value_gross: Option<f64>,
     pub unrealised_profit_loss: Option<f64>,
     pub realised_profit_loss: Option<f64>,
+    pub max_adverse_excursion: Option<f64>,
+    pub max_favourable_excursion: Option<f64>,
 }
 
 impl PositionBuilder {
@@ -308,6 +458,13 @@ impl PositionBuilder {
         }
     }
 
+    pub fn tag(self, value: PositionTag) -> Self {
+        Self {
+            tag: Some(value),
+            ..self
+        }
+    }
+
     pub fn meta(self, value: PositionMeta) -> Self {
         Self {
             meta: Some(value),
@@ -413,6 +570,20 @@ impl PositionBuilder {
         }
     }
 
+    pub fn max_adverse_excursion(self, value: f64) -> Self {
+        Self {
+            max_adverse_excursion: Some(value),
+            ..self
+        }
+    }
+
+    pub fn max_favourable_excursion(self, value: f64) -> Self {
+        Self {
+            max_favourable_excursion: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<Position, PortfolioError> {
         Ok(Position {
             position_id: self
@@ -424,6 +595,7 @@ impl PositionBuilder {
             instrument: self
                 .instrument
                 .ok_or(PortfolioError::BuilderIncomplete("instrument"))?,
+            tag: self.tag,
             meta: self.meta.ok_or(PortfolioError::BuilderIncomplete("meta"))?,
             side: self.side.ok_or(PortfolioError::BuilderIncomplete("side"))?,
             quantity: self
@@ -465,6 +637,8 @@ impl PositionBuilder {
             realised_profit_loss: self
                 .realised_profit_loss
                 .ok_or(PortfolioError::BuilderIncomplete("realised_profit_loss"))?,
+            max_adverse_excursion: self.max_adverse_excursion.unwrap_or_default(),
+            max_favourable_excursion: self.max_favourable_excursion.unwrap_or_default(),
         })
     }
 }
@@ -506,6 +680,12 @@ pub struct PositionUpdate {
     pub current_value_gross: f64,
     /// Unrealised P&L whilst the [`Position`] is open.
     pub unrealised_profit_loss: f64,
+    /// Maximum adverse excursion of the [`Position`] since it was entered.
+    #[serde(default)]
+    pub max_adverse_excursion: f64,
+    /// Maximum favourable excursion of the [`Position`] since it was entered.
+    #[serde(default)]
+    pub max_favourable_excursion: f64,
 }
 
 impl From<&mut Position> for PositionUpdate {
@@ -516,6 +696,8 @@ impl From<&mut Position> for PositionUpdate {
             current_symbol_price: updated_position.current_symbol_price,
             current_value_gross: updated_position.current_value_gross,
             unrealised_profit_loss: updated_position.unrealised_profit_loss,
+            max_adverse_excursion: updated_position.max_adverse_excursion,
+            max_favourable_excursion: updated_position.max_favourable_excursion,
         }
     }
 }
@@ -546,6 +728,14 @@ pub struct PositionExit {
 
     /// Realised P&L after the [`Position`] has closed.
     pub realised_profit_loss: f64,
+
+    /// Maximum adverse excursion of the [`Position`], including the exit price.
+    #[serde(default)]
+    pub max_adverse_excursion: f64,
+
+    /// Maximum favourable excursion of the [`Position`], including the exit price.
+    #[serde(default)]
+    pub max_favourable_excursion: f64,
 }
 
 impl TryFrom<&mut Position> for PositionExit {
@@ -564,15 +754,90 @@ impl TryFrom<&mut Position> for PositionExit {
             exit_avg_price_gross: exited_position.exit_avg_price_gross,
             exit_value_gross: exited_position.exit_value_gross,
             realised_profit_loss: exited_position.realised_profit_loss,
+            max_adverse_excursion: exited_position.max_adverse_excursion,
+            max_favourable_excursion: exited_position.max_favourable_excursion,
         })
     }
 }
 
+/// Net exposure of every (tagged or untagged) open [`Position`] held in a market. Independent
+/// sub-strategies may hold opposing [`Position`]s in the same market, whereas the execution venue
+/// reports a single position per market, so tagged [`Position`]s are netted when reconciling.
+/// Likewise, the per-tag [`OrderEvent`](super::OrderEvent)s of a market are netted before
+/// execution (see [`NettedOrders`](crate::execution::netting::NettedOrders)).
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct NetPosition {
+    /// Identifier of the untagged [`Position`] in the market. See [`determine_position_id`].
+    pub position_id: PositionId,
+    /// [`Exchange`] associated with this [`NetPosition`].
+    pub exchange: Exchange,
+    /// [`Instrument`] associated with this [`NetPosition`].
+    pub instrument: Instrument,
+    /// Signed sum of every open [`Position`] quantity in the market (+ve Long, -ve Short).
+    pub quantity: f64,
+}
+
+/// Margin & PnL of an open [`Position`] leg (eg/ the Long or Short leg of a
+/// [`PositionMode::Hedge`](super::PositionMode::Hedge) market). The realised PnL of each exited
+/// leg is attributed to it's [`PositionTag`] in the
+/// [`SessionSummary`](crate::statistic::summary::session::SessionSummary).
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct PositionLeg {
+    /// Unique identifier of the leg's [`Position`].
+    pub position_id: PositionId,
+    /// Optional [`PositionTag`] of the leg's [`Position`].
+    pub tag: Option<PositionTag>,
+    /// Buy (Long) or Sell (Short) leg.
+    pub side: Side,
+    /// +ve or -ve quantity of symbol contracts held.
+    pub quantity: f64,
+    /// Cash committed to the leg on entry: enter_value_gross + enter_fees_total.
+    pub margin: f64,
+    /// Unrealised P&L whilst the leg is open.
+    pub unrealised_profit_loss: f64,
+}
+
+impl From<&Position> for PositionLeg {
+    fn from(position: &Position) -> Self {
+        Self {
+            position_id: position.position_id.clone(),
+            tag: position.tag.clone(),
+            side: position.side,
+            quantity: position.quantity,
+            margin: position.enter_value_gross + position.enter_fees_total,
+            unrealised_profit_loss: position.unrealised_profit_loss,
+        }
+    }
+}
+
+/// Nets the provided open [`Position`]s by market, returning one [`NetPosition`] per market.
+pub fn net_positions(positions: &[Position]) -> Vec<NetPosition> {
+    positions
+        .iter()
+        .fold(
+            BTreeMap::<PositionId, NetPosition>::new(),
+            |mut net_positions, position| {
+                net_positions
+                    .entry(position.market_position_id())
+                    .or_insert_with(|| NetPosition {
+                        position_id: position.market_position_id(),
+                        exchange: position.exchange.clone(),
+                        instrument: position.instrument.clone(),
+                        quantity: 0.0,
+                    })
+                    .quantity += position.quantity;
+                net_positions
+            },
+        )
+        .into_values()
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test_util::{fill_event, position};
-    use barter_data::test_util::market_trade;
+    use barter_data::test_util::{market_candle, market_trade};
     use barter_integration::model::Side;
 
     #[test]
@@ -1553,6 +1818,168 @@ mod tests {
         }
     }
 
+    #[test]
+    fn update_long_position_with_candle_tracks_excursions() {
+        // Initial Position
+        let mut position = position();
+        position.side = Side::Buy;
+        position.quantity = 2.0;
+        position.enter_avg_price_gross = 1000.0;
+        position.enter_value_gross = 2000.0;
+
+        // Input MarketEvent Candle with low 950.0 & high 1100.0
+        let input_market = market_candle(chrono::Duration::hours(1));
+
+        position.update(&input_market);
+
+        assert_eq!(position.max_adverse_excursion, (950.0 - 1000.0) * 2.0);
+        assert_eq!(position.max_favourable_excursion, (1100.0 - 1000.0) * 2.0);
+    }
+
+    #[test]
+    fn update_short_position_with_candle_tracks_excursions() {
+        // Initial Position
+        let mut position = position();
+        position.side = Side::Sell;
+        position.quantity = -2.0;
+        position.enter_avg_price_gross = 1000.0;
+        position.enter_value_gross = 2000.0;
+
+        // Input MarketEvent Candle with low 950.0 & high 1100.0
+        let input_market = market_candle(chrono::Duration::hours(1));
+
+        position.update(&input_market);
+
+        assert_eq!(position.max_adverse_excursion, (1000.0 - 1100.0) * 2.0);
+        assert_eq!(position.max_favourable_excursion, (1000.0 - 950.0) * 2.0);
+    }
+
+    #[test]
+    fn update_position_excursions_keep_most_extreme_values() {
+        let mut position = position();
+        position.side = Side::Buy;
+        position.quantity = 1.0;
+        position.enter_value_gross = 100.0;
+
+        struct TestCase {
+            input_symbol_price: f64,
+            expected_mae: f64,
+            expected_mfe: f64,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                // Test case 0: Price rises from entry
+                input_symbol_price: 110.0,
+                expected_mae: 0.0,
+                expected_mfe: 10.0,
+            },
+            TestCase {
+                // Test case 1: Price falls below entry
+                input_symbol_price: 80.0,
+                expected_mae: -20.0,
+                expected_mfe: 10.0,
+            },
+            TestCase {
+                // Test case 2: Price recovers but within the previous excursions
+                input_symbol_price: 105.0,
+                expected_mae: -20.0,
+                expected_mfe: 10.0,
+            },
+            TestCase {
+                // Test case 3: Price rises to new high
+                input_symbol_price: 130.0,
+                expected_mae: -20.0,
+                expected_mfe: 30.0,
+            },
+        ];
+
+        for (index, test) in test_cases.into_iter().enumerate() {
+            position.update_excursions(test.input_symbol_price);
+            assert_eq!(
+                position.max_adverse_excursion, test.expected_mae,
+                "MAE Input: {:?}",
+                index
+            );
+            assert_eq!(
+                position.max_favourable_excursion, test.expected_mfe,
+                "MFE Input: {:?}",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn exit_long_position_includes_exit_price_in_excursions() {
+        // Initial Position
+        let mut position = position();
+        position.side = Side::Buy;
+        position.quantity = 1.0;
+        position.enter_avg_price_gross = 100.0;
+        position.enter_value_gross = 100.0;
+        position.max_adverse_excursion = -5.0;
+        position.max_favourable_excursion = 10.0;
+
+        // Input FillEvent exiting above all previous prices
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::CloseLong;
+        input_fill.quantity = -1.0;
+        input_fill.fill_value_gross = 150.0;
+
+        position.exit(Balance::default(), &input_fill).unwrap();
+
+        assert_eq!(position.max_adverse_excursion, -5.0);
+        assert_eq!(position.max_favourable_excursion, 50.0);
+    }
+
+    #[test]
+    fn calculate_holding_duration_of_open_and_exited_positions() {
+        let base_time = Utc::now();
+
+        let mut position = position();
+        position.meta.enter_time = base_time;
+        position.meta.update_time = base_time + Duration::hours(2);
+        position.meta.exit_balance = None;
+        assert_eq!(position.calculate_holding_duration(), Duration::hours(2));
+
+        position.meta.exit_balance = Some(Balance {
+            time: base_time + Duration::hours(5),
+            total: 0.0,
+            available: 0.0,
+        });
+        assert_eq!(position.calculate_holding_duration(), Duration::hours(5));
+    }
+
+    #[test]
+    fn position_builder_defaults_excursions_to_zero() {
+        let expected = position();
+
+        let actual = Position::builder()
+            .position_id(expected.position_id.clone())
+            .exchange(expected.exchange.clone())
+            .instrument(expected.instrument.clone())
+            .meta(expected.meta)
+            .side(expected.side)
+            .quantity(expected.quantity)
+            .enter_fees(expected.enter_fees)
+            .enter_fees_total(expected.enter_fees_total)
+            .enter_avg_price_gross(expected.enter_avg_price_gross)
+            .enter_value_gross(expected.enter_value_gross)
+            .exit_fees(expected.exit_fees)
+            .exit_fees_total(expected.exit_fees_total)
+            .exit_avg_price_gross(expected.exit_avg_price_gross)
+            .exit_value_gross(expected.exit_value_gross)
+            .current_symbol_price(expected.current_symbol_price)
+            .current_value_gross(expected.current_value_gross)
+            .unrealised_profit_loss(expected.unrealised_profit_loss)
+            .realised_profit_loss(expected.realised_profit_loss)
+            .build()
+            .unwrap();
+
+        assert_eq!(actual.max_adverse_excursion, 0.0);
+        assert_eq!(actual.max_favourable_excursion, 0.0);
+    }
+
     #[test]
     fn position_determine_exit_decision() {
         // Side::Buy -> Decision::CloseLong
@@ -1639,4 +2066,71 @@ mod tests {
 
         assert!(PositionExit::try_from(&mut exited_position).is_err());
     }
+
+    #[test]
+    fn apply_position_update_and_exit_events_rebuilds_position() {
+        let mut position = position();
+        let mut rebuilt = position.clone();
+
+        // Update original Position & apply the PositionUpdate to the rebuilt Position
+        let update = position.update(&market_candle(chrono::Duration::hours(1)));
+        rebuilt.apply_update(&update);
+        assert_eq!(rebuilt, position);
+
+        // Exit original Position & apply the PositionExit to the rebuilt Position
+        let mut exit_fill = fill_event();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        exit_fill.fill_value_gross = 110.0;
+        let exit = position.exit(Balance::default(), &exit_fill).unwrap();
+        rebuilt.apply_exit(&exit);
+        assert_eq!(rebuilt, position);
+    }
+
+    #[test]
+    fn enter_tagged_position_derives_tagged_position_id() {
+        let engine_id = Uuid::new_v4();
+        let mut input_fill = fill_event();
+        input_fill.tag = Some("momentum".to_owned());
+
+        let position = Position::enter(engine_id, &input_fill).unwrap();
+        let untagged_id =
+            determine_position_id(engine_id, &input_fill.exchange, &input_fill.instrument);
+
+        assert_eq!(position.tag, input_fill.tag);
+        assert_eq!(
+            position.position_id,
+            format!("{}_tag_momentum", untagged_id)
+        );
+        assert_eq!(position.market_position_id(), untagged_id);
+        assert_eq!(
+            determine_market_position_id(&position.position_id),
+            untagged_id
+        );
+        assert_eq!(determine_market_position_id(&untagged_id), untagged_id);
+    }
+
+    #[test]
+    fn net_positions_sums_quantities_of_positions_in_the_same_market() {
+        let engine_id = Uuid::new_v4();
+        let mut long = fill_event();
+        long.tag = Some("trend".to_owned());
+        long.quantity = 2.0;
+        let mut short = fill_event();
+        short.tag = Some("reversion".to_owned());
+        short.decision = Decision::Short;
+        short.quantity = -0.5;
+        let untagged = fill_event();
+
+        let positions = [long, short, untagged]
+            .iter()
+            .map(|fill| Position::enter(engine_id, fill).unwrap())
+            .collect::<Vec<_>>();
+
+        let net = net_positions(&positions);
+
+        assert_eq!(net.len(), 1);
+        assert_eq!(net[0].position_id, positions[2].position_id);
+        assert_eq!(net[0].quantity, 2.0 - 0.5 + positions[2].quantity);
+    }
 }
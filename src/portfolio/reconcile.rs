@@ -0,0 +1,269 @@
+use crate::{
+    execution::{AccountSnapshot, VenuePosition},
+    portfolio::{
+        position::{net_positions, Position, PositionId},
+        Balance,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// Difference between the persisted Portfolio state & the account state reported by the
+/// execution venue. Discrepancies are reported rather than resolved, leaving the persisted
+/// state untouched.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum Discrepancy {
+    /// Persisted [`Balance`] differs from the [`Balance`] reported by the execution venue.
+    Balance { persisted: Balance, venue: Balance },
+    /// Persisted open [`Position`] is not held at the execution venue.
+    PositionNotAtVenue {
+        position_id: PositionId,
+        quantity: f64,
+    },
+    /// [`VenuePosition`] held at the execution venue has no persisted open [`Position`].
+    PositionNotPersisted(VenuePosition),
+    /// Persisted open [`Position`] quantity differs from the [`VenuePosition`] quantity.
+    PositionQuantity {
+        position_id: PositionId,
+        persisted: f64,
+        venue: f64,
+    },
+}
+
+/// Reconcile the persisted Portfolio [`Balance`] & open [`Position`]s against the
+/// [`AccountSnapshot`] reported by the execution venue. Values within the provided absolute
+/// tolerance are considered equal.
+///
+/// The (tagged) open [`Position`]s are netted per market before being compared. Venues running
+/// in hedge mode report a separate [`VenuePosition`] for the long & short side of a market, so
+/// every [`VenuePosition`] of a market is likewise summed before being compared. Position
+/// discrepancies are therefore reported using the market's untagged [`PositionId`].
+pub fn reconcile(
+    balance: &Balance,
+    open_positions: &[Position],
+    account: &AccountSnapshot,
+    tolerance: f64,
+) -> Vec<Discrepancy> {
+    let mut discrepancies = Vec::new();
+
+    if (balance.total - account.balance.total).abs() > tolerance
+        || (balance.available - account.balance.available).abs() > tolerance
+    {
+        discrepancies.push(Discrepancy::Balance {
+            persisted: *balance,
+            venue: account.balance,
+        });
+    }
+
+    for position in net_positions(open_positions) {
+        // Sum the (eg/ hedge mode long & short) VenuePositions held in the Position's market
+        let venue_quantity = account
+            .positions
+            .iter()
+            .filter(|venue_position| {
+                venue_position.exchange == position.exchange
+                    && venue_position.instrument == position.instrument
+            })
+            .map(|venue_position| venue_position.quantity)
+            .reduce(|net, quantity| net + quantity);
+
+        match venue_quantity {
+            // Opposing tagged Positions that net to zero are not held at the venue
+            None if position.quantity.abs() <= tolerance => {}
+            None => discrepancies.push(Discrepancy::PositionNotAtVenue {
+                position_id: position.position_id,
+                quantity: position.quantity,
+            }),
+            Some(venue_quantity) if (venue_quantity - position.quantity).abs() > tolerance => {
+                discrepancies.push(Discrepancy::PositionQuantity {
+                    position_id: position.position_id,
+                    persisted: position.quantity,
+                    venue: venue_quantity,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    discrepancies.extend(
+        account
+            .positions
+            .iter()
+            .filter(|venue_position| {
+                !open_positions.iter().any(|position| {
+                    position.exchange == venue_position.exchange
+                        && position.instrument == venue_position.instrument
+                })
+            })
+            .cloned()
+            .map(Discrepancy::PositionNotPersisted),
+    );
+
+    discrepancies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::position;
+    use chrono::{DateTime, Utc};
+
+    fn balance(total: f64, available: f64) -> Balance {
+        Balance {
+            time: DateTime::<Utc>::MIN_UTC,
+            total,
+            available,
+        }
+    }
+
+    fn venue_position(position: &Position, quantity: f64) -> VenuePosition {
+        VenuePosition {
+            exchange: position.exchange.clone(),
+            instrument: position.instrument.clone(),
+            quantity,
+        }
+    }
+
+    fn tagged(position: &Position, tag: &str, quantity: f64) -> Position {
+        Position {
+            position_id: format!("{}_tag_{}", position.position_id, tag),
+            tag: Some(tag.to_owned()),
+            quantity,
+            ..position.clone()
+        }
+    }
+
+    #[test]
+    fn reconcile_persisted_state_against_venue_account() {
+        struct TestCase {
+            balance: Balance,
+            open_positions: Vec<Position>,
+            account: AccountSnapshot,
+            expected: Vec<Discrepancy>,
+        }
+
+        let position = position();
+
+        let test_cases = vec![
+            TestCase {
+                // TC0: Persisted state matches venue within tolerance
+                balance: balance(1000.0, 900.0),
+                open_positions: vec![position.clone()],
+                account: AccountSnapshot {
+                    balance: balance(1000.0 + 1e-9, 900.0),
+                    positions: vec![venue_position(&position, position.quantity)],
+                },
+                expected: vec![],
+            },
+            TestCase {
+                // TC1: Persisted Balance differs from venue Balance
+                balance: balance(1000.0, 900.0),
+                open_positions: vec![],
+                account: AccountSnapshot {
+                    balance: balance(1000.0, 800.0),
+                    positions: vec![],
+                },
+                expected: vec![Discrepancy::Balance {
+                    persisted: balance(1000.0, 900.0),
+                    venue: balance(1000.0, 800.0),
+                }],
+            },
+            TestCase {
+                // TC2: Persisted open Position not held at venue
+                balance: balance(1000.0, 900.0),
+                open_positions: vec![position.clone()],
+                account: AccountSnapshot {
+                    balance: balance(1000.0, 900.0),
+                    positions: vec![],
+                },
+                expected: vec![Discrepancy::PositionNotAtVenue {
+                    position_id: position.position_id.clone(),
+                    quantity: position.quantity,
+                }],
+            },
+            TestCase {
+                // TC3: Venue position has no persisted open Position
+                balance: balance(1000.0, 900.0),
+                open_positions: vec![],
+                account: AccountSnapshot {
+                    balance: balance(1000.0, 900.0),
+                    positions: vec![venue_position(&position, 2.0)],
+                },
+                expected: vec![Discrepancy::PositionNotPersisted(venue_position(
+                    &position, 2.0,
+                ))],
+            },
+            TestCase {
+                // TC4: Persisted open Position quantity differs from venue quantity
+                balance: balance(1000.0, 900.0),
+                open_positions: vec![position.clone()],
+                account: AccountSnapshot {
+                    balance: balance(1000.0, 900.0),
+                    positions: vec![venue_position(&position, -1.0)],
+                },
+                expected: vec![Discrepancy::PositionQuantity {
+                    position_id: position.position_id.clone(),
+                    persisted: position.quantity,
+                    venue: -1.0,
+                }],
+            },
+            TestCase {
+                // TC5: Tagged open Positions are netted before comparing to the venue quantity
+                balance: balance(1000.0, 900.0),
+                open_positions: vec![position.clone(), tagged(&position, "hedge", -0.25)],
+                account: AccountSnapshot {
+                    balance: balance(1000.0, 900.0),
+                    positions: vec![venue_position(&position, position.quantity - 0.25)],
+                },
+                expected: vec![],
+            },
+            TestCase {
+                // TC6: Tagged open Positions netting to zero are not expected at the venue
+                balance: balance(1000.0, 900.0),
+                open_positions: vec![position.clone(), tagged(&position, "hedge", -1.0)],
+                account: AccountSnapshot {
+                    balance: balance(1000.0, 900.0),
+                    positions: vec![],
+                },
+                expected: vec![],
+            },
+            TestCase {
+                // TC7: Hedge mode long & short VenuePositions are summed before comparing
+                balance: balance(1000.0, 900.0),
+                open_positions: vec![
+                    tagged(&position, "long", 1.0),
+                    tagged(&position, "short", -0.4),
+                ],
+                account: AccountSnapshot {
+                    balance: balance(1000.0, 900.0),
+                    positions: vec![
+                        venue_position(&position, 1.0),
+                        venue_position(&position, -0.4),
+                    ],
+                },
+                expected: vec![],
+            },
+            TestCase {
+                // TC8: Hedge mode VenuePositions summing to a different quantity
+                balance: balance(1000.0, 900.0),
+                open_positions: vec![tagged(&position, "long", 1.0)],
+                account: AccountSnapshot {
+                    balance: balance(1000.0, 900.0),
+                    positions: vec![
+                        venue_position(&position, 1.0),
+                        venue_position(&position, -0.4),
+                    ],
+                },
+                expected: vec![Discrepancy::PositionQuantity {
+                    position_id: position.market_position_id(),
+                    persisted: 1.0,
+                    venue: 0.6,
+                }],
+            },
+        ];
+
+        for (index, test) in test_cases.into_iter().enumerate() {
+            let actual = reconcile(&test.balance, &test.open_positions, &test.account, 1e-6);
+            assert_eq!(actual, test.expected, "Test case {} failed", index);
+        }
+    }
+}
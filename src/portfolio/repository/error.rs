@@ -17,4 +17,16 @@ pub enum RepositoryError {
 
     #[error("Failed to retrieve expected data due to it not being present")]
     ExpectedDataNotPresentError,
+
+    #[error("Failed to migrate the repository schema to version {0}")]
+    MigrationError(usize),
+
+    #[error("Failed to begin a repository transaction since one is already active")]
+    TransactionAlreadyActive,
+
+    #[error("Failed to connect to the repository: {0}")]
+    ConnectionError(String),
+
+    #[error("Failed to access the repository files due to: {0}")]
+    IoError(#[from] std::io::Error),
 }
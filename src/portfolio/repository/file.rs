@@ -0,0 +1,712 @@
+use crate::{
+    portfolio::{
+        position::{determine_position_id, Position, PositionId},
+        repository::{
+            error::RepositoryError, BalanceHandler, PositionHandler, StatisticHandler,
+            TransactionHandler,
+        },
+        Balance,
+    },
+    statistic::summary::PositionSummariser,
+};
+use barter_integration::model::{Market, MarketId};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Name of the append-only log file in the repository directory.
+const LOG_FILE: &str = "repository.log";
+
+/// Name of the file a compacted log is written to before atomically replacing the log file.
+const COMPACTION_FILE: &str = "repository.log.compact";
+
+/// Configuration for constructing a [`FileRepository`] via the open() constructor method.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// Directory the repository files are stored in, created if it does not exist.
+    pub directory: PathBuf,
+    /// Policy determining how often the log file is synchronised with the storage device.
+    pub sync_policy: SyncPolicy,
+    /// Number of records appended to the log before it is compacted. Zero disables compaction.
+    pub compaction_threshold: usize,
+}
+
+/// Policy determining how often the [`FileRepository`] log is synchronised with the storage
+/// device (ie/ fsync). Every record is flushed to the OS before a write returns, so no policy
+/// loses records on a process crash - the policy only determines what survives power loss.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub enum SyncPolicy {
+    /// Synchronise after every record.
+    Always,
+    /// Synchronise after every n records.
+    EveryRecords(usize),
+    /// Never explicitly synchronise, leaving it to the OS.
+    Never,
+}
+
+/// Record in the [`FileRepository`] append-only log, describing a single state change.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+enum Record<Statistic> {
+    OpenPosition(Position),
+    RemovePosition(PositionId),
+    ExitedPosition {
+        engine_id: Uuid,
+        position: Position,
+    },
+    Balance {
+        engine_id: Uuid,
+        balance: Balance,
+    },
+    Statistics {
+        market_id: String,
+        statistic: Statistic,
+    },
+    /// Every [`Record`] of a committed transaction, written as a single line so a torn
+    /// transaction is discarded as a whole.
+    Transaction(Vec<Record<Statistic>>),
+}
+
+/// Current repository state, rebuilt on open by applying every [`Record`] in the log.
+#[derive(Debug)]
+struct State<Statistic> {
+    open_positions: HashMap<PositionId, Position>,
+    exited_positions: HashMap<Uuid, Vec<Position>>,
+    balances: HashMap<Uuid, Balance>,
+    statistics: HashMap<String, Statistic>,
+}
+
+impl<Statistic> State<Statistic> {
+    fn new() -> Self {
+        Self {
+            open_positions: HashMap::new(),
+            exited_positions: HashMap::new(),
+            balances: HashMap::new(),
+            statistics: HashMap::new(),
+        }
+    }
+
+    fn apply(&mut self, record: Record<Statistic>) {
+        match record {
+            Record::OpenPosition(position) => {
+                self.open_positions
+                    .insert(position.position_id.clone(), position);
+            }
+            Record::RemovePosition(position_id) => {
+                self.open_positions.remove(&position_id);
+            }
+            Record::ExitedPosition {
+                engine_id,
+                position,
+            } => {
+                self.exited_positions
+                    .entry(engine_id)
+                    .or_default()
+                    .push(position);
+            }
+            Record::Balance { engine_id, balance } => {
+                self.balances.insert(engine_id, balance);
+            }
+            Record::Statistics {
+                market_id,
+                statistic,
+            } => {
+                self.statistics.insert(market_id, statistic);
+            }
+            Record::Transaction(records) => {
+                records.into_iter().for_each(|record| self.apply(record));
+            }
+        }
+    }
+}
+
+/// File persisted repository that implements [`PositionHandler`], [`BalanceHandler`],
+/// [`StatisticHandler`] & [`TransactionHandler`]. Provides durable Portfolio state for single-host deployments without
+/// any external services.
+///
+/// Every state change is appended to a newline delimited JSON log in the configured directory,
+/// which is replayed when the repository is opened. A torn final record (eg/ from a crash
+/// mid-write) is discarded on open. The log is periodically compacted into the minimal set of
+/// records describing the current state, which atomically replaces the log.
+#[derive(Debug)]
+pub struct FileRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    cfg: Config,
+    writer: BufWriter<File>,
+    state: State<Statistic>,
+    records_since_compaction: usize,
+    records_since_sync: usize,
+    /// [`Record`]s buffered by the active transaction, appended as one [`Record`] on commit.
+    transaction: Option<Vec<Record<Statistic>>>,
+}
+
+impl<Statistic> PositionHandler for FileRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_open_position(&mut self, _: Uuid, position: Position) -> Result<(), RepositoryError> {
+        self.append(Record::OpenPosition(position))
+    }
+
+    fn get_open_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        Ok(self.state.open_positions.get(position_id).cloned())
+    }
+
+    fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+        &mut self,
+        engine_id: Uuid,
+        markets: Markets,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        // Include every tagged Position held in each market alongside the untagged Position
+        let open_positions = &self.state.open_positions;
+        Ok(markets
+            .flat_map(|market| {
+                let position_id =
+                    determine_position_id(engine_id, &market.exchange, &market.instrument);
+                open_positions
+                    .values()
+                    .filter(move |position| position.market_position_id() == position_id)
+                    .cloned()
+            })
+            .collect())
+    }
+
+    fn remove_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        let position = self.state.open_positions.get(position_id).cloned();
+
+        if position.is_some() {
+            self.append(Record::RemovePosition(position_id.clone()))?;
+        }
+
+        Ok(position)
+    }
+
+    fn set_exited_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.append(Record::ExitedPosition {
+            engine_id,
+            position,
+        })
+    }
+
+    fn get_exited_positions(&mut self, engine_id: Uuid) -> Result<Vec<Position>, RepositoryError> {
+        Ok(self
+            .state
+            .exited_positions
+            .get(&engine_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+impl<Statistic> BalanceHandler for FileRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
+        self.append(Record::Balance { engine_id, balance })
+    }
+
+    fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+        self.state
+            .balances
+            .get(&engine_id)
+            .copied()
+            .ok_or(RepositoryError::ExpectedDataNotPresentError)
+    }
+}
+
+impl<Statistic> StatisticHandler<Statistic> for FileRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_statistics(
+        &mut self,
+        market_id: MarketId,
+        statistic: Statistic,
+    ) -> Result<(), RepositoryError> {
+        self.append(Record::Statistics {
+            market_id: market_id.0,
+            statistic,
+        })
+    }
+
+    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+        self.state
+            .statistics
+            .get(&market_id.0)
+            .copied()
+            .ok_or(RepositoryError::ExpectedDataNotPresentError)
+    }
+}
+
+impl<Statistic> TransactionHandler for FileRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    /// Begin buffering every subsequent write. Reads during the transaction do not observe the
+    /// buffered writes.
+    fn begin_transaction(&mut self) -> Result<(), RepositoryError> {
+        match self.transaction {
+            Some(_) => Err(RepositoryError::TransactionAlreadyActive),
+            None => {
+                self.transaction = Some(Vec::new());
+                Ok(())
+            }
+        }
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), RepositoryError> {
+        match self.transaction.take() {
+            Some(records) if !records.is_empty() => self.append(Record::Transaction(records)),
+            _ => Ok(()),
+        }
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), RepositoryError> {
+        self.transaction = None;
+        Ok(())
+    }
+}
+
+impl<Statistic> FileRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    /// Open the [`FileRepository`] in the configured directory, rebuilding the state by replaying
+    /// the log. A torn final record is discarded, whereas a corrupt record elsewhere is an error.
+    pub fn open(cfg: Config) -> Result<Self, RepositoryError> {
+        fs::create_dir_all(&cfg.directory)?;
+
+        // Compaction file left by a crash mid-compaction never replaced the log, so discard it
+        let compaction_path = cfg.directory.join(COMPACTION_FILE);
+        if compaction_path.exists() {
+            warn!(
+                path = %compaction_path.display(),
+                "discarding incomplete FileRepository log compaction"
+            );
+            fs::remove_file(compaction_path)?;
+        }
+
+        let log_path = cfg.directory.join(LOG_FILE);
+        let (records, valid_len) = read_records::<Statistic>(&log_path)?;
+
+        let records_since_compaction = records.len();
+        let mut state = State::new();
+        records.into_iter().for_each(|record| state.apply(record));
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)?;
+
+        // Discard any torn final record so new records start on a fresh line
+        file.set_len(valid_len)?;
+
+        Ok(Self {
+            cfg,
+            writer: BufWriter::new(file),
+            state,
+            records_since_compaction,
+            records_since_sync: 0,
+            transaction: None,
+        })
+    }
+
+    /// Synchronise the log file with the underlying storage device.
+    pub fn sync(&mut self) -> Result<(), RepositoryError> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()?;
+        self.records_since_sync = 0;
+        Ok(())
+    }
+
+    /// Compact the log into the minimal set of records describing the current state. The
+    /// compacted log is written & synchronised before atomically replacing the log.
+    pub fn compact(&mut self) -> Result<(), RepositoryError> {
+        let compaction_path = self.cfg.directory.join(COMPACTION_FILE);
+        let log_path = self.cfg.directory.join(LOG_FILE);
+
+        let mut writer = BufWriter::new(File::create(&compaction_path)?);
+        let mut records = 0;
+        for record in self.compacted_records() {
+            write_record(&mut writer, &record)?;
+            records += 1;
+        }
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        drop(writer);
+
+        fs::rename(&compaction_path, &log_path)?;
+        sync_directory(&self.cfg.directory)?;
+
+        self.writer = BufWriter::new(OpenOptions::new().append(true).open(&log_path)?);
+        self.records_since_compaction = 0;
+        self.records_since_sync = 0;
+
+        debug!(
+            path = %log_path.display(),
+            records,
+            "compacted FileRepository log"
+        );
+
+        Ok(())
+    }
+
+    /// Append a [`Record`] to the log & apply it to the state, synchronising & compacting the log
+    /// as configured. If a transaction is active the [`Record`] is buffered instead.
+    fn append(&mut self, record: Record<Statistic>) -> Result<(), RepositoryError> {
+        if let Some(transaction) = &mut self.transaction {
+            transaction.push(record);
+            return Ok(());
+        }
+
+        write_record(&mut self.writer, &record)?;
+        self.writer.flush()?;
+        self.state.apply(record);
+
+        self.records_since_sync += 1;
+        let sync = match self.cfg.sync_policy {
+            SyncPolicy::Always => true,
+            SyncPolicy::EveryRecords(records) => self.records_since_sync >= records,
+            SyncPolicy::Never => false,
+        };
+        if sync {
+            self.sync()?;
+        }
+
+        self.records_since_compaction += 1;
+        if self.cfg.compaction_threshold > 0
+            && self.records_since_compaction >= self.cfg.compaction_threshold
+        {
+            self.compact()?;
+        }
+
+        Ok(())
+    }
+
+    /// Generate the minimal set of [`Record`]s that rebuild the current state.
+    fn compacted_records(&self) -> impl Iterator<Item = Record<Statistic>> + '_ {
+        let balances = self
+            .state
+            .balances
+            .iter()
+            .map(|(engine_id, balance)| Record::Balance {
+                engine_id: *engine_id,
+                balance: *balance,
+            });
+
+        let statistics =
+            self.state
+                .statistics
+                .iter()
+                .map(|(market_id, statistic)| Record::Statistics {
+                    market_id: market_id.clone(),
+                    statistic: *statistic,
+                });
+
+        let open_positions = self
+            .state
+            .open_positions
+            .values()
+            .cloned()
+            .map(Record::OpenPosition);
+
+        let exited_positions =
+            self.state
+                .exited_positions
+                .iter()
+                .flat_map(|(engine_id, positions)| {
+                    positions.iter().map(|position| Record::ExitedPosition {
+                        engine_id: *engine_id,
+                        position: position.clone(),
+                    })
+                });
+
+        balances
+            .chain(statistics)
+            .chain(open_positions)
+            .chain(exited_positions)
+    }
+}
+
+/// Write a [`Record`] to the writer as a newline terminated JSON line.
+fn write_record<Statistic, W>(
+    writer: &mut W,
+    record: &Record<Statistic>,
+) -> Result<(), RepositoryError>
+where
+    Statistic: Serialize,
+    W: Write,
+{
+    serde_json::to_writer(&mut *writer, record)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Read every complete [`Record`] in the log, along with the length in bytes of the log
+/// containing them. A torn final record is discarded, whereas a corrupt record elsewhere is an
+/// error.
+fn read_records<Statistic>(path: &Path) -> Result<(Vec<Record<Statistic>>, u64), RepositoryError>
+where
+    Statistic: DeserializeOwned,
+{
+    if !path.exists() {
+        return Ok((Vec::new(), 0));
+    }
+
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut records = Vec::new();
+    let mut valid_len = 0;
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        let bytes = reader.read_until(b'\n', &mut line)?;
+        if bytes == 0 {
+            break;
+        }
+
+        // Records are only durable once their terminating newline has been written
+        if !line.ends_with(b"\n") {
+            warn!(
+                path = %path.display(),
+                "discarding torn final FileRepository record"
+            );
+            break;
+        }
+
+        records.push(serde_json::from_slice::<Record<Statistic>>(&line)?);
+        valid_len += bytes as u64;
+    }
+
+    Ok((records, valid_len))
+}
+
+/// Synchronise a directory with the underlying storage device, persisting renamed entries.
+fn sync_directory(directory: &Path) -> Result<(), RepositoryError> {
+    #[cfg(unix)]
+    File::open(directory)?.sync_all()?;
+
+    #[cfg(not(unix))]
+    let _ = directory;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        statistic::summary::{pnl::PnLReturnSummary, Initialiser},
+        test_util::position,
+    };
+    use barter_integration::model::InstrumentKind;
+    use chrono::{DateTime, Utc};
+
+    fn config(compaction_threshold: usize) -> Config {
+        Config {
+            directory: std::env::temp_dir()
+                .join(format!("barter_file_repository_{}", Uuid::new_v4())),
+            sync_policy: SyncPolicy::Always,
+            compaction_threshold,
+        }
+    }
+
+    fn engine_position(engine_id: Uuid) -> Position {
+        let mut position = position();
+        position.position_id =
+            determine_position_id(engine_id, &position.exchange, &position.instrument);
+        position.meta.enter_time = DateTime::<Utc>::MIN_UTC;
+        position.meta.update_time = DateTime::<Utc>::MIN_UTC;
+        position
+    }
+
+    fn balance(total: f64) -> Balance {
+        Balance {
+            time: DateTime::<Utc>::MIN_UTC,
+            total,
+            available: total,
+        }
+    }
+
+    fn populate(repository: &mut FileRepository<PnLReturnSummary>, engine_id: Uuid) {
+        let position = engine_position(engine_id);
+        let market_id = MarketId::new(&position.exchange, &position.instrument);
+
+        repository.set_balance(engine_id, balance(1000.0)).unwrap();
+        repository
+            .set_statistics(market_id, PnLReturnSummary::init(()))
+            .unwrap();
+        repository
+            .set_open_position(engine_id, position.clone())
+            .unwrap();
+        repository.remove_position(&position.position_id).unwrap();
+        repository
+            .set_exited_position(engine_id, position.clone())
+            .unwrap();
+        repository.set_open_position(engine_id, position).unwrap();
+        repository.set_balance(engine_id, balance(1100.0)).unwrap();
+    }
+
+    fn assert_populated(repository: &mut FileRepository<PnLReturnSummary>, engine_id: Uuid) {
+        let position = engine_position(engine_id);
+        let market = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+
+        assert_eq!(repository.get_balance(engine_id).unwrap(), balance(1100.0));
+        assert!(repository.get_statistics(&MarketId::from(&market)).is_ok());
+        assert_eq!(
+            repository
+                .get_open_positions(engine_id, [market].iter())
+                .unwrap(),
+            vec![position.clone()]
+        );
+        assert_eq!(
+            repository.get_exited_positions(engine_id).unwrap(),
+            vec![position]
+        );
+    }
+
+    #[test]
+    fn reopened_repository_recovers_state_from_log() {
+        let cfg = config(0);
+        let engine_id = Uuid::new_v4();
+
+        let mut repository = FileRepository::<PnLReturnSummary>::open(cfg.clone()).unwrap();
+        populate(&mut repository, engine_id);
+        drop(repository);
+
+        let mut repository = FileRepository::<PnLReturnSummary>::open(cfg.clone()).unwrap();
+        assert_populated(&mut repository, engine_id);
+
+        fs::remove_dir_all(cfg.directory).unwrap();
+    }
+
+    #[test]
+    fn open_discards_torn_final_record_and_incomplete_compaction() {
+        let cfg = config(0);
+        let engine_id = Uuid::new_v4();
+
+        let mut repository = FileRepository::<PnLReturnSummary>::open(cfg.clone()).unwrap();
+        populate(&mut repository, engine_id);
+        drop(repository);
+
+        // Simulate a crash mid-write & mid-compaction
+        let mut log = OpenOptions::new()
+            .append(true)
+            .open(cfg.directory.join(LOG_FILE))
+            .unwrap();
+        log.write_all(b"{\"Balance\":{\"engine_id\"").unwrap();
+        fs::write(cfg.directory.join(COMPACTION_FILE), b"{\"Bal").unwrap();
+
+        let mut repository = FileRepository::<PnLReturnSummary>::open(cfg.clone()).unwrap();
+        assert_populated(&mut repository, engine_id);
+        assert!(!cfg.directory.join(COMPACTION_FILE).exists());
+
+        // New records start on a fresh line after the discarded torn record
+        repository.set_balance(engine_id, balance(1200.0)).unwrap();
+        drop(repository);
+
+        let mut repository = FileRepository::<PnLReturnSummary>::open(cfg.clone()).unwrap();
+        assert_eq!(repository.get_balance(engine_id).unwrap(), balance(1200.0));
+
+        fs::remove_dir_all(cfg.directory).unwrap();
+    }
+
+    #[test]
+    fn compaction_preserves_state_and_shrinks_log() {
+        let cfg = config(0);
+        let engine_id = Uuid::new_v4();
+
+        let mut repository = FileRepository::<PnLReturnSummary>::open(cfg.clone()).unwrap();
+        populate(&mut repository, engine_id);
+        let log_len = fs::metadata(cfg.directory.join(LOG_FILE)).unwrap().len();
+
+        repository.compact().unwrap();
+        assert!(fs::metadata(cfg.directory.join(LOG_FILE)).unwrap().len() < log_len);
+        assert_populated(&mut repository, engine_id);
+
+        // Records appended after compaction are persisted to the compacted log
+        repository.set_balance(engine_id, balance(1200.0)).unwrap();
+        drop(repository);
+
+        let mut repository = FileRepository::<PnLReturnSummary>::open(cfg.clone()).unwrap();
+        assert_eq!(repository.get_balance(engine_id).unwrap(), balance(1200.0));
+        assert_eq!(repository.get_exited_positions(engine_id).unwrap().len(), 1);
+
+        fs::remove_dir_all(cfg.directory).unwrap();
+    }
+
+    #[test]
+    fn compaction_is_triggered_by_threshold() {
+        let cfg = config(3);
+        let engine_id = Uuid::new_v4();
+
+        let mut repository = FileRepository::<PnLReturnSummary>::open(cfg.clone()).unwrap();
+        for total in 0..5 {
+            repository
+                .set_balance(engine_id, balance(total as f64))
+                .unwrap();
+        }
+        drop(repository);
+
+        // Compacted at the 3rd record, leaving 1 compacted Balance record + 2 appended records
+        let (records, _) = read_records::<PnLReturnSummary>(&cfg.directory.join(LOG_FILE)).unwrap();
+        assert_eq!(records.len(), 3);
+
+        let mut repository = FileRepository::<PnLReturnSummary>::open(cfg.clone()).unwrap();
+        assert_eq!(repository.get_balance(engine_id).unwrap(), balance(4.0));
+
+        fs::remove_dir_all(cfg.directory).unwrap();
+    }
+
+    #[test]
+    fn transaction_is_persisted_as_a_single_record_on_commit() {
+        let cfg = config(0);
+        let engine_id = Uuid::new_v4();
+
+        let mut repository = FileRepository::<PnLReturnSummary>::open(cfg.clone()).unwrap();
+        repository.begin_transaction().unwrap();
+        populate(&mut repository, engine_id);
+
+        // Buffered writes are not visible or persisted until commit
+        assert!(repository.get_balance(engine_id).is_err());
+        assert!(matches!(
+            repository.begin_transaction(),
+            Err(RepositoryError::TransactionAlreadyActive)
+        ));
+
+        repository.commit_transaction().unwrap();
+        assert_populated(&mut repository, engine_id);
+
+        // Rolled back writes are discarded
+        repository.begin_transaction().unwrap();
+        repository.set_balance(engine_id, balance(1200.0)).unwrap();
+        repository.rollback_transaction().unwrap();
+        drop(repository);
+
+        let (records, _) = read_records::<PnLReturnSummary>(&cfg.directory.join(LOG_FILE)).unwrap();
+        assert_eq!(records.len(), 1);
+
+        let mut repository = FileRepository::<PnLReturnSummary>::open(cfg.clone()).unwrap();
+        assert_populated(&mut repository, engine_id);
+
+        fs::remove_dir_all(cfg.directory).unwrap();
+    }
+}
@@ -3,19 +3,20 @@ use crate::{
         position::{determine_position_id, Position, PositionId},
         repository::{
             determine_exited_positions_id, error::RepositoryError, BalanceHandler, PositionHandler,
-            StatisticHandler,
+            StatisticHandler, TransactionHandler,
         },
         Balance, BalanceId,
     },
     statistic::summary::PositionSummariser,
 };
 use barter_integration::model::{Market, MarketId};
-use std::collections::HashMap;
+use std::{collections::HashMap, hash::Hash};
 use uuid::Uuid;
 
-/// In-Memory repository for Proof Of Concepts. Implements [`PositionHandler`], [`BalanceHandler`]
-/// & [`StatisticHandler`]. Used by a Proof Of Concept Portfolio implementation to
-/// save the current equity, available cash, Positions, and market pair statistics.
+/// In-Memory repository for Proof Of Concepts. Implements [`PositionHandler`], [`BalanceHandler`],
+/// [`StatisticHandler`] & [`TransactionHandler`]. Used by a Proof Of Concept Portfolio
+/// implementation to save the current equity, available cash, Positions, and market pair
+/// statistics.
 /// **Careful in production - no fault tolerant guarantees!**
 #[derive(Debug, Default)]
 pub struct InMemoryRepository<Statistic: PositionSummariser> {
@@ -23,12 +24,24 @@ pub struct InMemoryRepository<Statistic: PositionSummariser> {
     closed_positions: HashMap<String, Vec<Position>>,
     current_balances: HashMap<BalanceId, Balance>,
     statistics: HashMap<MarketId, Statistic>,
+    /// Undo log of the writes in the active transaction, applied in reverse on rollback.
+    transaction: Option<Vec<Undo<Statistic>>>,
+}
+
+/// Reverts a single write to an [`InMemoryRepository`] when a transaction is rolled back.
+#[derive(Debug)]
+enum Undo<Statistic> {
+    OpenPosition(PositionId, Option<Box<Position>>),
+    ExitedPosition(String),
+    Balance(BalanceId, Option<Balance>),
+    Statistics(MarketId, Option<Statistic>),
 }
 
 impl<Statistic: PositionSummariser> PositionHandler for InMemoryRepository<Statistic> {
-    fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
-        self.open_positions
-            .insert(position.position_id.clone(), position);
+    fn set_open_position(&mut self, _: Uuid, position: Position) -> Result<(), RepositoryError> {
+        let position_id = position.position_id.clone();
+        let previous = self.open_positions.insert(position_id.clone(), position);
+        self.record(Undo::OpenPosition(position_id, previous.map(Box::new)));
         Ok(())
     }
 
@@ -44,15 +57,16 @@ impl<Statistic: PositionSummariser> PositionHandler for InMemoryRepository<Stati
         engine_id: Uuid,
         markets: Markets,
     ) -> Result<Vec<Position>, RepositoryError> {
+        // Include every tagged Position held in each market alongside the untagged Position
+        let open_positions = &self.open_positions;
         Ok(markets
-            .filter_map(|market| {
-                self.open_positions
-                    .get(&determine_position_id(
-                        engine_id,
-                        &market.exchange,
-                        &market.instrument,
-                    ))
-                    .map(Position::clone)
+            .flat_map(|market| {
+                let position_id =
+                    determine_position_id(engine_id, &market.exchange, &market.instrument);
+                open_positions
+                    .values()
+                    .filter(move |position| position.market_position_id() == position_id)
+                    .cloned()
             })
             .collect())
     }
@@ -61,7 +75,14 @@ impl<Statistic: PositionSummariser> PositionHandler for InMemoryRepository<Stati
         &mut self,
         position_id: &String,
     ) -> Result<Option<Position>, RepositoryError> {
-        Ok(self.open_positions.remove(position_id))
+        let position = self.open_positions.remove(position_id);
+        if position.is_some() {
+            self.record(Undo::OpenPosition(
+                position_id.clone(),
+                position.clone().map(Box::new),
+            ));
+        }
+        Ok(position)
     }
 
     fn set_exited_position(
@@ -74,10 +95,11 @@ impl<Statistic: PositionSummariser> PositionHandler for InMemoryRepository<Stati
         match self.closed_positions.get_mut(&exited_positions_key) {
             None => {
                 self.closed_positions
-                    .insert(exited_positions_key, vec![position]);
+                    .insert(exited_positions_key.clone(), vec![position]);
             }
             Some(closed_positions) => closed_positions.push(position),
         }
+        self.record(Undo::ExitedPosition(exited_positions_key));
         Ok(())
     }
 
@@ -92,8 +114,9 @@ impl<Statistic: PositionSummariser> PositionHandler for InMemoryRepository<Stati
 
 impl<Statistic: PositionSummariser> BalanceHandler for InMemoryRepository<Statistic> {
     fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
-        self.current_balances
-            .insert(Balance::balance_id(engine_id), balance);
+        let balance_id = Balance::balance_id(engine_id);
+        let previous = self.current_balances.insert(balance_id.clone(), balance);
+        self.record(Undo::Balance(balance_id, previous));
         Ok(())
     }
 
@@ -111,7 +134,8 @@ impl<Statistic: PositionSummariser> StatisticHandler<Statistic> for InMemoryRepo
         market_id: MarketId,
         statistic: Statistic,
     ) -> Result<(), RepositoryError> {
-        self.statistics.insert(market_id, statistic);
+        let previous = self.statistics.insert(market_id.clone(), statistic);
+        self.record(Undo::Statistics(market_id, previous));
         Ok(())
     }
 
@@ -123,6 +147,54 @@ impl<Statistic: PositionSummariser> StatisticHandler<Statistic> for InMemoryRepo
     }
 }
 
+impl<Statistic: PositionSummariser> TransactionHandler for InMemoryRepository<Statistic> {
+    fn begin_transaction(&mut self) -> Result<(), RepositoryError> {
+        match self.transaction {
+            Some(_) => Err(RepositoryError::TransactionAlreadyActive),
+            None => {
+                self.transaction = Some(Vec::new());
+                Ok(())
+            }
+        }
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), RepositoryError> {
+        // Writes are applied eagerly, so committing only discards the undo log
+        self.transaction = None;
+        Ok(())
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), RepositoryError> {
+        let undo_log = match self.transaction.take() {
+            Some(undo_log) => undo_log,
+            None => return Ok(()),
+        };
+
+        for undo in undo_log.into_iter().rev() {
+            match undo {
+                Undo::OpenPosition(position_id, previous) => {
+                    restore(&mut self.open_positions, position_id, previous.map(|p| *p))
+                }
+                Undo::ExitedPosition(exited_positions_key) => {
+                    if let Some(closed_positions) =
+                        self.closed_positions.get_mut(&exited_positions_key)
+                    {
+                        closed_positions.pop();
+                    }
+                }
+                Undo::Balance(balance_id, previous) => {
+                    restore(&mut self.current_balances, balance_id, previous)
+                }
+                Undo::Statistics(market_id, previous) => {
+                    restore(&mut self.statistics, market_id, previous)
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<Statistic: PositionSummariser> InMemoryRepository<Statistic> {
     /// Constructs a new [`InMemoryRepository`] component.
     pub fn new() -> Self {
@@ -131,6 +203,29 @@ impl<Statistic: PositionSummariser> InMemoryRepository<Statistic> {
             closed_positions: HashMap::new(),
             current_balances: HashMap::new(),
             statistics: HashMap::new(),
+            transaction: None,
+        }
+    }
+
+    /// Record how to revert a write if a transaction is active.
+    fn record(&mut self, undo: Undo<Statistic>) {
+        if let Some(undo_log) = &mut self.transaction {
+            undo_log.push(undo);
+        }
+    }
+}
+
+/// Restore the previous value of a key, removing the key if it had no previous value.
+fn restore<Key, Value>(map: &mut HashMap<Key, Value>, key: Key, previous: Option<Value>)
+where
+    Key: Eq + Hash,
+{
+    match previous {
+        Some(previous) => {
+            map.insert(key, previous);
+        }
+        None => {
+            map.remove(&key);
         }
     }
 }
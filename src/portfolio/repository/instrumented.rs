@@ -0,0 +1,178 @@
+use crate::{
+    event::sink::MetricsTx,
+    portfolio::{
+        position::{Position, PositionId},
+        repository::{
+            error::RepositoryError, BalanceHandler, PositionHandler, StatisticHandler,
+            TransactionHandler,
+        },
+        Balance,
+    },
+};
+use barter_integration::model::{Market, MarketId};
+use std::time::Instant;
+use uuid::Uuid;
+
+/// Repository wrapper that records the latency of every operation on the inner repository in the
+/// provided [`MetricsTx`].
+#[derive(Debug)]
+pub struct InstrumentedRepository<Repository> {
+    inner: Repository,
+    metrics: MetricsTx,
+}
+
+impl<Repository> InstrumentedRepository<Repository> {
+    /// Constructs a new [`InstrumentedRepository`] recording the latency of the inner repository's
+    /// operations in the provided [`MetricsTx`].
+    pub fn new(inner: Repository, metrics: MetricsTx) -> Self {
+        Self { inner, metrics }
+    }
+
+    /// Returns the inner repository.
+    pub fn into_inner(self) -> Repository {
+        self.inner
+    }
+
+    fn timed<Output>(
+        &mut self,
+        operation: &'static str,
+        action: impl FnOnce(&mut Repository) -> Output,
+    ) -> Output {
+        let start = Instant::now();
+        let output = action(&mut self.inner);
+        self.metrics.record_repository(operation, start.elapsed());
+        output
+    }
+}
+
+impl<Repository: PositionHandler> PositionHandler for InstrumentedRepository<Repository> {
+    fn set_open_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.timed("set_open_position", |inner| {
+            inner.set_open_position(engine_id, position)
+        })
+    }
+
+    fn get_open_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        self.timed("get_open_position", |inner| {
+            inner.get_open_position(position_id)
+        })
+    }
+
+    fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+        &mut self,
+        engine_id: Uuid,
+        markets: Markets,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        self.timed("get_open_positions", |inner| {
+            inner.get_open_positions(engine_id, markets)
+        })
+    }
+
+    fn remove_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        self.timed("remove_position", |inner| {
+            inner.remove_position(position_id)
+        })
+    }
+
+    fn set_exited_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.timed("set_exited_position", |inner| {
+            inner.set_exited_position(engine_id, position)
+        })
+    }
+
+    fn get_exited_positions(&mut self, engine_id: Uuid) -> Result<Vec<Position>, RepositoryError> {
+        self.timed("get_exited_positions", |inner| {
+            inner.get_exited_positions(engine_id)
+        })
+    }
+}
+
+impl<Repository: BalanceHandler> BalanceHandler for InstrumentedRepository<Repository> {
+    fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
+        self.timed("set_balance", |inner| inner.set_balance(engine_id, balance))
+    }
+
+    fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+        self.timed("get_balance", |inner| inner.get_balance(engine_id))
+    }
+}
+
+impl<Repository, Statistic> StatisticHandler<Statistic> for InstrumentedRepository<Repository>
+where
+    Repository: StatisticHandler<Statistic>,
+{
+    fn set_statistics(
+        &mut self,
+        market_id: MarketId,
+        statistic: Statistic,
+    ) -> Result<(), RepositoryError> {
+        self.timed("set_statistics", |inner| {
+            inner.set_statistics(market_id, statistic)
+        })
+    }
+
+    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+        self.timed("get_statistics", |inner| inner.get_statistics(market_id))
+    }
+}
+
+impl<Repository: TransactionHandler> TransactionHandler for InstrumentedRepository<Repository> {
+    fn begin_transaction(&mut self) -> Result<(), RepositoryError> {
+        self.timed("begin_transaction", |inner| inner.begin_transaction())
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), RepositoryError> {
+        self.timed("commit_transaction", |inner| inner.commit_transaction())
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), RepositoryError> {
+        self.timed("rollback_transaction", |inner| inner.rollback_transaction())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::repository::in_memory::InMemoryRepository,
+        statistic::summary::pnl::PnLReturnSummary,
+    };
+
+    #[test]
+    fn instrumented_repository_records_operation_latency() {
+        let metrics = MetricsTx::new();
+        let mut repository = InstrumentedRepository::new(
+            InMemoryRepository::<PnLReturnSummary>::new(),
+            metrics.clone(),
+        );
+
+        let engine_id = Uuid::new_v4();
+        repository
+            .set_balance(engine_id, Balance::default())
+            .unwrap();
+        repository.get_balance(engine_id).unwrap();
+        repository.get_balance(engine_id).unwrap();
+
+        let output = crate::metrics::render(&metrics.metrics());
+        assert!(
+            output.contains("barter_repository_latency_seconds_count{operation=\"set_balance\"} 1")
+        );
+        assert!(
+            output.contains("barter_repository_latency_seconds_count{operation=\"get_balance\"} 2")
+        );
+    }
+}
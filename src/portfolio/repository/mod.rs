@@ -9,16 +9,37 @@ use uuid::Uuid;
 /// Barter repository module specific errors.
 pub mod error;
 
+/// File repository for durable state keeping using an append-only log in a local directory.
+/// Requires no external services.
+pub mod file;
+
 /// In-Memory repository for convenient state keeping. No fault tolerant guarantees.
 pub mod in_memory;
 
 /// Redis repository for state keeping.
 pub mod redis;
 
+/// Repository wrapper that records the latency of every repository operation in the
+/// [`MetricsTx`](crate::event::sink::MetricsTx).
+pub mod instrumented;
+
+/// Repository wrapper that keeps the [`Balance`] in a ledger shared by several Portfolios, eg/ the
+/// per-market shards of a [`PortfolioActor`](crate::portfolio::actor::PortfolioActor).
+pub mod shared_balance;
+
+/// SQLite repository for state keeping, with a queryable history of exited [`Position`]s &
+/// [`Balance`]s. Requires the `sqlite` feature.
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
 /// Handles the reading & writing of a [`Position`] to/from the persistence layer.
 pub trait PositionHandler {
-    /// Upsert the open [`Position`] using it's [`PositionId`].
-    fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError>;
+    /// Upsert the open [`Position`] associated with the engine using it's [`PositionId`].
+    fn set_open_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError>;
 
     /// Get an open [`Position`] using the [`PositionId`] provided.
     fn get_open_position(
@@ -71,6 +92,25 @@ pub trait StatisticHandler<Statistic> {
     fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError>;
 }
 
+/// Groups a sequence of repository writes into an atomic unit of work, so either every write is
+/// persisted or none are. eg/ Used by the
+/// [`MetaPortfolio`](crate::portfolio::portfolio::MetaPortfolio) so each FillEvent state
+/// transition is all-or-nothing.
+///
+/// Writes are only guaranteed to be visible to reads once the unit of work is committed.
+pub trait TransactionHandler {
+    /// Begin a unit of work containing every subsequent write until it is committed or rolled
+    /// back. Fails if a unit of work is already active.
+    fn begin_transaction(&mut self) -> Result<(), RepositoryError>;
+
+    /// Atomically persist every write in the active unit of work. Does nothing if no unit of
+    /// work is active.
+    fn commit_transaction(&mut self) -> Result<(), RepositoryError>;
+
+    /// Discard every write in the active unit of work. Does nothing if no unit of work is active.
+    fn rollback_transaction(&mut self) -> Result<(), RepositoryError>;
+}
+
 /// Communicates a String represents a unique identifier for all a Portfolio's exited [`Position`]s.
 /// Used to append new exited [`Position`]s to the entry in the [`PositionHandler`].
 pub type ExitedPositionsId = String;
@@ -0,0 +1,346 @@
+use parking_lot::Mutex;
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    io::{BufRead, BufReader, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::Arc,
+};
+
+/// In-process Redis server speaking the RESP protocol, supporting the subset of commands used by
+/// the [`RedisRepository`](super::RedisRepository) & [`RedisPool`](super::pool::RedisPool).
+/// Used to test them without a Redis installation.
+#[derive(Clone, Debug)]
+pub struct FakeRedisServer {
+    pub uri: String,
+    state: Arc<Mutex<HashMap<String, Value>>>,
+    connections: Arc<Mutex<Vec<TcpStream>>>,
+}
+
+#[derive(Debug)]
+enum Value {
+    String(String),
+    List(VecDeque<String>),
+    Set(BTreeSet<String>),
+    SortedSet(Vec<(f64, String)>),
+}
+
+#[derive(Debug)]
+enum Reply {
+    Ok,
+    Status(&'static str),
+    Error(String),
+    Integer(i64),
+    Bulk(Option<String>),
+    Array(Vec<Reply>),
+}
+
+impl FakeRedisServer {
+    /// Start a [`FakeRedisServer`] listening on a random local port.
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server = Self {
+            uri: format!("redis://{}/", listener.local_addr().unwrap()),
+            state: Arc::new(Mutex::new(HashMap::new())),
+            connections: Arc::new(Mutex::new(Vec::new())),
+        };
+
+        let state = Arc::clone(&server.state);
+        let connections = Arc::clone(&server.connections);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                connections.lock().push(stream.try_clone().unwrap());
+                let state = Arc::clone(&state);
+                std::thread::spawn(move || serve(stream, state));
+            }
+        });
+
+        server
+    }
+
+    /// Close every open client connection, simulating the server dropping them.
+    pub fn drop_connections(&self) {
+        for connection in self.connections.lock().drain(..) {
+            let _ = connection.shutdown(Shutdown::Both);
+        }
+    }
+
+    /// Number of client connections accepted since the last [`Self::drop_connections`].
+    pub fn connection_count(&self) -> usize {
+        self.connections.lock().len()
+    }
+
+    /// Seed a list key, eg/ to simulate data persisted by an earlier version.
+    pub fn lpush(&self, key: &str, values: &[String]) {
+        let mut state = self.state.lock();
+        let list = state
+            .entry(key.to_owned())
+            .or_insert_with(|| Value::List(VecDeque::new()));
+        if let Value::List(list) = list {
+            values
+                .iter()
+                .for_each(|value| list.push_front(value.clone()));
+        }
+    }
+
+    /// Redis type of the key, as returned by the TYPE command.
+    pub fn key_type(&self, key: &str) -> &'static str {
+        type_name(self.state.lock().get(key))
+    }
+}
+
+fn serve(stream: TcpStream, state: Arc<Mutex<HashMap<String, Value>>>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+    let mut transaction: Option<Vec<Vec<String>>> = None;
+
+    while let Some(command) = read_command(&mut reader) {
+        let name = command[0].to_ascii_uppercase();
+        let reply = match (name.as_str(), &mut transaction) {
+            ("MULTI", None) => {
+                transaction = Some(Vec::new());
+                Reply::Ok
+            }
+            ("EXEC", Some(_)) => {
+                let queued = transaction.take().unwrap();
+                let mut state = state.lock();
+                Reply::Array(
+                    queued
+                        .iter()
+                        .map(|command| execute(&mut state, command))
+                        .collect(),
+                )
+            }
+            (_, Some(queued)) => {
+                queued.push(command);
+                Reply::Status("QUEUED")
+            }
+            (_, None) => execute(&mut state.lock(), &command),
+        };
+
+        let mut buffer = Vec::new();
+        encode(&reply, &mut buffer);
+        if writer.write_all(&buffer).is_err() {
+            return;
+        }
+    }
+}
+
+fn read_command(reader: &mut impl BufRead) -> Option<Vec<String>> {
+    fn read_line(reader: &mut impl BufRead) -> Option<String> {
+        let mut line = String::new();
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim_end().to_owned()),
+        }
+    }
+
+    let count = read_line(reader)?
+        .strip_prefix('*')?
+        .parse::<usize>()
+        .ok()?;
+    (0..count)
+        .map(|_| {
+            let len = read_line(reader)?
+                .strip_prefix('$')?
+                .parse::<usize>()
+                .ok()?;
+            let mut argument = vec![0; len + 2];
+            reader.read_exact(&mut argument).ok()?;
+            argument.truncate(len);
+            String::from_utf8(argument).ok()
+        })
+        .collect()
+}
+
+fn execute(keys: &mut HashMap<String, Value>, command: &[String]) -> Reply {
+    let wrong_type =
+        || Reply::Error("WRONGTYPE Operation against a key holding the wrong kind of value".into());
+    let args = &command[1..];
+
+    match command[0].to_ascii_uppercase().as_str() {
+        "PING" => Reply::Status("PONG"),
+        "TYPE" => Reply::Status(type_name(keys.get(&args[0]))),
+        "DEL" => Reply::Integer(
+            args.iter()
+                .filter(|key| keys.remove(key.as_str()).is_some())
+                .count() as i64,
+        ),
+        "SET" => {
+            keys.insert(args[0].clone(), Value::String(args[1].clone()));
+            Reply::Ok
+        }
+        "GET" => match keys.get(&args[0]) {
+            None => Reply::Bulk(None),
+            Some(Value::String(value)) => Reply::Bulk(Some(value.clone())),
+            Some(_) => wrong_type(),
+        },
+        "LRANGE" => match keys.get(&args[0]) {
+            None => Reply::Array(Vec::new()),
+            Some(Value::List(list)) => Reply::Array(bulks(slice(
+                list.iter().cloned().collect(),
+                &args[1],
+                &args[2],
+            ))),
+            Some(_) => wrong_type(),
+        },
+        "SADD" => match keys
+            .entry(args[0].clone())
+            .or_insert_with(|| Value::Set(BTreeSet::new()))
+        {
+            Value::Set(set) => Reply::Integer(
+                args[1..]
+                    .iter()
+                    .filter(|member| set.insert(member.to_string()))
+                    .count() as i64,
+            ),
+            _ => wrong_type(),
+        },
+        "SREM" => match keys.get_mut(&args[0]) {
+            None => Reply::Integer(0),
+            Some(Value::Set(set)) => Reply::Integer(
+                args[1..]
+                    .iter()
+                    .filter(|member| set.remove(member.as_str()))
+                    .count() as i64,
+            ),
+            Some(_) => wrong_type(),
+        },
+        "SMEMBERS" => match keys.get(&args[0]) {
+            None => Reply::Array(Vec::new()),
+            Some(Value::Set(set)) => Reply::Array(bulks(set.iter().cloned().collect())),
+            Some(_) => wrong_type(),
+        },
+        "ZADD" => match keys
+            .entry(args[0].clone())
+            .or_insert_with(|| Value::SortedSet(Vec::new()))
+        {
+            Value::SortedSet(sorted_set) => {
+                let mut added = 0;
+                for pair in args[1..].chunks(2) {
+                    let score = pair[0].parse::<f64>().unwrap();
+                    match sorted_set.iter_mut().find(|(_, member)| *member == pair[1]) {
+                        Some(entry) => entry.0 = score,
+                        None => {
+                            sorted_set.push((score, pair[1].clone()));
+                            added += 1;
+                        }
+                    }
+                }
+                sorted_set.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                Reply::Integer(added)
+            }
+            _ => wrong_type(),
+        },
+        "ZRANGE" => match keys.get(&args[0]) {
+            None => Reply::Array(Vec::new()),
+            Some(Value::SortedSet(sorted_set)) => Reply::Array(bulks(slice(
+                sorted_set
+                    .iter()
+                    .map(|(_, member)| member.clone())
+                    .collect(),
+                &args[1],
+                &args[2],
+            ))),
+            Some(_) => wrong_type(),
+        },
+        "ZRANGEBYSCORE" => match keys.get(&args[0]) {
+            None => Reply::Array(Vec::new()),
+            Some(Value::SortedSet(sorted_set)) => {
+                let (offset, limit) = match args.get(3).map(|arg| arg.to_ascii_uppercase()) {
+                    Some(limit) if limit == "LIMIT" => (
+                        args[4].parse::<usize>().unwrap(),
+                        args[5].parse::<usize>().unwrap(),
+                    ),
+                    _ => (0, usize::MAX),
+                };
+                Reply::Array(bulks(
+                    sorted_set
+                        .iter()
+                        .filter(|(score, _)| {
+                            within_bound(*score, &args[1], true)
+                                && within_bound(*score, &args[2], false)
+                        })
+                        .map(|(_, member)| member.clone())
+                        .skip(offset)
+                        .take(limit)
+                        .collect(),
+                ))
+            }
+            Some(_) => wrong_type(),
+        },
+        other => Reply::Error(format!("ERR unknown command '{other}'")),
+    }
+}
+
+fn type_name(value: Option<&Value>) -> &'static str {
+    match value {
+        None => "none",
+        Some(Value::String(_)) => "string",
+        Some(Value::List(_)) => "list",
+        Some(Value::Set(_)) => "set",
+        Some(Value::SortedSet(_)) => "zset",
+    }
+}
+
+/// Elements within the inclusive start & stop indexes, where negative indexes count from the end.
+fn slice(values: Vec<String>, start: &str, stop: &str) -> Vec<String> {
+    let len = values.len() as i64;
+    let index = |index: &str| {
+        let index = index.parse::<i64>().unwrap();
+        if index < 0 {
+            len + index
+        } else {
+            index
+        }
+    };
+    let (start, stop) = (index(start).max(0), index(stop).min(len - 1));
+
+    match start > stop {
+        true => Vec::new(),
+        false => values[start as usize..=stop as usize].to_vec(),
+    }
+}
+
+/// Determine if the score is within the min (or max) bound, which is exclusive if prefixed by '('.
+fn within_bound(score: f64, bound: &str, min: bool) -> bool {
+    let (exclusive, bound) = match bound.strip_prefix('(') {
+        Some(bound) => (true, bound),
+        None => (false, bound),
+    };
+    let bound = match bound {
+        "-inf" => f64::NEG_INFINITY,
+        "+inf" | "inf" => f64::INFINITY,
+        bound => bound.parse::<f64>().unwrap(),
+    };
+
+    match (min, exclusive) {
+        (true, true) => score > bound,
+        (true, false) => score >= bound,
+        (false, true) => score < bound,
+        (false, false) => score <= bound,
+    }
+}
+
+fn bulks(values: Vec<String>) -> Vec<Reply> {
+    values
+        .into_iter()
+        .map(|value| Reply::Bulk(Some(value)))
+        .collect()
+}
+
+fn encode(reply: &Reply, buffer: &mut Vec<u8>) {
+    match reply {
+        Reply::Ok => buffer.extend_from_slice(b"+OK\r\n"),
+        Reply::Status(status) => buffer.extend_from_slice(format!("+{status}\r\n").as_bytes()),
+        Reply::Error(error) => buffer.extend_from_slice(format!("-{error}\r\n").as_bytes()),
+        Reply::Integer(integer) => buffer.extend_from_slice(format!(":{integer}\r\n").as_bytes()),
+        Reply::Bulk(None) => buffer.extend_from_slice(b"$-1\r\n"),
+        Reply::Bulk(Some(value)) => {
+            buffer.extend_from_slice(format!("${}\r\n{value}\r\n", value.len()).as_bytes())
+        }
+        Reply::Array(replies) => {
+            buffer.extend_from_slice(format!("*{}\r\n", replies.len()).as_bytes());
+            replies.iter().for_each(|reply| encode(reply, buffer));
+        }
+    }
+}
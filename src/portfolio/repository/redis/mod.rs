@@ -0,0 +1,677 @@
+use self::pool::{is_connection_error, RedisPool};
+use crate::{
+    portfolio::{
+        error::PortfolioError,
+        position::{determine_position_id, Position, PositionId},
+        repository::{
+            determine_exited_positions_id, error::RepositoryError, BalanceHandler, PositionHandler,
+            StatisticHandler, TransactionHandler,
+        },
+        Balance,
+    },
+    statistic::summary::PositionSummariser,
+};
+use barter_integration::model::{Market, MarketId};
+use chrono::{DateTime, Utc};
+use redis::{Cmd, Connection, Pipeline, RedisError};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+};
+use tracing::info;
+use uuid::Uuid;
+
+/// Thread safe pool of Redis connections that reconnects with exponential backoff.
+pub mod pool;
+
+#[cfg(test)]
+mod fake_server;
+
+/// Configuration for constructing a [`RedisRepository`] via the new() constructor method.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize)]
+pub struct Config {
+    pub uri: String,
+}
+
+/// Redis persisted repository that implements [`PositionHandler`], [`BalanceHandler`],
+/// & [`PositionSummariser`]. Used by a Portfolio implementation to persist the Portfolio state,
+/// including total equity, available cash & Positions.
+///
+/// Connections are taken from a [`RedisPool`], which reconnects with backoff if the Redis server
+/// drops the connection. Writes can be grouped into an atomic (MULTI/EXEC) transaction using the
+/// [`TransactionHandler`] implementation.
+///
+/// Exited Positions persisted by earlier versions in a list are migrated to a sorted set the first
+/// time the engine_id's exited Positions are touched.
+pub struct RedisRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    pool: RedisPool,
+    transaction: Option<Pipeline>,
+    migrated_engine_ids: HashSet<Uuid>,
+    _statistic_marker: PhantomData<Statistic>,
+}
+
+impl<Statistic> PositionHandler for RedisRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_open_position(&mut self, _: Uuid, position: Position) -> Result<(), RepositoryError> {
+        let position_string = serde_json::to_string(&position)?;
+
+        // Index tagged Positions by market, so every Position in a market can be retrieved
+        let tagged_positions_id = position
+            .tag
+            .is_some()
+            .then(|| determine_tagged_positions_id(&position.market_position_id()));
+
+        self.write(RepositoryError::WriteError, |pipeline| {
+            let pipeline = pipeline.set(&position.position_id, &position_string);
+            match &tagged_positions_id {
+                Some(tagged_positions_id) => {
+                    pipeline.sadd(tagged_positions_id, &position.position_id)
+                }
+                None => pipeline,
+            }
+        })
+    }
+
+    fn get_open_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        self.pool
+            .query::<Option<String>>(&Cmd::get(position_id))
+            .map_err(|error| repository_error(error, RepositoryError::ReadError))?
+            .map(|position| serde_json::from_str::<Position>(&position))
+            .transpose()
+            .map_err(RepositoryError::JsonSerDeError)
+    }
+
+    fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+        &mut self,
+        engine_id: Uuid,
+        markets: Markets,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        let mut positions = Vec::new();
+
+        for market in markets {
+            let position_id =
+                determine_position_id(engine_id, &market.exchange, &market.instrument);
+
+            // Retrieve the identifiers of every tagged Position indexed for the market
+            let tagged_positions_id = determine_tagged_positions_id(&position_id);
+            let tagged_position_ids = self
+                .pool
+                .query::<Vec<PositionId>>(&Cmd::smembers(&tagged_positions_id))
+                .map_err(|error| repository_error(error, RepositoryError::ReadError))?;
+
+            for position_id in std::iter::once(position_id).chain(tagged_position_ids) {
+                if let Some(position) = self.get_open_position(&position_id)? {
+                    positions.push(position);
+                }
+            }
+        }
+
+        Ok(positions)
+    }
+
+    fn remove_position(
+        &mut self,
+        position_id: &String,
+    ) -> Result<Option<Position>, RepositoryError> {
+        let position = self.get_open_position(position_id)?;
+        let tagged_positions_id = position
+            .as_ref()
+            .filter(|position| position.tag.is_some())
+            .map(|position| determine_tagged_positions_id(&position.market_position_id()));
+
+        self.write(RepositoryError::DeleteError, |pipeline| {
+            let pipeline = pipeline.del(position_id);
+            match &tagged_positions_id {
+                Some(tagged_positions_id) => pipeline.srem(tagged_positions_id, position_id),
+                None => pipeline,
+            }
+        })?;
+
+        Ok(position)
+    }
+
+    fn set_exited_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.migrate_exited_positions(engine_id)?;
+        let exited_positions_id = determine_exited_positions_id(engine_id);
+        let position_string = serde_json::to_string(&position)?;
+
+        // Sorted set scored by exit time, so exited Positions can be queried by time range
+        self.write(RepositoryError::WriteError, |pipeline| {
+            pipeline.zadd(
+                &exited_positions_id,
+                &position_string,
+                score(position.meta.update_time),
+            )
+        })
+    }
+
+    fn get_exited_positions(&mut self, engine_id: Uuid) -> Result<Vec<Position>, RepositoryError> {
+        self.migrate_exited_positions(engine_id)?;
+        let exited_positions_id = determine_exited_positions_id(engine_id);
+
+        let positions = self
+            .pool
+            .query(&Cmd::zrange(&exited_positions_id, 0, -1))
+            .map_err(|error| repository_error(error, RepositoryError::ReadError))?;
+
+        deserialise_all(positions)
+    }
+}
+
+impl<Statistic> BalanceHandler for RedisRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
+        let balance_history_id = determine_balance_history_id(engine_id);
+        let balance_string = serde_json::to_string(&balance)?;
+
+        self.write(RepositoryError::WriteError, |pipeline| {
+            pipeline
+                .zadd(&balance_history_id, &balance_string, score(balance.time))
+                .ignore()
+                .set(Balance::balance_id(engine_id), &balance_string)
+        })
+    }
+
+    fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+        let balance_value = self
+            .pool
+            .query::<Option<String>>(&Cmd::get(Balance::balance_id(engine_id)))
+            .map_err(|error| repository_error(error, RepositoryError::ReadError))?
+            .ok_or(RepositoryError::ExpectedDataNotPresentError)?;
+
+        Ok(serde_json::from_str::<Balance>(&balance_value)?)
+    }
+}
+
+impl<Statistic> StatisticHandler<Statistic> for RedisRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_statistics(
+        &mut self,
+        market_id: MarketId,
+        statistic: Statistic,
+    ) -> Result<(), RepositoryError> {
+        let statistic_string = serde_json::to_string(&statistic)?;
+
+        self.write(RepositoryError::WriteError, |pipeline| {
+            pipeline.set(&market_id.0, &statistic_string)
+        })
+    }
+
+    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+        let statistics = self
+            .pool
+            .query::<Option<String>>(&Cmd::get(&market_id.0))
+            .map_err(|error| repository_error(error, RepositoryError::ReadError))?
+            .ok_or(RepositoryError::ExpectedDataNotPresentError)?;
+
+        serde_json::from_str(&statistics).map_err(RepositoryError::JsonSerDeError)
+    }
+}
+
+impl<Statistic> TransactionHandler for RedisRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    /// Begin buffering every subsequent write into an atomic (MULTI/EXEC) transaction. Reads
+    /// during the transaction do not observe the buffered writes.
+    fn begin_transaction(&mut self) -> Result<(), RepositoryError> {
+        if self.transaction.is_some() {
+            return Err(RepositoryError::TransactionAlreadyActive);
+        }
+
+        let mut transaction = redis::pipe();
+        transaction.atomic();
+        self.transaction = Some(transaction);
+        Ok(())
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), RepositoryError> {
+        match self.transaction.take() {
+            Some(transaction) => self
+                .pool
+                .query_pipeline::<()>(&transaction)
+                .map_err(|error| repository_error(error, RepositoryError::WriteError)),
+            None => Ok(()),
+        }
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), RepositoryError> {
+        self.transaction = None;
+        Ok(())
+    }
+}
+
+impl<Statistic: PositionSummariser> Debug for RedisRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisRepository")
+            .field("pool", &self.pool)
+            .field("transaction_active", &self.transaction.is_some())
+            .finish()
+    }
+}
+
+impl<Statistic: PositionSummariser> RedisRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    /// Constructs a new [`RedisRepository`] component using the provided Redis connection struct.
+    /// The [`RedisRepository`] cannot reconnect if the connection is dropped - use
+    /// [`RedisRepository::with_pool`] for automatic reconnection.
+    pub fn new(connection: Connection) -> Self {
+        Self::with_pool(RedisPool::from_connection(connection))
+    }
+
+    /// Constructs a new [`RedisRepository`] component using the provided [`RedisPool`].
+    pub fn with_pool(pool: RedisPool) -> Self {
+        Self {
+            pool,
+            transaction: None,
+            migrated_engine_ids: HashSet::new(),
+            _statistic_marker: PhantomData::<Statistic>::default(),
+        }
+    }
+
+    /// Returns a [`RedisRepositoryBuilder`] instance.
+    pub fn builder() -> RedisRepositoryBuilder<Statistic> {
+        RedisRepositoryBuilder::new()
+    }
+
+    /// Get a [`Page`] of the exited [`Position`]s associated with the engine_id that exited within
+    /// the provided time range (start inclusive, end exclusive), ordered by exit time.
+    pub fn get_exited_positions_between(
+        &mut self,
+        engine_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        page: Page,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        self.migrate_exited_positions(engine_id)?;
+        let positions =
+            self.range_by_time(determine_exited_positions_id(engine_id), start, end, page)?;
+        deserialise_all(positions)
+    }
+
+    /// Get a [`Page`] of the [`Balance`] history associated with the engine_id within the
+    /// provided time range (start inclusive, end exclusive), ordered by time.
+    pub fn get_balance_history(
+        &mut self,
+        engine_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        page: Page,
+    ) -> Result<Vec<Balance>, RepositoryError> {
+        let balances =
+            self.range_by_time(determine_balance_history_id(engine_id), start, end, page)?;
+        deserialise_all(balances)
+    }
+
+    /// Migrate the exited [`Position`]s associated with the engine_id from the newest-first list
+    /// persisted by earlier versions to a sorted set scored by exit time. The list is replaced
+    /// atomically, & each engine_id is only checked once per [`RedisRepository`].
+    fn migrate_exited_positions(&mut self, engine_id: Uuid) -> Result<(), RepositoryError> {
+        if self.migrated_engine_ids.contains(&engine_id) {
+            return Ok(());
+        }
+
+        let exited_positions_id = determine_exited_positions_id(engine_id);
+        let legacy_positions = self
+            .pool
+            .query::<String>(redis::cmd("TYPE").arg(&exited_positions_id))
+            .and_then(|key_type| match key_type.as_str() {
+                "list" => self
+                    .pool
+                    .query::<Vec<String>>(&Cmd::lrange(&exited_positions_id, 0, -1))
+                    .map(Some),
+                _ => Ok(None),
+            })
+            .map_err(|error| repository_error(error, RepositoryError::ReadError))?;
+
+        if let Some(legacy_positions) = legacy_positions {
+            let scored_positions = legacy_positions
+                .into_iter()
+                .map(|position_string| {
+                    serde_json::from_str::<Position>(&position_string)
+                        .map(|position| (score(position.meta.update_time), position_string))
+                })
+                .collect::<Result<Vec<(i64, String)>, serde_json::Error>>()?;
+
+            let mut pipeline = redis::pipe();
+            pipeline.atomic().del(&exited_positions_id).ignore();
+            if !scored_positions.is_empty() {
+                pipeline
+                    .zadd_multiple(&exited_positions_id, &scored_positions)
+                    .ignore();
+            }
+
+            self.pool
+                .query_pipeline::<()>(&pipeline)
+                .map_err(|error| repository_error(error, RepositoryError::WriteError))?;
+
+            info!(
+                %engine_id,
+                migrated = scored_positions.len(),
+                "migrated legacy exited Positions list to sorted set"
+            );
+        }
+
+        self.migrated_engine_ids.insert(engine_id);
+        Ok(())
+    }
+
+    /// Get a [`Page`] of the sorted set members scored within the provided time range.
+    fn range_by_time(
+        &mut self,
+        key: String,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        page: Page,
+    ) -> Result<Vec<String>, RepositoryError> {
+        self.pool
+            .query(&Cmd::zrangebyscore_limit(
+                &key,
+                score(start),
+                // Exclusive upper bound
+                format!("({}", score(end)),
+                page.offset as isize,
+                page.limit as isize,
+            ))
+            .map_err(|error| repository_error(error, RepositoryError::ReadError))
+    }
+
+    /// Write to Redis, or buffer the write if a transaction is active. Writes must be
+    /// idempotent since they are retried after a connection error.
+    fn write<Build>(&mut self, error: RepositoryError, build: Build) -> Result<(), RepositoryError>
+    where
+        Build: FnOnce(&mut Pipeline) -> &mut Pipeline,
+    {
+        match &mut self.transaction {
+            Some(transaction) => {
+                build(transaction).ignore();
+                Ok(())
+            }
+            None => {
+                let mut pipeline = redis::pipe();
+                build(&mut pipeline).ignore();
+
+                self.pool
+                    .query_pipeline::<()>(&pipeline)
+                    .map_err(|redis_error| repository_error(redis_error, error))
+            }
+        }
+    }
+
+    /// Establish & return a Redis connection.
+    ///
+    /// Panics if Redis is unreachable - use [`RedisPool::new`] & [`RedisRepository::with_pool`] to
+    /// connect with retries & backoff.
+    pub fn setup_redis_connection(cfg: Config) -> Connection {
+        redis::Client::open(cfg.uri)
+            .expect("Failed to create Redis client")
+            .get_connection()
+            .expect("Failed to connect to Redis")
+    }
+}
+
+/// Page of the results of a range query, used to paginate over long histories.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct Page {
+    /// Number of results to skip.
+    pub offset: usize,
+    /// Maximum number of results to return.
+    pub limit: usize,
+}
+
+impl Page {
+    /// Construct the [`Page`] at the zero-based page number, given the page size.
+    pub fn number(number: usize, size: usize) -> Self {
+        Self {
+            offset: number * size,
+            limit: size,
+        }
+    }
+}
+
+/// Returns the unique identifier for a Portfolio's [`Balance`] history, given an engine_id.
+pub fn determine_balance_history_id(engine_id: Uuid) -> String {
+    format!("{}_history", Balance::balance_id(engine_id))
+}
+
+/// Returns the unique identifier for the set indexing every tagged [`Position`] in a market,
+/// given the market's untagged [`PositionId`].
+fn determine_tagged_positions_id(position_id: &PositionId) -> String {
+    format!("{}_tags", position_id)
+}
+
+/// Sorted set score of a timestamp, in milliseconds since the epoch.
+fn score(time: DateTime<Utc>) -> i64 {
+    time.timestamp_millis()
+}
+
+/// Map a [`RedisError`] to a [`RepositoryError`], distinguishing connection failures from the
+/// provided command failure.
+fn repository_error(error: RedisError, command_error: RepositoryError) -> RepositoryError {
+    if is_connection_error(&error) {
+        RepositoryError::ConnectionError(error.to_string())
+    } else {
+        command_error
+    }
+}
+
+/// Deserialise every JSON value.
+fn deserialise_all<T: DeserializeOwned>(values: Vec<String>) -> Result<Vec<T>, RepositoryError> {
+    values
+        .iter()
+        .map(|value| serde_json::from_str::<T>(value))
+        .collect::<Result<Vec<T>, serde_json::Error>>()
+        .map_err(RepositoryError::JsonSerDeError)
+}
+
+/// Builder to construct [`RedisRepository`] instances.
+#[derive(Default)]
+pub struct RedisRepositoryBuilder<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    pool: Option<RedisPool>,
+    _statistic_marker: PhantomData<Statistic>,
+}
+
+impl<Statistic: PositionSummariser> RedisRepositoryBuilder<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    pub fn new() -> Self {
+        Self {
+            pool: None,
+            _statistic_marker: PhantomData::<Statistic>::default(),
+        }
+    }
+
+    pub fn conn(self, value: Connection) -> Self {
+        Self {
+            pool: Some(RedisPool::from_connection(value)),
+            ..self
+        }
+    }
+
+    pub fn pool(self, value: RedisPool) -> Self {
+        Self {
+            pool: Some(value),
+            ..self
+        }
+    }
+
+    pub fn build(self) -> Result<RedisRepository<Statistic>, PortfolioError> {
+        Ok(RedisRepository::with_pool(
+            self.pool.ok_or(PortfolioError::BuilderIncomplete("pool"))?,
+        ))
+    }
+}
+
+impl<Statistic> Debug for RedisRepositoryBuilder<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisRepositoryBuilder")
+            .field("pool", &self.pool)
+            .field("_statistic_market", &self._statistic_marker)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::repository::redis::{fake_server::FakeRedisServer, pool},
+        statistic::summary::pnl::PnLReturnSummary,
+        test_util::position,
+    };
+    use chrono::Duration;
+
+    fn repository(server: &FakeRedisServer) -> RedisRepository<PnLReturnSummary> {
+        RedisRepository::with_pool(RedisPool::new(&server.uri, pool::Config::default()).unwrap())
+    }
+
+    fn exited_position(update_time: DateTime<Utc>) -> Position {
+        let mut position = position();
+        position.meta.update_time = update_time;
+        position.position_id = format!("exited_at_{}", score(update_time));
+        position
+    }
+
+    /// Seed the newest-first list of exited Positions persisted by earlier versions.
+    fn seed_legacy_exited_positions(
+        server: &FakeRedisServer,
+        engine_id: Uuid,
+        positions: &[Position],
+    ) {
+        server.lpush(
+            &determine_exited_positions_id(engine_id),
+            &positions
+                .iter()
+                .map(|position| serde_json::to_string(position).unwrap())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn set_exited_position_migrates_legacy_list_to_sorted_set() {
+        let server = FakeRedisServer::start();
+        let mut repository = repository(&server);
+        let engine_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        let legacy = vec![
+            exited_position(now - Duration::minutes(2)),
+            exited_position(now - Duration::minutes(1)),
+        ];
+        seed_legacy_exited_positions(&server, engine_id, &legacy);
+
+        let exited = exited_position(now);
+        repository
+            .set_exited_position(engine_id, exited.clone())
+            .unwrap();
+
+        assert_eq!(
+            server.key_type(&determine_exited_positions_id(engine_id)),
+            "zset"
+        );
+        assert_eq!(
+            repository.get_exited_positions(engine_id).unwrap(),
+            vec![legacy[0].clone(), legacy[1].clone(), exited]
+        );
+    }
+
+    #[test]
+    fn get_exited_positions_between_migrates_legacy_list_to_sorted_set() {
+        let server = FakeRedisServer::start();
+        let mut repository = repository(&server);
+        let engine_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        let legacy = (0..4)
+            .map(|minutes| exited_position(now + Duration::minutes(minutes)))
+            .collect::<Vec<_>>();
+        seed_legacy_exited_positions(&server, engine_id, &legacy);
+
+        let actual = repository
+            .get_exited_positions_between(
+                engine_id,
+                now + Duration::minutes(1),
+                now + Duration::minutes(3),
+                Page::number(0, 10),
+            )
+            .unwrap();
+
+        assert_eq!(actual, legacy[1..3].to_vec());
+        assert_eq!(
+            server.key_type(&determine_exited_positions_id(engine_id)),
+            "zset"
+        );
+    }
+
+    #[test]
+    fn get_exited_positions_migrates_legacy_list_in_exit_time_order() {
+        let server = FakeRedisServer::start();
+        let mut repository = repository(&server);
+        let engine_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        let legacy = vec![
+            exited_position(now - Duration::minutes(1)),
+            exited_position(now),
+        ];
+        seed_legacy_exited_positions(&server, engine_id, &legacy);
+
+        assert_eq!(repository.get_exited_positions(engine_id).unwrap(), legacy);
+        assert_eq!(
+            server.key_type(&determine_exited_positions_id(engine_id)),
+            "zset"
+        );
+    }
+
+    #[test]
+    fn exited_positions_without_legacy_list_are_stored_in_sorted_set() {
+        let server = FakeRedisServer::start();
+        let mut repository = repository(&server);
+        let engine_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        let later = exited_position(now);
+        let earlier = exited_position(now - Duration::minutes(1));
+        repository
+            .set_exited_position(engine_id, later.clone())
+            .unwrap();
+        repository
+            .set_exited_position(engine_id, earlier.clone())
+            .unwrap();
+
+        assert_eq!(
+            repository.get_exited_positions(engine_id).unwrap(),
+            vec![earlier, later]
+        );
+    }
+}
@@ -0,0 +1,513 @@
+use crate::portfolio::block_on;
+use parking_lot::Mutex;
+use redis::{
+    aio::MultiplexedConnection, Client, Cmd, Connection, FromRedisValue, Pipeline, RedisError,
+    RedisResult,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Formatter},
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{runtime::Runtime, sync::Semaphore};
+use tracing::{info, warn};
+
+/// Configuration for constructing a [`RedisPool`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// Maximum number of connections in the pool, and therefore the maximum number of operations
+    /// in flight concurrently.
+    #[serde(default = "default_max_connections")]
+    pub max_connections: usize,
+    /// Timeout for establishing a new connection.
+    pub connect_timeout_ms: u64,
+    /// Maximum number of background attempts to re-establish a dropped connection before giving
+    /// up until the next operation.
+    pub max_connect_attempts: u32,
+    /// Delay before the second background connection attempt, doubling for every subsequent
+    /// attempt.
+    pub initial_backoff_ms: u64,
+    /// Upper bound of the delay between background connection attempts.
+    pub max_backoff_ms: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_connections: default_max_connections(),
+            connect_timeout_ms: 1_000,
+            max_connect_attempts: 5,
+            initial_backoff_ms: 50,
+            max_backoff_ms: 2_000,
+        }
+    }
+}
+
+fn default_max_connections() -> usize {
+    4
+}
+
+/// Thread safe pool of up to [`Config::max_connections`] Redis connections that reconnects with
+/// exponential backoff.
+///
+/// Every operation checks out a connection for it's duration, waiting for one to be checked back
+/// in if the pool is exhausted, so one slow round-trip never delays operations on the other
+/// connections. The connections are driven by the [`RedisPool`]'s own Tokio runtime, & are
+/// shared by every cloned [`RedisPool`].
+///
+/// Async callers should use the query_async() methods. The blocking query() methods may be
+/// called from inside a multi-threaded Tokio runtime, in which case the worker thread is handed
+/// off (ie/ `block_in_place`) so it's other tasks are not stalled whilst waiting on Redis.
+///
+/// If a connection is dropped (eg/ by the server), the operation is retried once on another
+/// connection, so operations must be idempotent. Should establishing a new connection fail, it's
+/// re-established in the background with exponential backoff & operations without an idle
+/// connection fail fast with a connection error in the meantime. Callers (eg/ a Portfolio
+/// holding it's lock) therefore never wait on the backoff.
+#[derive(Clone)]
+pub struct RedisPool {
+    connections: Connections,
+}
+
+#[derive(Clone)]
+enum Connections {
+    /// Pool of [`MultiplexedConnection`]s that are re-established in the background.
+    Pooled {
+        runtime: Arc<PoolRuntime>,
+        shared: Arc<Shared>,
+    },
+    /// Single pre-established blocking [`Connection`] that cannot be re-established.
+    Single(Arc<Mutex<Connection>>),
+}
+
+/// State shared between every cloned [`RedisPool`] & the background reconnection task.
+struct Shared {
+    client: Client,
+    /// Established connections that are not checked out.
+    idle: Mutex<Vec<MultiplexedConnection>>,
+    /// Permits to check out a connection, limiting them to [`Config::max_connections`].
+    permits: Semaphore,
+    reconnecting: AtomicBool,
+    cfg: Config,
+}
+
+/// Tokio runtime driving the [`MultiplexedConnection`]s & background reconnection task. Shut down
+/// without blocking when the last [`RedisPool`] is dropped, since that may happen inside another
+/// async context.
+struct PoolRuntime(Option<Runtime>);
+
+impl PoolRuntime {
+    fn runtime(&self) -> &Runtime {
+        self.0
+            .as_ref()
+            .expect("PoolRuntime is only empty once dropped")
+    }
+}
+
+impl Drop for PoolRuntime {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.0.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+/// Command or [`Pipeline`] executed by a [`RedisPool`].
+#[derive(Copy, Clone)]
+enum Request<'a> {
+    Cmd(&'a Cmd),
+    Pipeline(&'a Pipeline),
+}
+
+impl Request<'_> {
+    fn query<T: FromRedisValue>(self, connection: &mut Connection) -> RedisResult<T> {
+        match self {
+            Request::Cmd(command) => command.query(connection),
+            Request::Pipeline(pipeline) => pipeline.query(connection),
+        }
+    }
+
+    async fn query_async<T: FromRedisValue>(
+        self,
+        connection: &mut MultiplexedConnection,
+    ) -> RedisResult<T> {
+        match self {
+            Request::Cmd(command) => command.query_async(connection).await,
+            Request::Pipeline(pipeline) => pipeline.query_async(connection).await,
+        }
+    }
+}
+
+impl RedisPool {
+    /// Constructs a new [`RedisPool`] for the Redis server at the provided uri. No connection is
+    /// established until one is first required.
+    pub fn new(uri: &str, cfg: Config) -> RedisResult<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("redis-pool")
+            .enable_all()
+            .build()?;
+
+        Ok(Self {
+            connections: Connections::Pooled {
+                runtime: Arc::new(PoolRuntime(Some(runtime))),
+                shared: Arc::new(Shared {
+                    client: Client::open(uri)?,
+                    idle: Mutex::new(Vec::with_capacity(cfg.max_connections)),
+                    permits: Semaphore::new(cfg.max_connections.max(1)),
+                    reconnecting: AtomicBool::new(false),
+                    cfg,
+                }),
+            },
+        })
+    }
+
+    /// Constructs a [`RedisPool`] containing a single pre-established blocking [`Connection`].
+    /// The [`RedisPool`] cannot reconnect if the [`Connection`] is dropped, and every operation
+    /// (including those via the query_async() methods) blocks on it.
+    pub fn from_connection(connection: Connection) -> Self {
+        Self {
+            connections: Connections::Single(Arc::new(Mutex::new(connection))),
+        }
+    }
+
+    /// Execute the command, retrying once after a connection error. Blocks until complete.
+    pub fn query<T: FromRedisValue>(&self, command: &Cmd) -> RedisResult<T> {
+        block_on(self.execute(Request::Cmd(command)))
+    }
+
+    /// Execute the [`Pipeline`], retrying once after a connection error. Blocks until complete.
+    pub fn query_pipeline<T: FromRedisValue>(&self, pipeline: &Pipeline) -> RedisResult<T> {
+        block_on(self.execute(Request::Pipeline(pipeline)))
+    }
+
+    /// Asynchronously execute the command, retrying once after a connection error.
+    pub async fn query_async<T: FromRedisValue>(&self, command: &Cmd) -> RedisResult<T> {
+        self.execute(Request::Cmd(command)).await
+    }
+
+    /// Asynchronously execute the [`Pipeline`], retrying once after a connection error.
+    pub async fn query_pipeline_async<T: FromRedisValue>(
+        &self,
+        pipeline: &Pipeline,
+    ) -> RedisResult<T> {
+        self.execute(Request::Pipeline(pipeline)).await
+    }
+
+    async fn execute<T: FromRedisValue>(&self, request: Request<'_>) -> RedisResult<T> {
+        let (runtime, shared) = match &self.connections {
+            Connections::Pooled { runtime, shared } => (runtime, shared),
+            Connections::Single(connection) => return request.query(&mut connection.lock()),
+        };
+
+        match shared.query(runtime, request).await {
+            Err(error) if is_connection_error(&error) => {
+                warn!(%error, "retrying Redis operation after connection error");
+                shared.query(runtime, request).await
+            }
+            result => result,
+        }
+    }
+}
+
+impl Shared {
+    /// Execute the [`Request`] using a checked out [`MultiplexedConnection`]. After a connection
+    /// error, it's discarded along with every idle connection rather than being checked back in.
+    async fn query<T: FromRedisValue>(
+        self: &Arc<Self>,
+        runtime: &Arc<PoolRuntime>,
+        request: Request<'_>,
+    ) -> RedisResult<T> {
+        let _permit = self.permits.acquire().await.map_err(|_| {
+            connection_error(
+                io::ErrorKind::NotConnected,
+                "Redis connection pool is closed",
+            )
+        })?;
+
+        let mut connection = self.checkout(runtime).await?;
+
+        let result = request.query_async(&mut connection).await;
+        match &result {
+            // Idle connections were likely dropped alongside this one (eg/ server restart)
+            Err(error) if is_connection_error(error) => self.idle.lock().clear(),
+            _ => self.checkin(connection),
+        }
+
+        result
+    }
+
+    /// Check out an idle [`MultiplexedConnection`]. If there is none, make a single connection
+    /// attempt unless connections are already being re-established in the background.
+    async fn checkout(
+        self: &Arc<Self>,
+        runtime: &Arc<PoolRuntime>,
+    ) -> RedisResult<MultiplexedConnection> {
+        if let Some(connection) = self.idle.lock().pop() {
+            return Ok(connection);
+        }
+
+        if self.reconnecting.load(Ordering::Acquire) {
+            return Err(connection_error(
+                io::ErrorKind::NotConnected,
+                "Redis connection is being re-established in the background",
+            ));
+        }
+
+        match runtime.runtime().spawn(Arc::clone(self).connect()).await {
+            Ok(Ok(connection)) => Ok(connection),
+            Ok(Err(error)) => {
+                Self::reconnect_in_background(self, runtime);
+                Err(error)
+            }
+            Err(error) => Err(connection_error(
+                io::ErrorKind::Interrupted,
+                format!("Redis connection attempt was cancelled: {error}"),
+            )),
+        }
+    }
+
+    /// Check a [`MultiplexedConnection`] back in so it can be reused by the next operation.
+    fn checkin(&self, connection: MultiplexedConnection) {
+        let mut idle = self.idle.lock();
+        if idle.len() < self.cfg.max_connections {
+            idle.push(connection);
+        }
+    }
+
+    /// Establish a new [`MultiplexedConnection`].
+    async fn connect(self: Arc<Self>) -> RedisResult<MultiplexedConnection> {
+        tokio::time::timeout(
+            Duration::from_millis(self.cfg.connect_timeout_ms),
+            self.client.get_multiplexed_tokio_connection(),
+        )
+        .await
+        .map_err(|_| connection_error(io::ErrorKind::TimedOut, "timed out connecting to Redis"))?
+    }
+
+    /// Re-establish a [`MultiplexedConnection`] in a background task, retrying with exponential
+    /// backoff, and check it in once connected. Does nothing if the background task is already
+    /// running.
+    fn reconnect_in_background(shared: &Arc<Self>, runtime: &Arc<PoolRuntime>) {
+        if shared.reconnecting.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let shared = Arc::clone(shared);
+        runtime.runtime().spawn(async move {
+            let mut backoff = Duration::from_millis(shared.cfg.initial_backoff_ms);
+            let mut attempt = 1;
+            loop {
+                match Arc::clone(&shared).connect().await {
+                    Ok(connection) => {
+                        info!(attempt, "connected to Redis");
+                        shared.checkin(connection);
+                        break;
+                    }
+                    Err(error) if attempt >= shared.cfg.max_connect_attempts => {
+                        warn!(%error, attempt, "failed to connect to Redis, giving up");
+                        break;
+                    }
+                    Err(error) => {
+                        warn!(
+                            %error,
+                            attempt,
+                            backoff_ms = backoff.as_millis() as u64,
+                            "failed to connect to Redis, retrying after backoff"
+                        );
+                        tokio::time::sleep(backoff).await;
+                        backoff =
+                            (backoff * 2).min(Duration::from_millis(shared.cfg.max_backoff_ms));
+                        attempt += 1;
+                    }
+                }
+            }
+            shared.reconnecting.store(false, Ordering::Release);
+        });
+    }
+}
+
+impl Debug for RedisPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.connections {
+            Connections::Pooled { shared, .. } => f
+                .debug_struct("RedisPool")
+                .field("idle", &shared.idle.lock().len())
+                .field("available", &shared.permits.available_permits())
+                .field("reconnecting", &shared.reconnecting.load(Ordering::Acquire))
+                .field("cfg", &shared.cfg)
+                .finish(),
+            Connections::Single(_) => f
+                .debug_struct("RedisPool")
+                .field("connection", &"single")
+                .finish(),
+        }
+    }
+}
+
+/// Construct a [`RedisError`] recognised by [`is_connection_error`].
+fn connection_error(kind: io::ErrorKind, message: impl Into<String>) -> RedisError {
+    RedisError::from(io::Error::new(kind, message.into()))
+}
+
+/// Determines if the [`RedisError`] was caused by the connection rather than the command.
+pub fn is_connection_error(error: &RedisError) -> bool {
+    error.is_io_error()
+        || error.is_connection_dropped()
+        || error.is_connection_refusal()
+        || error.is_timeout()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::repository::redis::fake_server::FakeRedisServer;
+    use std::time::Instant;
+
+    fn config() -> Config {
+        Config {
+            max_connections: 2,
+            connect_timeout_ms: 500,
+            max_connect_attempts: 3,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 2,
+        }
+    }
+
+    #[test]
+    fn query_round_trips_commands_and_pipelines() {
+        let server = FakeRedisServer::start();
+        let pool = RedisPool::new(&server.uri, config()).unwrap();
+
+        pool.query_pipeline::<()>(redis::pipe().atomic().set("key", "value").ignore())
+            .unwrap();
+
+        assert_eq!(
+            pool.query::<Option<String>>(&Cmd::get("key")).unwrap(),
+            Some("value".to_owned())
+        );
+        assert_eq!(
+            pool.query::<Option<String>>(&Cmd::get("missing")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn cloned_pools_share_at_most_max_connections() {
+        let server = FakeRedisServer::start();
+        let pool = RedisPool::new(&server.uri, config()).unwrap();
+
+        let handles = (0..8)
+            .map(|index| {
+                let pool = pool.clone();
+                std::thread::spawn(move || {
+                    let key = format!("key_{index}");
+                    pool.query::<()>(&Cmd::set(&key, index)).unwrap();
+                    pool.query::<Option<u32>>(&Cmd::get(&key)).unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for (index, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join().unwrap(), Some(index as u32));
+        }
+        assert!((1..=config().max_connections).contains(&server.connection_count()));
+    }
+
+    #[test]
+    fn query_async_round_trips_commands_and_pipelines() {
+        let server = FakeRedisServer::start();
+        let pool = RedisPool::new(&server.uri, config()).unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let value = runtime.block_on(async {
+            pool.query_pipeline_async::<()>(redis::pipe().atomic().set("key", "value").ignore())
+                .await
+                .unwrap();
+            pool.query_async::<Option<String>>(&Cmd::get("key"))
+                .await
+                .unwrap()
+        });
+
+        assert_eq!(value, Some("value".to_owned()));
+    }
+
+    #[test]
+    fn query_inside_multi_threaded_runtime_does_not_stall_other_tasks() {
+        let server = FakeRedisServer::start();
+        let pool = RedisPool::new(&server.uri, config()).unwrap();
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let value = runtime.block_on(async move {
+            let ticker = tokio::spawn(tokio::time::sleep(Duration::from_millis(1)));
+            pool.query::<()>(&Cmd::set("key", "value")).unwrap();
+            ticker.await.unwrap();
+            pool.query::<Option<String>>(&Cmd::get("key")).unwrap()
+        });
+
+        assert_eq!(value, Some("value".to_owned()));
+    }
+
+    #[test]
+    fn query_reconnects_after_server_drops_connection() {
+        let server = FakeRedisServer::start();
+        let pool = RedisPool::new(&server.uri, config()).unwrap();
+        pool.query::<()>(&Cmd::set("key", "value")).unwrap();
+
+        server.drop_connections();
+
+        assert_eq!(
+            pool.query::<Option<String>>(&Cmd::get("key")).unwrap(),
+            Some("value".to_owned())
+        );
+    }
+
+    #[test]
+    fn single_connection_pool_queries_blocking_connection() {
+        let server = FakeRedisServer::start();
+        let connection = Client::open(server.uri.as_str())
+            .unwrap()
+            .get_connection()
+            .unwrap();
+        let pool = RedisPool::from_connection(connection);
+
+        pool.query::<()>(&Cmd::set("key", "value")).unwrap();
+        assert_eq!(
+            pool.query::<Option<String>>(&Cmd::get("key")).unwrap(),
+            Some("value".to_owned())
+        );
+    }
+
+    #[test]
+    fn unreachable_server_fails_fast_while_reconnecting_in_background() {
+        let pool = RedisPool::new(
+            "redis://127.0.0.1:1/",
+            Config {
+                max_connections: 1,
+                connect_timeout_ms: 50,
+                max_connect_attempts: 3,
+                initial_backoff_ms: 5_000,
+                max_backoff_ms: 5_000,
+            },
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        let error = pool.query::<()>(&Cmd::set("key", "value")).err().unwrap();
+
+        assert!(is_connection_error(&error));
+        assert!(start.elapsed() < Duration::from_millis(5_000));
+    }
+}
@@ -0,0 +1,298 @@
+use crate::portfolio::{
+    position::{Position, PositionId},
+    repository::{
+        error::RepositoryError, BalanceHandler, PositionHandler, StatisticHandler,
+        TransactionHandler,
+    },
+    Balance,
+};
+use barter_integration::model::{Market, MarketId};
+use parking_lot::{Condvar, Mutex};
+use std::{collections::HashMap, sync::Arc};
+use uuid::Uuid;
+
+/// [`Balance`] of every engine_id, shared by several Portfolios that each own a subset of the
+/// markets (eg/ the shards behind a
+/// [`ShardedPortfolioHandle`](crate::portfolio::actor::ShardedPortfolioHandle)).
+///
+/// A Portfolio updating the [`Balance`] inside a transaction holds the [`BalanceLedger`] until
+/// it's committed or rolled back, so concurrent read-modify-write updates from different shards
+/// are never lost.
+#[derive(Debug, Default)]
+pub struct BalanceLedger {
+    balances: Mutex<HashMap<Uuid, Balance>>,
+    held: Mutex<bool>,
+    released: Condvar,
+}
+
+impl BalanceLedger {
+    /// Constructs a new empty [`BalanceLedger`], ready to be shared between Portfolios.
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Block until no other Portfolio holds the [`BalanceLedger`], then hold it.
+    fn acquire(&self) {
+        let mut held = self.held.lock();
+        while *held {
+            self.released.wait(&mut held);
+        }
+        *held = true;
+    }
+
+    /// Release the [`BalanceLedger`] so the next waiting Portfolio can hold it.
+    fn release(&self) {
+        *self.held.lock() = false;
+        self.released.notify_one();
+    }
+}
+
+/// Repository wrapper that keeps the [`Balance`] in a [`BalanceLedger`] shared with other
+/// Portfolios, whilst the [`Position`]s & statistics of it's markets stay in the inner repository.
+///
+/// Every [`Balance`] write is also persisted in the inner repository, and a [`Balance`] missing
+/// from the [`BalanceLedger`] (eg/ after a restart) is loaded from it.
+#[derive(Debug)]
+pub struct SharedBalanceRepository<Repository> {
+    inner: Repository,
+    ledger: Arc<BalanceLedger>,
+    /// [`Balance`]s written in the active transaction, published to the [`BalanceLedger`] once
+    /// committed. `Some` whilst the [`BalanceLedger`] is held.
+    transaction: Option<HashMap<Uuid, Balance>>,
+}
+
+impl<Repository> SharedBalanceRepository<Repository> {
+    /// Constructs a new [`SharedBalanceRepository`] keeping the [`Balance`] in the provided
+    /// [`BalanceLedger`].
+    pub fn new(inner: Repository, ledger: Arc<BalanceLedger>) -> Self {
+        Self {
+            inner,
+            ledger,
+            transaction: None,
+        }
+    }
+}
+
+impl<Repository: PositionHandler> PositionHandler for SharedBalanceRepository<Repository> {
+    fn set_open_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.inner.set_open_position(engine_id, position)
+    }
+
+    fn get_open_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        self.inner.get_open_position(position_id)
+    }
+
+    fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+        &mut self,
+        engine_id: Uuid,
+        markets: Markets,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        self.inner.get_open_positions(engine_id, markets)
+    }
+
+    fn remove_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        self.inner.remove_position(position_id)
+    }
+
+    fn set_exited_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.inner.set_exited_position(engine_id, position)
+    }
+
+    fn get_exited_positions(&mut self, engine_id: Uuid) -> Result<Vec<Position>, RepositoryError> {
+        self.inner.get_exited_positions(engine_id)
+    }
+}
+
+impl<Repository: BalanceHandler> BalanceHandler for SharedBalanceRepository<Repository> {
+    fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
+        match &mut self.transaction {
+            Some(written) => {
+                self.inner.set_balance(engine_id, balance)?;
+                written.insert(engine_id, balance);
+            }
+            None => {
+                self.ledger.acquire();
+                let result = self.inner.set_balance(engine_id, balance);
+                if result.is_ok() {
+                    self.ledger.balances.lock().insert(engine_id, balance);
+                }
+                self.ledger.release();
+                result?;
+            }
+        }
+        Ok(())
+    }
+
+    fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+        if let Some(balance) = self
+            .transaction
+            .as_ref()
+            .and_then(|written| written.get(&engine_id))
+        {
+            return Ok(*balance);
+        }
+
+        if let Some(balance) = self.ledger.balances.lock().get(&engine_id) {
+            return Ok(*balance);
+        }
+
+        let balance = self.inner.get_balance(engine_id)?;
+        self.ledger
+            .balances
+            .lock()
+            .entry(engine_id)
+            .or_insert(balance);
+        Ok(balance)
+    }
+}
+
+impl<Repository, Statistic> StatisticHandler<Statistic> for SharedBalanceRepository<Repository>
+where
+    Repository: StatisticHandler<Statistic>,
+{
+    fn set_statistics(
+        &mut self,
+        market_id: MarketId,
+        statistic: Statistic,
+    ) -> Result<(), RepositoryError> {
+        self.inner.set_statistics(market_id, statistic)
+    }
+
+    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+        self.inner.get_statistics(market_id)
+    }
+}
+
+impl<Repository: TransactionHandler> TransactionHandler for SharedBalanceRepository<Repository> {
+    fn begin_transaction(&mut self) -> Result<(), RepositoryError> {
+        if self.transaction.is_some() {
+            return Err(RepositoryError::TransactionAlreadyActive);
+        }
+
+        self.inner.begin_transaction()?;
+        self.ledger.acquire();
+        self.transaction = Some(HashMap::new());
+        Ok(())
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), RepositoryError> {
+        let written = match self.transaction.take() {
+            Some(written) => written,
+            None => return self.inner.commit_transaction(),
+        };
+
+        let result = self.inner.commit_transaction();
+        if result.is_ok() {
+            self.ledger.balances.lock().extend(written);
+        }
+        self.ledger.release();
+        result
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), RepositoryError> {
+        if self.transaction.take().is_some() {
+            self.ledger.release();
+        }
+        self.inner.rollback_transaction()
+    }
+}
+
+impl<Repository> Drop for SharedBalanceRepository<Repository> {
+    fn drop(&mut self) {
+        // Never leave the BalanceLedger held by a transaction that can no longer complete
+        if self.transaction.take().is_some() {
+            self.ledger.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::repository::in_memory::InMemoryRepository,
+        statistic::summary::pnl::PnLReturnSummary,
+    };
+    use std::thread;
+
+    fn repository(
+        ledger: &Arc<BalanceLedger>,
+    ) -> SharedBalanceRepository<InMemoryRepository<PnLReturnSummary>> {
+        SharedBalanceRepository::new(InMemoryRepository::new(), Arc::clone(ledger))
+    }
+
+    #[test]
+    fn shared_balance_repositories_never_lose_concurrent_balance_updates() {
+        let engine_id = Uuid::new_v4();
+        let ledger = BalanceLedger::new();
+        repository(&ledger)
+            .set_balance(engine_id, Balance::default())
+            .unwrap();
+
+        let shards = (0..4)
+            .map(|_| {
+                let mut repository = repository(&ledger);
+                thread::spawn(move || {
+                    for _ in 0..250 {
+                        repository.begin_transaction().unwrap();
+                        let mut balance = repository.get_balance(engine_id).unwrap();
+                        balance.available += 1.0;
+                        repository.set_balance(engine_id, balance).unwrap();
+                        repository.commit_transaction().unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        shards.into_iter().for_each(|shard| shard.join().unwrap());
+
+        assert_eq!(
+            repository(&ledger)
+                .get_balance(engine_id)
+                .unwrap()
+                .available,
+            1000.0
+        );
+    }
+
+    #[test]
+    fn shared_balance_rollback_discards_balance_written_in_transaction() {
+        let engine_id = Uuid::new_v4();
+        let ledger = BalanceLedger::new();
+        let mut shard = repository(&ledger);
+        let mut other_shard = repository(&ledger);
+        shard.set_balance(engine_id, Balance::default()).unwrap();
+
+        shard.begin_transaction().unwrap();
+        shard
+            .set_balance(
+                engine_id,
+                Balance {
+                    available: 10.0,
+                    ..Balance::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(shard.get_balance(engine_id).unwrap().available, 10.0);
+        assert_eq!(other_shard.get_balance(engine_id).unwrap().available, 0.0);
+        shard.rollback_transaction().unwrap();
+
+        // BalanceLedger is released, so other shards can update the Balance again
+        other_shard.begin_transaction().unwrap();
+        other_shard.commit_transaction().unwrap();
+        assert_eq!(shard.get_balance(engine_id).unwrap().available, 0.0);
+    }
+}
@@ -0,0 +1,680 @@
+use crate::{
+    portfolio::{
+        position::{Position, PositionId},
+        repository::{
+            error::RepositoryError, BalanceHandler, PositionHandler, StatisticHandler,
+            TransactionHandler,
+        },
+        Balance,
+    },
+    statistic::summary::PositionSummariser,
+};
+use barter_integration::model::{Market, MarketId, Side};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Params};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+};
+use uuid::Uuid;
+
+/// Ordered schema migrations. The schema version of each migration is it's index + 1, and the
+/// current schema version is tracked using the SQLite `user_version` pragma. New migrations must
+/// be appended - existing migrations must never be edited.
+const MIGRATIONS: &[&str] = &[r#"
+    CREATE TABLE open_positions (
+        position_id TEXT PRIMARY KEY NOT NULL,
+        engine_id TEXT NOT NULL,
+        market_id TEXT NOT NULL,
+        side TEXT NOT NULL,
+        quantity REAL NOT NULL,
+        enter_time TEXT NOT NULL,
+        update_time TEXT NOT NULL,
+        position TEXT NOT NULL
+    );
+    CREATE INDEX open_positions_engine_market ON open_positions (engine_id, market_id);
+
+    CREATE TABLE exited_positions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        position_id TEXT NOT NULL,
+        engine_id TEXT NOT NULL,
+        market_id TEXT NOT NULL,
+        side TEXT NOT NULL,
+        quantity REAL NOT NULL,
+        enter_time TEXT NOT NULL,
+        exit_time TEXT NOT NULL,
+        realised_profit_loss REAL NOT NULL,
+        position TEXT NOT NULL
+    );
+    CREATE INDEX exited_positions_engine_exit_time ON exited_positions (engine_id, exit_time);
+    CREATE INDEX exited_positions_market_exit_time ON exited_positions (market_id, exit_time);
+
+    CREATE TABLE balances (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        engine_id TEXT NOT NULL,
+        time TEXT NOT NULL,
+        total REAL NOT NULL,
+        available REAL NOT NULL
+    );
+    CREATE INDEX balances_engine_time ON balances (engine_id, time);
+
+    CREATE TABLE statistics (
+        market_id TEXT PRIMARY KEY NOT NULL,
+        statistic TEXT NOT NULL
+    );
+"#];
+
+/// Configuration for constructing a [`SqliteRepository`] via the open() constructor method.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize)]
+pub struct Config {
+    /// Path of the SQLite database file, created if it does not exist.
+    pub path: String,
+}
+
+/// SQLite persisted repository that implements [`PositionHandler`], [`BalanceHandler`],
+/// [`StatisticHandler`] & [`TransactionHandler`]. Used by a Portfolio implementation to persist the Portfolio state.
+///
+/// Unlike the [`RedisRepository`](super::redis::RedisRepository), every [`Balance`] is appended
+/// to a history table, and exited [`Position`]s are indexed by engine, market & exit time so
+/// trade history can be analysed with SQL.
+pub struct SqliteRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    conn: Connection,
+    _statistic_marker: PhantomData<Statistic>,
+}
+
+impl<Statistic> PositionHandler for SqliteRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_open_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO open_positions \
+                (position_id, engine_id, market_id, side, quantity, enter_time, update_time, position) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    position.position_id,
+                    engine_id.to_string(),
+                    MarketId::new(&position.exchange, &position.instrument).0,
+                    side(position.side),
+                    position.quantity,
+                    timestamp(position.meta.enter_time),
+                    timestamp(position.meta.update_time),
+                    serde_json::to_string(&position)?,
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| RepositoryError::WriteError)
+    }
+
+    fn get_open_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        self.conn
+            .query_row(
+                "SELECT position FROM open_positions WHERE position_id = ?1",
+                params![position_id],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(|_| RepositoryError::ReadError)?
+            .map(|position| serde_json::from_str::<Position>(&position))
+            .transpose()
+            .map_err(RepositoryError::JsonSerDeError)
+    }
+
+    fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+        &mut self,
+        engine_id: Uuid,
+        markets: Markets,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        let engine_id = engine_id.to_string();
+
+        markets
+            .map(|market| {
+                self.query_positions(
+                    "SELECT position FROM open_positions \
+                    WHERE engine_id = ?1 AND market_id = ?2",
+                    params![engine_id, MarketId::from(market).0],
+                )
+            })
+            .collect::<Result<Vec<Vec<Position>>, RepositoryError>>()
+            .map(|positions| positions.into_iter().flatten().collect())
+    }
+
+    fn remove_position(
+        &mut self,
+        position_id: &String,
+    ) -> Result<Option<Position>, RepositoryError> {
+        let position = self.get_open_position(position_id)?;
+
+        self.conn
+            .execute(
+                "DELETE FROM open_positions WHERE position_id = ?1",
+                params![position_id],
+            )
+            .map_err(|_| RepositoryError::DeleteError)?;
+
+        Ok(position)
+    }
+
+    fn set_exited_position(
+        &mut self,
+        engine_id: Uuid,
+        position: Position,
+    ) -> Result<(), RepositoryError> {
+        self.conn
+            .execute(
+                "INSERT INTO exited_positions \
+                (position_id, engine_id, market_id, side, quantity, enter_time, exit_time, \
+                realised_profit_loss, position) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    position.position_id,
+                    engine_id.to_string(),
+                    MarketId::new(&position.exchange, &position.instrument).0,
+                    side(position.side),
+                    position.quantity,
+                    timestamp(position.meta.enter_time),
+                    timestamp(position.meta.update_time),
+                    position.realised_profit_loss,
+                    serde_json::to_string(&position)?,
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| RepositoryError::WriteError)
+    }
+
+    fn get_exited_positions(&mut self, engine_id: Uuid) -> Result<Vec<Position>, RepositoryError> {
+        self.query_positions(
+            "SELECT position FROM exited_positions WHERE engine_id = ?1 ORDER BY id",
+            params![engine_id.to_string()],
+        )
+    }
+}
+
+impl<Statistic> BalanceHandler for SqliteRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError> {
+        self.conn
+            .execute(
+                "INSERT INTO balances (engine_id, time, total, available) \
+                VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    engine_id.to_string(),
+                    timestamp(balance.time),
+                    balance.total,
+                    balance.available
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| RepositoryError::WriteError)
+    }
+
+    fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
+        self.conn
+            .query_row(
+                "SELECT time, total, available FROM balances \
+                WHERE engine_id = ?1 ORDER BY id DESC LIMIT 1",
+                params![engine_id.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()
+            .map_err(|_| RepositoryError::ReadError)?
+            .ok_or(RepositoryError::ExpectedDataNotPresentError)
+            .and_then(balance)
+    }
+}
+
+impl<Statistic> StatisticHandler<Statistic> for SqliteRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn set_statistics(
+        &mut self,
+        market_id: MarketId,
+        statistic: Statistic,
+    ) -> Result<(), RepositoryError> {
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO statistics (market_id, statistic) VALUES (?1, ?2)",
+                params![market_id.0, serde_json::to_string(&statistic)?],
+            )
+            .map(|_| ())
+            .map_err(|_| RepositoryError::WriteError)
+    }
+
+    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+        let statistic = self
+            .conn
+            .query_row(
+                "SELECT statistic FROM statistics WHERE market_id = ?1",
+                params![market_id.0],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(|_| RepositoryError::ReadError)?
+            .ok_or(RepositoryError::ExpectedDataNotPresentError)?;
+
+        serde_json::from_str(&statistic).map_err(RepositoryError::JsonSerDeError)
+    }
+}
+
+impl<Statistic> TransactionHandler for SqliteRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    /// Begin an SQLite transaction. Reads during the transaction observe it's own writes.
+    fn begin_transaction(&mut self) -> Result<(), RepositoryError> {
+        if !self.conn.is_autocommit() {
+            return Err(RepositoryError::TransactionAlreadyActive);
+        }
+
+        self.conn
+            .execute_batch("BEGIN IMMEDIATE")
+            .map_err(|_| RepositoryError::WriteError)
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), RepositoryError> {
+        if self.conn.is_autocommit() {
+            return Ok(());
+        }
+
+        self.conn
+            .execute_batch("COMMIT")
+            .map_err(|_| RepositoryError::WriteError)
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), RepositoryError> {
+        if self.conn.is_autocommit() {
+            return Ok(());
+        }
+
+        self.conn
+            .execute_batch("ROLLBACK")
+            .map_err(|_| RepositoryError::WriteError)
+    }
+}
+
+impl<Statistic> Debug for SqliteRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqliteRepository").finish()
+    }
+}
+
+impl<Statistic> SqliteRepository<Statistic>
+where
+    Statistic: PositionSummariser + Serialize + DeserializeOwned,
+{
+    /// Constructs a new [`SqliteRepository`] component using the provided SQLite connection,
+    /// migrating the database schema to the latest version.
+    pub fn new(mut connection: Connection) -> Result<Self, RepositoryError> {
+        migrate(&mut connection)?;
+
+        Ok(Self {
+            conn: connection,
+            _statistic_marker: PhantomData,
+        })
+    }
+
+    /// Open the SQLite database at the configured path & construct a new [`SqliteRepository`].
+    pub fn open(cfg: Config) -> Result<Self, RepositoryError> {
+        Connection::open(cfg.path)
+            .map_err(|_| RepositoryError::ReadError)
+            .and_then(Self::new)
+    }
+
+    /// Return every exited [`Position`] associated with the engine that exited within the
+    /// provided time range (start inclusive, end exclusive), ordered by exit time.
+    pub fn get_exited_positions_between(
+        &mut self,
+        engine_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        self.query_positions(
+            "SELECT position FROM exited_positions \
+            WHERE engine_id = ?1 AND exit_time >= ?2 AND exit_time < ?3 \
+            ORDER BY exit_time, id",
+            params![engine_id.to_string(), timestamp(start), timestamp(end)],
+        )
+    }
+
+    /// Return every exited [`Position`] of the [`Market`] that exited within the provided time
+    /// range (start inclusive, end exclusive), ordered by exit time.
+    pub fn get_market_exited_positions_between(
+        &mut self,
+        market_id: &MarketId,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        self.query_positions(
+            "SELECT position FROM exited_positions \
+            WHERE market_id = ?1 AND exit_time >= ?2 AND exit_time < ?3 \
+            ORDER BY exit_time, id",
+            params![market_id.0, timestamp(start), timestamp(end)],
+        )
+    }
+
+    /// Return the history of every [`Balance`] associated with the engine within the provided
+    /// time range (start inclusive, end exclusive), ordered by time.
+    pub fn get_balance_history(
+        &mut self,
+        engine_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Balance>, RepositoryError> {
+        let mut statement = self
+            .conn
+            .prepare(
+                "SELECT time, total, available FROM balances \
+                WHERE engine_id = ?1 AND time >= ?2 AND time < ?3 \
+                ORDER BY time, id",
+            )
+            .map_err(|_| RepositoryError::ReadError)?;
+
+        let rows = statement
+            .query_map(
+                params![engine_id.to_string(), timestamp(start), timestamp(end)],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .map_err(|_| RepositoryError::ReadError)?
+            .collect::<Result<Vec<(String, f64, f64)>, _>>()
+            .map_err(|_| RepositoryError::ReadError)?;
+
+        rows.into_iter().map(balance).collect()
+    }
+
+    /// Query the serialised [`Position`]s selected by the provided SQL statement.
+    fn query_positions<P: Params>(
+        &self,
+        sql: &str,
+        params: P,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        let mut statement = self
+            .conn
+            .prepare(sql)
+            .map_err(|_| RepositoryError::ReadError)?;
+
+        let positions = statement
+            .query_map(params, |row| row.get::<_, String>(0))
+            .map_err(|_| RepositoryError::ReadError)?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|_| RepositoryError::ReadError)?;
+
+        positions
+            .iter()
+            .map(|position| serde_json::from_str::<Position>(position))
+            .collect::<Result<Vec<Position>, serde_json::Error>>()
+            .map_err(RepositoryError::JsonSerDeError)
+    }
+}
+
+/// Apply every [`MIGRATIONS`] entry newer than the current schema version, each in it's own
+/// transaction.
+fn migrate(conn: &mut Connection) -> Result<(), RepositoryError> {
+    let current_version = conn
+        .query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))
+        .map_err(|_| RepositoryError::ReadError)?;
+
+    for (index, migration) in MIGRATIONS
+        .iter()
+        .enumerate()
+        .skip(current_version.max(0) as usize)
+    {
+        let version = index + 1;
+
+        let transaction = conn
+            .transaction()
+            .map_err(|_| RepositoryError::MigrationError(version))?;
+        transaction
+            .execute_batch(migration)
+            .and_then(|_| transaction.execute_batch(&format!("PRAGMA user_version = {version}")))
+            .map_err(|_| RepositoryError::MigrationError(version))?;
+        transaction
+            .commit()
+            .map_err(|_| RepositoryError::MigrationError(version))?;
+    }
+
+    Ok(())
+}
+
+/// Format a timestamp as a fixed precision RFC3339 string, so lexicographic ordering in SQL
+/// matches chronological ordering.
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+/// Parse a [`Balance`] from it's (time, total, available) columns.
+fn balance((time, total, available): (String, f64, f64)) -> Result<Balance, RepositoryError> {
+    Ok(Balance {
+        time: time
+            .parse::<DateTime<Utc>>()
+            .map_err(|_| RepositoryError::ReadError)?,
+        total,
+        available,
+    })
+}
+
+/// Column representation of a [`Position`] [`Side`].
+fn side(side: Side) -> &'static str {
+    match side {
+        Side::Buy => "buy",
+        Side::Sell => "sell",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::position::determine_position_id, statistic::summary::pnl::PnLReturnSummary,
+        statistic::summary::Initialiser, test_util::position,
+    };
+    use barter_integration::model::{Exchange, Instrument, InstrumentKind};
+    use chrono::Duration;
+
+    fn repository() -> SqliteRepository<PnLReturnSummary> {
+        SqliteRepository::new(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    fn engine_position(engine_id: Uuid, time: DateTime<Utc>) -> Position {
+        let mut position = position();
+        position.position_id =
+            determine_position_id(engine_id, &position.exchange, &position.instrument);
+        position.meta.enter_time = time;
+        position.meta.update_time = time;
+        position
+    }
+
+    #[test]
+    fn migrations_are_only_applied_once() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        migrate(&mut connection).unwrap();
+        migrate(&mut connection).unwrap();
+
+        let version = connection
+            .query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len() as i64);
+    }
+
+    #[test]
+    fn set_get_and_remove_open_positions() {
+        let mut repository = repository();
+        let engine_id = Uuid::new_v4();
+        let position = engine_position(engine_id, Utc::now());
+        let market = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+
+        repository
+            .set_open_position(engine_id, position.clone())
+            .unwrap();
+        assert_eq!(
+            repository.get_open_position(&position.position_id).unwrap(),
+            Some(position.clone())
+        );
+        assert_eq!(
+            repository
+                .get_open_positions(engine_id, [market.clone()].iter())
+                .unwrap(),
+            vec![position.clone()]
+        );
+        assert!(repository
+            .get_open_positions(Uuid::new_v4(), [market].iter())
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(
+            repository.remove_position(&position.position_id).unwrap(),
+            Some(position.clone())
+        );
+        assert_eq!(
+            repository.get_open_position(&position.position_id).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn query_exited_positions_by_time_range() {
+        let mut repository = repository();
+        let engine_id = Uuid::new_v4();
+        let start = Utc::now();
+
+        let exited = (0..3)
+            .map(|hour| engine_position(engine_id, start + Duration::hours(hour)))
+            .collect::<Vec<Position>>();
+        for position in &exited {
+            repository
+                .set_exited_position(engine_id, position.clone())
+                .unwrap();
+        }
+
+        assert_eq!(repository.get_exited_positions(engine_id).unwrap(), exited);
+
+        let actual = repository
+            .get_exited_positions_between(
+                engine_id,
+                start + Duration::hours(1),
+                start + Duration::hours(3),
+            )
+            .unwrap();
+        assert_eq!(actual, exited[1..]);
+
+        let market_id = MarketId::new(
+            &Exchange::from("binance"),
+            &Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+        );
+        let actual = repository
+            .get_market_exited_positions_between(&market_id, start, start + Duration::hours(1))
+            .unwrap();
+        assert_eq!(actual, exited[..1]);
+    }
+
+    #[test]
+    fn balance_history_is_kept() {
+        let mut repository = repository();
+        let engine_id = Uuid::new_v4();
+        let start = Utc::now();
+
+        assert!(matches!(
+            repository.get_balance(engine_id),
+            Err(RepositoryError::ExpectedDataNotPresentError)
+        ));
+
+        let balances = (0..3)
+            .map(|hour| Balance {
+                time: start + Duration::hours(hour),
+                total: 1000.0 + hour as f64,
+                available: 900.0,
+            })
+            .collect::<Vec<Balance>>();
+        for balance in &balances {
+            repository.set_balance(engine_id, *balance).unwrap();
+        }
+
+        assert_eq!(repository.get_balance(engine_id).unwrap(), balances[2]);
+        assert_eq!(
+            repository
+                .get_balance_history(engine_id, start, start + Duration::hours(2))
+                .unwrap(),
+            balances[..2]
+        );
+    }
+
+    #[test]
+    fn set_and_get_statistics() {
+        let mut repository = repository();
+        let market_id = MarketId("binance_eth_usdt_spot".to_owned());
+
+        assert!(matches!(
+            repository.get_statistics(&market_id),
+            Err(RepositoryError::ExpectedDataNotPresentError)
+        ));
+
+        let mut statistic = PnLReturnSummary::init(());
+        statistic.update(&position());
+        repository
+            .set_statistics(market_id.clone(), statistic)
+            .unwrap();
+
+        assert_eq!(
+            repository.get_statistics(&market_id).unwrap().total,
+            statistic.total
+        );
+    }
+
+    #[test]
+    fn rolled_back_transaction_is_discarded() {
+        let mut repository = repository();
+        let engine_id = Uuid::new_v4();
+        let position = engine_position(engine_id, Utc::now());
+        let balance = Balance {
+            time: Utc::now(),
+            total: 1000.0,
+            available: 1000.0,
+        };
+
+        repository.begin_transaction().unwrap();
+        repository.set_balance(engine_id, balance).unwrap();
+        repository.commit_transaction().unwrap();
+
+        repository.begin_transaction().unwrap();
+        assert!(matches!(
+            repository.begin_transaction(),
+            Err(RepositoryError::TransactionAlreadyActive)
+        ));
+        repository
+            .set_open_position(engine_id, position.clone())
+            .unwrap();
+        repository
+            .set_balance(
+                engine_id,
+                Balance {
+                    available: 900.0,
+                    ..balance
+                },
+            )
+            .unwrap();
+        repository.rollback_transaction().unwrap();
+
+        assert_eq!(repository.get_balance(engine_id).unwrap(), balance);
+        assert_eq!(
+            repository.get_open_position(&position.position_id).unwrap(),
+            None
+        );
+    }
+}
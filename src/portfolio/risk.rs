@@ -13,6 +13,32 @@ pub trait OrderEvaluator {
     fn evaluate_order(&self, order: OrderEvent) -> Option<OrderEvent>;
 }
 
+/// Object safe equivalent of [`OrderEvaluator`], implemented for every [`OrderEvaluator`]. Allows
+/// a risk manager to be selected at runtime as a `Box<dyn DynOrderEvaluator + Send>` (eg/ by
+/// name from an engine configuration file).
+pub trait DynOrderEvaluator {
+    /// Evaluate the [`OrderEvent`] using the underlying [`OrderEvaluator`].
+    fn evaluate_order_dyn(&self, order: OrderEvent) -> Option<OrderEvent>;
+}
+
+impl<RiskManager> DynOrderEvaluator for RiskManager
+where
+    RiskManager: OrderEvaluator,
+{
+    fn evaluate_order_dyn(&self, order: OrderEvent) -> Option<OrderEvent> {
+        self.evaluate_order(order)
+    }
+}
+
+impl OrderEvaluator for Box<dyn DynOrderEvaluator + Send> {
+    /// Unused by the boxed risk manager, which applies it's own default [`OrderType`].
+    const DEFAULT_ORDER_TYPE: OrderType = OrderType::Market;
+
+    fn evaluate_order(&self, order: OrderEvent) -> Option<OrderEvent> {
+        (**self).evaluate_order_dyn(order)
+    }
+}
+
 /// Default risk manager that implements [`OrderEvaluator`].
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
 pub struct DefaultRisk {}
@@ -0,0 +1,62 @@
+use crate::{
+    engine::outcome::{Termination, TraderFailure},
+    regression::{Difference, UPDATE_GOLDEN_ENV_VAR},
+};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Maximum number of [`Difference`]s displayed in a [`RegressionError::Mismatch`].
+const MAX_DISPLAYED_DIFFERENCES: usize = 25;
+
+/// All errors generated in the barter::regression module.
+#[derive(Error, Debug)]
+pub enum RegressionError {
+    #[error(
+        "Backtest did not stop cleanly: termination {termination:?}, trader failures {failures:?}"
+    )]
+    UncleanSession {
+        termination: Termination,
+        failures: Vec<TraderFailure>,
+    },
+
+    #[error("{}", display_mismatch(path, differences))]
+    Mismatch {
+        path: PathBuf,
+        differences: Vec<Difference>,
+    },
+
+    #[error(
+        "Golden file {} does not exist, set the {} environment variable to write it",
+        .0.display(),
+        UPDATE_GOLDEN_ENV_VAR
+    )]
+    MissingGoldenFile(PathBuf),
+
+    #[error("Failed to read or write golden file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to serialise or deserialise Snapshot: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Display every [`Difference`] (up to [`MAX_DISPLAYED_DIFFERENCES`]) on it's own line.
+fn display_mismatch(path: &Path, differences: &[Difference]) -> String {
+    let mut display = format!(
+        "Snapshot does not match golden file {} ({} differences):",
+        path.display(),
+        differences.len()
+    );
+
+    for difference in differences.iter().take(MAX_DISPLAYED_DIFFERENCES) {
+        display.push_str(&format!("\n  {}", difference));
+    }
+
+    if differences.len() > MAX_DISPLAYED_DIFFERENCES {
+        display.push_str(&format!(
+            "\n  ... and {} more",
+            differences.len() - MAX_DISPLAYED_DIFFERENCES
+        ));
+    }
+
+    display
+}
@@ -0,0 +1,468 @@
+use crate::{
+    data::MarketGenerator,
+    engine::{outcome::SessionOutcome, Engine},
+    event::{Event, MessageTransmitter},
+    execution::ExecutionClient,
+    portfolio::{
+        repository::{BalanceHandler, PositionHandler, StatisticHandler},
+        FillUpdater, MarketInitialiser, MarketUpdater, OrderGenerator,
+    },
+    regression::error::RegressionError,
+    statistic::summary::{PositionSummariser, TableBuilder},
+    strategy::SignalGenerator,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fmt::{Display, Formatter},
+    fs,
+    path::PathBuf,
+};
+use tokio::sync::mpsc;
+
+/// Barter regression module specific errors.
+pub mod error;
+
+/// Environment variable that, when set, overwrites golden files with the latest [`Snapshot`]
+/// rather than comparing against them.
+pub const UPDATE_GOLDEN_ENV_VAR: &str = "BARTER_UPDATE_GOLDEN";
+
+/// Recorded result of a backtest [`Engine`] run, containing the full [`Event`] stream and the
+/// final statistics. Serialised as JSON to allow comparison with a golden file.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Snapshot {
+    /// Every [`Event`] produced during the backtest, in the order they were produced.
+    pub events: Vec<Value>,
+    /// Final statistics for each market traded, keyed by
+    /// [`MarketId`](barter_integration::model::MarketId).
+    pub market_statistics: Value,
+    /// Final statistics across all markets traded.
+    pub statistics: Value,
+}
+
+impl Snapshot {
+    /// Constructs a new [`Snapshot`] from the [`Event`]s & [`SessionOutcome`] of a backtest.
+    pub fn new<Events, Statistic>(
+        events: Events,
+        outcome: &SessionOutcome<Statistic>,
+    ) -> Result<Self, RegressionError>
+    where
+        Events: IntoIterator<Item = Event>,
+        Statistic: Serialize,
+    {
+        Ok(Self {
+            events: events
+                .into_iter()
+                .map(|event| serde_json::to_value(&event))
+                .collect::<Result<Vec<Value>, _>>()?,
+            market_statistics: serde_json::to_value(&outcome.summary.markets)?,
+            statistics: serde_json::to_value(&outcome.summary.total)?,
+        })
+    }
+}
+
+/// Run the backtest [`Engine`] to completion and record a [`Snapshot`] of the [`Event`]s received
+/// on the provided `event_rx`, and the final statistics.
+///
+/// For the [`Snapshot`] to be deterministic the [`Engine`] must be constructed with a fixed
+/// engine_id & historical data, and contain a single [`Trader`](crate::engine::trader::Trader)
+/// (the [`Event`]s of concurrent [`Trader`](crate::engine::trader::Trader)s interleave
+/// non-deterministically).
+pub async fn record<EventTx, Statistic, Portfolio, Data, Strategy, Execution>(
+    engine: Engine<EventTx, Statistic, Portfolio, Data, Strategy, Execution>,
+    mut event_rx: mpsc::UnboundedReceiver<Event>,
+) -> Result<Snapshot, RegressionError>
+where
+    EventTx: MessageTransmitter<Event> + Send + 'static,
+    Statistic: PositionSummariser + TableBuilder + Serialize + Send + 'static,
+    Portfolio: PositionHandler
+        + BalanceHandler
+        + StatisticHandler<Statistic>
+        + MarketUpdater
+        + MarketInitialiser
+        + OrderGenerator
+        + FillUpdater
+        + Send
+        + 'static,
+    Data: MarketGenerator + Send + 'static,
+    Strategy: SignalGenerator + Send + 'static,
+    Execution: ExecutionClient + Send + 'static,
+{
+    let outcome = engine.run().await;
+
+    if !outcome.is_clean() {
+        return Err(RegressionError::UncleanSession {
+            termination: outcome.termination,
+            failures: outcome.trader_failures,
+        });
+    }
+
+    // Traders have stopped organically, so every Event has already been sent
+    let mut events = Vec::new();
+    while let Ok(event) = event_rx.try_recv() {
+        events.push(event);
+    }
+
+    Snapshot::new(events, &outcome)
+}
+
+/// Tolerance used when comparing floating point numbers. Two numbers are considered equal if the
+/// absolute difference between them is within the absolute tolerance, or within the relative
+/// tolerance scaled by the largest magnitude of the two.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            absolute: 1e-9,
+            relative: 1e-9,
+        }
+    }
+}
+
+impl Tolerance {
+    /// Determines if the two numbers are equal within this [`Tolerance`].
+    pub fn approx_eq(&self, expected: f64, actual: f64) -> bool {
+        let difference = (expected - actual).abs();
+        let largest = expected.abs().max(actual.abs());
+        expected == actual || difference <= self.absolute || difference <= self.relative * largest
+    }
+}
+
+/// Difference between an expected & actual JSON [`Value`] at a path (eg/
+/// `events[12].Fill.fill_value_gross`). A missing [`Value`] is represented by `None`.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Difference {
+    pub path: String,
+    pub expected: Option<Value>,
+    pub actual: Option<Value>,
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let display = |value: &Option<Value>| match value {
+            Some(value) => value.to_string(),
+            None => "<missing>".to_owned(),
+        };
+
+        write!(
+            f,
+            "{}: expected {}, actual {}",
+            self.path,
+            display(&self.expected),
+            display(&self.actual)
+        )
+    }
+}
+
+/// Compare the expected & actual JSON [`Value`]s, returning every [`Difference`] between them.
+/// Numbers are compared using the provided [`Tolerance`], and object keys contained in
+/// `ignored_keys` are skipped (eg/ wall-clock timestamps produced by custom components).
+pub fn diff(
+    expected: &Value,
+    actual: &Value,
+    tolerance: Tolerance,
+    ignored_keys: &[String],
+) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    diff_at(
+        String::new(),
+        expected,
+        actual,
+        tolerance,
+        ignored_keys,
+        &mut differences,
+    );
+    differences
+}
+
+fn diff_at(
+    path: String,
+    expected: &Value,
+    actual: &Value,
+    tolerance: Tolerance,
+    ignored_keys: &[String],
+    differences: &mut Vec<Difference>,
+) {
+    let join = |key: &str| match path.is_empty() {
+        true => key.to_owned(),
+        false => format!("{}.{}", path, key),
+    };
+
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected_value) in expected {
+                if ignored_keys.iter().any(|ignored| ignored == key) {
+                    continue;
+                }
+
+                match actual.get(key) {
+                    Some(actual_value) => diff_at(
+                        join(key),
+                        expected_value,
+                        actual_value,
+                        tolerance,
+                        ignored_keys,
+                        differences,
+                    ),
+                    None => differences.push(Difference {
+                        path: join(key),
+                        expected: Some(expected_value.clone()),
+                        actual: None,
+                    }),
+                }
+            }
+
+            for (key, actual_value) in actual {
+                if !expected.contains_key(key) && !ignored_keys.iter().any(|ignored| ignored == key)
+                {
+                    differences.push(Difference {
+                        path: join(key),
+                        expected: None,
+                        actual: Some(actual_value.clone()),
+                    });
+                }
+            }
+        }
+
+        (Value::Array(expected), Value::Array(actual)) => {
+            for index in 0..expected.len().max(actual.len()) {
+                let path = format!("{}[{}]", path, index);
+                match (expected.get(index), actual.get(index)) {
+                    (Some(expected), Some(actual)) => {
+                        diff_at(path, expected, actual, tolerance, ignored_keys, differences)
+                    }
+                    (expected, actual) => differences.push(Difference {
+                        path,
+                        expected: expected.cloned(),
+                        actual: actual.cloned(),
+                    }),
+                }
+            }
+        }
+
+        (Value::Number(expected_number), Value::Number(actual_number)) => {
+            let matches = match (expected_number.as_f64(), actual_number.as_f64()) {
+                (Some(expected), Some(actual)) => tolerance.approx_eq(expected, actual),
+                _ => expected_number == actual_number,
+            };
+
+            if !matches {
+                differences.push(Difference {
+                    path,
+                    expected: Some(expected.clone()),
+                    actual: Some(actual.clone()),
+                });
+            }
+        }
+
+        (expected, actual) => {
+            if expected != actual {
+                differences.push(Difference {
+                    path,
+                    expected: Some(expected.clone()),
+                    actual: Some(actual.clone()),
+                });
+            }
+        }
+    }
+}
+
+/// Golden file containing the expected [`Snapshot`] of a backtest.
+///
+/// If the [`UPDATE_GOLDEN_ENV_VAR`] environment variable is set, the golden file is (re)written
+/// with the latest [`Snapshot`] rather than compared.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GoldenFile {
+    pub path: PathBuf,
+    pub tolerance: Tolerance,
+    pub ignored_keys: Vec<String>,
+}
+
+impl GoldenFile {
+    /// Constructs a new [`GoldenFile`] at the provided path, using the default [`Tolerance`].
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            tolerance: Tolerance::default(),
+            ignored_keys: Vec::new(),
+        }
+    }
+
+    /// Use the provided [`Tolerance`] when comparing numbers.
+    pub fn tolerance(self, tolerance: Tolerance) -> Self {
+        Self { tolerance, ..self }
+    }
+
+    /// Skip object keys with the provided name when comparing.
+    pub fn ignore_key<K: Into<String>>(mut self, key: K) -> Self {
+        self.ignored_keys.push(key.into());
+        self
+    }
+
+    /// Verify the provided [`Snapshot`] matches this [`GoldenFile`]. Returns a
+    /// [`RegressionError::MissingGoldenFile`] if it does not exist, unless the
+    /// [`UPDATE_GOLDEN_ENV_VAR`] environment variable is set.
+    pub fn verify(&self, snapshot: &Snapshot) -> Result<(), RegressionError> {
+        if std::env::var_os(UPDATE_GOLDEN_ENV_VAR).is_some() {
+            return self.write(snapshot);
+        }
+
+        if !self.path.exists() {
+            return Err(RegressionError::MissingGoldenFile(self.path.clone()));
+        }
+
+        let expected = serde_json::from_str::<Value>(&fs::read_to_string(&self.path)?)?;
+        let actual = serde_json::to_value(snapshot)?;
+
+        let differences = diff(&expected, &actual, self.tolerance, &self.ignored_keys);
+        match differences.is_empty() {
+            true => Ok(()),
+            false => Err(RegressionError::Mismatch {
+                path: self.path.clone(),
+                differences,
+            }),
+        }
+    }
+
+    /// Verify the provided [`Snapshot`] matches this [`GoldenFile`], panicking with a readable
+    /// diff if it does not.
+    pub fn assert_matches(&self, snapshot: &Snapshot) {
+        if let Err(error) = self.verify(snapshot) {
+            panic!("{}", error)
+        }
+    }
+
+    /// Write the provided [`Snapshot`] to this [`GoldenFile`] as pretty JSON.
+    fn write(&self, snapshot: &Snapshot) -> Result<(), RegressionError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut json = serde_json::to_string_pretty(snapshot)?;
+        json.push('\n');
+        fs::write(&self.path, json).map_err(RegressionError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn tolerance_approx_eq() {
+        struct TestCase {
+            expected: f64,
+            actual: f64,
+            result: bool,
+        }
+
+        let tolerance = Tolerance {
+            absolute: 1e-6,
+            relative: 1e-3,
+        };
+
+        let test_cases = vec![
+            TestCase {
+                // Test case 0: Identical
+                expected: 100.0,
+                actual: 100.0,
+                result: true,
+            },
+            TestCase {
+                // Test case 1: Within absolute tolerance
+                expected: 0.0,
+                actual: 1e-7,
+                result: true,
+            },
+            TestCase {
+                // Test case 2: Within relative tolerance
+                expected: 10_000.0,
+                actual: 10_001.0,
+                result: true,
+            },
+            TestCase {
+                // Test case 3: Outside both tolerances
+                expected: 1.0,
+                actual: 1.01,
+                result: false,
+            },
+        ];
+
+        for (index, test) in test_cases.into_iter().enumerate() {
+            let actual = tolerance.approx_eq(test.expected, test.actual);
+            assert_eq!(actual, test.result, "Test case {} failed", index);
+        }
+    }
+
+    #[test]
+    fn diff_reports_paths_of_differences() {
+        let expected = json!({
+            "events": [
+                { "Fill": { "time": "2022-04-05T20:00:00Z", "fill_value_gross": 100.0 } },
+                { "Balance": { "total": 1000.0 } }
+            ],
+            "statistics": { "pnl": { "total": 10.0, "trades": 2 } }
+        });
+
+        let actual = json!({
+            "events": [
+                { "Fill": { "time": "2022-10-18T12:00:00Z", "fill_value_gross": 100.000000000001 } }
+            ],
+            "statistics": { "pnl": { "total": 12.5, "trades": 2, "win_rate": 0.5 } }
+        });
+
+        let actual = diff(
+            &expected,
+            &actual,
+            Tolerance::default(),
+            &["time".to_owned()],
+        );
+
+        let expected = vec![
+            Difference {
+                path: "events[1]".to_owned(),
+                expected: Some(json!({ "Balance": { "total": 1000.0 } })),
+                actual: None,
+            },
+            Difference {
+                path: "statistics.pnl.total".to_owned(),
+                expected: Some(json!(10.0)),
+                actual: Some(json!(12.5)),
+            },
+            Difference {
+                path: "statistics.pnl.win_rate".to_owned(),
+                expected: None,
+                actual: Some(json!(0.5)),
+            },
+        ];
+
+        assert_eq!(actual, expected);
+        assert_eq!(
+            actual[1].to_string(),
+            "statistics.pnl.total: expected 10.0, actual 12.5"
+        );
+    }
+
+    #[test]
+    fn verify_missing_golden_file_fails_without_writing_it() {
+        let path = std::env::temp_dir().join(format!("{}.json", uuid::Uuid::new_v4()));
+        let snapshot = Snapshot {
+            events: Vec::new(),
+            market_statistics: json!({}),
+            statistics: json!({}),
+        };
+
+        let actual = GoldenFile::new(&path).verify(&snapshot);
+
+        assert!(
+            matches!(actual, Err(RegressionError::MissingGoldenFile(missing)) if missing == path)
+        );
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,53 @@
+use crate::engine::error::EngineError;
+use hyper::StatusCode;
+use thiserror::Error;
+
+/// All errors generated in the barter::server module.
+#[derive(Error, Debug)]
+pub enum ServerError {
+    #[error("Failed to build struct due to missing attributes: {0}")]
+    BuilderIncomplete(&'static str),
+
+    #[error("Invalid ServerConfig: {0}")]
+    InvalidConfig(&'static str),
+
+    #[error("Missing or invalid API token")]
+    Unauthorised,
+
+    #[error("No route for {0}")]
+    NotFound(String),
+
+    #[error("Method not allowed for {0}")]
+    MethodNotAllowed(String),
+
+    #[error("Invalid request: {0}")]
+    BadRequest(String),
+
+    #[error("Failed to send Command since the Engine has stopped")]
+    EngineStopped,
+
+    #[error("Engine failed to action Command: {0}")]
+    Engine(#[from] EngineError),
+
+    #[error("Failed to serialise response: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("HTTP server error: {0}")]
+    Http(#[from] hyper::Error),
+}
+
+impl ServerError {
+    /// HTTP [`StatusCode`] communicating this [`ServerError`] to the client.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ServerError::Unauthorised => StatusCode::UNAUTHORIZED,
+            ServerError::NotFound(_) => StatusCode::NOT_FOUND,
+            ServerError::MethodNotAllowed(_) => StatusCode::METHOD_NOT_ALLOWED,
+            ServerError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ServerError::EngineStopped => StatusCode::SERVICE_UNAVAILABLE,
+            ServerError::Engine(EngineError::MarketAlreadyTraded(_)) => StatusCode::CONFLICT,
+            ServerError::Engine(EngineError::MarketNotTraded(_)) => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{event::sink::MetricsTx, metrics, server::error::ServerError};
+use hyper::{
+    header::{HeaderValue, CONTENT_TYPE},
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, StatusCode,
+};
+use std::{convert::Infallible, future::Future, net::SocketAddr};
+use tracing::info;
+
+/// Content type of the Prometheus text exposition format.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Serves the [`EventMetrics`](crate::event::sink::EventMetrics) aggregated by a [`MetricsTx`] in
+/// the Prometheus text exposition format on GET /metrics, so they can be scraped by a Prometheus
+/// server. Unlike the [`Server`](super::Server) it is unauthenticated, so should only be bound to
+/// a local or private address.
+#[derive(Debug)]
+pub struct MetricsExporter {
+    address: SocketAddr,
+    metrics: MetricsTx,
+}
+
+impl MetricsExporter {
+    /// Constructs a new [`MetricsExporter`] serving the metrics aggregated by the provided
+    /// [`MetricsTx`] on the provided address.
+    pub fn new(address: SocketAddr, metrics: MetricsTx) -> Self {
+        Self { address, metrics }
+    }
+
+    /// Binds the [`MetricsExporter`] to it's address, returning the bound address & a future that
+    /// serves requests until the provided shutdown future resolves. Must be called from within a
+    /// tokio runtime.
+    pub fn bind<Shutdown>(
+        self,
+        shutdown: Shutdown,
+    ) -> Result<(SocketAddr, impl Future<Output = Result<(), ServerError>>), ServerError>
+    where
+        Shutdown: Future<Output = ()>,
+    {
+        let metrics = self.metrics;
+        let make_service = make_service_fn(move |_connection| {
+            let metrics = metrics.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let response = handle(&metrics, request);
+                    async move { Ok::<_, Infallible>(response) }
+                }))
+            }
+        });
+
+        let server = hyper::Server::try_bind(&self.address)?.serve(make_service);
+        let address = server.local_addr();
+        info!(%address, "MetricsExporter listening");
+
+        let server = server.with_graceful_shutdown(shutdown);
+        Ok((
+            address,
+            async move { server.await.map_err(ServerError::from) },
+        ))
+    }
+
+    /// Serve requests until the provided shutdown future resolves.
+    pub async fn run<Shutdown>(self, shutdown: Shutdown) -> Result<(), ServerError>
+    where
+        Shutdown: Future<Output = ()>,
+    {
+        let (_, server) = self.bind(shutdown)?;
+        server.await
+    }
+}
+
+fn handle(metrics: &MetricsTx, request: Request<Body>) -> Response<Body> {
+    let (status, body) = match (request.method(), request.uri().path()) {
+        (&Method::GET, "/metrics") => (StatusCode::OK, metrics::render(&metrics.metrics())),
+        (_, "/metrics") => (StatusCode::METHOD_NOT_ALLOWED, String::new()),
+        _ => (StatusCode::NOT_FOUND, String::new()),
+    };
+
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(PROMETHEUS_CONTENT_TYPE),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::{InstrumentKind, Market};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    #[tokio::test]
+    async fn metrics_exporter_serves_rendered_metrics() {
+        let metrics = MetricsTx::new();
+        metrics.record_feed_unhealthy(&Market::new(
+            "binance",
+            ("btc", "usdt", InstrumentKind::Spot),
+        ));
+
+        let (address, exporter) = MetricsExporter::new("127.0.0.1:0".parse().unwrap(), metrics)
+            .bind(futures::future::pending())
+            .unwrap();
+        tokio::spawn(exporter);
+
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("content-type: text/plain; version=0.0.4"));
+        assert!(
+            response.contains("barter_feed_unhealthy_total{market=\"binance_btc_usdt_spot\"} 1")
+        );
+    }
+}
@@ -0,0 +1,660 @@
+use crate::{engine::Command, event::sink::BroadcastEventTx, server::error::ServerError};
+use barter_integration::model::Market;
+use hyper::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use std::{convert::Infallible, fmt::Debug, future::Future, net::SocketAddr, sync::Arc};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{info, warn};
+
+/// Barter server module specific errors.
+pub mod error;
+
+/// Unauthenticated endpoint serving the [`EventMetrics`](crate::event::sink::EventMetrics) in the
+/// Prometheus text exposition format.
+pub mod exporter;
+
+/// WebSocket endpoint streaming the [`Event`](crate::event::Event) feed to clients as JSON.
+pub mod websocket;
+
+/// Configuration for constructing a [`Server`].
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ServerConfig {
+    /// Socket address the [`Server`] listens on (eg/ 127.0.0.1:8080).
+    pub address: SocketAddr,
+    /// Token clients must provide as an `Authorization: Bearer <token>` header, or as a `token`
+    /// query parameter when a header cannot be set (eg/ browser WebSockets).
+    pub api_token: String,
+}
+
+impl Debug for ServerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerConfig")
+            .field("address", &self.address)
+            .field("api_token", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Embedded HTTP & WebSocket server for remotely controlling & monitoring an
+/// [`Engine`](crate::engine::Engine). Every request is translated into a [`Command`] sent to the
+/// [`Engine`](crate::engine::Engine) `command_rx`.
+///
+/// | Method | Path            | Action                                                            |
+/// |--------|-----------------|-------------------------------------------------------------------|
+/// | GET    | /positions      | Fetch the open Positions                                          |
+/// | GET    | /balance        | Fetch the current Balance                                         |
+/// | GET    | /statistics     | Fetch the statistics of every [`Market`]                          |
+/// | POST   | /positions/legs | Fetch the open Position legs of the JSON [`Market`] body          |
+/// | POST   | /positions/exit | Exit the JSON [`Market`] body's Position, or every Position       |
+/// | POST   | /markets        | Add the JSON [`Market`] body to the Engine                        |
+/// | POST   | /markets/remove | Exit the JSON [`Market`] body's Position & retire it's Trader     |
+/// | POST   | /terminate      | Terminate the Engine, using the optional text body as the message |
+/// | GET    | /events         | WebSocket streaming every Event as JSON                           |
+#[derive(Debug)]
+pub struct Server {
+    config: ServerConfig,
+    state: Arc<ServerState>,
+}
+
+/// State shared by every request handled by the [`Server`].
+#[derive(Debug)]
+struct ServerState {
+    api_token: String,
+    command_tx: mpsc::Sender<Command>,
+    events: Option<BroadcastEventTx>,
+}
+
+/// Message used to terminate the [`Engine`](crate::engine::Engine) if the /terminate request has
+/// no body.
+const DEFAULT_TERMINATE_MESSAGE: &str = "terminated via server";
+
+impl Server {
+    /// Builder to construct [`Server`] instances.
+    pub fn builder() -> ServerBuilder {
+        ServerBuilder::new()
+    }
+
+    /// Binds the [`Server`] to it's configured address, returning the bound address & a future
+    /// that serves requests until the provided shutdown future resolves. Must be called from
+    /// within a tokio runtime.
+    pub fn bind<Shutdown>(
+        self,
+        shutdown: Shutdown,
+    ) -> Result<(SocketAddr, impl Future<Output = Result<(), ServerError>>), ServerError>
+    where
+        Shutdown: Future<Output = ()>,
+    {
+        let state = self.state;
+        let make_service = make_service_fn(move |_connection| {
+            let state = Arc::clone(&state);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    handle(Arc::clone(&state), request)
+                }))
+            }
+        });
+
+        let server = hyper::Server::try_bind(&self.config.address)?.serve(make_service);
+        let address = server.local_addr();
+        info!(%address, "Server listening");
+
+        let server = server.with_graceful_shutdown(shutdown);
+        Ok((
+            address,
+            async move { server.await.map_err(ServerError::from) },
+        ))
+    }
+
+    /// Serve requests until the provided shutdown future resolves.
+    pub async fn run<Shutdown>(self, shutdown: Shutdown) -> Result<(), ServerError>
+    where
+        Shutdown: Future<Output = ()>,
+    {
+        let (_, server) = self.bind(shutdown)?;
+        server.await
+    }
+}
+
+/// Handle a request, converting any [`ServerError`] into a JSON error response.
+async fn handle(
+    state: Arc<ServerState>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let method = request.method().clone();
+    let path = request.uri().path().to_owned();
+
+    let response = match route(&state, request).await {
+        Ok(response) => response,
+        Err(error) => {
+            warn!(%method, %path, %error, "Server failed to handle request");
+            json_response(
+                error.status(),
+                &serde_json::json!({ "error": error.to_string() }),
+            )
+        }
+    };
+
+    Ok(response)
+}
+
+/// Authenticate the request & route it to the relevant handler.
+async fn route(state: &ServerState, request: Request<Body>) -> Result<Response<Body>, ServerError> {
+    if !authorised(&state.api_token, &request) {
+        return Err(ServerError::Unauthorised);
+    }
+
+    let method = request.method().clone();
+    let path = request.uri().path().to_owned();
+    let route = format!("{} {}", method, path);
+
+    match (&method, path.as_str()) {
+        (&Method::GET, "/positions") => {
+            let positions = request_command(state, Command::FetchOpenPositions).await?;
+            Ok(json_response(StatusCode::OK, &positions))
+        }
+        (&Method::GET, "/balance") => {
+            let balance = request_command(state, Command::FetchBalance).await?;
+            Ok(json_response(StatusCode::OK, &balance))
+        }
+        (&Method::GET, "/statistics") => {
+            let statistics = request_command(state, Command::FetchStatistics).await?;
+            Ok(json_response(StatusCode::OK, &statistics))
+        }
+        (&Method::POST, "/positions/legs") => {
+            let market = market_body(request).await?;
+            let legs =
+                request_command(state, |legs_tx| Command::FetchLegs(market, legs_tx)).await?;
+            Ok(json_response(StatusCode::OK, &legs))
+        }
+        (&Method::POST, "/positions/exit") => {
+            let body = hyper::body::to_bytes(request.into_body()).await?;
+            let command = if body.is_empty() {
+                Command::ExitAllPositions
+            } else {
+                serde_json::from_slice::<Market>(&body)
+                    .map(Command::ExitPosition)
+                    .map_err(|error| ServerError::BadRequest(error.to_string()))?
+            };
+
+            send_command(state, command).await
+        }
+        (&Method::POST, "/markets") => {
+            let market = market_body(request).await?;
+            request_command(state, |added_tx| Command::AddMarket(market, added_tx)).await?;
+            Ok(json_response(
+                StatusCode::OK,
+                &serde_json::json!({ "status": "added" }),
+            ))
+        }
+        (&Method::POST, "/markets/remove") => {
+            let market = market_body(request).await?;
+            request_command(state, |removed_tx| {
+                Command::RemoveMarket(market, removed_tx)
+            })
+            .await?;
+            Ok(json_response(
+                StatusCode::OK,
+                &serde_json::json!({ "status": "removed" }),
+            ))
+        }
+        (&Method::POST, "/terminate") => {
+            let body = hyper::body::to_bytes(request.into_body()).await?;
+            let message = match String::from_utf8_lossy(&body).trim() {
+                "" => DEFAULT_TERMINATE_MESSAGE.to_owned(),
+                message => message.to_owned(),
+            };
+
+            send_command(state, Command::Terminate(message)).await
+        }
+        (&Method::GET, "/events") => match &state.events {
+            Some(events) => websocket::upgrade(request, events.subscribe()),
+            None => Err(ServerError::NotFound(route)),
+        },
+        (
+            _,
+            "/positions" | "/balance" | "/statistics" | "/positions/legs" | "/positions/exit"
+            | "/markets" | "/markets/remove" | "/terminate",
+        ) => Err(ServerError::MethodNotAllowed(route)),
+        _ => Err(ServerError::NotFound(route)),
+    }
+}
+
+/// Deserialise the JSON [`Market`] body of the request.
+async fn market_body(request: Request<Body>) -> Result<Market, ServerError> {
+    let body = hyper::body::to_bytes(request.into_body()).await?;
+    serde_json::from_slice(&body).map_err(|error| ServerError::BadRequest(error.to_string()))
+}
+
+/// Determine if the request provides the API token via it's `Authorization` header or `token`
+/// query parameter.
+fn authorised(api_token: &str, request: &Request<Body>) -> bool {
+    let header_token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "));
+
+    let query_token = request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|parameter| parameter.strip_prefix("token="))
+    });
+
+    header_token
+        .into_iter()
+        .chain(query_token)
+        .any(|token| constant_time_eq(token.as_bytes(), api_token.as_bytes()))
+}
+
+/// Compare two byte slices in constant time (for equal lengths) to avoid leaking the API token
+/// via response timings.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Send a fire-and-forget [`Command`] to the [`Engine`](crate::engine::Engine).
+async fn send_command(
+    state: &ServerState,
+    command: Command,
+) -> Result<Response<Body>, ServerError> {
+    state
+        .command_tx
+        .send(command)
+        .await
+        .map_err(|_| ServerError::EngineStopped)?;
+
+    Ok(json_response(
+        StatusCode::ACCEPTED,
+        &serde_json::json!({ "status": "accepted" }),
+    ))
+}
+
+/// Send a [`Command`] to the [`Engine`](crate::engine::Engine) & await it's response.
+async fn request_command<Output, Error>(
+    state: &ServerState,
+    command: impl FnOnce(oneshot::Sender<Result<Output, Error>>) -> Command,
+) -> Result<Output, ServerError>
+where
+    ServerError: From<Error>,
+{
+    let (response_tx, response_rx) = oneshot::channel();
+
+    state
+        .command_tx
+        .send(command(response_tx))
+        .await
+        .map_err(|_| ServerError::EngineStopped)?;
+
+    response_rx
+        .await
+        .map_err(|_| ServerError::EngineStopped)?
+        .map_err(ServerError::from)
+}
+
+/// Construct a JSON [`Response`] with the provided [`StatusCode`].
+fn json_response<T: Serialize>(status: StatusCode, body: &T) -> Response<Body> {
+    let (status, body) = match serde_json::to_vec(body) {
+        Ok(body) => (status, body),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("{{\"error\":\"failed to serialise response: {}\"}}", error).into_bytes(),
+        ),
+    };
+
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        hyper::header::HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+/// Builder to construct [`Server`] instances.
+#[derive(Debug, Default)]
+pub struct ServerBuilder {
+    config: Option<ServerConfig>,
+    command_tx: Option<mpsc::Sender<Command>>,
+    events: Option<BroadcastEventTx>,
+}
+
+impl ServerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn config(self, value: ServerConfig) -> Self {
+        Self {
+            config: Some(value),
+            ..self
+        }
+    }
+
+    pub fn command_tx(self, value: mpsc::Sender<Command>) -> Self {
+        Self {
+            command_tx: Some(value),
+            ..self
+        }
+    }
+
+    /// [`BroadcastEventTx`] the [`Engine`](crate::engine::Engine)'s
+    /// [`Trader`](crate::engine::trader::Trader)s send [`Event`](crate::event::Event)s to. The
+    /// /events WebSocket is only served if this is provided.
+    pub fn events(self, value: BroadcastEventTx) -> Self {
+        Self {
+            events: Some(value),
+            ..self
+        }
+    }
+
+    pub fn build(self) -> Result<Server, ServerError> {
+        let config = self
+            .config
+            .ok_or(ServerError::BuilderIncomplete("config"))?;
+
+        // An empty token would authorise every request providing an empty Bearer token
+        if config.api_token.trim().is_empty() {
+            return Err(ServerError::InvalidConfig("api_token must not be empty"));
+        }
+
+        Ok(Server {
+            state: Arc::new(ServerState {
+                api_token: config.api_token.clone(),
+                command_tx: self
+                    .command_tx
+                    .ok_or(ServerError::BuilderIncomplete("command_tx"))?,
+                events: self.events,
+            }),
+            config,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::error::EngineError,
+        event::Event,
+        portfolio::{position::PositionLeg, Balance},
+        test_util::position,
+    };
+    use barter_integration::model::InstrumentKind;
+    use chrono::{DateTime, Utc};
+    use futures::StreamExt;
+    use std::collections::BTreeMap;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+        sync::broadcast,
+    };
+    use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message};
+
+    const API_TOKEN: &str = "secret";
+
+    fn balance() -> Balance {
+        Balance {
+            time: DateTime::<Utc>::MIN_UTC,
+            total: 1000.0,
+            available: 900.0,
+        }
+    }
+
+    /// Spawn a [`Server`] on an ephemeral port, returning it's address & the receiver of the
+    /// [`Command`]s it sends.
+    fn spawn_server(events: Option<BroadcastEventTx>) -> (SocketAddr, mpsc::Receiver<Command>) {
+        let (command_tx, command_rx) = mpsc::channel(10);
+
+        let mut builder = Server::builder()
+            .config(ServerConfig {
+                address: "127.0.0.1:0".parse().unwrap(),
+                api_token: API_TOKEN.to_owned(),
+            })
+            .command_tx(command_tx);
+        if let Some(events) = events {
+            builder = builder.events(events);
+        }
+
+        let (address, server) = builder
+            .build()
+            .unwrap()
+            .bind(futures::future::pending())
+            .unwrap();
+        tokio::spawn(server);
+
+        (address, command_rx)
+    }
+
+    /// Send a raw HTTP/1.1 request, returning the response status code & body.
+    async fn http(
+        address: SocketAddr,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+        body: &str,
+    ) -> (u16, String) {
+        let authorization = token
+            .map(|token| format!("Authorization: Bearer {}\r\n", token))
+            .unwrap_or_default();
+
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream
+            .write_all(
+                format!(
+                    "{} {} HTTP/1.1\r\nHost: localhost\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    method, path, authorization, body.len(), body
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        let status = response[9..12].parse().unwrap();
+        let body = response
+            .split_once("\r\n\r\n")
+            .map(|(_, body)| body.to_owned())
+            .unwrap_or_default();
+        (status, body)
+    }
+
+    #[test]
+    fn server_builder_rejects_empty_api_token() {
+        for api_token in ["", "   ", "\t\n"] {
+            let (command_tx, _command_rx) = mpsc::channel(10);
+            let result = Server::builder()
+                .config(ServerConfig {
+                    address: "127.0.0.1:0".parse().unwrap(),
+                    api_token: api_token.to_owned(),
+                })
+                .command_tx(command_tx)
+                .build();
+
+            assert!(
+                matches!(result, Err(ServerError::InvalidConfig(_))),
+                "api_token: {:?}",
+                api_token
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn server_rejects_unauthorised_requests() {
+        let (address, _command_rx) = spawn_server(None);
+
+        assert_eq!(http(address, "GET", "/balance", None, "").await.0, 401);
+        assert_eq!(
+            http(address, "GET", "/balance", Some("wrong"), "").await.0,
+            401
+        );
+        assert_eq!(
+            http(address, "GET", "/unknown", Some(API_TOKEN), "")
+                .await
+                .0,
+            404
+        );
+        assert_eq!(
+            http(address, "DELETE", "/balance", Some(API_TOKEN), "")
+                .await
+                .0,
+            405
+        );
+    }
+
+    #[tokio::test]
+    async fn server_fetches_balance_and_statistics_from_engine() {
+        let (address, mut command_rx) = spawn_server(None);
+
+        tokio::spawn(async move {
+            while let Some(command) = command_rx.recv().await {
+                match command {
+                    Command::FetchBalance(balance_tx) => balance_tx.send(Ok(balance())).unwrap(),
+                    Command::FetchStatistics(statistics_tx) => statistics_tx
+                        .send(Ok(BTreeMap::from([(
+                            "binance_eth_usdt_spot".to_owned(),
+                            serde_json::json!({ "trades": 1 }),
+                        )])))
+                        .unwrap(),
+                    Command::FetchOpenPositions(positions_tx) => positions_tx
+                        .send(Err(EngineError::RepositoryInteractionError(
+                            crate::portfolio::repository::error::RepositoryError::ReadError,
+                        )))
+                        .unwrap(),
+                    Command::FetchLegs(_, legs_tx) => legs_tx
+                        .send(Ok(vec![PositionLeg::from(&position())]))
+                        .unwrap(),
+                    _ => {}
+                }
+            }
+        });
+
+        let (status, body) = http(address, "GET", "/balance", Some(API_TOKEN), "").await;
+        assert_eq!(status, 200);
+        assert_eq!(serde_json::from_str::<Balance>(&body).unwrap(), balance());
+
+        let (status, body) = http(address, "GET", "/statistics", Some(API_TOKEN), "").await;
+        assert_eq!(status, 200);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+            serde_json::json!({ "binance_eth_usdt_spot": { "trades": 1 } })
+        );
+
+        let (status, _) = http(address, "GET", "/positions", Some(API_TOKEN), "").await;
+        assert_eq!(status, 500);
+
+        let market = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+        let body = serde_json::to_string(&market).unwrap();
+        let (status, body) = http(address, "POST", "/positions/legs", Some(API_TOKEN), &body).await;
+        assert_eq!(status, 200);
+        assert_eq!(
+            serde_json::from_str::<Vec<PositionLeg>>(&body).unwrap(),
+            vec![PositionLeg::from(&position())]
+        );
+    }
+
+    #[tokio::test]
+    async fn server_sends_commands_to_engine() {
+        let (address, mut command_rx) = spawn_server(None);
+
+        let (status, _) = http(address, "POST", "/positions/exit", Some(API_TOKEN), "").await;
+        assert_eq!(status, 202);
+        assert!(matches!(
+            command_rx.recv().await,
+            Some(Command::ExitAllPositions)
+        ));
+
+        let market = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+        let body = serde_json::to_string(&market).unwrap();
+        let (status, _) = http(address, "POST", "/positions/exit", Some(API_TOKEN), &body).await;
+        assert_eq!(status, 202);
+        match command_rx.recv().await {
+            Some(Command::ExitPosition(exited)) => assert_eq!(exited, market),
+            other => panic!("expected Command::ExitPosition, received: {:?}", other),
+        }
+
+        let (status, _) = http(address, "POST", "/positions/exit", Some(API_TOKEN), "{").await;
+        assert_eq!(status, 400);
+
+        let engine = tokio::spawn(async move {
+            match command_rx.recv().await {
+                Some(Command::AddMarket(added, added_tx)) => {
+                    assert_eq!(added, market);
+                    added_tx.send(Ok(())).unwrap()
+                }
+                other => panic!("expected Command::AddMarket, received: {:?}", other),
+            };
+            match command_rx.recv().await {
+                Some(Command::RemoveMarket(removed, removed_tx)) => removed_tx
+                    .send(Err(EngineError::MarketNotTraded(removed)))
+                    .unwrap(),
+                other => panic!("expected Command::RemoveMarket, received: {:?}", other),
+            };
+            command_rx
+        });
+
+        let (status, _) = http(address, "POST", "/markets", Some(API_TOKEN), &body).await;
+        assert_eq!(status, 200);
+        let (status, _) = http(address, "POST", "/markets/remove", Some(API_TOKEN), &body).await;
+        assert_eq!(status, 404);
+        let mut command_rx = engine.await.unwrap();
+
+        let (status, _) = http(
+            address,
+            "POST",
+            "/terminate",
+            Some(API_TOKEN),
+            "maintenance",
+        )
+        .await;
+        assert_eq!(status, 202);
+        assert!(matches!(
+            command_rx.recv().await,
+            Some(Command::Terminate(message)) if message == "maintenance"
+        ));
+
+        // Engine stopped
+        drop(command_rx);
+        let (status, _) = http(address, "POST", "/terminate", Some(API_TOKEN), "").await;
+        assert_eq!(status, 503);
+    }
+
+    #[tokio::test]
+    async fn server_streams_events_over_websocket() {
+        let (event_tx, _) = broadcast::channel(10);
+        let events = BroadcastEventTx::new(event_tx);
+        let (address, _command_rx) = spawn_server(Some(events.clone()));
+
+        // Unauthorised WebSocket handshake is rejected
+        let url = format!("ws://{}/events", address);
+        assert!(tokio_tungstenite::connect_async(url.as_str())
+            .await
+            .is_err());
+
+        let mut request = url.into_client_request().unwrap();
+        request.headers_mut().insert(
+            AUTHORIZATION,
+            format!("Bearer {}", API_TOKEN).parse().unwrap(),
+        );
+        let (mut websocket, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+
+        // Connection subscribes to the Events before the handshake response is sent
+        let mut events_tx = events.clone();
+        crate::event::MessageTransmitter::send(&mut events_tx, Event::Balance(balance()));
+
+        match websocket.next().await {
+            Some(Ok(Message::Text(text))) => {
+                assert_eq!(
+                    serde_json::from_str::<Event>(&text).unwrap(),
+                    Event::Balance(balance())
+                );
+            }
+            other => panic!("expected Event text message, received: {:?}", other),
+        }
+    }
+}
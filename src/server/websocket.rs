@@ -0,0 +1,102 @@
+use crate::{event::Event, server::error::ServerError};
+use futures::{SinkExt, StreamExt};
+use hyper::{
+    header::{HeaderValue, CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, UPGRADE},
+    upgrade::Upgraded,
+    Body, Request, Response, StatusCode,
+};
+use tokio::sync::broadcast;
+use tokio_tungstenite::{
+    tungstenite::{handshake::derive_accept_key, protocol::Role, Message},
+    WebSocketStream,
+};
+use tracing::{debug, warn};
+
+/// Accept a WebSocket upgrade request & spawn a task streaming every [`Event`] received by the
+/// provided broadcast receiver to the client as a JSON text message.
+pub fn upgrade(
+    request: Request<Body>,
+    event_rx: broadcast::Receiver<Event>,
+) -> Result<Response<Body>, ServerError> {
+    let is_websocket = matches!(
+        request.headers().get(UPGRADE).and_then(|upgrade| upgrade.to_str().ok()),
+        Some(upgrade) if upgrade.eq_ignore_ascii_case("websocket")
+    );
+    if !is_websocket {
+        return Err(ServerError::BadRequest(
+            "expected a WebSocket upgrade request".to_owned(),
+        ));
+    }
+
+    let accept_key = request
+        .headers()
+        .get(SEC_WEBSOCKET_KEY)
+        .map(|key| derive_accept_key(key.as_bytes()))
+        .ok_or_else(|| ServerError::BadRequest("missing Sec-WebSocket-Key header".to_owned()))?;
+
+    tokio::spawn(async move {
+        match hyper::upgrade::on(request).await {
+            Ok(upgraded) => {
+                let websocket =
+                    WebSocketStream::from_raw_socket(upgraded, Role::Server, None).await;
+                stream_events(websocket, event_rx).await;
+            }
+            Err(error) => warn!(%error, "failed to upgrade connection to WebSocket"),
+        }
+    });
+
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = StatusCode::SWITCHING_PROTOCOLS;
+    let headers = response.headers_mut();
+    headers.insert(CONNECTION, HeaderValue::from_static("upgrade"));
+    headers.insert(UPGRADE, HeaderValue::from_static("websocket"));
+    headers.insert(
+        SEC_WEBSOCKET_ACCEPT,
+        HeaderValue::from_str(&accept_key)
+            .map_err(|error| ServerError::BadRequest(error.to_string()))?,
+    );
+
+    Ok(response)
+}
+
+/// Stream every [`Event`] to the WebSocket client until it disconnects or the [`Event`] feed
+/// closes. Clients that fall behind skip the [`Event`]s they missed.
+async fn stream_events(
+    mut websocket: WebSocketStream<Upgraded>,
+    mut event_rx: broadcast::Receiver<Event>,
+) {
+    loop {
+        tokio::select! {
+            event = event_rx.recv() => match event {
+                Ok(event) => {
+                    let message = match serde_json::to_string(&event) {
+                        Ok(message) => message,
+                        Err(error) => {
+                            warn!(%error, "failed to serialise Event for WebSocket client");
+                            continue;
+                        }
+                    };
+
+                    if websocket.send(Message::Text(message)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "WebSocket client lagging behind Event feed, skipping Events");
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    let _ = websocket.close(None).await;
+                    break;
+                }
+            },
+
+            message = websocket.next() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Pings are answered by tungstenite, any other client messages are ignored
+                Some(Ok(_)) => {}
+            }
+        }
+    }
+
+    debug!("WebSocket client disconnected from Event feed");
+}
@@ -1,12 +1,25 @@
 use crate::statistic::algorithm::welford_online;
+use crate::statistic::{de_f64_non_finite_from_str, se_f64_non_finite_as_str};
 use serde::{Deserialize, Serialize};
 
 /// Representation of a dataset using measures of dispersion - range, variance & standard deviation.
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
 pub struct Dispersion {
     pub range: Range,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub recurrence_relation_m: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub variance: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub std_dev: f64,
 }
 
@@ -39,7 +52,15 @@ impl Dispersion {
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
 pub struct Range {
     pub activated: bool,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub high: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub low: f64,
 }
 
@@ -1,7 +1,10 @@
 use crate::statistic::algorithm::welford_online;
 use crate::statistic::dispersion::Range;
 use crate::statistic::metric::EquityPoint;
-use crate::statistic::{de_duration_from_secs, se_duration_as_secs};
+use crate::statistic::{
+    de_duration_from_secs, de_f64_non_finite_from_str, se_duration_as_secs,
+    se_f64_non_finite_as_str,
+};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -12,6 +15,10 @@ use serde::{Deserialize, Serialize};
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct Drawdown {
     pub equity_range: Range,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub drawdown: f64,
     pub start_time: DateTime<Utc>,
     #[serde(
@@ -145,6 +152,10 @@ impl MaxDrawdown {
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct AvgDrawdown {
     pub count: u64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub mean_drawdown: f64,
     #[serde(
         deserialize_with = "de_duration_from_secs",
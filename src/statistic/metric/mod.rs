@@ -10,8 +10,8 @@ pub mod ratio;
 /// Total equity at a point in time - equates to [`Balance.total`](Balance).
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct EquityPoint {
-    time: DateTime<Utc>,
-    total: f64,
+    pub time: DateTime<Utc>,
+    pub total: f64,
 }
 
 impl Default for EquityPoint {
@@ -49,6 +49,29 @@ impl PositionSummariser for EquityPoint {
     }
 }
 
+/// Derives the strategy equity curve from the provided [`Position`]s. The curve starts with the
+/// starting equity at the first [`Position`] enter time, followed by the exit [`Balance`] of every
+/// exited [`Position`] in time order.
+pub fn equity_curve(starting_equity: f64, positions: &[Position]) -> Vec<EquityPoint> {
+    let mut exits = positions
+        .iter()
+        .filter_map(|position| position.meta.exit_balance.map(EquityPoint::from))
+        .collect::<Vec<EquityPoint>>();
+
+    exits.sort_by_key(|equity| equity.time);
+
+    let start = positions
+        .iter()
+        .map(|position| position.meta.enter_time)
+        .min()
+        .map(|time| EquityPoint {
+            time,
+            total: starting_equity,
+        });
+
+    start.into_iter().chain(exits).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +160,54 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn equity_curve_starts_with_starting_equity_and_skips_open_positions() {
+        fn exited_position(
+            enter_time: DateTime<Utc>,
+            exit_time: DateTime<Utc>,
+            total: f64,
+        ) -> Position {
+            let mut position = position();
+            position.meta.enter_time = enter_time;
+            position.meta.exit_balance = Some(Balance {
+                time: exit_time,
+                total,
+                available: total,
+            });
+            position
+        }
+
+        let base_time = Utc::now();
+        let day = |days: i64| base_time.add(Duration::days(days));
+
+        let mut open_position = position();
+        open_position.meta.enter_time = day(4);
+        open_position.meta.exit_balance = None;
+
+        let positions = vec![
+            exited_position(day(3), day(6), 90.0),
+            exited_position(day(1), day(2), 110.0),
+            open_position,
+        ];
+
+        let actual = equity_curve(100.0, &positions);
+
+        let expected = vec![
+            EquityPoint {
+                time: day(1),
+                total: 100.0,
+            },
+            EquityPoint {
+                time: day(2),
+                total: 110.0,
+            },
+            EquityPoint {
+                time: day(6),
+                total: 90.0,
+            },
+        ];
+
+        assert_eq!(actual, expected);
+    }
 }
@@ -1,4 +1,5 @@
 use crate::statistic::summary::pnl::PnLReturnSummary;
+use crate::statistic::{de_f64_non_finite_from_str, se_f64_non_finite_as_str};
 use serde::{Deserialize, Serialize};
 
 pub trait Ratio {
@@ -16,7 +17,15 @@ pub trait Ratio {
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct SharpeRatio {
     pub risk_free_return: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub trades_per_day: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub sharpe_ratio_per_trade: f64,
 }
 
@@ -57,7 +66,15 @@ impl SharpeRatio {
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct SortinoRatio {
     pub risk_free_return: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub trades_per_day: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub sortino_ratio_per_trade: f64,
 }
 
@@ -98,7 +115,15 @@ impl SortinoRatio {
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct CalmarRatio {
     pub risk_free_return: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub trades_per_day: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub calmar_ratio_per_trade: f64,
 }
 
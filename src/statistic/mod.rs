@@ -5,6 +5,7 @@ pub mod algorithm;
 pub mod dispersion;
 pub mod error;
 pub mod metric;
+pub mod report;
 pub mod summary;
 
 /// Serialize a [`Duration`] into a `u64` representing the associated seconds.
@@ -23,3 +24,78 @@ where
     let seconds: i64 = Deserialize::deserialize(deserializer)?;
     Ok(Duration::seconds(seconds))
 }
+
+/// Serialize an `f64` statistic, representing non-finite values as the strings "NaN", "inf" &
+/// "-inf" since formats such as JSON have no representation for them (eg/ serde_json writes
+/// `null`, which cannot be deserialized back into an `f64`).
+pub fn se_f64_non_finite_as_str<S>(value: &f64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        value if value.is_nan() => serializer.serialize_str("NaN"),
+        value if *value == f64::INFINITY => serializer.serialize_str("inf"),
+        value if *value == f64::NEG_INFINITY => serializer.serialize_str("-inf"),
+        value => serializer.serialize_f64(*value),
+    }
+}
+
+/// Deserialize an `f64` statistic serialized via [`se_f64_non_finite_as_str`]. A `null` value
+/// (eg/ written by serde_json before non-finite values were represented as strings) is
+/// deserialized as NaN.
+pub fn de_f64_non_finite_from_str<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum F64OrStr {
+        F64(f64),
+        Str(String),
+    }
+
+    match Option::<F64OrStr>::deserialize(deserializer)? {
+        None => Ok(f64::NAN),
+        Some(F64OrStr::F64(value)) => Ok(value),
+        Some(F64OrStr::Str(value)) => match value.as_str() {
+            "NaN" => Ok(f64::NAN),
+            "inf" => Ok(f64::INFINITY),
+            "-inf" => Ok(f64::NEG_INFINITY),
+            other => Err(serde::de::Error::custom(format!(
+                "invalid non-finite f64 statistic: {other}"
+            ))),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Statistic {
+        #[serde(
+            deserialize_with = "de_f64_non_finite_from_str",
+            serialize_with = "se_f64_non_finite_as_str"
+        )]
+        value: f64,
+    }
+
+    #[test]
+    fn f64_statistic_round_trips_non_finite_values_through_json() {
+        for value in [1.5, -0.25, f64::INFINITY, f64::NEG_INFINITY] {
+            let json = serde_json::to_string(&Statistic { value }).unwrap();
+            let actual: Statistic = serde_json::from_str(&json).unwrap();
+            assert_eq!(actual.value, value, "json: {json}");
+        }
+
+        let json = serde_json::to_string(&Statistic { value: f64::NAN }).unwrap();
+        assert_eq!(json, r#"{"value":"NaN"}"#);
+        let actual: Statistic = serde_json::from_str(&json).unwrap();
+        assert!(actual.value.is_nan());
+
+        let legacy: Statistic = serde_json::from_str(r#"{"value":null}"#).unwrap();
+        assert!(legacy.value.is_nan());
+    }
+}
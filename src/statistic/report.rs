@@ -0,0 +1,266 @@
+use crate::statistic::summary::{
+    session::{position_record, row_contents, SessionSummary, POSITION_TITLES},
+    TableBuilder,
+};
+use std::fmt::Write;
+
+/// Width of the generated SVG charts in pixels.
+const CHART_WIDTH: f64 = 960.0;
+
+/// Height of the generated SVG charts in pixels.
+const CHART_HEIGHT: f64 = 280.0;
+
+/// Padding between the SVG chart edges & the plotted data in pixels.
+const CHART_PADDING: f64 = 40.0;
+
+const STYLE: &str = "\
+body { font-family: -apple-system, Helvetica, Arial, sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.5em; } h2 { font-size: 1.2em; margin-top: 2em; }
+table { border-collapse: collapse; font-size: 0.85em; }
+th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: right; white-space: nowrap; }
+th { background: #f3f3f3; } td:first-child, th:first-child { text-align: left; }
+svg { background: #fafafa; border: 1px solid #ddd; }
+.axis { stroke: #999; stroke-width: 1; } .label { font-size: 11px; fill: #555; }
+.equity { fill: none; stroke: #1f6feb; stroke-width: 2; }
+.win { fill: #2da44e; } .loss { fill: #cf222e; }";
+
+/// Generate a self-contained static HTML report for the provided [`SessionSummary`]. All styling &
+/// charts are inlined, so the report can be shared as a single file.
+pub fn html<Statistic>(summary: &SessionSummary<Statistic>) -> String
+where
+    Statistic: TableBuilder + Clone,
+{
+    let mut html = String::new();
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+        <title>Trading Session {engine_id}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n\
+        <h1>Trading Session {engine_id}</h1>\n",
+        engine_id = summary.engine_id,
+    );
+
+    // Statistics Table
+    html.push_str("<h2>Statistics</h2>\n");
+    let mut titles = vec![String::new()];
+    titles.extend(row_contents(&summary.total.titles()));
+    let rows = summary.statistic_rows().map(|(market_id, statistic)| {
+        std::iter::once(market_id)
+            .chain(row_contents(&statistic.row()))
+            .collect()
+    });
+    push_table(&mut html, &titles, rows);
+
+    // Equity Curve Chart
+    html.push_str("<h2>Equity Curve</h2>\n");
+    let equity = summary
+        .equity_curve
+        .iter()
+        .map(|equity| equity.total)
+        .collect::<Vec<f64>>();
+    html.push_str(&equity_curve_svg(&equity));
+
+    // Trade PnL Chart
+    html.push_str("<h2>Trade PnL</h2>\n");
+    let trade_pnl = summary
+        .exited_positions
+        .iter()
+        .map(|position| position.realised_profit_loss)
+        .collect::<Vec<f64>>();
+    html.push_str(&trade_pnl_svg(&trade_pnl));
+
+    // Exited Position Blotter
+    html.push_str("<h2>Exited Positions</h2>\n");
+    let titles = POSITION_TITLES
+        .iter()
+        .map(|title| title.to_string())
+        .collect::<Vec<String>>();
+    push_table(
+        &mut html,
+        &titles,
+        summary.exited_positions.iter().map(position_record),
+    );
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Append a HTML table with the provided titles & rows.
+fn push_table<Rows>(html: &mut String, titles: &[String], rows: Rows)
+where
+    Rows: IntoIterator<Item = Vec<String>>,
+{
+    html.push_str("<table>\n<tr>");
+    for title in titles {
+        let _ = write!(html, "<th>{}</th>", escape(title));
+    }
+    html.push_str("</tr>\n");
+
+    for row in rows {
+        html.push_str("<tr>");
+        for cell in row {
+            let _ = write!(html, "<td>{}</td>", escape(&cell));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+}
+
+/// Generate an SVG line chart of the equity curve.
+fn equity_curve_svg(equity: &[f64]) -> String {
+    let mut svg = svg_open();
+
+    if let Some((min, max)) = range(equity.iter().copied()) {
+        let step = plot_width() / (equity.len().max(2) - 1) as f64;
+        let points = equity
+            .iter()
+            .enumerate()
+            .map(|(index, total)| {
+                format!(
+                    "{:.2},{:.2}",
+                    CHART_PADDING + index as f64 * step,
+                    scale_y(*total, min, max)
+                )
+            })
+            .collect::<Vec<String>>();
+
+        let _ = writeln!(
+            svg,
+            "<polyline class=\"equity\" points=\"{}\"/>",
+            points.join(" ")
+        );
+        push_y_labels(&mut svg, min, max);
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Generate an SVG bar chart of the realised PnL of each trade.
+fn trade_pnl_svg(trade_pnl: &[f64]) -> String {
+    let mut svg = svg_open();
+
+    // Include zero in the range so bars always extend from the zero line
+    if let Some((min, max)) = range(trade_pnl.iter().copied().chain([0.0])) {
+        let zero = scale_y(0.0, min, max);
+        let bar_width = plot_width() / trade_pnl.len().max(1) as f64;
+
+        for (index, pnl) in trade_pnl.iter().enumerate() {
+            let y = scale_y(*pnl, min, max);
+            let _ = writeln!(
+                svg,
+                "<rect class=\"{}\" x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\"/>",
+                if pnl.is_sign_negative() {
+                    "loss"
+                } else {
+                    "win"
+                },
+                CHART_PADDING + index as f64 * bar_width,
+                y.min(zero),
+                (bar_width * 0.8).max(1.0),
+                (y - zero).abs(),
+            );
+        }
+
+        let _ = writeln!(
+            svg,
+            "<line class=\"axis\" x1=\"{CHART_PADDING}\" y1=\"{zero:.2}\" x2=\"{:.2}\" y2=\"{zero:.2}\"/>",
+            CHART_WIDTH - CHART_PADDING,
+        );
+        push_y_labels(&mut svg, min, max);
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Opening tag of an SVG chart.
+fn svg_open() -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{CHART_HEIGHT}\" \
+        viewBox=\"0 0 {CHART_WIDTH} {CHART_HEIGHT}\">\n"
+    )
+}
+
+/// Append labels for the minimum & maximum values of the y-axis.
+fn push_y_labels(svg: &mut String, min: f64, max: f64) {
+    let _ = writeln!(
+        svg,
+        "<text class=\"label\" x=\"2\" y=\"{:.2}\">{max:.2}</text>\n\
+        <text class=\"label\" x=\"2\" y=\"{:.2}\">{min:.2}</text>",
+        CHART_PADDING - 4.0,
+        CHART_HEIGHT - CHART_PADDING + 12.0,
+    );
+}
+
+/// Width available to plot data within a chart.
+fn plot_width() -> f64 {
+    CHART_WIDTH - 2.0 * CHART_PADDING
+}
+
+/// Scale a value within the range [min, max] to the y-coordinate of a chart.
+fn scale_y(value: f64, min: f64, max: f64) -> f64 {
+    let plot_height = CHART_HEIGHT - 2.0 * CHART_PADDING;
+    match max - min == 0.0 {
+        true => CHART_HEIGHT / 2.0,
+        false => CHART_PADDING + (max - value) / (max - min) * plot_height,
+    }
+}
+
+/// Minimum & maximum finite values, or `None` if there are none.
+fn range<Values>(values: Values) -> Option<(f64, f64)>
+where
+    Values: IntoIterator<Item = f64>,
+{
+    values
+        .into_iter()
+        .filter(|value| value.is_finite())
+        .fold(None, |range, value| match range {
+            None => Some((value, value)),
+            Some((min, max)) => Some((f64::min(min, value), f64::max(max, value))),
+        })
+}
+
+/// Escape characters that have special meaning in HTML.
+fn escape(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&#39;"),
+                c => escaped.push(c),
+            }
+            escaped
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_html_special_characters() {
+        assert_eq!(
+            escape("<b>\"PnL\" & 'Fees'</b>"),
+            "&lt;b&gt;&quot;PnL&quot; &amp; &#39;Fees&#39;&lt;/b&gt;"
+        );
+    }
+
+    #[test]
+    fn range_ignores_non_finite_values() {
+        assert_eq!(range([]), None);
+        assert_eq!(range([f64::NAN]), None);
+        assert_eq!(range([1.0, f64::INFINITY, -2.0, 3.0]), Some((-2.0, 3.0)));
+    }
+
+    #[test]
+    fn trade_pnl_svg_has_a_bar_per_trade() {
+        let svg = trade_pnl_svg(&[10.0, -5.0, 2.5]);
+
+        assert_eq!(svg.matches("class=\"win\"").count(), 2);
+        assert_eq!(svg.matches("class=\"loss\"").count(), 1);
+    }
+}
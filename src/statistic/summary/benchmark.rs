@@ -0,0 +1,353 @@
+use crate::{
+    portfolio::position::Position,
+    statistic::{
+        de_f64_non_finite_from_str,
+        metric::{equity_curve, EquityPoint},
+        se_f64_non_finite_as_str,
+        summary::{data::DataSummary, Initialiser, TableBuilder},
+    },
+};
+use chrono::{DateTime, Utc};
+use prettytable::Row;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for initialising a [`BenchmarkSummary`] via the init() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    pub starting_equity: f64,
+    pub risk_free_return: f64,
+}
+
+/// Price of a benchmark at a point in time. A benchmark could be a buy-and-hold of the traded
+/// instrument, or a reference index series.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BenchmarkPoint {
+    pub time: DateTime<Utc>,
+    pub price: f64,
+}
+
+impl BenchmarkPoint {
+    /// Construct a new [`BenchmarkPoint`] using the provided time & price.
+    pub fn new(time: DateTime<Utc>, price: f64) -> Self {
+        Self { time, price }
+    }
+}
+
+/// Summary of strategy performance relative to a benchmark price series. Strategy & benchmark
+/// returns are calculated over the same periods, where each period spans two consecutive points of
+/// the strategy's equity curve.
+///
+/// Key metrics are calculated in one-pass, and are expressed per period:
+/// - Beta: sensitivity of strategy returns to benchmark returns.
+/// - Alpha: strategy excess return not explained by Beta (Jensen's Alpha).
+/// - Tracking Error: standard deviation of the strategy active returns.
+/// - Information Ratio: mean active return per unit of Tracking Error.
+/// - Up & Down Capture: strategy mean return relative to the benchmark mean return during periods
+///   the benchmark rose & fell.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BenchmarkSummary {
+    pub starting_equity: f64,
+    pub risk_free_return: f64,
+    pub strategy_returns: DataSummary,
+    pub benchmark_returns: DataSummary,
+    /// Strategy return minus benchmark return for each period.
+    pub active_returns: DataSummary,
+    /// Welford Online co-moment of the strategy & benchmark returns, used to calculate covariance.
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
+    pub co_moment: f64,
+    pub up_capture: CaptureRatio,
+    pub down_capture: CaptureRatio,
+    /// Strategy [`EquityPoint`] & benchmark price at the end of the previous period.
+    last: Option<(EquityPoint, f64)>,
+}
+
+impl Initialiser for BenchmarkSummary {
+    type Config = Config;
+
+    fn init(config: Self::Config) -> Self {
+        Self::new(config.starting_equity, config.risk_free_return)
+    }
+}
+
+impl TableBuilder for BenchmarkSummary {
+    fn titles(&self) -> Row {
+        row![
+            "Periods",
+            "Mean Return",
+            "Benchmark Mean Return",
+            "Alpha",
+            "Beta",
+            "Tracking Error",
+            "Information Ratio",
+            "Up Capture",
+            "Down Capture",
+        ]
+    }
+
+    fn row(&self) -> Row {
+        row![
+            self.strategy_returns.count,
+            format!("{:.3}", self.strategy_returns.mean),
+            format!("{:.3}", self.benchmark_returns.mean),
+            format!("{:.3}", self.alpha()),
+            format!("{:.3}", self.beta()),
+            format!("{:.3}", self.tracking_error()),
+            format!("{:.3}", self.information_ratio()),
+            format!("{:.3}", self.up_capture.ratio()),
+            format!("{:.3}", self.down_capture.ratio()),
+        ]
+    }
+}
+
+impl BenchmarkSummary {
+    /// Constructs a new [`BenchmarkSummary`] with no periods analysed.
+    pub fn new(starting_equity: f64, risk_free_return: f64) -> Self {
+        Self {
+            starting_equity,
+            risk_free_return,
+            strategy_returns: DataSummary::default(),
+            benchmark_returns: DataSummary::default(),
+            active_returns: DataSummary::default(),
+            co_moment: 0.0,
+            up_capture: CaptureRatio::default(),
+            down_capture: CaptureRatio::default(),
+            last: None,
+        }
+    }
+
+    /// Updates the [`BenchmarkSummary`] with the next strategy [`EquityPoint`] & the benchmark
+    /// price at the same point in time. The first call only marks the start of the first period.
+    pub fn update(&mut self, equity: EquityPoint, benchmark_price: f64) {
+        // Replace the previous period end with the current one
+        let (prev_equity, prev_benchmark_price) = match self.last.replace((equity, benchmark_price))
+        {
+            None => return,
+            Some(last) => last,
+        };
+
+        // Returns are undefined from a zero starting value
+        if prev_equity.total == 0.0 || prev_benchmark_price == 0.0 {
+            return;
+        }
+
+        // Calculate the strategy & benchmark returns for the period
+        let strategy_return = (equity.total - prev_equity.total) / prev_equity.total;
+        let benchmark_return = (benchmark_price - prev_benchmark_price) / prev_benchmark_price;
+
+        // Update returns & Welford Online co-moment
+        let prev_strategy_mean = self.strategy_returns.mean;
+        self.strategy_returns.update(strategy_return);
+        self.benchmark_returns.update(benchmark_return);
+        self.co_moment += (strategy_return - prev_strategy_mean)
+            * (benchmark_return - self.benchmark_returns.mean);
+        self.active_returns
+            .update(strategy_return - benchmark_return);
+
+        // Update capture ratios if the benchmark moved
+        if benchmark_return > 0.0 {
+            self.up_capture.update(strategy_return, benchmark_return);
+        } else if benchmark_return < 0.0 {
+            self.down_capture.update(strategy_return, benchmark_return);
+        }
+    }
+
+    /// Generates the [`BenchmarkSummary`] using the equity curve derived from the provided exited
+    /// [`Position`]s, and a benchmark price series sorted by time.
+    ///
+    /// Each strategy [`EquityPoint`] is paired with the most recent [`BenchmarkPoint`] at or before
+    /// it. [`EquityPoint`]s preceding the start of the benchmark price series are skipped.
+    pub fn generate_summary(&mut self, positions: &[Position], benchmark: &[BenchmarkPoint]) {
+        let mut benchmark = benchmark.iter().peekable();
+        let mut benchmark_price = None;
+
+        for equity in equity_curve(self.starting_equity, positions) {
+            // Advance to the most recent benchmark price at or before the EquityPoint
+            while let Some(next) = benchmark.next_if(|point| point.time <= equity.time) {
+                benchmark_price = Some(next.price);
+            }
+
+            if let Some(benchmark_price) = benchmark_price {
+                self.update(equity, benchmark_price);
+            }
+        }
+    }
+
+    /// Population covariance of the strategy & benchmark returns.
+    pub fn covariance(&self) -> f64 {
+        match self.strategy_returns.count {
+            0 => 0.0,
+            count => self.co_moment / count as f64,
+        }
+    }
+
+    /// Sensitivity of the strategy returns to the benchmark returns.
+    pub fn beta(&self) -> f64 {
+        match self.benchmark_returns.dispersion.variance == 0.0 {
+            true => 0.0,
+            false => self.covariance() / self.benchmark_returns.dispersion.variance,
+        }
+    }
+
+    /// Jensen's Alpha - mean strategy excess return not explained by exposure to the benchmark.
+    pub fn alpha(&self) -> f64 {
+        (self.strategy_returns.mean - self.risk_free_return)
+            - self.beta() * (self.benchmark_returns.mean - self.risk_free_return)
+    }
+
+    /// Standard deviation of the active returns (strategy return minus benchmark return).
+    pub fn tracking_error(&self) -> f64 {
+        self.active_returns.dispersion.std_dev
+    }
+
+    /// Mean active return per unit of [`Self::tracking_error`].
+    pub fn information_ratio(&self) -> f64 {
+        match self.tracking_error() == 0.0 {
+            true => 0.0,
+            false => self.active_returns.mean / self.tracking_error(),
+        }
+    }
+}
+
+/// Strategy & benchmark returns for the periods where the benchmark moved in one direction. Used
+/// to calculate the up & down capture ratios.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct CaptureRatio {
+    pub strategy_returns: DataSummary,
+    pub benchmark_returns: DataSummary,
+}
+
+impl CaptureRatio {
+    /// Updates the [`CaptureRatio`] with the returns from the next relevant period.
+    pub fn update(&mut self, strategy_return: f64, benchmark_return: f64) {
+        self.strategy_returns.update(strategy_return);
+        self.benchmark_returns.update(benchmark_return);
+    }
+
+    /// Mean strategy return relative to the mean benchmark return.
+    pub fn ratio(&self) -> f64 {
+        match self.benchmark_returns.mean == 0.0 {
+            true => 0.0,
+            false => self.strategy_returns.mean / self.benchmark_returns.mean,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::Balance;
+    use crate::test_util::position;
+    use chrono::Duration;
+    use std::ops::Add;
+
+    fn equity_point(time: DateTime<Utc>, total: f64) -> EquityPoint {
+        EquityPoint { time, total }
+    }
+
+    fn exited_position(
+        enter_time: DateTime<Utc>,
+        exit_time: DateTime<Utc>,
+        total: f64,
+    ) -> Position {
+        let mut position = position();
+        position.meta.enter_time = enter_time;
+        position.meta.exit_balance = Some(Balance {
+            time: exit_time,
+            total,
+            available: total,
+        });
+        position
+    }
+
+    #[test]
+    fn update_benchmark_summary_with_leveraged_strategy() {
+        let base_time = Utc::now();
+        let mut summary = BenchmarkSummary::new(1000.0, 0.0);
+
+        // Strategy returns  = [0.2, -0.2, 0.2]
+        // Benchmark returns = [0.1, -0.1, 0.1]
+        let inputs = vec![
+            (1000.0, 100.0),
+            (1200.0, 110.0),
+            (960.0, 99.0),
+            (1152.0, 108.9),
+        ];
+
+        for (index, (equity, benchmark_price)) in inputs.into_iter().enumerate() {
+            summary.update(
+                equity_point(base_time.add(Duration::days(index as i64)), equity),
+                benchmark_price,
+            );
+        }
+
+        assert_eq!(summary.strategy_returns.count, 3);
+        assert!((summary.beta() - 2.0).abs() < 1e-10);
+        assert!(summary.alpha().abs() < 1e-10);
+        assert!((summary.up_capture.ratio() - 2.0).abs() < 1e-10);
+        assert!((summary.down_capture.ratio() - 2.0).abs() < 1e-10);
+
+        // Active returns are equal to the benchmark returns
+        assert!(
+            (summary.tracking_error() - summary.benchmark_returns.dispersion.std_dev).abs() < 1e-10
+        );
+        assert!(
+            (summary.information_ratio()
+                - summary.benchmark_returns.mean / summary.benchmark_returns.dispersion.std_dev)
+                .abs()
+                < 1e-10
+        );
+    }
+
+    #[test]
+    fn update_benchmark_summary_with_flat_benchmark() {
+        let base_time = Utc::now();
+        let mut summary = BenchmarkSummary::new(100.0, 0.0);
+
+        summary.update(equity_point(base_time, 100.0), 50.0);
+        summary.update(equity_point(base_time.add(Duration::days(1)), 110.0), 50.0);
+        summary.update(equity_point(base_time.add(Duration::days(2)), 99.0), 50.0);
+
+        assert_eq!(summary.beta(), 0.0);
+        assert!((summary.alpha() - summary.strategy_returns.mean).abs() < 1e-10);
+        assert_eq!(summary.up_capture.ratio(), 0.0);
+        assert_eq!(summary.down_capture.ratio(), 0.0);
+        assert_eq!(summary.up_capture.strategy_returns.count, 0);
+        assert_eq!(summary.down_capture.strategy_returns.count, 0);
+    }
+
+    #[test]
+    fn generate_benchmark_summary_aligns_benchmark_with_equity_curve() {
+        let base_time = Utc::now();
+        let day = |days: i64| base_time.add(Duration::days(days));
+
+        let positions = vec![
+            exited_position(day(1), day(2), 1100.0),
+            exited_position(day(3), day(5), 1210.0),
+        ];
+
+        let benchmark = vec![
+            BenchmarkPoint::new(day(0), 100.0),
+            BenchmarkPoint::new(day(1), 100.0),
+            BenchmarkPoint::new(day(2), 105.0),
+            BenchmarkPoint::new(day(4), 110.25),
+            BenchmarkPoint::new(day(6), 500.0),
+        ];
+
+        let mut summary = BenchmarkSummary::init(Config {
+            starting_equity: 1000.0,
+            risk_free_return: 0.0,
+        });
+        summary.generate_summary(&positions, &benchmark);
+
+        // Periods: day(1) -> day(2) & day(2) -> day(5), benchmark at day(5) is the day(4) price
+        assert_eq!(summary.strategy_returns.count, 2);
+        assert!((summary.strategy_returns.mean - 0.1).abs() < 1e-10);
+        assert!((summary.benchmark_returns.mean - 0.05).abs() < 1e-10);
+        assert!((summary.active_returns.mean - 0.05).abs() < 1e-10);
+        assert_eq!(summary.tracking_error(), 0.0);
+        assert_eq!(summary.information_ratio(), 0.0);
+    }
+}
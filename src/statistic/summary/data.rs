@@ -1,13 +1,22 @@
 use crate::statistic::algorithm::welford_online;
 use crate::statistic::dispersion::Dispersion;
 use crate::statistic::summary::TableBuilder;
+use crate::statistic::{de_f64_non_finite_from_str, se_f64_non_finite_as_str};
 use prettytable::Row;
 use serde::{Deserialize, Serialize};
 
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
 pub struct DataSummary {
     pub count: u64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub sum: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub mean: f64,
     pub dispersion: Dispersion,
 }
@@ -25,6 +25,15 @@ impl PositionSummariser for DrawdownSummary {
             self.avg_drawdown.update(&ended_drawdown);
             self.max_drawdown.update(&ended_drawdown);
         }
+
+        // Seed drawdown start_times that are not yet set by a drawdown from the Position history,
+        // rather than the wall-clock time they were initialised
+        if self.current_drawdown.drawdown == 0.0 {
+            self.current_drawdown.start_time = equity_point.time;
+        }
+        if self.avg_drawdown.count == 0 {
+            self.max_drawdown.drawdown.start_time = self.current_drawdown.start_time;
+        }
     }
 }
 
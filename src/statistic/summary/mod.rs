@@ -1,6 +1,9 @@
+pub mod benchmark;
 pub mod data;
 pub mod drawdown;
 pub mod pnl;
+pub mod session;
+pub mod trade;
 pub mod trading;
 
 use crate::portfolio::position::Position;
@@ -1,7 +1,8 @@
 use crate::{
     portfolio::position::Position,
     statistic::{
-        de_duration_from_secs, se_duration_as_secs,
+        de_duration_from_secs, de_f64_non_finite_from_str, se_duration_as_secs,
+        se_f64_non_finite_as_str,
         summary::{data::DataSummary, Initialiser, PositionSummariser, TableBuilder},
     },
 };
@@ -18,6 +19,10 @@ pub struct PnLReturnSummary {
         serialize_with = "se_duration_as_secs"
     )]
     pub duration: Duration,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub trades_per_day: f64,
     pub total: DataSummary,
     pub losses: DataSummary,
@@ -133,12 +138,24 @@ impl PnLReturnSummary {
 pub struct ProfitLossSummary {
     pub long_contracts: f64,
     pub long_pnl: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub long_pnl_per_contract: f64,
     pub short_contracts: f64,
     pub short_pnl: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub short_pnl_per_contract: f64,
     pub total_contracts: f64,
     pub total_pnl: f64,
+    #[serde(
+        deserialize_with = "de_f64_non_finite_from_str",
+        serialize_with = "se_f64_non_finite_as_str"
+    )]
     pub total_pnl_per_contract: f64,
 }
 
@@ -0,0 +1,415 @@
+use crate::{
+    portfolio::{
+        position::{Position, PositionTag},
+        Balance,
+    },
+    statistic::{
+        metric::{equity_curve, EquityPoint},
+        report,
+        summary::{combine, PositionSummariser, TableBuilder},
+    },
+};
+use prettytable::{Row, Table};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, io::Write};
+use uuid::Uuid;
+
+/// Summary of a trading session, returned by the [`Engine`](crate::engine::Engine) once it stops.
+///
+/// Contains the statistics for every [`Market`](barter_integration::model::Market) traded, the
+/// statistics across all markets, the statistics attributed to each [`PositionTag`], the exited
+/// [`Position`] blotter & the equity curve. It can be exported as JSON, CSV or a self-contained
+/// HTML report.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct SessionSummary<Statistic> {
+    /// Identifier for the [`Engine`](crate::engine::Engine) that generated this summary.
+    pub engine_id: Uuid,
+    /// Statistics for each market traded, keyed by [`MarketId`](barter_integration::model::MarketId).
+    pub markets: BTreeMap<String, Statistic>,
+    /// Statistics across all markets traded.
+    pub total: Statistic,
+    /// Statistics of the exited tagged [`Position`]s, keyed by [`PositionTag`] (eg/ strategy id).
+    #[serde(default = "BTreeMap::new", skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<PositionTag, Statistic>,
+    /// Every [`Position`] exited during the trading session.
+    pub exited_positions: Vec<Position>,
+    /// Total equity after every exited [`Position`], starting with the starting [`Balance`] total.
+    pub equity_curve: Vec<EquityPoint>,
+}
+
+impl<Statistic> SessionSummary<Statistic> {
+    /// Constructs a new [`SessionSummary`], deriving the equity curve from the starting
+    /// [`Balance`] of the trading session & the exited [`Position`]s.
+    pub fn new(
+        engine_id: Uuid,
+        starting_balance: Balance,
+        markets: BTreeMap<String, Statistic>,
+        total: Statistic,
+        exited_positions: Vec<Position>,
+    ) -> Self {
+        // Equity curve of a session without exited Positions is only the starting Balance
+        let mut equity_curve = equity_curve(starting_balance.total, &exited_positions);
+        if equity_curve.is_empty() {
+            equity_curve.push(EquityPoint::from(starting_balance));
+        }
+
+        Self {
+            engine_id,
+            markets,
+            total,
+            tags: BTreeMap::new(),
+            equity_curve,
+            exited_positions,
+        }
+    }
+
+    /// Attribute the performance of the exited tagged [`Position`]s to each [`PositionTag`],
+    /// summarising them from the provided initial statistics.
+    pub fn with_tags(self, initial: Statistic) -> Self
+    where
+        Statistic: PositionSummariser,
+    {
+        let tags = self
+            .exited_positions
+            .iter()
+            .filter_map(|position| position.tag.as_ref().map(|tag| (tag, position)))
+            .fold(BTreeMap::new(), |mut tags, (tag, position)| {
+                tags.entry(tag.clone()).or_insert(initial).update(position);
+                tags
+            });
+
+        Self { tags, ..self }
+    }
+
+    /// Serialise the [`SessionSummary`] as a pretty JSON `String`.
+    pub fn to_json(&self) -> Result<String, serde_json::Error>
+    where
+        Statistic: Serialize,
+    {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Write the exited [`Position`] blotter as CSV.
+    pub fn write_positions_csv<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        write_csv(
+            writer,
+            POSITION_TITLES,
+            self.exited_positions.iter().map(position_record),
+        )
+    }
+
+    /// Write the equity curve as CSV.
+    pub fn write_equity_curve_csv<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        write_csv(
+            writer,
+            &["time", "total"],
+            self.equity_curve
+                .iter()
+                .map(|equity| vec![equity.time.to_rfc3339(), equity.total.to_string()]),
+        )
+    }
+}
+
+impl<Statistic> SessionSummary<Statistic>
+where
+    Statistic: TableBuilder + Clone,
+{
+    /// Per-market, per-tag & total statistics, where the first cell of each row identifies the
+    /// market or tag.
+    pub(crate) fn statistic_rows(&self) -> impl Iterator<Item = (String, Statistic)> + '_ {
+        self.markets
+            .iter()
+            .map(|(market_id, statistic)| (market_id.clone(), statistic.clone()))
+            .chain(
+                self.tags
+                    .iter()
+                    .map(|(tag, statistic)| (format!("tag {}", tag), statistic.clone())),
+            )
+            .chain([("Total".to_owned(), self.total.clone())])
+    }
+
+    /// Combine the per-market, per-tag & total statistics into a [`Table`].
+    pub fn table(&self) -> Table {
+        combine(self.statistic_rows())
+    }
+
+    /// Write the per-market, per-tag & total statistics as CSV.
+    pub fn write_statistics_csv<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        let titles = row_contents(&self.total.titles());
+        let titles = std::iter::once("market")
+            .chain(titles.iter().map(String::as_str))
+            .collect::<Vec<&str>>();
+
+        write_csv(
+            writer,
+            &titles,
+            self.statistic_rows().map(|(market_id, statistic)| {
+                std::iter::once(market_id)
+                    .chain(row_contents(&statistic.row()))
+                    .collect()
+            }),
+        )
+    }
+
+    /// Generate a self-contained static HTML report containing the statistics tables, equity
+    /// curve & trade PnL charts, and the exited [`Position`] blotter.
+    pub fn to_html(&self) -> String {
+        report::html(self)
+    }
+}
+
+/// Column titles of the exited [`Position`] blotter.
+pub(crate) const POSITION_TITLES: &[&str] = &[
+    "position_id",
+    "exchange",
+    "instrument",
+    "side",
+    "quantity",
+    "enter_time",
+    "exit_time",
+    "enter_avg_price_gross",
+    "exit_avg_price_gross",
+    "enter_fees_total",
+    "exit_fees_total",
+    "realised_profit_loss",
+    "max_adverse_excursion",
+    "max_favourable_excursion",
+];
+
+/// Returns the content of every [`Cell`](prettytable::Cell) in the [`Row`].
+pub(crate) fn row_contents(row: &Row) -> Vec<String> {
+    row.iter().map(|cell| cell.get_content()).collect()
+}
+
+/// Returns the blotter record of an exited [`Position`].
+pub(crate) fn position_record(position: &Position) -> Vec<String> {
+    vec![
+        position.position_id.clone(),
+        position.exchange.to_string(),
+        position.instrument.to_string(),
+        format!("{:?}", position.side),
+        position.quantity.to_string(),
+        position.meta.enter_time.to_rfc3339(),
+        position
+            .meta
+            .exit_balance
+            .map(|balance| balance.time.to_rfc3339())
+            .unwrap_or_default(),
+        position.enter_avg_price_gross.to_string(),
+        position.exit_avg_price_gross.to_string(),
+        position.enter_fees_total.to_string(),
+        position.exit_fees_total.to_string(),
+        position.realised_profit_loss.to_string(),
+        position.max_adverse_excursion.to_string(),
+        position.max_favourable_excursion.to_string(),
+    ]
+}
+
+/// Write the titles & records as CSV, quoting fields where required.
+fn write_csv<W, Records>(mut writer: W, titles: &[&str], records: Records) -> std::io::Result<()>
+where
+    W: Write,
+    Records: IntoIterator<Item = Vec<String>>,
+{
+    fn escape(field: &str) -> String {
+        match field.contains([',', '"', '\n', '\r']) {
+            true => format!("\"{}\"", field.replace('"', "\"\"")),
+            false => field.to_owned(),
+        }
+    }
+
+    let titles = titles.iter().map(|title| escape(title)).collect::<Vec<_>>();
+    writeln!(writer, "{}", titles.join(","))?;
+
+    for record in records {
+        let record = record.iter().map(|field| escape(field)).collect::<Vec<_>>();
+        writeln!(writer, "{}", record.join(","))?;
+    }
+
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::Balance;
+    use crate::statistic::summary::pnl::ProfitLossSummary;
+    use crate::test_util::position;
+    use chrono::{Duration, Utc};
+
+    fn session_summary() -> SessionSummary<ProfitLossSummary> {
+        let base_time = Utc::now();
+
+        let mut first = position();
+        first.position_id = "first".to_owned();
+        first.meta.enter_time = base_time;
+        first.realised_profit_loss = 10.0;
+        first.meta.exit_balance = Some(Balance {
+            time: base_time + Duration::days(1),
+            total: 1010.0,
+            available: 1010.0,
+        });
+
+        let mut second = position();
+        second.position_id = "second, with comma".to_owned();
+        second.meta.enter_time = base_time + Duration::days(2);
+        second.realised_profit_loss = -20.0;
+        second.meta.exit_balance = Some(Balance {
+            time: base_time + Duration::days(3),
+            total: 990.0,
+            available: 990.0,
+        });
+
+        let statistic = ProfitLossSummary {
+            total_pnl: -10.0,
+            ..Default::default()
+        };
+
+        SessionSummary::new(
+            Uuid::new_v4(),
+            Balance::new(base_time, 1000.0, 1000.0),
+            BTreeMap::from([("binance_eth_usdt_spot".to_owned(), statistic)]),
+            statistic,
+            vec![second, first],
+        )
+    }
+
+    #[test]
+    fn new_session_summary_derives_equity_curve() {
+        let summary = session_summary();
+
+        let actual = summary
+            .equity_curve
+            .iter()
+            .map(|equity| equity.total)
+            .collect::<Vec<f64>>();
+
+        assert_eq!(actual, vec![1000.0, 1010.0, 990.0]);
+    }
+
+    #[test]
+    fn new_session_summary_starts_equity_curve_with_starting_balance() {
+        let base_time = Utc::now();
+        let starting_balance = Balance::new(base_time, 1000.0, 1000.0);
+
+        // First exit Balance includes the unrealised PnL of another Position still open
+        let mut exited = position();
+        exited.meta.enter_time = base_time;
+        exited.realised_profit_loss = 10.0;
+        exited.meta.exit_balance = Some(Balance {
+            time: base_time + Duration::days(1),
+            total: 1050.0,
+            available: 1010.0,
+        });
+
+        let summary = SessionSummary::new(
+            Uuid::new_v4(),
+            starting_balance,
+            BTreeMap::new(),
+            ProfitLossSummary::default(),
+            vec![exited],
+        );
+        let actual = summary
+            .equity_curve
+            .iter()
+            .map(|equity| equity.total)
+            .collect::<Vec<f64>>();
+        assert_eq!(actual, vec![1000.0, 1050.0]);
+
+        // Trading session without exited Positions
+        let summary = SessionSummary::new(
+            Uuid::new_v4(),
+            starting_balance,
+            BTreeMap::new(),
+            ProfitLossSummary::default(),
+            Vec::new(),
+        );
+        assert_eq!(
+            summary.equity_curve,
+            vec![EquityPoint::from(starting_balance)]
+        );
+    }
+
+    #[test]
+    fn session_summary_json_round_trip() {
+        let summary = session_summary();
+
+        let json = summary.to_json().unwrap();
+        let actual = serde_json::from_str::<SessionSummary<ProfitLossSummary>>(&json).unwrap();
+
+        assert_eq!(actual.engine_id, summary.engine_id);
+        assert_eq!(actual.markets, summary.markets);
+        assert_eq!(actual.exited_positions.len(), 2);
+        assert_eq!(actual.equity_curve.len(), 3);
+    }
+
+    #[test]
+    fn write_session_summary_csvs() {
+        let summary = session_summary();
+
+        let mut statistics = Vec::new();
+        summary.write_statistics_csv(&mut statistics).unwrap();
+        let statistics = String::from_utf8(statistics).unwrap();
+        let mut lines = statistics.lines();
+        assert!(lines.next().unwrap().starts_with("market,Long Contracts,"));
+        assert!(lines.next().unwrap().starts_with("binance_eth_usdt_spot,"));
+        assert!(lines.next().unwrap().starts_with("Total,"));
+        assert_eq!(lines.next(), None);
+
+        let mut positions = Vec::new();
+        summary.write_positions_csv(&mut positions).unwrap();
+        let positions = String::from_utf8(positions).unwrap();
+        assert_eq!(positions.lines().count(), 3);
+        assert!(positions.contains("\"second, with comma\","));
+
+        let mut equity_curve = Vec::new();
+        summary.write_equity_curve_csv(&mut equity_curve).unwrap();
+        let equity_curve = String::from_utf8(equity_curve).unwrap();
+        assert_eq!(equity_curve.lines().next(), Some("time,total"));
+        assert_eq!(equity_curve.lines().count(), 4);
+    }
+
+    #[test]
+    fn session_summary_html_report() {
+        let summary = session_summary();
+
+        let html = summary.to_html();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(&summary.engine_id.to_string()));
+        assert!(html.contains("<td>binance_eth_usdt_spot</td>"));
+        assert!(html.contains("<td>Total</td>"));
+        assert!(html.contains("<polyline class=\"equity\""));
+        assert_eq!(html.matches("<rect class=").count(), 2);
+        assert!(html.contains("<td>second, with comma</td>"));
+    }
+
+    #[test]
+    fn session_summary_attributes_statistics_to_position_tags() {
+        let mut summary = session_summary();
+        summary.exited_positions[0].tag = Some("trend".to_owned());
+        summary.exited_positions[1].tag = Some("trend".to_owned());
+        summary.exited_positions.push(Position {
+            tag: Some("reversion".to_owned()),
+            realised_profit_loss: 5.0,
+            ..position()
+        });
+
+        let summary = summary.with_tags(ProfitLossSummary::default());
+
+        assert_eq!(summary.tags.len(), 2);
+        assert_eq!(summary.tags["trend"].total_contracts, 2.0);
+        assert_eq!(summary.tags["trend"].total_pnl, -10.0);
+        assert_eq!(summary.tags["reversion"].total_pnl, 5.0);
+
+        let mut statistics = Vec::new();
+        summary.write_statistics_csv(&mut statistics).unwrap();
+        let statistics = String::from_utf8(statistics).unwrap();
+        assert!(statistics.contains("\ntag reversion,"));
+        assert!(statistics.contains("\ntag trend,"));
+
+        let html = summary.to_html();
+        assert!(html.contains("<td>tag reversion</td>"));
+        assert!(html.contains("<td>tag trend</td>"));
+    }
+}
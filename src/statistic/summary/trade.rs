@@ -0,0 +1,305 @@
+use crate::{
+    portfolio::position::Position,
+    statistic::summary::{data::DataSummary, Initialiser, PositionSummariser, TableBuilder},
+};
+use prettytable::Row;
+use serde::{Deserialize, Serialize};
+
+/// Trade-level analytics of exited [`Position`]s. Includes holding time distributions, win/loss
+/// streaks, excursions, profit factor, payoff ratio & expectancy.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct TradeSummary {
+    /// Realised PnL of winning trades.
+    pub wins: DataSummary,
+    /// Realised PnL of losing trades.
+    pub losses: DataSummary,
+    pub holding_time: HoldingTimeSummary,
+    pub streak: Streak,
+    /// [`Position::max_adverse_excursion`] of every trade.
+    pub max_adverse_excursion: DataSummary,
+    /// [`Position::max_favourable_excursion`] of every trade.
+    pub max_favourable_excursion: DataSummary,
+}
+
+impl Initialiser for TradeSummary {
+    type Config = ();
+
+    fn init(_: Self::Config) -> Self {
+        Self::default()
+    }
+}
+
+impl PositionSummariser for TradeSummary {
+    fn update(&mut self, position: &Position) {
+        // Only update TradeSummary with exited Positions
+        if position.meta.exit_balance.is_none() {
+            return;
+        }
+
+        let holding_seconds = position.calculate_holding_duration().num_seconds() as f64;
+        let is_loss = position.realised_profit_loss.is_sign_negative();
+
+        // Update win or loss specific summaries
+        match is_loss {
+            true => {
+                self.losses.update(position.realised_profit_loss);
+                self.holding_time.losses.update(holding_seconds);
+            }
+            false => {
+                self.wins.update(position.realised_profit_loss);
+                self.holding_time.wins.update(holding_seconds);
+            }
+        }
+
+        // Update summaries across all trades
+        self.holding_time.total.update(holding_seconds);
+        self.streak.update(!is_loss);
+        self.max_adverse_excursion
+            .update(position.max_adverse_excursion);
+        self.max_favourable_excursion
+            .update(position.max_favourable_excursion);
+    }
+}
+
+impl TableBuilder for TradeSummary {
+    fn titles(&self) -> Row {
+        row![
+            "Win Rate",
+            "Profit Factor",
+            "Payoff Ratio",
+            "Expectancy",
+            "Max Win Streak",
+            "Max Loss Streak",
+            "Mean Holding Hours",
+            "Mean MAE",
+            "Mean MFE",
+        ]
+    }
+
+    fn row(&self) -> Row {
+        row![
+            format!("{:.3}", self.win_rate()),
+            format!("{:.3}", self.profit_factor()),
+            format!("{:.3}", self.payoff_ratio()),
+            format!("{:.3}", self.expectancy()),
+            self.streak.max_wins,
+            self.streak.max_losses,
+            format!(
+                "{:.3}",
+                self.holding_time.total.mean / TradeSummary::SECONDS_IN_HOUR
+            ),
+            format!("{:.3}", self.max_adverse_excursion.mean),
+            format!("{:.3}", self.max_favourable_excursion.mean),
+        ]
+    }
+}
+
+impl TradeSummary {
+    const SECONDS_IN_HOUR: f64 = 3600.0;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total number of trades analysed.
+    pub fn trades(&self) -> u64 {
+        self.wins.count + self.losses.count
+    }
+
+    /// Proportion of trades that were winners.
+    pub fn win_rate(&self) -> f64 {
+        match self.trades() {
+            0 => 0.0,
+            trades => self.wins.count as f64 / trades as f64,
+        }
+    }
+
+    /// Gross profit of winning trades divided by the gross loss of losing trades.
+    pub fn profit_factor(&self) -> f64 {
+        match self.losses.sum == 0.0 {
+            true => 0.0,
+            false => self.wins.sum / self.losses.sum.abs(),
+        }
+    }
+
+    /// Mean winning trade PnL divided by the mean losing trade PnL.
+    pub fn payoff_ratio(&self) -> f64 {
+        match self.losses.mean == 0.0 {
+            true => 0.0,
+            false => self.wins.mean / self.losses.mean.abs(),
+        }
+    }
+
+    /// Expected PnL per trade, calculated from the win rate & mean PnL of winning & losing trades.
+    pub fn expectancy(&self) -> f64 {
+        let win_rate = self.win_rate();
+        win_rate * self.wins.mean + (1.0 - win_rate) * self.losses.mean
+    }
+}
+
+/// Distributions of the duration (in seconds) trades were held for.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct HoldingTimeSummary {
+    pub total: DataSummary,
+    pub wins: DataSummary,
+    pub losses: DataSummary,
+}
+
+/// Consecutive winning & losing trades.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct Streak {
+    /// Length of the ongoing streak. Positive for consecutive wins, negative for consecutive losses.
+    pub current: i64,
+    pub max_wins: u64,
+    pub max_losses: u64,
+}
+
+impl Streak {
+    /// Updates the [`Streak`] with the outcome of the next trade.
+    pub fn update(&mut self, is_win: bool) {
+        self.current = match (is_win, self.current.is_positive()) {
+            (true, true) => self.current + 1,
+            (true, false) => 1,
+            (false, true) => -1,
+            (false, false) => self.current - 1,
+        };
+
+        match is_win {
+            true => self.max_wins = self.max_wins.max(self.current.unsigned_abs()),
+            false => self.max_losses = self.max_losses.max(self.current.unsigned_abs()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::Balance;
+    use crate::test_util::position;
+    use chrono::{Duration, Utc};
+
+    fn exited_position(realised_profit_loss: f64, holding_hours: i64) -> Position {
+        let enter_time = Utc::now();
+        let mut position = position();
+        position.meta.enter_time = enter_time;
+        position.meta.exit_balance = Some(Balance {
+            time: enter_time + Duration::hours(holding_hours),
+            total: 0.0,
+            available: 0.0,
+        });
+        position.realised_profit_loss = realised_profit_loss;
+        position.max_adverse_excursion = -realised_profit_loss.abs();
+        position.max_favourable_excursion = realised_profit_loss.abs() * 2.0;
+        position
+    }
+
+    #[test]
+    fn update_trade_summary_with_exited_positions() {
+        let mut summary = TradeSummary::init(());
+
+        summary.generate_summary(&[
+            exited_position(30.0, 1),
+            exited_position(-10.0, 3),
+            exited_position(10.0, 2),
+            exited_position(-20.0, 4),
+        ]);
+
+        assert_eq!(summary.trades(), 4);
+        assert_eq!(summary.win_rate(), 0.5);
+        assert_eq!(summary.profit_factor(), 40.0 / 30.0);
+        assert_eq!(summary.payoff_ratio(), 20.0 / 15.0);
+        assert_eq!(summary.expectancy(), 0.5 * 20.0 + 0.5 * -15.0);
+        assert_eq!(summary.holding_time.total.mean, 2.5 * 3600.0);
+        assert_eq!(summary.holding_time.wins.mean, 1.5 * 3600.0);
+        assert_eq!(summary.holding_time.losses.mean, 3.5 * 3600.0);
+        assert!((summary.max_adverse_excursion.mean - -17.5).abs() < 1e-10);
+        assert!((summary.max_favourable_excursion.mean - 35.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn update_trade_summary_ignores_open_positions() {
+        let mut summary = TradeSummary::new();
+
+        let mut open_position = exited_position(10.0, 1);
+        open_position.meta.exit_balance = None;
+
+        summary.update(&open_position);
+
+        assert_eq!(summary, TradeSummary::default());
+    }
+
+    #[test]
+    fn trade_summary_ratios_with_no_losses() {
+        let mut summary = TradeSummary::new();
+
+        summary.update(&exited_position(10.0, 1));
+
+        assert_eq!(summary.win_rate(), 1.0);
+        assert_eq!(summary.profit_factor(), 0.0);
+        assert_eq!(summary.payoff_ratio(), 0.0);
+        assert_eq!(summary.expectancy(), 10.0);
+    }
+
+    #[test]
+    fn update_streak() {
+        struct TestCase {
+            input_is_win: bool,
+            expected: Streak,
+        }
+
+        let mut streak = Streak::default();
+
+        let test_cases = vec![
+            TestCase {
+                // Test case 0: First win
+                input_is_win: true,
+                expected: Streak {
+                    current: 1,
+                    max_wins: 1,
+                    max_losses: 0,
+                },
+            },
+            TestCase {
+                // Test case 1: Consecutive win
+                input_is_win: true,
+                expected: Streak {
+                    current: 2,
+                    max_wins: 2,
+                    max_losses: 0,
+                },
+            },
+            TestCase {
+                // Test case 2: Loss ends win streak
+                input_is_win: false,
+                expected: Streak {
+                    current: -1,
+                    max_wins: 2,
+                    max_losses: 1,
+                },
+            },
+            TestCase {
+                // Test case 3: Consecutive loss
+                input_is_win: false,
+                expected: Streak {
+                    current: -2,
+                    max_wins: 2,
+                    max_losses: 2,
+                },
+            },
+            TestCase {
+                // Test case 4: Win ends loss streak
+                input_is_win: true,
+                expected: Streak {
+                    current: 1,
+                    max_wins: 2,
+                    max_losses: 2,
+                },
+            },
+        ];
+
+        for (index, test) in test_cases.into_iter().enumerate() {
+            streak.update(test.input_is_win);
+            assert_eq!(streak, test.expected, "Test case {} failed", index);
+        }
+    }
+}
@@ -2,6 +2,7 @@ use crate::portfolio::position::Position;
 use crate::statistic::metric::ratio::{CalmarRatio, Ratio, SharpeRatio, SortinoRatio};
 use crate::statistic::summary::drawdown::DrawdownSummary;
 use crate::statistic::summary::pnl::PnLReturnSummary;
+use crate::statistic::summary::trade::TradeSummary;
 use crate::statistic::summary::{Initialiser, PositionSummariser, TableBuilder};
 use chrono::{DateTime, Duration, Utc};
 use prettytable::{Cell, Row};
@@ -20,6 +21,8 @@ pub struct TradingSummary {
     pnl_returns: PnLReturnSummary,
     drawdown: DrawdownSummary,
     tear_sheet: TearSheet,
+    #[serde(default = "TradeSummary::new")]
+    trades: TradeSummary,
 }
 
 impl Initialiser for TradingSummary {
@@ -30,6 +33,7 @@ impl Initialiser for TradingSummary {
             pnl_returns: PnLReturnSummary::new(),
             drawdown: DrawdownSummary::new(config.starting_equity),
             tear_sheet: TearSheet::new(config.risk_free_return),
+            trades: TradeSummary::new(),
         }
     }
 }
@@ -39,6 +43,7 @@ impl PositionSummariser for TradingSummary {
         self.pnl_returns.update(position);
         self.drawdown.update(position);
         self.tear_sheet.update(&self.pnl_returns, &self.drawdown);
+        self.trades.update(position);
     }
 }
 
@@ -58,6 +63,10 @@ impl TableBuilder for TradingSummary {
             titles.push(title.clone())
         }
 
+        for title in &self.trades.titles() {
+            titles.push(title.clone())
+        }
+
         Row::new(titles)
     }
 
@@ -76,6 +85,10 @@ impl TableBuilder for TradingSummary {
             cells.push(cell.clone())
         }
 
+        for cell in &self.trades.row() {
+            cells.push(cell.clone())
+        }
+
         Row::new(cells)
     }
 }
@@ -127,3 +140,25 @@ pub fn calculate_trading_duration(start_time: &DateTime<Utc>, position: &Positio
         Some(exit_balance) => exit_balance.time.signed_duration_since(*start_time),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trading_summary_persisted_without_trades_deserialises() {
+        let summary = TradingSummary::init(Config {
+            starting_equity: 1000.0,
+            trading_days_per_year: 365,
+            risk_free_return: 0.0,
+        });
+
+        // TradingSummary persisted before the trades field was added
+        let mut legacy = serde_json::to_value(summary).unwrap();
+        legacy.as_object_mut().unwrap().remove("trades");
+
+        let actual = serde_json::from_value::<TradingSummary>(legacy).unwrap();
+
+        assert_eq!(actual.trades, TradeSummary::new());
+    }
+}
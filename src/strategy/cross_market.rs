@@ -0,0 +1,237 @@
+use super::{Signal, SignalGenerator};
+use barter_data::model::MarketEvent;
+use barter_integration::model::Market;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Portfolio-level strategy that analyses the [`MarketEvent`]s of every [`Market`] traded by an
+/// [`Engine`](crate::engine::Engine), and may generate [`Signal`]s for any of them (eg/ pairs
+/// trading, spread trading or cross-sectional momentum).
+pub trait CrossMarketSignalGenerator {
+    /// Return every [`Signal`] generated given an input [`MarketEvent`] from any [`Market`].
+    fn generate_signals(&mut self, market: &MarketEvent) -> Vec<Signal>;
+}
+
+/// Per-[`Trader`](crate::engine::trader::Trader) handle to a [`CrossMarketSignalGenerator`]
+/// shared by every [`Trader`](crate::engine::trader::Trader) of an
+/// [`Engine`](crate::engine::Engine).
+///
+/// Each [`Trader`](crate::engine::trader::Trader) feeds it's [`MarketEvent`]s to the shared
+/// strategy. [`Signal`]s for the handle's own [`Market`] are returned to it's
+/// [`Trader`](crate::engine::trader::Trader), whereas [`Signal`]s for any other [`Market`] are
+/// sent to the [`Engine`](crate::engine::Engine) signal_rx, which routes them to the order path of
+/// the relevant [`Trader`](crate::engine::trader::Trader).
+///
+/// [`Signal`]s are sent on a dedicated unbounded channel, so they are never dropped by a full
+/// [`Engine`](crate::engine::Engine) command channel. Each handle holds a clone of the signal_tx
+/// rather than the [`Engine`](crate::engine::Engine)'s [`Command`](crate::engine::Command)
+/// transmitter, so live handles never prevent
+/// [`Termination::CommandChannelDropped`](crate::engine::outcome::Termination::CommandChannelDropped).
+///
+/// A routed [`Signal`] is only actioned whilst the relevant
+/// [`Trader`](crate::engine::trader::Trader) is running. [`Signal`]s for a [`Market`] who's
+/// [`Trader`](crate::engine::trader::Trader) has already stopped (eg/ it's backtest market data
+/// ended before this handle's) are discarded by the [`Engine`](crate::engine::Engine) with a
+/// warning.
+#[derive(Debug)]
+pub struct CrossMarketStrategy<Strategy> {
+    /// [`Market`] traded by the [`Trader`](crate::engine::trader::Trader) owning this handle.
+    market: Market,
+    /// Shared-access to the [`CrossMarketSignalGenerator`].
+    strategy: Arc<Mutex<Strategy>>,
+    /// Transmitter of the [`Engine`](crate::engine::Engine) signal_rx, used to route [`Signal`]s
+    /// for other [`Market`]s.
+    signal_tx: mpsc::UnboundedSender<Signal>,
+}
+
+impl<Strategy> SignalGenerator for CrossMarketStrategy<Strategy>
+where
+    Strategy: CrossMarketSignalGenerator,
+{
+    /// Returns the first [`Signal`] for the handle's own [`Market`]. Any further [`Signal`]s for
+    /// it are never dropped, but routed via the [`Engine`](crate::engine::Engine) signal_rx like
+    /// those for other [`Market`]s. Prefer [`Self::generate_signals`], which returns them all.
+    fn generate_signal(&mut self, market: &MarketEvent) -> Option<Signal> {
+        let mut signals = self.generate_signals(market).into_iter();
+        let signal = signals.next();
+
+        for signal in signals {
+            warn!(
+                market = ?self.market,
+                "generate_signal returns a single Signal, routing further Signal via the Engine"
+            );
+            self.route_signal(signal);
+        }
+
+        signal
+    }
+
+    fn generate_signals(&mut self, market: &MarketEvent) -> Vec<Signal> {
+        let signals = self.strategy.lock().generate_signals(market);
+
+        signals
+            .into_iter()
+            .filter_map(|signal| {
+                if signal.exchange == self.market.exchange
+                    && signal.instrument == self.market.instrument
+                {
+                    return Some(signal);
+                }
+
+                // Route Signal for another Market to it's Trader via the Engine
+                self.route_signal(signal);
+                None
+            })
+            .collect()
+    }
+}
+
+impl<Strategy> CrossMarketStrategy<Strategy> {
+    /// Send the [`Signal`] to the [`Engine`](crate::engine::Engine) signal_rx, which routes it to
+    /// the order path of the relevant [`Trader`](crate::engine::trader::Trader).
+    fn route_signal(&self, signal: Signal) {
+        if self.signal_tx.send(signal).is_err() {
+            warn!(
+                market = ?self.market,
+                why = "Engine signal_rx dropped",
+                "failed to route cross-market Signal to the Engine"
+            );
+        }
+    }
+
+    /// Constructs a new [`CrossMarketStrategy`] handle for the provided [`Market`], using the
+    /// shared [`CrossMarketSignalGenerator`] & the transmitter of the
+    /// [`Engine`](crate::engine::Engine) signal_rx.
+    pub fn new(
+        market: Market,
+        strategy: Arc<Mutex<Strategy>>,
+        signal_tx: mpsc::UnboundedSender<Signal>,
+    ) -> Self {
+        Self {
+            market,
+            strategy,
+            signal_tx,
+        }
+    }
+
+    /// Constructs a new [`CrossMarketStrategy`] handle for another [`Market`], sharing this
+    /// handle's [`CrossMarketSignalGenerator`] & signal_tx.
+    pub fn for_market(&self, market: Market) -> Self {
+        Self {
+            market,
+            strategy: Arc::clone(&self.strategy),
+            signal_tx: self.signal_tx.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::MarketMeta,
+        strategy::{Decision, SignalStrength},
+        test_util::signal,
+    };
+    use barter_data::{model::DataKind, test_util::market_trade};
+    use barter_integration::model::{Instrument, InstrumentKind, Side};
+    use std::collections::HashMap;
+
+    /// Goes long every [`Market`] it has seen whenever any [`Market`] trades.
+    #[derive(Default)]
+    struct LongEverything {
+        seen: Vec<MarketEvent>,
+    }
+
+    impl CrossMarketSignalGenerator for LongEverything {
+        fn generate_signals(&mut self, market: &MarketEvent) -> Vec<Signal> {
+            self.seen
+                .retain(|seen| seen.instrument != market.instrument);
+            self.seen.push(market.clone());
+
+            self.seen
+                .iter()
+                .map(|seen| Signal {
+                    time: market.exchange_time,
+                    exchange: seen.exchange.clone(),
+                    instrument: seen.instrument.clone(),
+                    signals: HashMap::from([(Decision::Long, SignalStrength(1.0))]),
+                    market_meta: MarketMeta {
+                        close: match &seen.kind {
+                            DataKind::Trade(trade) => trade.price,
+                            _ => unreachable!(),
+                        },
+                        time: seen.exchange_time,
+                    },
+                    tag: None,
+                    contributions: Vec::new(),
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn cross_market_strategy_routes_signals_for_other_markets_to_the_engine() {
+        let (signal_tx, mut signal_rx) = mpsc::unbounded_channel();
+        let btc = market_trade(Side::Buy);
+        let mut eth = market_trade(Side::Buy);
+        eth.instrument = Instrument::from(("eth", "usdt", InstrumentKind::Spot));
+
+        let btc_strategy = CrossMarketStrategy::new(
+            Market::new(btc.exchange.clone(), btc.instrument.clone()),
+            Arc::new(Mutex::new(LongEverything::default())),
+            signal_tx,
+        );
+        let mut eth_strategy =
+            btc_strategy.for_market(Market::new(eth.exchange.clone(), eth.instrument.clone()));
+        let mut btc_strategy = btc_strategy;
+
+        // Shared strategy has only seen the btc Market, so only the btc Trader is signalled
+        let signals = btc_strategy.generate_signals(&btc);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].instrument, btc.instrument);
+        assert!(signal_rx.try_recv().is_err());
+
+        // MarketEvent from the eth Market also yields a Signal for the btc Market
+        let signals = eth_strategy.generate_signals(&eth);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].instrument, eth.instrument);
+        assert_eq!(signal_rx.try_recv().unwrap().instrument, btc.instrument);
+    }
+
+    #[test]
+    fn cross_market_strategy_generate_signal_routes_further_signals_to_the_engine() {
+        /// Generates a tagged Signal for each of two sub-strategies of the input Market.
+        struct TwoSubStrategies;
+
+        impl CrossMarketSignalGenerator for TwoSubStrategies {
+            fn generate_signals(&mut self, market: &MarketEvent) -> Vec<Signal> {
+                ["trend", "hedge"]
+                    .into_iter()
+                    .map(|tag| Signal {
+                        exchange: market.exchange.clone(),
+                        instrument: market.instrument.clone(),
+                        tag: Some(tag.to_owned()),
+                        ..signal()
+                    })
+                    .collect()
+            }
+        }
+
+        let (signal_tx, mut signal_rx) = mpsc::unbounded_channel();
+        let btc = market_trade(Side::Buy);
+        let mut strategy = CrossMarketStrategy::new(
+            Market::new(btc.exchange.clone(), btc.instrument.clone()),
+            Arc::new(Mutex::new(TwoSubStrategies)),
+            signal_tx,
+        );
+
+        // First Signal is returned, the second is routed via the Engine rather than dropped
+        let signal = strategy.generate_signal(&btc).unwrap();
+        assert_eq!(signal.tag.as_deref(), Some("trend"));
+        assert_eq!(signal_rx.try_recv().unwrap().tag.as_deref(), Some("hedge"));
+        assert!(signal_rx.try_recv().is_err());
+    }
+}
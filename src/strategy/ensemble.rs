@@ -0,0 +1,362 @@
+use super::{Decision, Signal, SignalContribution, SignalGenerator, SignalStrength, StrategyId};
+use barter_data::model::MarketEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Combines the signals maps contributed by the members of an [`Ensemble`] into a single signals
+/// map. Implement this for a meta-model that learns how to combine it's members' signals.
+pub trait SignalAggregator {
+    /// Aggregate the [`SignalContribution`]s of the members that generated a [`Signal`], given
+    /// the total number of members in the [`Ensemble`].
+    fn aggregate(
+        &mut self,
+        members: usize,
+        contributions: &[SignalContribution],
+    ) -> HashMap<Decision, SignalStrength>;
+}
+
+/// Built-in [`SignalAggregator`] combination rules.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Aggregation {
+    /// Keep each [`Decision`] advised by more than half of the members, with the mean
+    /// [`SignalStrength`] of the members advising it.
+    MajorityVote,
+    /// Net the weighted mean member [`SignalStrength`] of opposing [`Decision`]s (Long vs Short &
+    /// CloseLong vs CloseShort), keeping the prevailing [`Decision`] of each pair if it's net
+    /// [`SignalStrength`] is at least min_strength.
+    ///
+    /// The weighted mean is normalised by the total weight of the members that generated a
+    /// [`Signal`], so members advising nothing abstain.
+    WeightedSum {
+        #[serde(default)]
+        min_strength: f64,
+    },
+    /// Keep each [`Decision`] advised by every member, with the minimum [`SignalStrength`] of the
+    /// members.
+    Unanimous,
+}
+
+impl SignalAggregator for Aggregation {
+    fn aggregate(
+        &mut self,
+        members: usize,
+        contributions: &[SignalContribution],
+    ) -> HashMap<Decision, SignalStrength> {
+        // Group the (weight, strength) of every member advising each Decision
+        let mut votes = HashMap::<Decision, Vec<(f64, f64)>>::with_capacity(4);
+        for contribution in contributions {
+            for (decision, strength) in &contribution.signals {
+                votes
+                    .entry(*decision)
+                    .or_default()
+                    .push((contribution.weight, strength.0));
+            }
+        }
+
+        match *self {
+            Aggregation::MajorityVote => votes
+                .into_iter()
+                .filter_map(|(decision, votes)| {
+                    (votes.len() * 2 > members).then(|| {
+                        let sum = votes.iter().map(|(_, strength)| strength).sum::<f64>();
+                        (decision, SignalStrength(sum / votes.len() as f64))
+                    })
+                })
+                .collect(),
+            Aggregation::WeightedSum { min_strength } => {
+                let total_weight = contributions
+                    .iter()
+                    .map(|contribution| contribution.weight)
+                    .sum::<f64>();
+                weighted_net_signals(&votes, total_weight, min_strength)
+            }
+            Aggregation::Unanimous => votes
+                .into_iter()
+                .filter_map(|(decision, votes)| {
+                    (votes.len() == members).then(|| {
+                        let min = votes
+                            .iter()
+                            .map(|(_, strength)| *strength)
+                            .fold(f64::MAX, f64::min);
+                        (decision, SignalStrength(min))
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Net the weighted member votes of each pair of opposing [`Decision`]s, normalised by the
+/// provided total member weight. See [`Aggregation::WeightedSum`].
+fn weighted_net_signals(
+    votes: &HashMap<Decision, Vec<(f64, f64)>>,
+    total_weight: f64,
+    min_strength: f64,
+) -> HashMap<Decision, SignalStrength> {
+    if total_weight <= 0.0 {
+        return HashMap::new();
+    }
+
+    let weighted_mean = |decision: Decision| {
+        votes.get(&decision).map_or(0.0, |votes| {
+            votes
+                .iter()
+                .map(|(weight, strength)| weight * strength)
+                .sum::<f64>()
+        }) / total_weight
+    };
+
+    [
+        (Decision::Long, Decision::Short),
+        (Decision::CloseLong, Decision::CloseShort),
+    ]
+    .into_iter()
+    .filter_map(|(positive, negative)| {
+        let net = weighted_mean(positive) - weighted_mean(negative);
+        let decision = if net > 0.0 { positive } else { negative };
+        (net != 0.0 && net.abs() >= min_strength).then(|| (decision, SignalStrength(net.abs())))
+    })
+    .collect()
+}
+
+/// Child strategy of an [`Ensemble`].
+#[derive(Clone, Debug)]
+pub struct EnsembleMember<Strategy> {
+    /// Identifier used to attribute the member's [`SignalContribution`]s.
+    pub id: StrategyId,
+    /// Weight of the member, used by weighted [`SignalAggregator`]s.
+    pub weight: f64,
+    pub strategy: Strategy,
+}
+
+impl<Strategy> EnsembleMember<Strategy> {
+    /// Constructs a new [`EnsembleMember`] with the provided identifier & weight.
+    pub fn new<Id>(id: Id, weight: f64, strategy: Strategy) -> Self
+    where
+        Id: Into<StrategyId>,
+    {
+        Self {
+            id: id.into(),
+            weight,
+            strategy,
+        }
+    }
+}
+
+/// Composite [`SignalGenerator`] that runs every member strategy on each [`MarketEvent`] &
+/// combines their signals maps using a [`SignalAggregator`] (eg/ majority vote, weighted sum,
+/// unanimous agreement or a meta-model).
+///
+/// Each member's signals map is recorded in the aggregated [`Signal::contributions`] for
+/// attribution.
+#[derive(Clone, Debug)]
+pub struct Ensemble<Strategy, Aggregator = Aggregation> {
+    members: Vec<EnsembleMember<Strategy>>,
+    aggregator: Aggregator,
+}
+
+impl<Strategy, Aggregator> SignalGenerator for Ensemble<Strategy, Aggregator>
+where
+    Strategy: SignalGenerator,
+    Aggregator: SignalAggregator,
+{
+    fn generate_signal(&mut self, market: &MarketEvent) -> Option<Signal> {
+        // Run every member strategy on the MarketEvent, recording each member's contribution
+        let mut template = None;
+        let contributions = self
+            .members
+            .iter_mut()
+            .filter_map(|member| {
+                let signal = member.strategy.generate_signal(market)?;
+                let contribution = SignalContribution {
+                    strategy_id: member.id.clone(),
+                    weight: member.weight,
+                    signals: signal.signals.clone(),
+                };
+                template.get_or_insert(signal);
+                Some(contribution)
+            })
+            .collect::<Vec<SignalContribution>>();
+
+        // If no member generated a Signal, return no Signal
+        let template = template?;
+
+        // Combine the member signals maps, returning no Signal if the aggregate is empty
+        let signals = self
+            .aggregator
+            .aggregate(self.members.len(), &contributions);
+        if signals.is_empty() {
+            return None;
+        }
+
+        Some(Signal {
+            signals,
+            tag: None,
+            contributions,
+            ..template
+        })
+    }
+}
+
+impl<Strategy, Aggregator> Ensemble<Strategy, Aggregator> {
+    /// Constructs a new [`Ensemble`] component using the provided members & [`SignalAggregator`].
+    pub fn new(members: Vec<EnsembleMember<Strategy>>, aggregator: Aggregator) -> Self {
+        Self {
+            members,
+            aggregator,
+        }
+    }
+
+    /// Returns the [`EnsembleMember`]s of this [`Ensemble`].
+    pub fn members(&self) -> &[EnsembleMember<Strategy>] {
+        &self.members
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::signal;
+    use barter_data::test_util::market_trade;
+    use barter_integration::model::Side;
+
+    /// Member strategy that always advises the same signals map.
+    struct FixedStrategy(HashMap<Decision, SignalStrength>);
+
+    impl SignalGenerator for FixedStrategy {
+        fn generate_signal(&mut self, _: &MarketEvent) -> Option<Signal> {
+            (!self.0.is_empty()).then(|| Signal {
+                signals: self.0.clone(),
+                ..signal()
+            })
+        }
+    }
+
+    fn member(id: &str, weight: f64, signals: &[(Decision, f64)]) -> EnsembleMember<FixedStrategy> {
+        EnsembleMember::new(
+            id,
+            weight,
+            FixedStrategy(
+                signals
+                    .iter()
+                    .map(|(decision, strength)| (*decision, SignalStrength(*strength)))
+                    .collect(),
+            ),
+        )
+    }
+
+    fn members() -> Vec<EnsembleMember<FixedStrategy>> {
+        vec![
+            member("trend", 2.0, &[(Decision::Long, 1.0)]),
+            member(
+                "momentum",
+                1.0,
+                &[(Decision::Long, 0.5), (Decision::CloseShort, 1.0)],
+            ),
+            member("reversion", 1.0, &[(Decision::Short, 1.0)]),
+        ]
+    }
+
+    #[test]
+    fn ensemble_aggregates_member_signals() {
+        struct TestCase {
+            aggregation: Aggregation,
+            expected: HashMap<Decision, SignalStrength>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                // TC0: Majority vote keeps Long (2/3 members) with the mean strength
+                aggregation: Aggregation::MajorityVote,
+                expected: HashMap::from([(Decision::Long, SignalStrength(0.75))]),
+            },
+            TestCase {
+                // TC1: Weighted sum nets Long (2.5 / 4) against Short (1 / 4), dropping
+                // CloseShort (1 / 4) below min_strength
+                aggregation: Aggregation::WeightedSum { min_strength: 0.3 },
+                expected: HashMap::from([(Decision::Long, SignalStrength(0.375))]),
+            },
+            TestCase {
+                // TC2: Weighted sum keeps the prevailing Decision of each opposing pair
+                aggregation: Aggregation::WeightedSum { min_strength: 0.0 },
+                expected: HashMap::from([
+                    (Decision::Long, SignalStrength(0.375)),
+                    (Decision::CloseShort, SignalStrength(0.25)),
+                ]),
+            },
+            TestCase {
+                // TC3: Unanimous agreement is never reached, so no Signal is generated
+                aggregation: Aggregation::Unanimous,
+                expected: HashMap::new(),
+            },
+        ];
+
+        for (index, test) in test_cases.into_iter().enumerate() {
+            let mut ensemble = Ensemble::new(members(), test.aggregation);
+            let actual = ensemble
+                .generate_signal(&market_trade(Side::Buy))
+                .map(|signal| signal.signals)
+                .unwrap_or_default();
+            assert_eq!(actual, test.expected, "Test case {} failed", index);
+        }
+    }
+
+    #[test]
+    fn ensemble_records_member_contributions() {
+        let mut members = members();
+        members.push(member("silent", 1.0, &[]));
+        let mut ensemble = Ensemble::new(members, Aggregation::WeightedSum { min_strength: 0.0 });
+
+        let signal = ensemble.generate_signal(&market_trade(Side::Buy)).unwrap();
+
+        let contributors = signal
+            .contributions
+            .iter()
+            .map(|contribution| contribution.strategy_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(contributors, vec!["trend", "momentum", "reversion"]);
+        assert_eq!(signal.contributions[0].weight, 2.0);
+        assert_eq!(
+            signal.contributions[1].signals[&Decision::CloseShort],
+            SignalStrength(1.0)
+        );
+    }
+
+    #[test]
+    fn ensemble_combines_member_signals_with_meta_model() {
+        /// Meta-model that only trusts the "reversion" member.
+        struct TrustReversion;
+
+        impl SignalAggregator for TrustReversion {
+            fn aggregate(
+                &mut self,
+                _: usize,
+                contributions: &[SignalContribution],
+            ) -> HashMap<Decision, SignalStrength> {
+                contributions
+                    .iter()
+                    .find(|contribution| contribution.strategy_id == "reversion")
+                    .map(|contribution| contribution.signals.clone())
+                    .unwrap_or_default()
+            }
+        }
+
+        let mut ensemble = Ensemble::new(members(), TrustReversion);
+
+        let signal = ensemble.generate_signal(&market_trade(Side::Buy)).unwrap();
+
+        assert_eq!(
+            signal.signals,
+            HashMap::from([(Decision::Short, SignalStrength(1.0))])
+        );
+    }
+
+    #[test]
+    fn aggregation_deserialises_from_config() {
+        let aggregation =
+            serde_json::from_str::<Aggregation>(r#"{ "kind": "weighted_sum" }"#).unwrap();
+
+        assert_eq!(aggregation, Aggregation::WeightedSum { min_strength: 0.0 });
+    }
+}
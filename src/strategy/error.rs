@@ -0,0 +1,15 @@
+use ta::errors::TaError;
+use thiserror::Error;
+
+/// All errors generated in the barter::strategy module.
+#[derive(Error, Debug)]
+pub enum StrategyError {
+    #[error("Failed to construct {indicator} indicator: {error}")]
+    Indicator {
+        indicator: &'static str,
+        error: TaError,
+    },
+
+    #[error("Invalid strategy configuration: {0}")]
+    InvalidConfig(String),
+}
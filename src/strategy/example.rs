@@ -1,7 +1,6 @@
 use super::{Decision, Signal, SignalGenerator, SignalStrength};
-use crate::data::MarketMeta;
+use crate::{clock::Clock, data::MarketMeta};
 use barter_data::model::{DataKind, MarketEvent};
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use ta::{indicators::RelativeStrengthIndex, Next};
@@ -10,12 +9,16 @@ use ta::{indicators::RelativeStrengthIndex, Next};
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct Config {
     pub rsi_period: usize,
+    /// [`Clock`] used to timestamp generated [`Signal`]s.
+    #[serde(default)]
+    pub clock: Clock,
 }
 
 #[derive(Clone, Debug)]
 /// Example RSI based strategy that implements [`SignalGenerator`].
 pub struct RSIStrategy {
     rsi: RelativeStrengthIndex,
+    clock: Clock,
 }
 
 impl SignalGenerator for RSIStrategy {
@@ -38,7 +41,7 @@ impl SignalGenerator for RSIStrategy {
         }
 
         Some(Signal {
-            time: Utc::now(),
+            time: self.clock.time(market.exchange_time),
             exchange: market.exchange.clone(),
             instrument: market.instrument.clone(),
             market_meta: MarketMeta {
@@ -46,6 +49,8 @@ impl SignalGenerator for RSIStrategy {
                 time: market.exchange_time,
             },
             signals,
+            tag: None,
+            contributions: Vec::new(),
         })
     }
 }
@@ -56,7 +61,10 @@ impl RSIStrategy {
         let rsi_indicator = RelativeStrengthIndex::new(config.rsi_period)
             .expect("Failed to construct RSI indicator");
 
-        Self { rsi: rsi_indicator }
+        Self {
+            rsi: rsi_indicator,
+            clock: config.clock,
+        }
     }
 
     /// Given the latest RSI value for a symbol, generates a map containing the [`SignalStrength`] for
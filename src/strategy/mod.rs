@@ -1,17 +1,55 @@
-use crate::data::MarketMeta;
+use crate::{data::MarketMeta, portfolio::position::PositionTag};
 use barter_data::model::MarketEvent;
 use barter_integration::model::{Exchange, Instrument, Market};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Barter strategy module specific errors.
+pub mod error;
+
 /// Barter example RSI strategy [`SignalGenerator`] implementation.
 pub mod example;
 
+/// Composite [`SignalGenerator`] that aggregates the [`Signal`]s of several child strategies.
+pub mod ensemble;
+
+/// Portfolio-level strategies that analyse the [`MarketEvent`]s of every market traded by an
+/// [`Engine`](crate::engine::Engine) & may generate [`Signal`]s for any of them.
+pub mod cross_market;
+
+/// Library of configurable [`SignalGenerator`] strategies built on the `ta` technical analysis
+/// indicators (eg/ moving average crossover, Bollinger bands, MACD, Donchian breakout & RSI).
+pub mod technical;
+
+/// Communicates a String represents a unique strategy identifier (eg/ an [`Ensemble`](ensemble::Ensemble)
+/// member).
+pub type StrategyId = String;
+
 /// May generate an advisory [`Signal`] as a result of analysing an input [`MarketEvent`].
 pub trait SignalGenerator {
     /// Optionally return a [`Signal`] given input [`MarketEvent`].
     fn generate_signal(&mut self, market: &MarketEvent) -> Option<Signal>;
+
+    /// Return every [`Signal`] generated given input [`MarketEvent`]. Used by the
+    /// [`Trader`](crate::engine::trader::Trader), defaulting to the optional [`Signal`] returned
+    /// by generate_signal().
+    fn generate_signals(&mut self, market: &MarketEvent) -> Vec<Signal> {
+        self.generate_signal(market).into_iter().collect()
+    }
+}
+
+impl<Strategy> SignalGenerator for Box<Strategy>
+where
+    Strategy: SignalGenerator + ?Sized,
+{
+    fn generate_signal(&mut self, market: &MarketEvent) -> Option<Signal> {
+        (**self).generate_signal(market)
+    }
+
+    fn generate_signals(&mut self, market: &MarketEvent) -> Vec<Signal> {
+        (**self).generate_signals(market)
+    }
 }
 
 /// Advisory [`Signal`] for a [`Market`] detailing the [`SignalStrength`] associated with each
@@ -24,6 +62,26 @@ pub struct Signal {
     pub signals: HashMap<Decision, SignalStrength>,
     /// Metadata propagated from the [`MarketEvent`] that yielded this [`Signal`].
     pub market_meta: MarketMeta,
+    /// Optional [`PositionTag`] (eg/ strategy id) of the [`Position`](crate::portfolio::position::Position)
+    /// this [`Signal`] is for. Untagged [`Signal`]s act on the market's untagged
+    /// [`Position`](crate::portfolio::position::Position).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<PositionTag>,
+    /// Contribution of each child strategy to an aggregated [`Signal`] (eg/ generated by an
+    /// [`Ensemble`](ensemble::Ensemble)), recorded for attribution.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contributions: Vec<SignalContribution>,
+}
+
+/// Signals map contributed by a child strategy to an aggregated [`Signal`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct SignalContribution {
+    /// Identifier of the contributing strategy.
+    pub strategy_id: StrategyId,
+    /// Weight of the contributing strategy.
+    pub weight: f64,
+    /// [`SignalStrength`] the contributing strategy associated with each [`Decision`].
+    pub signals: HashMap<Decision, SignalStrength>,
 }
 
 /// Describes the type of advisory signal the strategy is endorsing.
@@ -74,6 +132,11 @@ pub struct SignalForceExit {
     pub time: DateTime<Utc>,
     pub exchange: Exchange,
     pub instrument: Instrument,
+    /// Optional [`PositionTag`] of the [`Position`](crate::portfolio::position::Position) to
+    /// exit. If `None`, every open [`Position`](crate::portfolio::position::Position) in the
+    /// market is exited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<PositionTag>,
 }
 
 impl<M> From<M> for SignalForceExit
@@ -99,6 +162,20 @@ impl SignalForceExit {
             time: Utc::now(),
             exchange: exchange.into(),
             instrument: instrument.into(),
+            tag: None,
+        }
+    }
+
+    /// Constructs a new [`Self`] that only exits the [`Position`](crate::portfolio::position::Position)
+    /// with the provided [`PositionTag`].
+    pub fn tagged<E, I>(exchange: E, instrument: I, tag: PositionTag) -> Self
+    where
+        E: Into<Exchange>,
+        I: Into<Instrument>,
+    {
+        Self {
+            tag: Some(tag),
+            ..Self::new(exchange, instrument)
         }
     }
 }
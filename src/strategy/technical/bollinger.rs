@@ -0,0 +1,184 @@
+use super::{candle, signal, StrengthScaling};
+use crate::strategy::{error::StrategyError, Decision, Signal, SignalGenerator, SignalStrength};
+use barter_data::model::MarketEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ta::{
+    indicators::{BollingerBands, BollingerBandsOutput},
+    Next,
+};
+
+/// Configuration for constructing a [`BollingerStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    pub period: usize,
+    /// Number of standard deviations between the moving average & each band.
+    pub multiplier: f64,
+    /// Scales the entry [`SignalStrength`] by the distance the close is beyond a band, relative to
+    /// the band width.
+    #[serde(default)]
+    pub scaling: StrengthScaling,
+}
+
+/// Bollinger band mean-reversion strategy that implements [`SignalGenerator`]. Goes long when the
+/// close falls below the lower band & short when it rises above the upper band, closing each
+/// position once the close reverts to the moving average.
+#[derive(Clone, Debug)]
+pub struct BollingerStrategy {
+    bands: BollingerBands,
+    scaling: StrengthScaling,
+    /// Number of Candles required before the bands are fully formed.
+    warm_up: usize,
+    /// Number of Candles analysed so far.
+    candles: usize,
+}
+
+impl SignalGenerator for BollingerStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent) -> Option<Signal> {
+        let close = candle(market)?.close;
+
+        // Calculate the next Bollinger bands using the new MarketEvent Candle data
+        let bands = self.bands.next(close);
+        self.candles += 1;
+        if self.candles < self.warm_up {
+            return None;
+        }
+
+        // Generate advisory signals map
+        let signals = self.generate_signals_map(close, bands);
+
+        // If signals map is empty, return no SignalEvent
+        signal(market, close, signals)
+    }
+}
+
+impl BollingerStrategy {
+    /// Constructs a new [`BollingerStrategy`] component using the provided configuration struct.
+    pub fn new(config: Config) -> Result<Self, StrategyError> {
+        Ok(Self {
+            bands: BollingerBands::new(config.period, config.multiplier).map_err(|error| {
+                StrategyError::Indicator {
+                    indicator: "Bollinger Bands",
+                    error,
+                }
+            })?,
+            scaling: config.scaling,
+            warm_up: config.period,
+            candles: 0,
+        })
+    }
+
+    /// Given the latest close & Bollinger bands for a symbol, generates a map containing the
+    /// [`SignalStrength`] for [`Decision`] under consideration.
+    fn generate_signals_map(
+        &self,
+        close: f64,
+        bands: BollingerBandsOutput,
+    ) -> HashMap<Decision, SignalStrength> {
+        let width = bands.upper - bands.lower;
+
+        let mut signals = HashMap::with_capacity(4);
+        if close >= bands.average {
+            signals.insert(Decision::CloseLong, SignalStrength(1.0));
+        }
+        if close <= bands.average {
+            signals.insert(Decision::CloseShort, SignalStrength(1.0));
+        }
+        if close < bands.lower {
+            signals.insert(
+                Decision::Long,
+                self.scaling.strength(bands.lower - close, width),
+            );
+        }
+        if close > bands.upper {
+            signals.insert(
+                Decision::Short,
+                self.scaling.strength(close - bands.upper, width),
+            );
+        }
+        signals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_candle_hlc;
+
+    fn strategy(scaling: StrengthScaling) -> BollingerStrategy {
+        BollingerStrategy::new(Config {
+            period: 4,
+            multiplier: 1.0,
+            scaling,
+        })
+        .unwrap()
+    }
+
+    fn generate_signals(strategy: &mut BollingerStrategy, closes: &[f64]) -> Vec<Option<Signal>> {
+        closes
+            .iter()
+            .map(|close| strategy.generate_signal(&market_candle_hlc(*close, *close, *close)))
+            .collect()
+    }
+
+    #[test]
+    fn bollinger_does_not_signal_before_bands_are_formed() {
+        let actual = generate_signals(&mut strategy(StrengthScaling::Constant), &[10.0, 5.0, 20.0]);
+
+        assert!(actual.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn bollinger_enters_beyond_bands_and_exits_at_the_average() {
+        let mut strategy = strategy(StrengthScaling::Constant);
+
+        let signals = generate_signals(&mut strategy, &[10.0, 10.0, 10.0, 10.0, 5.0, 9.0]);
+
+        // Close at the average closes both Long & Short Positions
+        assert_eq!(
+            signals[3].as_ref().unwrap().signals,
+            HashMap::from([
+                (Decision::CloseLong, SignalStrength(1.0)),
+                (Decision::CloseShort, SignalStrength(1.0)),
+            ])
+        );
+
+        // Close below the lower band enters Long
+        assert_eq!(
+            signals[4].as_ref().unwrap().signals,
+            HashMap::from([
+                (Decision::Long, SignalStrength(1.0)),
+                (Decision::CloseShort, SignalStrength(1.0)),
+            ])
+        );
+
+        // Close reverting above the average closes the Long Position
+        assert_eq!(
+            signals[5].as_ref().unwrap().signals,
+            HashMap::from([(Decision::CloseLong, SignalStrength(1.0))])
+        );
+    }
+
+    #[test]
+    fn bollinger_scales_entry_strength_by_distance_beyond_band() {
+        let mut strategy = strategy(StrengthScaling::Linear);
+
+        let signal = generate_signals(&mut strategy, &[10.0, 10.0, 10.0, 10.0, 15.0])
+            .pop()
+            .flatten()
+            .unwrap();
+
+        // Average 11.25 & standard deviation ~2.165, so upper band ~13.415 & width ~4.33
+        let strength = signal.signals[&Decision::Short].0;
+        assert!((strength - (15.0 - 13.415) / 4.33).abs() < 1e-3);
+        assert_eq!(signal.signals[&Decision::CloseLong], SignalStrength(1.0));
+    }
+
+    #[test]
+    fn bollinger_config_deserialises_with_default_scaling() {
+        let config =
+            serde_json::from_str::<Config>(r#"{ "period": 20, "multiplier": 2.0 }"#).unwrap();
+
+        assert_eq!(config.scaling, StrengthScaling::Constant);
+    }
+}
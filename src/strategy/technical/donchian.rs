@@ -0,0 +1,118 @@
+use super::{candle, entry_signals, signal};
+use crate::strategy::{error::StrategyError, Decision, Signal, SignalGenerator, SignalStrength};
+use barter_data::model::MarketEvent;
+use serde::{Deserialize, Serialize};
+use ta::{
+    indicators::{Maximum, Minimum},
+    Next,
+};
+
+/// Configuration for constructing a [`DonchianStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// Number of previous Candles the Donchian channel spans.
+    pub period: usize,
+}
+
+/// Donchian channel breakout strategy that implements [`SignalGenerator`]. Goes long when the
+/// close breaks above the highest high of the previous period Candles, and short when it breaks
+/// below the lowest low.
+#[derive(Clone, Debug)]
+pub struct DonchianStrategy {
+    highest: Maximum,
+    lowest: Minimum,
+    /// Number of Candles required before the channel is fully formed.
+    warm_up: usize,
+    /// Number of Candles analysed so far.
+    candles: usize,
+    /// Upper & lower bounds of the channel formed by the previous Candles, once warmed up.
+    channel: Option<(f64, f64)>,
+}
+
+impl SignalGenerator for DonchianStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent) -> Option<Signal> {
+        let candle = candle(market)?;
+
+        // Determine if the close broke out of the channel formed by the previous Candles
+        let signals = self.channel.and_then(|(upper, lower)| {
+            if candle.close > upper {
+                Some(entry_signals(Decision::Long, SignalStrength(1.0)))
+            } else if candle.close < lower {
+                Some(entry_signals(Decision::Short, SignalStrength(1.0)))
+            } else {
+                None
+            }
+        });
+
+        // Update the channel using the new MarketEvent Candle data
+        let upper = self.highest.next(candle.high);
+        let lower = self.lowest.next(candle.low);
+        self.candles += 1;
+        if self.candles >= self.warm_up {
+            self.channel = Some((upper, lower));
+        }
+
+        signal(market, candle.close, signals?)
+    }
+}
+
+impl DonchianStrategy {
+    /// Constructs a new [`DonchianStrategy`] component using the provided configuration struct.
+    pub fn new(config: Config) -> Result<Self, StrategyError> {
+        Ok(Self {
+            highest: Maximum::new(config.period).map_err(|error| StrategyError::Indicator {
+                indicator: "Maximum",
+                error,
+            })?,
+            lowest: Minimum::new(config.period).map_err(|error| StrategyError::Indicator {
+                indicator: "Minimum",
+                error,
+            })?,
+            warm_up: config.period,
+            candles: 0,
+            channel: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_candle_hlc;
+
+    #[test]
+    fn donchian_signals_when_close_breaks_out_of_previous_channel() {
+        let mut strategy = DonchianStrategy::new(Config { period: 3 }).unwrap();
+
+        let actual = [
+            (11.0, 9.0, 10.0),
+            (12.0, 8.0, 10.0),
+            (11.0, 9.0, 10.0),
+            (12.0, 10.0, 11.0),
+            (13.0, 12.0, 13.0),
+            (13.0, 11.0, 12.0),
+            (9.0, 7.0, 7.0),
+        ]
+        .into_iter()
+        .map(|(high, low, close)| {
+            strategy
+                .generate_signal(&market_candle_hlc(high, low, close))
+                .map(|signal| signal.signals)
+        })
+        .collect::<Vec<_>>();
+
+        let mut expected = vec![None; 7];
+        // Close 13.0 breaks above the highest high of 12.0
+        expected[4] = Some(entry_signals(Decision::Long, SignalStrength(1.0)));
+        // Close 7.0 breaks below the lowest low of 10.0
+        expected[6] = Some(entry_signals(Decision::Short, SignalStrength(1.0)));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn donchian_config_deserialises() {
+        let config = serde_json::from_str::<Config>(r#"{ "period": 20 }"#).unwrap();
+
+        assert_eq!(config, Config { period: 20 });
+    }
+}
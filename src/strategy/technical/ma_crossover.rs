@@ -0,0 +1,196 @@
+use super::{candle, entry_signals, signal};
+use crate::strategy::{error::StrategyError, Decision, Signal, SignalGenerator, SignalStrength};
+use barter_data::model::MarketEvent;
+use serde::{Deserialize, Serialize};
+use ta::{
+    indicators::{ExponentialMovingAverage, SimpleMovingAverage},
+    Next,
+};
+
+/// Type of moving average used by a [`MACrossoverStrategy`].
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MovingAverage {
+    #[default]
+    Simple,
+    Exponential,
+}
+
+/// Configuration for constructing a [`MACrossoverStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    pub fast_period: usize,
+    pub slow_period: usize,
+    #[serde(default)]
+    pub moving_average: MovingAverage,
+}
+
+/// Moving average indicator of the configured [`MovingAverage`] type.
+#[derive(Clone, Debug)]
+enum Indicator {
+    Simple(SimpleMovingAverage),
+    Exponential(ExponentialMovingAverage),
+}
+
+impl Indicator {
+    fn new(moving_average: MovingAverage, period: usize) -> Result<Self, StrategyError> {
+        match moving_average {
+            MovingAverage::Simple => SimpleMovingAverage::new(period)
+                .map(Indicator::Simple)
+                .map_err(|error| StrategyError::Indicator {
+                    indicator: "SMA",
+                    error,
+                }),
+            MovingAverage::Exponential => ExponentialMovingAverage::new(period)
+                .map(Indicator::Exponential)
+                .map_err(|error| StrategyError::Indicator {
+                    indicator: "EMA",
+                    error,
+                }),
+        }
+    }
+
+    fn next(&mut self, close: f64) -> f64 {
+        match self {
+            Indicator::Simple(sma) => sma.next(close),
+            Indicator::Exponential(ema) => ema.next(close),
+        }
+    }
+}
+
+/// Moving average crossover strategy that implements [`SignalGenerator`]. Goes long when the
+/// fast moving average crosses above the slow moving average, and short when it crosses below.
+#[derive(Clone, Debug)]
+pub struct MACrossoverStrategy {
+    fast: Indicator,
+    slow: Indicator,
+    /// Number of Candles required before the slow moving average is fully formed.
+    warm_up: usize,
+    /// Number of Candles analysed so far.
+    candles: usize,
+    /// Fast minus slow moving average of the previous Candle, once warmed up.
+    previous_spread: Option<f64>,
+}
+
+impl SignalGenerator for MACrossoverStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent) -> Option<Signal> {
+        let close = candle(market)?.close;
+
+        // Calculate the next moving average values using the new MarketEvent Candle data
+        let spread = self.fast.next(close) - self.slow.next(close);
+        self.candles += 1;
+        if self.candles < self.warm_up {
+            return None;
+        }
+
+        // Generate advisory signals map if the moving averages crossed
+        let previous_spread = self.previous_spread.replace(spread)?;
+        let signals = match (previous_spread <= 0.0, spread > 0.0) {
+            (true, true) => entry_signals(Decision::Long, SignalStrength(1.0)),
+            (false, false) => entry_signals(Decision::Short, SignalStrength(1.0)),
+            _ => return None,
+        };
+
+        signal(market, close, signals)
+    }
+}
+
+impl MACrossoverStrategy {
+    /// Constructs a new [`MACrossoverStrategy`] component using the provided configuration struct.
+    /// The fast_period must be shorter than the slow_period.
+    pub fn new(config: Config) -> Result<Self, StrategyError> {
+        if config.fast_period >= config.slow_period {
+            return Err(StrategyError::InvalidConfig(format!(
+                "fast_period {} must be less than slow_period {}",
+                config.fast_period, config.slow_period
+            )));
+        }
+
+        Ok(Self {
+            fast: Indicator::new(config.moving_average, config.fast_period)?,
+            slow: Indicator::new(config.moving_average, config.slow_period)?,
+            warm_up: config.slow_period,
+            candles: 0,
+            previous_spread: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_candle_hlc;
+
+    fn decisions(strategy: &mut MACrossoverStrategy, closes: &[f64]) -> Vec<Option<Decision>> {
+        closes
+            .iter()
+            .map(|close| {
+                strategy
+                    .generate_signal(&market_candle_hlc(*close, *close, *close))
+                    .map(|signal| {
+                        *signal
+                            .signals
+                            .keys()
+                            .find(|decision| decision.is_entry())
+                            .unwrap()
+                    })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ma_crossover_signals_when_fast_average_crosses_slow_average() {
+        let mut strategy = MACrossoverStrategy::new(Config {
+            fast_period: 2,
+            slow_period: 3,
+            moving_average: MovingAverage::Simple,
+        })
+        .unwrap();
+
+        let actual = decisions(&mut strategy, &[10.0, 10.0, 9.0, 8.0, 12.0, 13.0, 9.0, 6.0]);
+
+        assert_eq!(
+            actual,
+            vec![
+                None,
+                None,
+                None,
+                None,
+                Some(Decision::Long),
+                None,
+                Some(Decision::Short),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn ma_crossover_entry_signal_closes_opposite_position() {
+        let mut strategy = MACrossoverStrategy::new(Config {
+            fast_period: 2,
+            slow_period: 3,
+            moving_average: MovingAverage::Exponential,
+        })
+        .unwrap();
+
+        let signal = [10.0, 9.0, 8.0, 7.0, 15.0]
+            .into_iter()
+            .filter_map(|close| strategy.generate_signal(&market_candle_hlc(close, close, close)))
+            .last()
+            .unwrap();
+
+        assert_eq!(
+            signal.signals,
+            entry_signals(Decision::Long, SignalStrength(1.0))
+        );
+        assert_eq!(signal.market_meta.close, 15.0);
+    }
+
+    #[test]
+    fn ma_crossover_config_deserialises_with_default_moving_average() {
+        let config =
+            serde_json::from_str::<Config>(r#"{ "fast_period": 10, "slow_period": 30 }"#).unwrap();
+
+        assert_eq!(config.moving_average, MovingAverage::Simple);
+    }
+}
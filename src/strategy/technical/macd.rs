@@ -0,0 +1,124 @@
+use super::{candle, entry_signals, signal};
+use crate::strategy::{error::StrategyError, Decision, Signal, SignalGenerator, SignalStrength};
+use barter_data::model::MarketEvent;
+use serde::{Deserialize, Serialize};
+use ta::{indicators::MovingAverageConvergenceDivergence, Next};
+
+/// Configuration for constructing a [`MACDStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    pub fast_period: usize,
+    pub slow_period: usize,
+    pub signal_period: usize,
+}
+
+/// MACD strategy that implements [`SignalGenerator`]. Goes long when the MACD line crosses above
+/// it's signal line (ie/ the histogram turns positive), and short when it crosses below.
+#[derive(Clone, Debug)]
+pub struct MACDStrategy {
+    macd: MovingAverageConvergenceDivergence,
+    /// Number of Candles required before the slow moving average is fully formed.
+    warm_up: usize,
+    /// Number of Candles analysed so far.
+    candles: usize,
+    /// MACD histogram of the previous Candle, once warmed up.
+    previous_histogram: Option<f64>,
+}
+
+impl SignalGenerator for MACDStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent) -> Option<Signal> {
+        let close = candle(market)?.close;
+
+        // Calculate the next MACD histogram value using the new MarketEvent Candle data
+        let histogram = self.macd.next(close).histogram;
+        self.candles += 1;
+        if self.candles < self.warm_up {
+            return None;
+        }
+
+        // Generate advisory signals map if the MACD line crossed it's signal line
+        let previous_histogram = self.previous_histogram.replace(histogram)?;
+        let signals = match (previous_histogram <= 0.0, histogram > 0.0) {
+            (true, true) => entry_signals(Decision::Long, SignalStrength(1.0)),
+            (false, false) => entry_signals(Decision::Short, SignalStrength(1.0)),
+            _ => return None,
+        };
+
+        signal(market, close, signals)
+    }
+}
+
+impl MACDStrategy {
+    /// Constructs a new [`MACDStrategy`] component using the provided configuration struct. The
+    /// fast_period must be shorter than the slow_period.
+    pub fn new(config: Config) -> Result<Self, StrategyError> {
+        if config.fast_period >= config.slow_period {
+            return Err(StrategyError::InvalidConfig(format!(
+                "fast_period {} must be less than slow_period {}",
+                config.fast_period, config.slow_period
+            )));
+        }
+
+        Ok(Self {
+            macd: MovingAverageConvergenceDivergence::new(
+                config.fast_period,
+                config.slow_period,
+                config.signal_period,
+            )
+            .map_err(|error| StrategyError::Indicator {
+                indicator: "MACD",
+                error,
+            })?,
+            warm_up: config.slow_period,
+            candles: 0,
+            previous_histogram: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_candle_hlc;
+
+    #[test]
+    fn macd_signals_when_histogram_crosses_zero() {
+        let mut strategy = MACDStrategy::new(Config {
+            fast_period: 2,
+            slow_period: 3,
+            signal_period: 2,
+        })
+        .unwrap();
+
+        let actual = [10.0, 9.0, 8.0, 7.0, 6.0, 12.0, 14.0, 16.0, 10.0, 6.0, 4.0]
+            .into_iter()
+            .map(|close| {
+                strategy
+                    .generate_signal(&market_candle_hlc(close, close, close))
+                    .map(|signal| signal.signals)
+            })
+            .collect::<Vec<_>>();
+
+        let mut expected = vec![None; 11];
+        expected[5] = Some(entry_signals(Decision::Long, SignalStrength(1.0)));
+        expected[8] = Some(entry_signals(Decision::Short, SignalStrength(1.0)));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn macd_config_deserialises() {
+        let config = serde_json::from_str::<Config>(
+            r#"{ "fast_period": 12, "slow_period": 26, "signal_period": 9 }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                fast_period: 12,
+                slow_period: 26,
+                signal_period: 9,
+            }
+        );
+    }
+}
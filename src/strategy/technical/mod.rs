@@ -0,0 +1,141 @@
+use super::{Decision, Signal, SignalStrength};
+use crate::data::MarketMeta;
+use barter_data::model::{Candle, DataKind, MarketEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Moving average crossover [`SignalGenerator`](super::SignalGenerator) strategy.
+pub mod ma_crossover;
+
+/// Bollinger band mean-reversion [`SignalGenerator`](super::SignalGenerator) strategy.
+pub mod bollinger;
+
+/// MACD histogram crossover [`SignalGenerator`](super::SignalGenerator) strategy.
+pub mod macd;
+
+/// Donchian channel breakout [`SignalGenerator`](super::SignalGenerator) strategy.
+pub mod donchian;
+
+/// RSI [`SignalGenerator`](super::SignalGenerator) strategy with configurable thresholds &
+/// [`StrengthScaling`].
+pub mod rsi;
+
+/// Determines how the [`SignalStrength`] of a [`Decision`] is derived from how far an indicator
+/// has moved beyond it's threshold.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StrengthScaling {
+    /// Every [`Decision`] has a [`SignalStrength`] of 1.0.
+    #[default]
+    Constant,
+    /// [`SignalStrength`] scales linearly from 0.0 at the threshold to 1.0 at the indicator's
+    /// extreme (eg/ an RSI of 0 or 100), clamped between 0.0 & 1.0.
+    Linear,
+}
+
+impl StrengthScaling {
+    /// Calculates the [`SignalStrength`] given the distance an indicator is beyond it's threshold,
+    /// and the range between the threshold & the indicator's extreme.
+    pub fn strength(&self, distance: f64, range: f64) -> SignalStrength {
+        match self {
+            StrengthScaling::Constant => SignalStrength(1.0),
+            StrengthScaling::Linear if range <= 0.0 => SignalStrength(1.0),
+            StrengthScaling::Linear => SignalStrength((distance / range).clamp(0.0, 1.0)),
+        }
+    }
+}
+
+/// Returns the [`Candle`] contained in the [`MarketEvent`], if any.
+fn candle(market: &MarketEvent) -> Option<&Candle> {
+    match &market.kind {
+        DataKind::Candle(candle) => Some(candle),
+        _ => None,
+    }
+}
+
+/// Generates a signals map entering the provided [`Decision`] (Long or Short), and closing any
+/// opposite position, with the provided [`SignalStrength`].
+fn entry_signals(entry: Decision, strength: SignalStrength) -> HashMap<Decision, SignalStrength> {
+    let exit = match entry {
+        Decision::Short => Decision::CloseLong,
+        _ => Decision::CloseShort,
+    };
+
+    HashMap::from([(entry, strength), (exit, strength)])
+}
+
+/// Constructs a [`Signal`] for the [`MarketEvent`] Candle close using the provided signals map,
+/// returning no [`Signal`] if the signals map is empty.
+fn signal(
+    market: &MarketEvent,
+    close: f64,
+    signals: HashMap<Decision, SignalStrength>,
+) -> Option<Signal> {
+    if signals.is_empty() {
+        return None;
+    }
+
+    Some(Signal {
+        time: market.exchange_time,
+        exchange: market.exchange.clone(),
+        instrument: market.instrument.clone(),
+        market_meta: MarketMeta {
+            close,
+            time: market.exchange_time,
+        },
+        signals,
+        tag: None,
+        contributions: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strength_scaling_derives_signal_strength_from_distance_beyond_threshold() {
+        struct TestCase {
+            scaling: StrengthScaling,
+            distance: f64,
+            range: f64,
+            expected: SignalStrength,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                // TC0: Constant scaling ignores the distance
+                scaling: StrengthScaling::Constant,
+                distance: 5.0,
+                range: 30.0,
+                expected: SignalStrength(1.0),
+            },
+            TestCase {
+                // TC1: Linear scaling is proportional to the distance
+                scaling: StrengthScaling::Linear,
+                distance: 15.0,
+                range: 30.0,
+                expected: SignalStrength(0.5),
+            },
+            TestCase {
+                // TC2: Linear scaling is clamped beyond the extreme
+                scaling: StrengthScaling::Linear,
+                distance: 45.0,
+                range: 30.0,
+                expected: SignalStrength(1.0),
+            },
+            TestCase {
+                // TC3: Linear scaling with no range is full strength
+                scaling: StrengthScaling::Linear,
+                distance: 1.0,
+                range: 0.0,
+                expected: SignalStrength(1.0),
+            },
+        ];
+
+        for (index, test) in test_cases.into_iter().enumerate() {
+            let actual = test.scaling.strength(test.distance, test.range);
+            assert_eq!(actual, test.expected, "Test case {} failed", index);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use super::{candle, signal, StrengthScaling};
+use crate::strategy::{error::StrategyError, Decision, Signal, SignalGenerator, SignalStrength};
+use barter_data::model::MarketEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ta::{indicators::RelativeStrengthIndex, Next};
+
+/// Configuration for constructing a [`RSIThresholdStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    pub rsi_period: usize,
+    /// RSI below which the market is considered oversold (eg/ 30.0).
+    pub oversold: f64,
+    /// RSI above which the market is considered overbought (eg/ 70.0).
+    pub overbought: f64,
+    /// Scales the [`SignalStrength`] by the distance the RSI is beyond it's threshold, relative
+    /// to the distance between the threshold & the RSI extreme (0 or 100).
+    #[serde(default)]
+    pub scaling: StrengthScaling,
+}
+
+/// RSI strategy with configurable thresholds & [`StrengthScaling`] that implements
+/// [`SignalGenerator`]. Goes long when the market is oversold, and short when it's overbought.
+#[derive(Clone, Debug)]
+pub struct RSIThresholdStrategy {
+    rsi: RelativeStrengthIndex,
+    oversold: f64,
+    overbought: f64,
+    scaling: StrengthScaling,
+    /// Number of Candles required before the RSI is fully formed.
+    warm_up: usize,
+    /// Number of Candles analysed so far.
+    candles: usize,
+}
+
+impl SignalGenerator for RSIThresholdStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent) -> Option<Signal> {
+        let close = candle(market)?.close;
+
+        // Calculate the next RSI value using the new MarketEvent Candle data
+        let rsi = self.rsi.next(close);
+        self.candles += 1;
+        if self.candles < self.warm_up {
+            return None;
+        }
+
+        // Generate advisory signals map
+        let signals = self.generate_signals_map(rsi);
+
+        // If signals map is empty, return no SignalEvent
+        signal(market, close, signals)
+    }
+}
+
+impl RSIThresholdStrategy {
+    /// Constructs a new [`RSIThresholdStrategy`] component using the provided configuration
+    /// struct. The oversold threshold must be below the overbought threshold.
+    pub fn new(config: Config) -> Result<Self, StrategyError> {
+        if config.oversold >= config.overbought {
+            return Err(StrategyError::InvalidConfig(format!(
+                "oversold {} must be less than overbought {}",
+                config.oversold, config.overbought
+            )));
+        }
+
+        Ok(Self {
+            rsi: RelativeStrengthIndex::new(config.rsi_period).map_err(|error| {
+                StrategyError::Indicator {
+                    indicator: "RSI",
+                    error,
+                }
+            })?,
+            oversold: config.oversold,
+            overbought: config.overbought,
+            scaling: config.scaling,
+            warm_up: config.rsi_period,
+            candles: 0,
+        })
+    }
+
+    /// Given the latest RSI value for a symbol, generates a map containing the [`SignalStrength`]
+    /// for [`Decision`] under consideration.
+    fn generate_signals_map(&self, rsi: f64) -> HashMap<Decision, SignalStrength> {
+        let mut signals = HashMap::with_capacity(4);
+        if rsi < self.oversold {
+            let strength = self.scaling.strength(self.oversold - rsi, self.oversold);
+            signals.insert(Decision::Long, strength);
+            signals.insert(Decision::CloseShort, strength);
+        }
+        if rsi > self.overbought {
+            let strength = self
+                .scaling
+                .strength(rsi - self.overbought, 100.0 - self.overbought);
+            signals.insert(Decision::Short, strength);
+            signals.insert(Decision::CloseLong, strength);
+        }
+        signals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_candle_hlc;
+
+    fn generate_signals(
+        strategy: &mut RSIThresholdStrategy,
+        closes: &[f64],
+    ) -> Vec<Option<HashMap<Decision, SignalStrength>>> {
+        closes
+            .iter()
+            .map(|close| {
+                strategy
+                    .generate_signal(&market_candle_hlc(*close, *close, *close))
+                    .map(|signal| signal.signals)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rsi_threshold_signals_beyond_configured_thresholds() {
+        let mut strategy = RSIThresholdStrategy::new(Config {
+            rsi_period: 2,
+            oversold: 30.0,
+            overbought: 70.0,
+            scaling: StrengthScaling::Constant,
+        })
+        .unwrap();
+
+        // RSI: 50.0, 50.0, ~0.33, ~89.97
+        let actual = generate_signals(&mut strategy, &[10.0, 10.0, 5.0, 20.0]);
+
+        assert_eq!(
+            actual,
+            vec![
+                None,
+                None,
+                Some(HashMap::from([
+                    (Decision::Long, SignalStrength(1.0)),
+                    (Decision::CloseShort, SignalStrength(1.0)),
+                ])),
+                Some(HashMap::from([
+                    (Decision::Short, SignalStrength(1.0)),
+                    (Decision::CloseLong, SignalStrength(1.0)),
+                ])),
+            ]
+        );
+    }
+
+    #[test]
+    fn rsi_threshold_does_not_signal_within_thresholds() {
+        let mut strategy = RSIThresholdStrategy::new(Config {
+            rsi_period: 2,
+            oversold: 0.1,
+            overbought: 95.0,
+            scaling: StrengthScaling::Constant,
+        })
+        .unwrap();
+
+        let actual = generate_signals(&mut strategy, &[10.0, 10.0, 5.0, 20.0]);
+
+        assert!(actual.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn rsi_threshold_scales_strength_by_distance_beyond_threshold() {
+        let mut strategy = RSIThresholdStrategy::new(Config {
+            rsi_period: 2,
+            oversold: 30.0,
+            overbought: 70.0,
+            scaling: StrengthScaling::Linear,
+        })
+        .unwrap();
+
+        let actual = generate_signals(&mut strategy, &[10.0, 10.0, 5.0, 20.0]);
+
+        let long = actual[2].as_ref().unwrap()[&Decision::Long].0;
+        assert!((long - (30.0 - 0.3311) / 30.0).abs() < 1e-3);
+        let short = actual[3].as_ref().unwrap()[&Decision::Short].0;
+        assert!((short - (89.9734 - 70.0) / 30.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn rsi_threshold_config_deserialises_with_default_scaling() {
+        let config = serde_json::from_str::<Config>(
+            r#"{ "rsi_period": 14, "oversold": 30.0, "overbought": 70.0 }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.scaling, StrengthScaling::Constant);
+    }
+}
@@ -1,26 +1,55 @@
 use barter::{
-    data::historical,
-    engine::{trader::Trader, Engine},
-    event::EventTx,
+    clock::Clock,
+    config::{registry::Registry, EngineConfig},
+    data::{historical, live, MarketMeta},
+    engine::{
+        error::EngineError,
+        outcome::Termination,
+        trader::{Trader, TraderFactory},
+        Command, Engine, Reconciliation,
+    },
+    event::{
+        sink::{FanOutTx, MetricsTx},
+        Event, EventTx,
+    },
     execution::{
+        error::ExecutionError,
         simulated::{Config as ExecutionConfig, SimulatedExecution},
-        Fees,
+        AccountReporter, AccountSnapshot, AsyncExecutionClient, ExecutionClient, Fees, FillEvent,
+        VenuePosition,
     },
+    metrics,
     portfolio::{
-        allocator::DefaultAllocator, portfolio::MetaPortfolio,
-        repository::in_memory::InMemoryRepository, risk::DefaultRisk,
+        actor::PortfolioActor,
+        allocator::DefaultAllocator,
+        portfolio::MetaPortfolio,
+        position::determine_position_id,
+        reconcile::Discrepancy,
+        repository::{
+            in_memory::InMemoryRepository, instrumented::InstrumentedRepository, PositionHandler,
+        },
+        risk::DefaultRisk,
+        Balance, OrderEvent,
     },
     statistic::summary::{
         trading::{Config as StatisticConfig, TradingSummary},
         Initialiser,
     },
-    strategy::example::{Config as StrategyConfig, RSIStrategy},
+    strategy::{
+        cross_market::{CrossMarketSignalGenerator, CrossMarketStrategy},
+        example::{Config as StrategyConfig, RSIStrategy},
+        Decision, Signal, SignalGenerator, SignalStrength,
+    },
+    test_util::position,
 };
-use barter_data::test_util::market_trade;
-use barter_integration::model::{InstrumentKind, Market, Side};
+use barter_data::{
+    model::{DataKind, MarketEvent},
+    test_util::market_trade,
+};
+use barter_integration::model::{Instrument, InstrumentKind, Market, Side};
 use parking_lot::Mutex;
 use std::{collections::HashMap, sync::Arc, time::Duration};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use uuid::Uuid;
 
 #[tokio::test]
@@ -74,13 +103,17 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
             .data(historical::MarketFeed::new(
                 [market_trade(Side::Buy)].into_iter(),
             ))
-            .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
+            .strategy(RSIStrategy::new(StrategyConfig {
+                rsi_period: 14,
+                clock: Clock::Live,
+            }))
             .execution(SimulatedExecution::new(ExecutionConfig {
                 simulated_fees_pct: Fees {
                     exchange: 0.1,
                     slippage: 0.05,
                     network: 0.0,
                 },
+                clock: Clock::Live,
             }))
             .build()
             .expect("failed to build trader"),
@@ -114,5 +147,961 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
     assert!(
         actual.is_ok(),
         "failed because Engine's command_rx.await is blocking the Engine from stopping"
-    )
+    );
+
+    let outcome = actual.unwrap();
+    assert_eq!(outcome.termination, Termination::Organic);
+    assert!(outcome.trader_failures.is_empty());
+}
+
+#[tokio::test]
+async fn engine_with_portfolio_actor_stops_after_candles_finished() {
+    let (_command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, _event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+    let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+    // Spawn a PortfolioActor that owns the MetaPortfolio, instead of sharing it behind a lock
+    let (portfolio, _actor) = PortfolioActor::spawn(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(StatisticConfig {
+                starting_equity: 10_000.0,
+                trading_days_per_year: 365,
+                risk_free_return: 0.0,
+            })
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    );
+
+    // Every Trader is given it's own PortfolioHandle, so the Traders never contend on a lock
+    let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+    let trader = Trader::builder()
+        .engine_id(engine_id)
+        .market(market.clone())
+        .command_rx(trader_command_rx)
+        .event_tx(event_tx)
+        .portfolio(Arc::new(Mutex::new(portfolio.clone())))
+        .data(historical::MarketFeed::new(
+            [market_trade(Side::Buy)].into_iter(),
+        ))
+        .strategy(RSIStrategy::new(StrategyConfig {
+            rsi_period: 14,
+            clock: Clock::Live,
+        }))
+        .execution(SimulatedExecution::new(ExecutionConfig {
+            simulated_fees_pct: Fees {
+                exchange: 0.1,
+                slippage: 0.05,
+                network: 0.0,
+            },
+            clock: Clock::Live,
+        }))
+        .build()
+        .expect("failed to build trader");
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(Arc::new(Mutex::new(portfolio)))
+        .traders(vec![trader])
+        .trader_command_txs(HashMap::from_iter([(market, trader_command_tx)]))
+        .statistics_summary(TradingSummary::init(StatisticConfig {
+            starting_equity: 1000.0,
+            trading_days_per_year: 365,
+            risk_free_return: 0.0,
+        }))
+        .build()
+        .expect("failed to build engine");
+
+    let actual = tokio::time::timeout(Duration::from_millis(100), engine.run()).await;
+
+    assert!(actual.is_ok(), "failed because Engine did not stop");
+
+    let outcome = actual.unwrap();
+    assert_eq!(outcome.termination, Termination::Organic);
+    assert!(outcome.trader_failures.is_empty());
+    assert_eq!(outcome.balance.map(|balance| balance.total), Some(10_000.0));
+}
+
+#[tokio::test]
+async fn engine_with_async_traders_stops_after_candles_finished() {
+    let (_command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, _event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+
+    // Many more Markets than threads, since every Trader runs as a task on the runtime
+    let markets = (0..100)
+        .map(|index| {
+            Market::new(
+                "binance",
+                (
+                    format!("coin{}", index),
+                    "usdt".to_owned(),
+                    InstrumentKind::Spot,
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(markets.clone())
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(StatisticConfig {
+                starting_equity: 10_000.0,
+                trading_days_per_year: 365,
+                risk_free_return: 0.0,
+            })
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    let mut traders = Vec::with_capacity(markets.len());
+    let mut trader_command_txs = HashMap::with_capacity(markets.len());
+    for market in markets {
+        let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+        trader_command_txs.insert(market.clone(), trader_command_tx);
+
+        let mut trade = market_trade(Side::Buy);
+        trade.exchange = market.exchange.clone();
+        trade.instrument = market.instrument.clone();
+
+        traders.push(
+            Trader::builder()
+                .engine_id(engine_id)
+                .market(market)
+                .command_rx(trader_command_rx)
+                .event_tx(event_tx.clone())
+                .portfolio(Arc::clone(&portfolio))
+                .data(historical::MarketFeed::new([trade].into_iter()))
+                .strategy(RSIStrategy::new(StrategyConfig {
+                    rsi_period: 14,
+                    clock: Clock::Live,
+                }))
+                .execution(SimulatedExecution::new(ExecutionConfig {
+                    simulated_fees_pct: Fees {
+                        exchange: 0.1,
+                        slippage: 0.05,
+                        network: 0.0,
+                    },
+                    clock: Clock::Live,
+                }))
+                .build()
+                .expect("failed to build trader"),
+        );
+    }
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(portfolio)
+        .traders(traders)
+        .trader_command_txs(trader_command_txs)
+        .statistics_summary(TradingSummary::init(StatisticConfig {
+            starting_equity: 1000.0,
+            trading_days_per_year: 365,
+            risk_free_return: 0.0,
+        }))
+        .build()
+        .expect("failed to build engine");
+
+    let actual = tokio::time::timeout(Duration::from_millis(100), engine.run_async()).await;
+
+    assert!(actual.is_ok(), "failed because Engine did not stop");
+
+    let outcome = actual.unwrap();
+    assert_eq!(outcome.termination, Termination::Organic);
+    assert!(outcome.trader_failures.is_empty());
+}
+
+#[tokio::test]
+async fn engine_with_metrics_records_events_trader_loops_and_repository_latency() {
+    let (_command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, _event_rx) = mpsc::unbounded_channel();
+
+    // Record Event derived metrics by fanning out every Event to the MetricsTx
+    let metrics = MetricsTx::new();
+    let event_tx = FanOutTx::new(EventTx::new(event_tx), metrics.clone());
+
+    let engine_id = Uuid::new_v4();
+    let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InstrumentedRepository::new(
+                InMemoryRepository::new(),
+                metrics.clone(),
+            ))
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(StatisticConfig {
+                starting_equity: 10_000.0,
+                trading_days_per_year: 365,
+                risk_free_return: 0.0,
+            })
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+    let trader = Trader::builder()
+        .engine_id(engine_id)
+        .market(market.clone())
+        .command_rx(trader_command_rx)
+        .event_tx(event_tx)
+        .portfolio(Arc::clone(&portfolio))
+        .data(historical::MarketFeed::new(
+            vec![market_trade(Side::Buy), market_trade(Side::Sell)].into_iter(),
+        ))
+        .strategy(RSIStrategy::new(StrategyConfig {
+            rsi_period: 14,
+            clock: Clock::Live,
+        }))
+        .execution(SimulatedExecution::new(ExecutionConfig {
+            simulated_fees_pct: Fees::default(),
+            clock: Clock::Live,
+        }))
+        .metrics(metrics.clone())
+        .build()
+        .expect("failed to build trader");
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(portfolio)
+        .traders(vec![trader])
+        .trader_command_txs(HashMap::from_iter([(market, trader_command_tx)]))
+        .statistics_summary(TradingSummary::init(StatisticConfig {
+            starting_equity: 1000.0,
+            trading_days_per_year: 365,
+            risk_free_return: 0.0,
+        }))
+        .build()
+        .expect("failed to build engine");
+
+    let outcome = tokio::time::timeout(Duration::from_secs(5), engine.run())
+        .await
+        .expect("Engine did not stop after the candles finished");
+    assert_eq!(outcome.termination, Termination::Organic);
+
+    let rendered = metrics::render(&metrics.metrics());
+    let market_events = rendered
+        .lines()
+        .find(|line| line.starts_with("barter_market_events_total{"));
+    assert!(
+        matches!(market_events, Some(line) if line.ends_with(" 2")),
+        "missing MarketEvent counter: {}",
+        rendered
+    );
+    assert!(
+        rendered.contains("barter_trader_loop_latency_seconds_count{market="),
+        "missing trader loop latency: {}",
+        rendered
+    );
+    assert!(
+        rendered
+            .contains("barter_repository_latency_seconds_count{operation=\"get_open_positions\"}"),
+        "missing repository latency: {}",
+        rendered
+    );
+}
+
+#[tokio::test]
+async fn engine_from_config_file_stops_after_candles_finished() {
+    let (_command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+
+    let engine = EngineConfig::from_path("examples/config/backtest.json")
+        .expect("failed to parse engine config")
+        .init(&Registry::default(), command_rx, EventTx::new(event_tx))
+        .await
+        .expect("failed to initialise engine from config");
+
+    let outcome = tokio::time::timeout(Duration::from_secs(5), engine.run())
+        .await
+        .expect("Engine did not stop after the candles finished");
+
+    assert_eq!(outcome.termination, Termination::Organic);
+    assert!(outcome.trader_failures.is_empty());
+    assert!(outcome
+        .summary
+        .markets
+        .contains_key("binance_btc_usdt_spot"));
+    assert!(event_rx.try_recv().is_ok());
+}
+
+#[tokio::test]
+async fn engine_adds_and_retires_markets_while_running() {
+    let (command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, _event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+
+    let initial = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+    let listed = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+    let statistic_config = StatisticConfig {
+        starting_equity: 10_000.0,
+        trading_days_per_year: 365,
+        risk_free_return: 0.0,
+    };
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![initial.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(statistic_config)
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    // Every Trader is fed by a live MarketFeed, whose transmitter is sent to the test
+    let (feed_txs, mut feed_rxs) = mpsc::unbounded_channel();
+    let trader = {
+        let (event_tx, portfolio, feed_txs) = (event_tx.clone(), Arc::clone(&portfolio), feed_txs);
+        move |market: Market, command_rx| {
+            let (market_tx, market_rx) = mpsc::unbounded_channel();
+            feed_txs.send((market.clone(), market_tx)).unwrap();
+
+            Trader::builder()
+                .engine_id(engine_id)
+                .market(market)
+                .command_rx(command_rx)
+                .event_tx(event_tx.clone())
+                .portfolio(Arc::clone(&portfolio))
+                .data(live::MarketFeed { market_rx })
+                .strategy(RSIStrategy::new(StrategyConfig {
+                    rsi_period: 14,
+                    clock: Clock::Live,
+                }))
+                .execution(SimulatedExecution::new(ExecutionConfig {
+                    simulated_fees_pct: Fees {
+                        exchange: 0.1,
+                        slippage: 0.05,
+                        network: 0.0,
+                    },
+                    clock: Clock::Live,
+                }))
+                .build()
+        }
+    };
+
+    let (initial_command_tx, initial_command_rx) = mpsc::channel(10);
+    let initial_trader = trader(initial.clone(), initial_command_rx).unwrap();
+    let (_, initial_feed_tx) = feed_rxs.recv().await.unwrap();
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(Arc::clone(&portfolio))
+        .traders(vec![initial_trader])
+        .trader_command_txs(HashMap::from([(initial.clone(), initial_command_tx)]))
+        // Engine statistics_summary deliberately differs from the Portfolio statistic_config
+        .statistics_summary(TradingSummary::init(StatisticConfig {
+            starting_equity: 1_000.0,
+            ..statistic_config
+        }))
+        .trader_factory(TraderFactory::new(move |market, command_rx| {
+            std::future::ready(trader(market, command_rx))
+        }))
+        .build()
+        .expect("failed to build engine");
+    let engine = tokio::spawn(engine.run_async());
+
+    // Add a newly listed Market & check it's statistics are bootstrapped by the Portfolio
+    let (added_tx, added_rx) = oneshot::channel();
+    command_tx
+        .send(Command::AddMarket(listed.clone(), added_tx))
+        .await
+        .unwrap();
+    assert!(added_rx.await.unwrap().is_ok());
+    let (listed_market, listed_feed_tx) = feed_rxs.recv().await.unwrap();
+    assert_eq!(listed_market, listed);
+
+    let (added_tx, added_rx) = oneshot::channel();
+    command_tx
+        .send(Command::AddMarket(listed.clone(), added_tx))
+        .await
+        .unwrap();
+    assert!(matches!(
+        added_rx.await.unwrap(),
+        Err(EngineError::MarketAlreadyTraded(market)) if market == listed
+    ));
+
+    let (statistics_tx, statistics_rx) = oneshot::channel();
+    command_tx
+        .send(Command::FetchStatistics(statistics_tx))
+        .await
+        .unwrap();
+    let statistics = statistics_rx.await.unwrap().unwrap();
+    assert_eq!(
+        statistics["binance_eth_usdt_spot"].pointer("/drawdown/current_drawdown/equity_range/high"),
+        Some(&serde_json::json!(statistic_config.starting_equity))
+    );
+
+    // Open a Position in the listed Market, then retire it
+    let mut position = position();
+    position.position_id = determine_position_id(engine_id, &listed.exchange, &listed.instrument);
+    portfolio
+        .lock()
+        .set_open_position(engine_id, position)
+        .unwrap();
+
+    let mut trade = market_trade(Side::Buy);
+    trade.exchange = listed.exchange.clone();
+    trade.instrument = listed.instrument.clone();
+    listed_feed_tx.send(trade).unwrap();
+
+    let (removed_tx, removed_rx) = oneshot::channel();
+    command_tx
+        .send(Command::RemoveMarket(listed.clone(), removed_tx))
+        .await
+        .unwrap();
+    assert!(removed_rx.await.unwrap().is_ok());
+
+    // Engine stops organically once the initial Market's feed finishes, since the retired
+    // Market's Trader has terminated
+    drop(initial_feed_tx);
+    let outcome = tokio::time::timeout(Duration::from_secs(5), engine)
+        .await
+        .expect("Engine did not stop after every Trader stopped")
+        .unwrap();
+
+    assert_eq!(outcome.termination, Termination::Organic);
+    assert!(outcome.trader_failures.is_empty());
+    assert!(outcome.open_positions.is_empty());
+    assert_eq!(outcome.summary.exited_positions.len(), 1);
+    assert!(outcome
+        .summary
+        .markets
+        .contains_key("binance_eth_usdt_spot"));
+}
+
+/// Cross-market strategy that goes long the follower [`Market`] whenever the leader trades.
+struct LeadLag {
+    leader: Instrument,
+    follower: Option<MarketEvent>,
+}
+
+impl CrossMarketSignalGenerator for LeadLag {
+    fn generate_signals(&mut self, market: &MarketEvent) -> Vec<Signal> {
+        if market.instrument != self.leader {
+            self.follower = Some(market.clone());
+            return Vec::new();
+        }
+
+        self.follower
+            .iter()
+            .map(|follower| Signal {
+                time: market.exchange_time,
+                exchange: follower.exchange.clone(),
+                instrument: follower.instrument.clone(),
+                signals: HashMap::from([(Decision::Long, SignalStrength(1.0))]),
+                market_meta: MarketMeta {
+                    close: match &follower.kind {
+                        DataKind::Trade(trade) => trade.price,
+                        _ => unreachable!(),
+                    },
+                    time: follower.exchange_time,
+                },
+                tag: None,
+                contributions: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+#[tokio::test]
+async fn engine_routes_cross_market_signals_to_the_relevant_trader() {
+    let (command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, _event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+
+    let leader = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+    let follower = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+    let statistic_config = StatisticConfig {
+        starting_equity: 10_000.0,
+        trading_days_per_year: 365,
+        risk_free_return: 0.0,
+    };
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![leader.clone(), follower.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(statistic_config)
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    // Every Trader shares the same cross-market strategy via it's own CrossMarketStrategy handle
+    let (signal_tx, signal_rx) = mpsc::unbounded_channel();
+    let strategy = CrossMarketStrategy::new(
+        leader.clone(),
+        Arc::new(Mutex::new(LeadLag {
+            leader: leader.instrument.clone(),
+            follower: None,
+        })),
+        signal_tx,
+    );
+
+    let mut traders = Vec::new();
+    let mut trader_command_txs = HashMap::new();
+    let mut feed_txs = HashMap::new();
+    for market in [leader.clone(), follower.clone()] {
+        let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+        let (market_tx, market_rx) = mpsc::unbounded_channel();
+        trader_command_txs.insert(market.clone(), trader_command_tx);
+        feed_txs.insert(market.clone(), market_tx);
+
+        traders.push(
+            Trader::builder()
+                .engine_id(engine_id)
+                .market(market.clone())
+                .command_rx(trader_command_rx)
+                .event_tx(event_tx.clone())
+                .portfolio(Arc::clone(&portfolio))
+                .data(live::MarketFeed { market_rx })
+                .strategy(strategy.for_market(market))
+                .execution(SimulatedExecution::new(ExecutionConfig {
+                    simulated_fees_pct: Fees {
+                        exchange: 0.1,
+                        slippage: 0.05,
+                        network: 0.0,
+                    },
+                    clock: Clock::Live,
+                }))
+                .build()
+                .expect("failed to build trader"),
+        );
+    }
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(Arc::clone(&portfolio))
+        .traders(traders)
+        .trader_command_txs(trader_command_txs)
+        .signal_rx(signal_rx)
+        .statistics_summary(TradingSummary::init(statistic_config))
+        .build()
+        .expect("failed to build engine");
+    let engine = tokio::spawn(engine.run_async());
+
+    // Follower trades first, then the leader's trade yields a Signal for the follower Market
+    let mut follower_trade = market_trade(Side::Buy);
+    follower_trade.instrument = follower.instrument.clone();
+    feed_txs[&follower].send(follower_trade).unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    feed_txs[&leader].send(market_trade(Side::Buy)).unwrap();
+
+    // Follower's Trader enters a Position via it's own order path
+    let open_positions = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let (positions_tx, positions_rx) = oneshot::channel();
+            command_tx
+                .send(Command::FetchOpenPositions(positions_tx))
+                .await
+                .unwrap();
+            let open_positions = positions_rx.await.unwrap().unwrap();
+            if !open_positions.is_empty() {
+                break open_positions;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("cross-market Signal was not routed to the follower's Trader");
+
+    assert_eq!(open_positions.len(), 1);
+    assert_eq!(open_positions[0].instrument, follower.instrument);
+
+    command_tx
+        .send(Command::Terminate("done".to_owned()))
+        .await
+        .unwrap();
+    let outcome = engine.await.unwrap();
+    assert_eq!(
+        outcome.termination,
+        Termination::Commanded("done".to_owned())
+    );
+}
+
+/// Two independent sub-strategies trading the same market, advising opposing Positions under
+/// their own tag on every trade.
+struct LongShortSubStrategies;
+
+impl SignalGenerator for LongShortSubStrategies {
+    fn generate_signal(&mut self, _: &MarketEvent) -> Option<Signal> {
+        None
+    }
+
+    fn generate_signals(&mut self, market: &MarketEvent) -> Vec<Signal> {
+        let close = match &market.kind {
+            DataKind::Trade(trade) => trade.price,
+            _ => return Vec::new(),
+        };
+
+        [
+            ("trend", Decision::Long, 1.0),
+            ("hedge", Decision::Short, 0.5),
+        ]
+        .into_iter()
+        .map(|(tag, decision, strength)| Signal {
+            time: market.exchange_time,
+            exchange: market.exchange.clone(),
+            instrument: market.instrument.clone(),
+            signals: HashMap::from([(decision, SignalStrength(strength))]),
+            market_meta: MarketMeta {
+                close,
+                time: market.exchange_time,
+            },
+            tag: Some(tag.to_owned()),
+            contributions: Vec::new(),
+        })
+        .collect()
+    }
+}
+
+/// Simulated execution venue recording every [`OrderEvent`] it executes.
+struct RecordingExecution {
+    orders: Arc<Mutex<Vec<OrderEvent>>>,
+    execution: SimulatedExecution,
+}
+
+impl ExecutionClient for RecordingExecution {
+    fn generate_fill(&self, order: &OrderEvent) -> Result<FillEvent, ExecutionError> {
+        self.orders.lock().push(order.clone());
+        self.execution.generate_fill(order)
+    }
+}
+
+#[test]
+fn trader_nets_tagged_orders_before_execution_and_splits_the_fill_per_tag() {
+    let (event_tx, _event_rx) = mpsc::unbounded_channel();
+    let (_command_tx, command_rx) = mpsc::channel(10);
+    let engine_id = Uuid::new_v4();
+    let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::<TradingSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(StatisticConfig {
+                starting_equity: 10_000.0,
+                trading_days_per_year: 365,
+                risk_free_return: 0.0,
+            })
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    let orders = Arc::new(Mutex::new(Vec::new()));
+    Trader::<_, TradingSummary, _, _, _, _>::builder()
+        .engine_id(engine_id)
+        .market(market.clone())
+        .command_rx(command_rx)
+        .event_tx(EventTx::new(event_tx))
+        .portfolio(Arc::clone(&portfolio))
+        .data(historical::MarketFeed::new(
+            [market_trade(Side::Buy)].into_iter(),
+        ))
+        .strategy(LongShortSubStrategies)
+        .execution(RecordingExecution {
+            orders: Arc::clone(&orders),
+            execution: SimulatedExecution::new(ExecutionConfig {
+                simulated_fees_pct: Fees {
+                    exchange: 0.1,
+                    slippage: 0.0,
+                    network: 0.0,
+                },
+                clock: Clock::Live,
+            }),
+        })
+        .build()
+        .expect("failed to build trader")
+        .run()
+        .expect("Trader failed");
+
+    let mut positions = portfolio
+        .lock()
+        .get_open_positions(engine_id, [market].iter())
+        .unwrap();
+    positions.sort_by(|a, b| a.tag.cmp(&b.tag));
+    let (hedge, trend) = (&positions[0], &positions[1]);
+    assert_eq!(positions.len(), 2);
+    assert_eq!(hedge.tag.as_deref(), Some("hedge"));
+    assert!(hedge.quantity < 0.0);
+    assert_eq!(trend.tag.as_deref(), Some("trend"));
+    assert!(trend.quantity > 0.0);
+
+    // Venue only executed the net quantity of both sub-strategies' OrderEvents
+    let orders = orders.lock();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].tag, None);
+    assert!((orders[0].quantity - (trend.quantity + hedge.quantity)).abs() < 1e-9);
+
+    // Venue fees of the net OrderEvent are attributed to each tag by quantity
+    let venue_fees = orders[0].quantity.abs() * orders[0].market_meta.close * 0.1;
+    assert!((trend.enter_fees_total + hedge.enter_fees_total - venue_fees).abs() < 1e-9);
+}
+
+/// Strategy that advises going long on every trade.
+struct AlwaysLong;
+
+impl SignalGenerator for AlwaysLong {
+    fn generate_signal(&mut self, market: &MarketEvent) -> Option<Signal> {
+        let close = match &market.kind {
+            DataKind::Trade(trade) => trade.price,
+            _ => return None,
+        };
+
+        Some(Signal {
+            time: market.exchange_time,
+            exchange: market.exchange.clone(),
+            instrument: market.instrument.clone(),
+            signals: HashMap::from([(Decision::Long, SignalStrength(1.0))]),
+            market_meta: MarketMeta {
+                close,
+                time: market.exchange_time,
+            },
+            tag: None,
+            contributions: Vec::new(),
+        })
+    }
+}
+
+/// Execution venue that rejects every order.
+struct UnavailableExecution;
+
+#[async_trait::async_trait]
+impl AsyncExecutionClient for UnavailableExecution {
+    async fn execute(&self, _: &OrderEvent) -> Result<FillEvent, ExecutionError> {
+        Err(ExecutionError::AccountUnavailable(
+            "venue offline".to_owned(),
+        ))
+    }
+}
+
+#[tokio::test]
+async fn engine_terminates_and_awaits_every_trader_recording_failures() {
+    let (command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, _event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+
+    let failing = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+    let healthy = Market::new("binance", ("eth", "usdt", InstrumentKind::Spot));
+    let statistic_config = StatisticConfig {
+        starting_equity: 10_000.0,
+        trading_days_per_year: 365,
+        risk_free_return: 0.0,
+    };
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![failing.clone(), healthy.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(statistic_config)
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    let mut traders = Vec::new();
+    let mut trader_command_txs = HashMap::new();
+    let mut feed_txs = HashMap::new();
+    for market in [failing.clone(), healthy.clone()] {
+        let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+        let (market_tx, market_rx) = mpsc::unbounded_channel();
+        trader_command_txs.insert(market.clone(), trader_command_tx);
+        feed_txs.insert(market.clone(), market_tx);
+
+        traders.push(
+            Trader::builder()
+                .engine_id(engine_id)
+                .market(market)
+                .command_rx(trader_command_rx)
+                .event_tx(event_tx.clone())
+                .portfolio(Arc::clone(&portfolio))
+                .data(live::MarketFeed { market_rx })
+                .strategy(AlwaysLong)
+                .execution(UnavailableExecution)
+                .build()
+                .expect("failed to build trader"),
+        );
+    }
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(Arc::clone(&portfolio))
+        .traders(traders)
+        .trader_command_txs(trader_command_txs)
+        .statistics_summary(TradingSummary::init(statistic_config))
+        .build()
+        .expect("failed to build engine");
+    let engine = tokio::spawn(engine.run_async());
+
+    // Failing Market's order is rejected by the venue, then the remote command_tx is dropped
+    feed_txs[&failing].send(market_trade(Side::Buy)).unwrap();
+    drop(command_tx);
+
+    // Engine terminates the healthy Trader & awaits every Trader before returning
+    let outcome = tokio::time::timeout(Duration::from_secs(5), engine)
+        .await
+        .expect("Engine did not stop after the command transmitter was dropped")
+        .unwrap();
+
+    assert_eq!(outcome.termination, Termination::CommandChannelDropped);
+    assert_eq!(outcome.trader_failures.len(), 1);
+    assert_eq!(outcome.trader_failures[0].market, failing);
+    assert!(outcome.trader_failures[0].reason.contains("venue offline"));
+}
+
+/// Execution venue reporting a fixed account state.
+struct VenueAccount(AccountSnapshot);
+
+impl AccountReporter for VenueAccount {
+    fn fetch_account(&self) -> Result<AccountSnapshot, ExecutionError> {
+        Ok(self.0.clone())
+    }
+}
+
+#[tokio::test]
+async fn engine_reconciles_persisted_state_against_venue_on_startup() {
+    let (_command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+
+    let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+    let statistic_config = StatisticConfig {
+        starting_equity: 10_000.0,
+        trading_days_per_year: 365,
+        risk_free_return: 0.0,
+    };
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(statistic_config)
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    // Venue holds a different Balance, a position the Portfolio hasn't persisted, and a position
+    // in a Market the Engine doesn't trade
+    let venue = VenueAccount(AccountSnapshot {
+        balance: Balance {
+            time: chrono::Utc::now(),
+            total: 9_000.0,
+            available: 9_000.0,
+        },
+        positions: vec![
+            VenuePosition {
+                exchange: market.exchange.clone(),
+                instrument: market.instrument.clone(),
+                quantity: 1.0,
+            },
+            VenuePosition {
+                exchange: market.exchange.clone(),
+                instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+                quantity: 2.0,
+            },
+        ],
+    });
+
+    let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+    let trader = Trader::builder()
+        .engine_id(engine_id)
+        .market(market.clone())
+        .command_rx(trader_command_rx)
+        .event_tx(event_tx.clone())
+        .portfolio(Arc::clone(&portfolio))
+        .data(historical::MarketFeed::new(Vec::new().into_iter()))
+        .strategy(AlwaysLong)
+        .execution(SimulatedExecution::new(ExecutionConfig::default()))
+        .build()
+        .expect("failed to build trader");
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(Arc::clone(&portfolio))
+        .traders(vec![trader])
+        .trader_command_txs(HashMap::from([(market.clone(), trader_command_tx)]))
+        .statistics_summary(TradingSummary::init(statistic_config))
+        .reconciliation(Reconciliation::new(venue, 1e-6, event_tx))
+        .build()
+        .expect("failed to build engine");
+
+    let outcome = engine.run().await;
+    assert_eq!(outcome.termination, Termination::Organic);
+
+    let mut discrepancies = Vec::new();
+    while let Ok(event) = event_rx.try_recv() {
+        if let Event::Discrepancy(discrepancy) = event {
+            discrepancies.push(discrepancy);
+        }
+    }
+
+    assert_eq!(discrepancies.len(), 2);
+    assert!(matches!(discrepancies[0], Discrepancy::Balance { .. }));
+    assert!(matches!(
+        &discrepancies[1],
+        Discrepancy::PositionNotPersisted(venue_position)
+            if venue_position.instrument == market.instrument
+    ));
 }
@@ -0,0 +1,134 @@
+use barter::{
+    clock::Clock,
+    data::historical,
+    engine::{trader::Trader, Engine},
+    event::EventTx,
+    execution::{
+        simulated::{Config as ExecutionConfig, SimulatedExecution},
+        Fees,
+    },
+    portfolio::{
+        allocator::DefaultAllocator, portfolio::MetaPortfolio,
+        repository::in_memory::InMemoryRepository, risk::DefaultRisk,
+    },
+    regression::{self, GoldenFile},
+    statistic::summary::{
+        trading::{Config as StatisticConfig, TradingSummary},
+        Initialiser,
+    },
+    strategy::example::{Config as StrategyConfig, RSIStrategy},
+};
+use barter_data::model::{Candle, DataKind, MarketEvent};
+use barter_integration::model::{Exchange, Instrument, InstrumentKind, Market};
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn rsi_strategy_backtest_matches_golden_snapshot() {
+    let (_command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+
+    // Fixed engine_id so the PositionIds are deterministic
+    let engine_id = Uuid::from_u128(1);
+    let market = Market::new("binance", ("btc", "usdt", InstrumentKind::Spot));
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(StatisticConfig {
+                starting_equity: 10_000.0,
+                trading_days_per_year: 365,
+                risk_free_return: 0.0,
+            })
+            .clock(Clock::Historical)
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+
+    let trader = Trader::builder()
+        .engine_id(engine_id)
+        .market(market.clone())
+        .command_rx(trader_command_rx)
+        .event_tx(event_tx)
+        .portfolio(Arc::clone(&portfolio))
+        .data(historical::MarketFeed::new(sine_wave_candles().into_iter()))
+        .strategy(RSIStrategy::new(StrategyConfig {
+            rsi_period: 5,
+            clock: Clock::Historical,
+        }))
+        .execution(SimulatedExecution::new(ExecutionConfig {
+            simulated_fees_pct: Fees {
+                exchange: 0.1,
+                slippage: 0.05,
+                network: 0.0,
+            },
+            clock: Clock::Historical,
+        }))
+        .build()
+        .expect("failed to build trader");
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(portfolio)
+        .traders(vec![trader])
+        .trader_command_txs(HashMap::from_iter([(market, trader_command_tx)]))
+        .statistics_summary(TradingSummary::init(StatisticConfig {
+            starting_equity: 10_000.0,
+            trading_days_per_year: 365,
+            risk_free_return: 0.0,
+        }))
+        .build()
+        .expect("failed to build engine");
+
+    let snapshot = regression::record(engine, event_rx)
+        .await
+        .expect("failed to record backtest snapshot");
+
+    GoldenFile::new("tests/golden/rsi_strategy_backtest.json").assert_matches(&snapshot);
+}
+
+/// Hourly candles with closes following a sine wave, so the RSIStrategy enters & exits Positions.
+fn sine_wave_candles() -> Vec<MarketEvent> {
+    let start_time = "2022-01-01T00:00:00Z"
+        .parse::<DateTime<Utc>>()
+        .expect("failed to parse start time");
+
+    (0..60)
+        .map(|index| {
+            let open = 1000.0 + 100.0 * (index as f64 / 4.0).sin();
+            let close = 1000.0 + 100.0 * ((index + 1) as f64 / 4.0).sin();
+            let end_time = start_time + Duration::hours(index + 1);
+
+            MarketEvent {
+                exchange_time: end_time,
+                received_time: end_time,
+                exchange: Exchange::from("binance"),
+                instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+                kind: DataKind::Candle(Candle {
+                    start_time: end_time - Duration::hours(1),
+                    end_time,
+                    open,
+                    high: open.max(close) + 5.0,
+                    low: open.min(close) - 5.0,
+                    close,
+                    volume: 1000.0,
+                    trade_count: 100,
+                }),
+            }
+        })
+        .collect()
+}